@@ -17,6 +17,9 @@ pub enum OviaError {
     #[error("conflict: {0}")]
     Conflict(String),
 
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
     #[error("internal error: {0}")]
     Internal(String),
 }
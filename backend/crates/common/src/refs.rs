@@ -0,0 +1,68 @@
+//! References to work items inside free text.
+
+/// Jira issue keys (`BEE-123`) in `text`, in order of first appearance and
+/// without duplicates. A key is an uppercase project key of 2-10 letters, digits
+/// or underscores starting with a letter, a dash and a number, not glued to
+/// surrounding words.
+pub fn find_jira_issue_keys(text: &str) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut keys: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let starts_word = i == 0 || !is_word(bytes[i - 1]);
+        if !starts_word || !bytes[i].is_ascii_uppercase() {
+            i += 1;
+            continue;
+        }
+        let project_end = i + bytes[i..]
+            .iter()
+            .take_while(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || **b == b'_')
+            .count();
+        let digits_start = project_end + 1;
+        let digits_end = digits_start
+            + bytes
+                .get(digits_start..)
+                .unwrap_or_default()
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+        let project_len = project_end - i;
+        let is_key = (2..=10).contains(&project_len)
+            && bytes.get(project_end) == Some(&b'-')
+            && digits_end > digits_start
+            && bytes.get(digits_end).is_none_or(|b| !is_word(*b));
+        if is_key {
+            let key = &text[i..digits_end];
+            if !keys.iter().any(|k| k == key) {
+                keys.push(key.to_string());
+            }
+            i = digits_end;
+        } else {
+            i = project_end.max(i + 1);
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_keys_in_order_without_duplicates() {
+        assert_eq!(
+            find_jira_issue_keys(
+                "Covers BEE-123 and OPS2-7 (see BEE-123, X-1, bee-9, ABC-12a, BEE-5)."
+            ),
+            vec!["BEE-123", "OPS2-7", "BEE-5"]
+        );
+        assert_eq!(
+            find_jira_issue_keys("https://acme.atlassian.net/browse/CORE_X-42?focus=1"),
+            vec!["CORE_X-42"]
+        );
+        assert!(find_jira_issue_keys("no keys, UTF- or -12 here").is_empty());
+    }
+}
@@ -17,7 +17,7 @@ impl PgAskRepository {
     }
 }
 
-fn map_session_row(row: &sqlx::postgres::PgRow) -> AskSession {
+pub(crate) fn map_session_row(row: &sqlx::postgres::PgRow) -> AskSession {
     let citations_json: Option<serde_json::Value> = row.get("citations");
     let citations: Option<Vec<Citation>> =
         citations_json.and_then(|v| serde_json::from_value(v).ok());
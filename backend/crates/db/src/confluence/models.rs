@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluenceSpace {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub space_id: String,
+    pub space_key: String,
+    pub name: String,
+    pub space_type: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluencePage {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub page_id: String,
+    pub space_key: String,
+    pub title: String,
+    pub status: String,
+    pub parent_page_id: Option<String>,
+    pub author_account_id: Option<String>,
+    /// Author of the current version
+    pub last_editor_account_id: Option<String>,
+    pub version_number: i32,
+    /// Body in Confluence storage format (XHTML with `ac:` macros)
+    pub body_storage: Option<String>,
+    /// Body converted to plain text
+    pub body_text: Option<String>,
+    pub web_url: Option<String>,
+    pub created_at_confluence: Option<DateTime<Utc>>,
+    pub updated_at_confluence: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfluencePageVersion {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub page_id: String,
+    pub version_number: i32,
+    pub author_account_id: Option<String>,
+    pub message: Option<String>,
+    pub minor_edit: bool,
+    pub created_at_confluence: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A link from a page to a Jira issue, GitLab project or merge request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfluencePageLink {
+    pub org_id: Uuid,
+    pub page_id: String,
+    /// "jira_issue", "gitlab_project" or "gitlab_merge_request"
+    pub target_type: String,
+    /// "BEE-123", "group/project" or "group/project!42"
+    pub target_ref: String,
+    /// "jira_macro", "issue_key" or "url"
+    pub link_source: String,
+}
+
+/// Body of a page whose links have not been extracted at its current version.
+#[derive(Debug, Clone)]
+pub struct ConfluencePageBody {
+    pub page_id: String,
+    pub version_number: i32,
+    pub body_storage: Option<String>,
+    pub body_text: Option<String>,
+}
+
+/// A page linking to a given target.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedPage {
+    pub page_id: String,
+    pub title: String,
+    pub space_key: String,
+    pub web_url: Option<String>,
+    pub updated_at_confluence: Option<DateTime<Utc>>,
+    pub link_source: String,
+}
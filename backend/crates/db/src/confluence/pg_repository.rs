@@ -0,0 +1,575 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::confluence::models::{
+    ConfluencePage, ConfluencePageBody, ConfluencePageLink, ConfluencePageVersion, ConfluenceSpace,
+    LinkedPage,
+};
+use crate::confluence::repositories::ConfluenceLinkRepository;
+use ovia_common::error::{OviaError, OviaResult};
+
+#[derive(Clone)]
+pub struct PgConfluenceRepository {
+    pool: PgPool,
+}
+
+impl PgConfluenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Upsert a space (idempotent on org_id + space_key).
+    pub async fn upsert_space(&self, s: &ConfluenceSpace) -> OviaResult<()> {
+        sqlx::query(
+            "insert into confluence_spaces (id, org_id, space_id, space_key, name, space_type)
+             values ($1, $2, $3, $4, $5, $6)
+             on conflict (org_id, space_key) do update set
+               space_id = excluded.space_id,
+               name = excluded.name,
+               space_type = excluded.space_type,
+               updated_at = now()",
+        )
+        .bind(s.id)
+        .bind(s.org_id)
+        .bind(&s.space_id)
+        .bind(&s.space_key)
+        .bind(&s.name)
+        .bind(&s.space_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Upsert a page (idempotent on org_id + page_id). Erased authors and editors are
+    /// stored under their pseudonym.
+    pub async fn upsert_page(&self, p: &ConfluencePage) -> OviaResult<()> {
+        sqlx::query(
+            "insert into confluence_pages
+             (id, org_id, page_id, space_key, title, status, parent_page_id, author_account_id,
+              last_editor_account_id, version_number, body_storage, body_text, web_url,
+              created_at_confluence, updated_at_confluence)
+             values ($1, $2, $3, $4, $5, $6, $7, coalesce(erased_alias($2, 'confluence', $8), $8),
+                     coalesce(erased_alias($2, 'confluence', $9), $9), $10, $11, $12, $13, $14, $15)
+             on conflict (org_id, page_id) do update set
+               space_key = excluded.space_key,
+               title = excluded.title,
+               status = excluded.status,
+               parent_page_id = excluded.parent_page_id,
+               author_account_id = excluded.author_account_id,
+               last_editor_account_id = excluded.last_editor_account_id,
+               version_number = excluded.version_number,
+               body_storage = excluded.body_storage,
+               body_text = excluded.body_text,
+               web_url = excluded.web_url,
+               created_at_confluence = excluded.created_at_confluence,
+               updated_at_confluence = excluded.updated_at_confluence,
+               updated_at = now()",
+        )
+        .bind(p.id)
+        .bind(p.org_id)
+        .bind(&p.page_id)
+        .bind(&p.space_key)
+        .bind(&p.title)
+        .bind(&p.status)
+        .bind(&p.parent_page_id)
+        .bind(&p.author_account_id)
+        .bind(&p.last_editor_account_id)
+        .bind(p.version_number)
+        .bind(&p.body_storage)
+        .bind(&p.body_text)
+        .bind(&p.web_url)
+        .bind(p.created_at_confluence)
+        .bind(p.updated_at_confluence)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn get_page(
+        &self,
+        org_id: Uuid,
+        page_id: &str,
+    ) -> OviaResult<Option<ConfluencePage>> {
+        let row = sqlx::query(
+            "select id, org_id, page_id, space_key, title, status, parent_page_id,
+                    author_account_id, last_editor_account_id, version_number, body_storage,
+                    body_text, web_url, created_at_confluence, updated_at_confluence,
+                    created_at, updated_at
+             from confluence_pages
+             where org_id = $1 and page_id = $2",
+        )
+        .bind(org_id)
+        .bind(page_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(map_page_row))
+    }
+
+    /// Upsert a page version (idempotent on org_id + page_id + version_number).
+    pub async fn upsert_page_version(&self, v: &ConfluencePageVersion) -> OviaResult<()> {
+        sqlx::query(
+            "insert into confluence_page_versions
+             (id, org_id, page_id, version_number, author_account_id, message, minor_edit,
+              created_at_confluence)
+             values ($1, $2, $3, $4, coalesce(erased_alias($2, 'confluence', $5), $5), $6, $7, $8)
+             on conflict (org_id, page_id, version_number) do update set
+               author_account_id = excluded.author_account_id,
+               message = excluded.message,
+               minor_edit = excluded.minor_edit,
+               created_at_confluence = excluded.created_at_confluence",
+        )
+        .bind(v.id)
+        .bind(v.org_id)
+        .bind(&v.page_id)
+        .bind(v.version_number)
+        .bind(&v.author_account_id)
+        .bind(&v.message)
+        .bind(v.minor_edit)
+        .bind(v.created_at_confluence)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Versions of a page, oldest first.
+    pub async fn list_page_versions(
+        &self,
+        org_id: Uuid,
+        page_id: &str,
+    ) -> OviaResult<Vec<ConfluencePageVersion>> {
+        let rows = sqlx::query(
+            "select id, org_id, page_id, version_number, author_account_id, message, minor_edit,
+                    created_at_confluence, created_at
+             from confluence_page_versions
+             where org_id = $1 and page_id = $2
+             order by version_number",
+        )
+        .bind(org_id)
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ConfluencePageVersion {
+                id: r.get("id"),
+                org_id: r.get("org_id"),
+                page_id: r.get("page_id"),
+                version_number: r.get("version_number"),
+                author_account_id: r.get("author_account_id"),
+                message: r.get("message"),
+                minor_edit: r.get("minor_edit"),
+                created_at_confluence: r.get("created_at_confluence"),
+                created_at: r.get("created_at"),
+            })
+            .collect())
+    }
+
+    // ── Page links ───────────────────────────────────────────────
+
+    /// Pages whose links were not extracted at their current version, oldest
+    /// change first.
+    pub async fn list_pages_pending_links(
+        &self,
+        org_id: Uuid,
+        limit: i64,
+    ) -> OviaResult<Vec<ConfluencePageBody>> {
+        let rows = sqlx::query(
+            "select page_id, version_number, body_storage, body_text
+             from confluence_pages
+             where org_id = $1
+               and links_extracted_version is distinct from version_number
+             order by updated_at_confluence nulls first, page_id
+             limit $2",
+        )
+        .bind(org_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ConfluencePageBody {
+                page_id: r.get("page_id"),
+                version_number: r.get("version_number"),
+                body_storage: r.get("body_storage"),
+                body_text: r.get("body_text"),
+            })
+            .collect())
+    }
+
+    /// Replace the links of a page and record the version they came from.
+    pub async fn replace_page_links(
+        &self,
+        org_id: Uuid,
+        page_id: &str,
+        version_number: i32,
+        links: &[ConfluencePageLink],
+    ) -> OviaResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query("delete from confluence_page_links where org_id = $1 and page_id = $2")
+            .bind(org_id)
+            .bind(page_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for link in links {
+            sqlx::query(
+                "insert into confluence_page_links
+                 (org_id, page_id, target_type, target_ref, link_source)
+                 values ($1, $2, $3, $4, $5)
+                 on conflict (org_id, page_id, target_type, target_ref) do nothing",
+            )
+            .bind(org_id)
+            .bind(page_id)
+            .bind(&link.target_type)
+            .bind(&link.target_ref)
+            .bind(&link.link_source)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        sqlx::query(
+            "update confluence_pages set links_extracted_version = $3
+             where org_id = $1 and page_id = $2",
+        )
+        .bind(org_id)
+        .bind(page_id)
+        .bind(version_number)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Links of a page, by target.
+    pub async fn list_page_links(
+        &self,
+        org_id: Uuid,
+        page_id: &str,
+    ) -> OviaResult<Vec<ConfluencePageLink>> {
+        let rows = sqlx::query(
+            "select org_id, page_id, target_type, target_ref, link_source
+             from confluence_page_links
+             where org_id = $1 and page_id = $2
+             order by target_type, target_ref",
+        )
+        .bind(org_id)
+        .bind(page_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ConfluencePageLink {
+                org_id: r.get("org_id"),
+                page_id: r.get("page_id"),
+                target_type: r.get("target_type"),
+                target_ref: r.get("target_ref"),
+                link_source: r.get("link_source"),
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl ConfluenceLinkRepository for PgConfluenceRepository {
+    async fn list_pages_linked_to(
+        &self,
+        org_id: Uuid,
+        target_type: &str,
+        target_ref: &str,
+    ) -> OviaResult<Vec<LinkedPage>> {
+        let rows = sqlx::query(
+            "select p.page_id, p.title, p.space_key, p.web_url, p.updated_at_confluence,
+                    l.link_source
+             from confluence_page_links l
+             join confluence_pages p on p.org_id = l.org_id and p.page_id = l.page_id
+             where l.org_id = $1 and l.target_type = $2 and l.target_ref = $3
+               and p.status = 'current'
+             order by p.updated_at_confluence desc nulls last, p.page_id",
+        )
+        .bind(org_id)
+        .bind(target_type)
+        .bind(target_ref)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| LinkedPage {
+                page_id: r.get("page_id"),
+                title: r.get("title"),
+                space_key: r.get("space_key"),
+                web_url: r.get("web_url"),
+                updated_at_confluence: r.get("updated_at_confluence"),
+                link_source: r.get("link_source"),
+            })
+            .collect())
+    }
+}
+
+fn map_page_row(r: &sqlx::postgres::PgRow) -> ConfluencePage {
+    ConfluencePage {
+        id: r.get("id"),
+        org_id: r.get("org_id"),
+        page_id: r.get("page_id"),
+        space_key: r.get("space_key"),
+        title: r.get("title"),
+        status: r.get("status"),
+        parent_page_id: r.get("parent_page_id"),
+        author_account_id: r.get("author_account_id"),
+        last_editor_account_id: r.get("last_editor_account_id"),
+        version_number: r.get("version_number"),
+        body_storage: r.get("body_storage"),
+        body_text: r.get("body_text"),
+        web_url: r.get("web_url"),
+        created_at_confluence: r.get("created_at_confluence"),
+        updated_at_confluence: r.get("updated_at_confluence"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+    use chrono::{Duration, Utc};
+
+    async fn test_repo() -> Option<PgConfluenceRepository> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        // Create tables inline for test isolation
+        for stmt in [
+            "create table if not exists confluence_spaces (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, space_id text not null, space_key text not null,
+              name text not null, space_type text,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists confluence_spaces_org_key_uidx
+              on confluence_spaces(org_id, space_key)",
+            "create table if not exists confluence_pages (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, page_id text not null, space_key text not null,
+              title text not null, status text not null, parent_page_id text,
+              author_account_id text, last_editor_account_id text,
+              version_number integer not null, body_storage text, body_text text, web_url text,
+              created_at_confluence timestamptz, updated_at_confluence timestamptz,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists confluence_pages_org_page_uidx
+              on confluence_pages(org_id, page_id)",
+            "create table if not exists confluence_page_versions (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, page_id text not null, version_number integer not null,
+              author_account_id text, message text, minor_edit boolean not null default false,
+              created_at_confluence timestamptz not null,
+              created_at timestamptz not null default now()
+            )",
+            "create unique index if not exists confluence_page_versions_org_page_version_uidx
+              on confluence_page_versions(org_id, page_id, version_number)",
+            "create table if not exists confluence_page_links (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, page_id text not null, target_type text not null,
+              target_ref text not null, link_source text not null,
+              created_at timestamptz not null default now()
+            )",
+            "create unique index if not exists confluence_page_links_org_page_target_uidx
+              on confluence_page_links(org_id, page_id, target_type, target_ref)",
+            "alter table confluence_pages add column if not exists links_extracted_version integer",
+            "create table if not exists erased_accounts (
+              org_id uuid not null, source text not null, account_hash text not null,
+              alias text not null, created_at timestamptz not null default now(),
+              primary key (org_id, source, account_hash)
+            )",
+            "create or replace function erased_alias(p_org_id uuid, p_source text, p_account text)
+             returns text language sql stable as $$
+               select alias from erased_accounts
+               where org_id = p_org_id and source = p_source
+                 and account_hash = encode(sha256(convert_to(p_account, 'UTF8')), 'hex')
+             $$",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+
+        Some(PgConfluenceRepository::new(pool))
+    }
+
+    fn make_page(org_id: Uuid, page_id: &str, version: i32, body: &str) -> ConfluencePage {
+        let now = Utc::now();
+        ConfluencePage {
+            id: Uuid::new_v4(),
+            org_id,
+            page_id: page_id.to_string(),
+            space_key: "ENG".to_string(),
+            title: "Checkout design".to_string(),
+            status: "current".to_string(),
+            parent_page_id: None,
+            author_account_id: Some("user-1".to_string()),
+            last_editor_account_id: Some("user-2".to_string()),
+            version_number: version,
+            body_storage: Some(format!("<p>{body}</p>")),
+            body_text: Some(body.to_string()),
+            web_url: None,
+            created_at_confluence: Some(now - Duration::days(3)),
+            updated_at_confluence: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_page_and_versions() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+
+        repo.upsert_space(&ConfluenceSpace {
+            id: Uuid::new_v4(),
+            org_id: org,
+            space_id: "98306".to_string(),
+            space_key: "ENG".to_string(),
+            name: "Engineering".to_string(),
+            space_type: Some("global".to_string()),
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+
+        repo.upsert_page(&make_page(org, "1001", 1, "Draft"))
+            .await
+            .unwrap();
+        repo.upsert_page(&make_page(org, "1001", 2, "Final"))
+            .await
+            .unwrap();
+        let page = repo.get_page(org, "1001").await.unwrap().unwrap();
+        assert_eq!(page.version_number, 2);
+        assert_eq!(page.body_text.as_deref(), Some("Final"));
+        assert!(repo.get_page(org, "1002").await.unwrap().is_none());
+
+        for (number, author) in [(2, "user-2"), (1, "user-1"), (2, "user-2")] {
+            repo.upsert_page_version(&ConfluencePageVersion {
+                id: Uuid::new_v4(),
+                org_id: org,
+                page_id: "1001".to_string(),
+                version_number: number,
+                author_account_id: Some(author.to_string()),
+                message: None,
+                minor_edit: false,
+                created_at_confluence: now - Duration::days(i64::from(3 - number)),
+                created_at: now,
+            })
+            .await
+            .unwrap();
+        }
+        let versions = repo.list_page_versions(org, "1001").await.unwrap();
+        let versions: Vec<_> = versions
+            .iter()
+            .map(|v| (v.version_number, v.author_account_id.as_deref()))
+            .collect();
+        assert_eq!(versions, vec![(1, Some("user-1")), (2, Some("user-2"))]);
+    }
+
+    #[tokio::test]
+    async fn page_links_are_replaced_and_queryable_by_target() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        repo.upsert_page(&make_page(org, "2001", 4, "Covers BEE-1"))
+            .await
+            .unwrap();
+        let mut archived = make_page(org, "2002", 1, "Old BEE-1 notes");
+        archived.status = "archived".to_string();
+        repo.upsert_page(&archived).await.unwrap();
+
+        let pending = repo.list_pages_pending_links(org, 10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+
+        let link = |page_id: &str, target_type: &str, target_ref: &str| ConfluencePageLink {
+            org_id: org,
+            page_id: page_id.to_string(),
+            target_type: target_type.to_string(),
+            target_ref: target_ref.to_string(),
+            link_source: "issue_key".to_string(),
+        };
+        repo.replace_page_links(
+            org,
+            "2001",
+            4,
+            &[
+                link("2001", "jira_issue", "BEE-2"),
+                link("2001", "gitlab_project", "acme/shop"),
+            ],
+        )
+        .await
+        .unwrap();
+        repo.replace_page_links(org, "2001", 4, &[link("2001", "jira_issue", "BEE-1")])
+            .await
+            .unwrap();
+        repo.replace_page_links(org, "2002", 1, &[link("2002", "jira_issue", "BEE-1")])
+            .await
+            .unwrap();
+
+        assert!(repo
+            .list_pages_pending_links(org, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            repo.list_page_links(org, "2001").await.unwrap(),
+            vec![link("2001", "jira_issue", "BEE-1")]
+        );
+
+        // Archived pages are left out
+        let pages = repo
+            .list_pages_linked_to(org, "jira_issue", "BEE-1")
+            .await
+            .unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].page_id, "2001");
+        assert_eq!(pages[0].title, "Checkout design");
+        assert!(repo
+            .list_pages_linked_to(org, "gitlab_project", "acme/shop")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // A new version is pending again
+        repo.upsert_page(&make_page(org, "2001", 5, "Covers BEE-1"))
+            .await
+            .unwrap();
+        let pending = repo.list_pages_pending_links(org, 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].version_number, 5);
+    }
+}
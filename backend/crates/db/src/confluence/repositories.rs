@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::confluence::models::LinkedPage;
+use ovia_common::error::OviaResult;
+
+#[async_trait]
+pub trait ConfluenceLinkRepository: Send + Sync {
+    /// Current pages linking to a target, most recently updated first.
+    async fn list_pages_linked_to(
+        &self,
+        org_id: Uuid,
+        target_type: &str,
+        target_ref: &str,
+    ) -> OviaResult<Vec<LinkedPage>>;
+}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sources that can hold credentials in the vault.
+pub const CREDENTIAL_SOURCES: &[&str] = &[
+    "jira",
+    "gitlab",
+    "confluence",
+    "github",
+    "gitlab_webhook",
+    "jira_webhook",
+];
+
+/// Vault entries that hold shared secrets for inbound webhooks rather than API credentials.
+/// They have no principal and cannot be probed against the source.
+pub const WEBHOOK_SECRET_SOURCES: &[&str] = &["gitlab_webhook", "jira_webhook"];
+
+/// Metadata about a stored credential. The secret itself is never part of this struct.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceCredential {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub source: String,
+    /// Non-secret account identifier, e.g. the Atlassian account email.
+    pub principal: Option<String>,
+    /// Master key the data key is currently wrapped with.
+    pub key_id: String,
+    pub last_tested_at: Option<DateTime<Utc>>,
+    pub last_test_ok: Option<bool>,
+    pub last_test_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A decrypted credential, handed only to connectors. `Debug` redacts the secret.
+#[derive(Clone)]
+pub struct SourceSecret {
+    pub source: String,
+    pub principal: Option<String>,
+    pub secret: String,
+}
+
+impl std::fmt::Debug for SourceSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceSecret")
+            .field("source", &self.source)
+            .field("principal", &self.principal)
+            .field("secret", &"<redacted>")
+            .finish()
+    }
+}
@@ -0,0 +1,419 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::credentials::models::{SourceCredential, SourceSecret, CREDENTIAL_SOURCES};
+use crate::credentials::repositories::CredentialRepository;
+use crate::credentials::vault::{MasterKeyring, SealedSecret, MASTER_KEYS_ENV};
+use ovia_common::error::{OviaError, OviaResult};
+
+const METADATA_COLUMNS: &str = "id, org_id, source, principal, key_id, last_tested_at, \
+     last_test_ok, last_test_error, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct PgCredentialRepository {
+    pool: PgPool,
+    keyring: Option<MasterKeyring>,
+}
+
+impl PgCredentialRepository {
+    /// Without a keyring, metadata can still be listed but nothing can be
+    /// stored, decrypted or rotated.
+    pub fn new(pool: PgPool, keyring: Option<MasterKeyring>) -> Self {
+        Self { pool, keyring }
+    }
+
+    fn keyring(&self) -> OviaResult<&MasterKeyring> {
+        self.keyring.as_ref().ok_or_else(|| {
+            OviaError::Config(format!(
+                "credential vault is not configured ({MASTER_KEYS_ENV} is not set)"
+            ))
+        })
+    }
+
+    /// Associated data binding a ciphertext to its org and source.
+    fn aad(org_id: Uuid, source: &str) -> String {
+        format!("{org_id}:{source}")
+    }
+
+    fn map_row(row: &sqlx::postgres::PgRow) -> SourceCredential {
+        SourceCredential {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            source: row.get("source"),
+            principal: row.get("principal"),
+            key_id: row.get("key_id"),
+            last_tested_at: row.get("last_tested_at"),
+            last_test_ok: row.get("last_test_ok"),
+            last_test_error: row.get("last_test_error"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    fn sealed_from_row(row: &sqlx::postgres::PgRow) -> SealedSecret {
+        SealedSecret {
+            ciphertext: row.get("secret_ciphertext"),
+            nonce: row.get("secret_nonce"),
+            wrapped_key: row.get("wrapped_key"),
+            key_nonce: row.get("key_nonce"),
+            key_id: row.get("key_id"),
+        }
+    }
+
+    fn open_row(&self, row: &sqlx::postgres::PgRow) -> OviaResult<SourceSecret> {
+        let org_id: Uuid = row.get("org_id");
+        let source: String = row.get("source");
+        let secret = self
+            .keyring()?
+            .open(&Self::aad(org_id, &source), &Self::sealed_from_row(row))?;
+        Ok(SourceSecret {
+            source,
+            principal: row.get("principal"),
+            secret,
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialRepository for PgCredentialRepository {
+    async fn list(&self, org_id: Uuid) -> OviaResult<Vec<SourceCredential>> {
+        let rows = sqlx::query(&format!(
+            "select {METADATA_COLUMNS} from source_credentials where org_id = $1 order by source"
+        ))
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(Self::map_row).collect())
+    }
+
+    async fn get(&self, org_id: Uuid, source: &str) -> OviaResult<Option<SourceCredential>> {
+        let row = sqlx::query(&format!(
+            "select {METADATA_COLUMNS} from source_credentials where org_id = $1 and source = $2"
+        ))
+        .bind(org_id)
+        .bind(source)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(Self::map_row))
+    }
+
+    async fn upsert(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        principal: Option<&str>,
+        secret: &str,
+    ) -> OviaResult<SourceCredential> {
+        if !CREDENTIAL_SOURCES.contains(&source) {
+            return Err(OviaError::Validation(format!(
+                "unsupported credential source: {source}"
+            )));
+        }
+        if secret.is_empty() {
+            return Err(OviaError::Validation(
+                "secret must not be empty".to_string(),
+            ));
+        }
+
+        let sealed = self.keyring()?.seal(&Self::aad(org_id, source), secret)?;
+        let row = sqlx::query(&format!(
+            "insert into source_credentials
+               (id, org_id, source, principal, secret_ciphertext, secret_nonce,
+                wrapped_key, key_nonce, key_id)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+             on conflict (org_id, source) do update set
+               principal = excluded.principal,
+               secret_ciphertext = excluded.secret_ciphertext,
+               secret_nonce = excluded.secret_nonce,
+               wrapped_key = excluded.wrapped_key,
+               key_nonce = excluded.key_nonce,
+               key_id = excluded.key_id,
+               last_tested_at = null,
+               last_test_ok = null,
+               last_test_error = null,
+               updated_at = now()
+             returning {METADATA_COLUMNS}"
+        ))
+        .bind(Uuid::new_v4())
+        .bind(org_id)
+        .bind(source)
+        .bind(principal)
+        .bind(&sealed.ciphertext)
+        .bind(&sealed.nonce)
+        .bind(&sealed.wrapped_key)
+        .bind(&sealed.key_nonce)
+        .bind(&sealed.key_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(Self::map_row(&row))
+    }
+
+    async fn reveal(&self, org_id: Uuid, source: &str) -> OviaResult<Option<SourceSecret>> {
+        let row = sqlx::query("select * from source_credentials where org_id = $1 and source = $2")
+            .bind(org_id)
+            .bind(source)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        row.as_ref().map(|r| self.open_row(r)).transpose()
+    }
+
+    async fn reveal_all(&self, org_id: Uuid) -> OviaResult<Vec<SourceSecret>> {
+        let rows =
+            sqlx::query("select * from source_credentials where org_id = $1 order by source")
+                .bind(org_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        rows.iter().map(|r| self.open_row(r)).collect()
+    }
+
+    async fn record_test(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        ok: bool,
+        error: Option<&str>,
+    ) -> OviaResult<SourceCredential> {
+        let row = sqlx::query(&format!(
+            "update source_credentials
+             set last_tested_at = now(), last_test_ok = $3, last_test_error = $4
+             where org_id = $1 and source = $2
+             returning {METADATA_COLUMNS}"
+        ))
+        .bind(org_id)
+        .bind(source)
+        .bind(ok)
+        .bind(error)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?
+        .ok_or_else(|| OviaError::NotFound(format!("no {source} credential for org")))?;
+
+        Ok(Self::map_row(&row))
+    }
+
+    async fn rotate_keys(&self, org_id: Uuid) -> OviaResult<usize> {
+        let keyring = self.keyring()?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let rows = sqlx::query(
+            "select * from source_credentials where org_id = $1 and key_id <> $2 for update",
+        )
+        .bind(org_id)
+        .bind(keyring.active_key_id())
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for row in &rows {
+            let id: Uuid = row.get("id");
+            let source: String = row.get("source");
+            let rewrapped =
+                keyring.rewrap(&Self::aad(org_id, &source), &Self::sealed_from_row(row))?;
+
+            sqlx::query(
+                "update source_credentials
+                 set wrapped_key = $2, key_nonce = $3, key_id = $4, updated_at = now()
+                 where id = $1",
+            )
+            .bind(id)
+            .bind(&rewrapped.wrapped_key)
+            .bind(&rewrapped.key_nonce)
+            .bind(&rewrapped.key_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+    use base64::Engine;
+
+    fn keyring(spec: &[(&str, u8)]) -> MasterKeyring {
+        let raw: Vec<String> = spec
+            .iter()
+            .map(|(id, b)| {
+                format!(
+                    "{id}:{}",
+                    base64::engine::general_purpose::STANDARD.encode([*b; 32])
+                )
+            })
+            .collect();
+        MasterKeyring::parse(&raw.join(",")).unwrap()
+    }
+
+    async fn test_pool() -> Option<PgPool> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        sqlx::query(
+            "create table if not exists source_credentials (
+               id uuid primary key,
+               org_id uuid not null,
+               source text not null,
+               principal text,
+               secret_ciphertext bytea not null,
+               secret_nonce bytea not null,
+               wrapped_key bytea not null,
+               key_nonce bytea not null,
+               key_id text not null,
+               last_tested_at timestamptz,
+               last_test_ok boolean,
+               last_test_error text,
+               created_at timestamptz not null default now(),
+               updated_at timestamptz not null default now()
+             )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists source_credentials_org_source_uidx
+             on source_credentials(org_id, source)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        Some(pool)
+    }
+
+    #[tokio::test]
+    async fn upsert_stores_ciphertext_and_reveal_decrypts() {
+        let pool = match test_pool().await {
+            Some(p) => p,
+            None => return,
+        };
+        let repo = PgCredentialRepository::new(pool.clone(), Some(keyring(&[("v1", 1)])));
+        let org_id = Uuid::new_v4();
+
+        let meta = repo
+            .upsert(org_id, "jira", Some("bot@acme.com"), "jira-token-xyz")
+            .await
+            .unwrap();
+        assert_eq!(meta.source, "jira");
+        assert_eq!(meta.principal.as_deref(), Some("bot@acme.com"));
+        assert_eq!(meta.key_id, "v1");
+
+        let stored: Vec<u8> = sqlx::query_scalar(
+            "select secret_ciphertext from source_credentials where org_id = $1",
+        )
+        .bind(org_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(!String::from_utf8_lossy(&stored).contains("jira-token-xyz"));
+
+        let secret = repo.reveal(org_id, "jira").await.unwrap().unwrap();
+        assert_eq!(secret.secret, "jira-token-xyz");
+        assert!(!format!("{secret:?}").contains("jira-token-xyz"));
+
+        assert!(repo.reveal(org_id, "gitlab").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_secret_and_resets_test_result() {
+        let pool = match test_pool().await {
+            Some(p) => p,
+            None => return,
+        };
+        let repo = PgCredentialRepository::new(pool, Some(keyring(&[("v1", 1)])));
+        let org_id = Uuid::new_v4();
+
+        repo.upsert(org_id, "gitlab", None, "old").await.unwrap();
+        let tested = repo
+            .record_test(org_id, "gitlab", false, Some("HTTP 401"))
+            .await
+            .unwrap();
+        assert_eq!(tested.last_test_ok, Some(false));
+
+        let meta = repo.upsert(org_id, "gitlab", None, "new").await.unwrap();
+        assert!(meta.last_test_ok.is_none());
+        assert_eq!(
+            repo.reveal(org_id, "gitlab").await.unwrap().unwrap().secret,
+            "new"
+        );
+        assert_eq!(repo.list(org_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn upsert_rejects_unknown_source() {
+        let pool = match test_pool().await {
+            Some(p) => p,
+            None => return,
+        };
+        let repo = PgCredentialRepository::new(pool, Some(keyring(&[("v1", 1)])));
+
+        let err = repo
+            .upsert(Uuid::new_v4(), "svn", None, "x")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OviaError::Validation(_)));
+    }
+
+    #[tokio::test]
+    async fn upsert_without_keyring_is_config_error() {
+        let pool = match test_pool().await {
+            Some(p) => p,
+            None => return,
+        };
+        let repo = PgCredentialRepository::new(pool, None);
+
+        let err = repo
+            .upsert(Uuid::new_v4(), "jira", None, "x")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, OviaError::Config(_)));
+    }
+
+    #[tokio::test]
+    async fn rotate_keys_rewraps_to_active_key() {
+        let pool = match test_pool().await {
+            Some(p) => p,
+            None => return,
+        };
+        let org_id = Uuid::new_v4();
+        let old = PgCredentialRepository::new(pool.clone(), Some(keyring(&[("old", 1)])));
+        old.upsert(org_id, "confluence", Some("me@acme.com"), "conf-token")
+            .await
+            .unwrap();
+
+        let rotated =
+            PgCredentialRepository::new(pool.clone(), Some(keyring(&[("new", 2), ("old", 1)])));
+        assert_eq!(rotated.rotate_keys(org_id).await.unwrap(), 1);
+        assert_eq!(rotated.rotate_keys(org_id).await.unwrap(), 0);
+        let meta = rotated.get(org_id, "confluence").await.unwrap().unwrap();
+        assert_eq!(meta.key_id, "new");
+
+        let new_only = PgCredentialRepository::new(pool, Some(keyring(&[("new", 2)])));
+        let secret = new_only
+            .reveal(org_id, "confluence")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(secret.secret, "conf-token");
+    }
+}
@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::credentials::models::{SourceCredential, SourceSecret};
+use ovia_common::error::OviaResult;
+
+#[async_trait]
+pub trait CredentialRepository: Send + Sync {
+    async fn list(&self, org_id: Uuid) -> OviaResult<Vec<SourceCredential>>;
+
+    async fn get(&self, org_id: Uuid, source: &str) -> OviaResult<Option<SourceCredential>>;
+
+    /// Encrypt and store a credential, replacing any existing one for the source.
+    /// Resets the last test result.
+    async fn upsert(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        principal: Option<&str>,
+        secret: &str,
+    ) -> OviaResult<SourceCredential>;
+
+    /// Decrypt the credential for a source.
+    async fn reveal(&self, org_id: Uuid, source: &str) -> OviaResult<Option<SourceSecret>>;
+
+    /// Decrypt every credential of an org.
+    async fn reveal_all(&self, org_id: Uuid) -> OviaResult<Vec<SourceSecret>>;
+
+    async fn record_test(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        ok: bool,
+        error: Option<&str>,
+    ) -> OviaResult<SourceCredential>;
+
+    /// Re-wrap every data key of the org that is not under the active master key.
+    /// Returns the number of credentials rewrapped.
+    async fn rotate_keys(&self, org_id: Uuid) -> OviaResult<usize>;
+}
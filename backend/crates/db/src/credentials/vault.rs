@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+
+use ovia_common::error::{OviaError, OviaResult};
+
+pub const MASTER_KEYS_ENV: &str = "CREDENTIALS_MASTER_KEYS";
+
+/// A secret encrypted under its own data key, with the data key wrapped by a master key.
+#[derive(Debug, Clone)]
+pub struct SealedSecret {
+    pub ciphertext: Vec<u8>,
+    pub nonce: Vec<u8>,
+    pub wrapped_key: Vec<u8>,
+    pub key_nonce: Vec<u8>,
+    pub key_id: String,
+}
+
+/// Master keys used to wrap per-secret data keys.
+///
+/// The first key is active and wraps every new secret; the rest are retired keys
+/// kept around only to unwrap secrets until they are rotated.
+#[derive(Clone)]
+pub struct MasterKeyring {
+    active_id: String,
+    keys: Arc<HashMap<String, Key<Aes256Gcm>>>,
+}
+
+impl std::fmt::Debug for MasterKeyring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ids: Vec<&String> = self.keys.keys().collect();
+        ids.sort();
+        f.debug_struct("MasterKeyring")
+            .field("active_id", &self.active_id)
+            .field("key_ids", &ids)
+            .finish()
+    }
+}
+
+impl MasterKeyring {
+    /// Load the keyring from `CREDENTIALS_MASTER_KEYS`.
+    ///
+    /// Returns `Ok(None)` when the variable is unset, `Err` when it is malformed.
+    pub fn from_env() -> OviaResult<Option<Self>> {
+        match std::env::var(MASTER_KEYS_ENV) {
+            Ok(raw) => Self::parse(&raw).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Parse `id:base64key[,id:base64key...]`. Keys are 32 random bytes, base64-encoded.
+    pub fn parse(raw: &str) -> OviaResult<Self> {
+        let mut active_id = None;
+        let mut keys = HashMap::new();
+
+        for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (id, encoded) = entry.split_once(':').ok_or_else(|| {
+                OviaError::Config(format!("{MASTER_KEYS_ENV}: expected id:key entries"))
+            })?;
+            let id = id.trim();
+            if id.is_empty() {
+                return Err(OviaError::Config(format!(
+                    "{MASTER_KEYS_ENV}: key id must not be empty"
+                )));
+            }
+            let bytes = base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .map_err(|_| {
+                    OviaError::Config(format!("{MASTER_KEYS_ENV}: key {id} is not valid base64"))
+                })?;
+            if bytes.len() != 32 {
+                return Err(OviaError::Config(format!(
+                    "{MASTER_KEYS_ENV}: key {id} must be 32 bytes, got {}",
+                    bytes.len()
+                )));
+            }
+            if keys
+                .insert(id.to_string(), *Key::<Aes256Gcm>::from_slice(&bytes))
+                .is_some()
+            {
+                return Err(OviaError::Config(format!(
+                    "{MASTER_KEYS_ENV}: duplicate key id {id}"
+                )));
+            }
+            active_id.get_or_insert_with(|| id.to_string());
+        }
+
+        let active_id = active_id
+            .ok_or_else(|| OviaError::Config(format!("{MASTER_KEYS_ENV} contains no keys")))?;
+        Ok(Self {
+            active_id,
+            keys: Arc::new(keys),
+        })
+    }
+
+    pub fn active_key_id(&self) -> &str {
+        &self.active_id
+    }
+
+    /// Encrypt `plaintext` under a fresh data key wrapped with the active master key.
+    /// `aad` binds the ciphertext to its row so it cannot be swapped to another one.
+    pub fn seal(&self, aad: &str, plaintext: &str) -> OviaResult<SealedSecret> {
+        let data_key = Aes256Gcm::generate_key(OsRng);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new(&data_key)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| OviaError::Internal("credential encryption failed".to_string()))?;
+
+        let (wrapped_key, key_nonce) = self.wrap(aad, &data_key)?;
+        Ok(SealedSecret {
+            ciphertext,
+            nonce: nonce.to_vec(),
+            wrapped_key,
+            key_nonce,
+            key_id: self.active_id.clone(),
+        })
+    }
+
+    /// Decrypt a sealed secret. Fails if its master key is not in the keyring or the
+    /// ciphertext does not match `aad`.
+    pub fn open(&self, aad: &str, sealed: &SealedSecret) -> OviaResult<String> {
+        let data_key = self.unwrap(aad, sealed)?;
+        let plaintext = Aes256Gcm::new(&data_key)
+            .decrypt(
+                nonce_from(&sealed.nonce)?,
+                Payload {
+                    msg: &sealed.ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| OviaError::Internal("credential decryption failed".to_string()))?;
+        String::from_utf8(plaintext)
+            .map_err(|_| OviaError::Internal("credential is not valid utf-8".to_string()))
+    }
+
+    /// Re-wrap the data key under the active master key. The secret ciphertext is unchanged.
+    pub fn rewrap(&self, aad: &str, sealed: &SealedSecret) -> OviaResult<SealedSecret> {
+        let data_key = self.unwrap(aad, sealed)?;
+        let (wrapped_key, key_nonce) = self.wrap(aad, &data_key)?;
+        Ok(SealedSecret {
+            ciphertext: sealed.ciphertext.clone(),
+            nonce: sealed.nonce.clone(),
+            wrapped_key,
+            key_nonce,
+            key_id: self.active_id.clone(),
+        })
+    }
+
+    fn wrap(&self, aad: &str, data_key: &Key<Aes256Gcm>) -> OviaResult<(Vec<u8>, Vec<u8>)> {
+        let master = &self.keys[&self.active_id];
+        let key_nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let wrapped = Aes256Gcm::new(master)
+            .encrypt(
+                &key_nonce,
+                Payload {
+                    msg: data_key.as_slice(),
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| OviaError::Internal("data key wrapping failed".to_string()))?;
+        Ok((wrapped, key_nonce.to_vec()))
+    }
+
+    fn unwrap(&self, aad: &str, sealed: &SealedSecret) -> OviaResult<Key<Aes256Gcm>> {
+        let master = self.keys.get(&sealed.key_id).ok_or_else(|| {
+            OviaError::Config(format!(
+                "master key {} is not in {MASTER_KEYS_ENV}",
+                sealed.key_id
+            ))
+        })?;
+        let data_key = Aes256Gcm::new(master)
+            .decrypt(
+                nonce_from(&sealed.key_nonce)?,
+                Payload {
+                    msg: &sealed.wrapped_key,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| OviaError::Internal("data key unwrapping failed".to_string()))?;
+        if data_key.len() != 32 {
+            return Err(OviaError::Internal("data key has wrong length".to_string()));
+        }
+        Ok(*Key::<Aes256Gcm>::from_slice(&data_key))
+    }
+}
+
+fn nonce_from(bytes: &[u8]) -> OviaResult<&Nonce<aes_gcm::aead::consts::U12>> {
+    if bytes.len() != 12 {
+        return Err(OviaError::Internal("nonce has wrong length".to_string()));
+    }
+    Ok(Nonce::from_slice(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded_key(byte: u8) -> String {
+        base64::engine::general_purpose::STANDARD.encode([byte; 32])
+    }
+
+    fn keyring(spec: &[(&str, u8)]) -> MasterKeyring {
+        let raw: Vec<String> = spec
+            .iter()
+            .map(|(id, b)| format!("{id}:{}", encoded_key(*b)))
+            .collect();
+        MasterKeyring::parse(&raw.join(",")).unwrap()
+    }
+
+    #[test]
+    fn seal_and_open_roundtrip() {
+        let ring = keyring(&[("v1", 1)]);
+        let sealed = ring.seal("org:jira", "api-token-123").unwrap();
+
+        assert_eq!(sealed.key_id, "v1");
+        assert!(!sealed
+            .ciphertext
+            .windows(b"api-token-123".len())
+            .any(|w| w == b"api-token-123"));
+        assert_eq!(ring.open("org:jira", &sealed).unwrap(), "api-token-123");
+    }
+
+    #[test]
+    fn open_fails_for_other_row() {
+        let ring = keyring(&[("v1", 1)]);
+        let sealed = ring.seal("org-a:jira", "secret").unwrap();
+
+        assert!(ring.open("org-b:jira", &sealed).is_err());
+    }
+
+    #[test]
+    fn rewrap_moves_secret_to_active_key() {
+        let old = keyring(&[("v1", 1)]);
+        let sealed = old.seal("org:gitlab", "glpat-abc").unwrap();
+
+        let rotated = keyring(&[("v2", 2), ("v1", 1)]);
+        assert_eq!(rotated.open("org:gitlab", &sealed).unwrap(), "glpat-abc");
+
+        let rewrapped = rotated.rewrap("org:gitlab", &sealed).unwrap();
+        assert_eq!(rewrapped.key_id, "v2");
+        assert_eq!(rewrapped.ciphertext, sealed.ciphertext);
+
+        // once rewrapped, the retired key can be dropped
+        let new_only = keyring(&[("v2", 2)]);
+        assert_eq!(
+            new_only.open("org:gitlab", &rewrapped).unwrap(),
+            "glpat-abc"
+        );
+        assert!(new_only.open("org:gitlab", &sealed).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_bad_input() {
+        assert!(MasterKeyring::parse("").is_err());
+        assert!(MasterKeyring::parse("no-separator").is_err());
+        assert!(MasterKeyring::parse("v1:not-base64!").is_err());
+        assert!(MasterKeyring::parse("v1:c2hvcnQ=").is_err());
+        let dup = format!("v1:{},v1:{}", encoded_key(1), encoded_key(2));
+        assert!(MasterKeyring::parse(&dup).is_err());
+    }
+
+    #[test]
+    fn debug_does_not_print_keys() {
+        let ring = keyring(&[("v1", 7)]);
+        let out = format!("{ring:?}");
+        assert!(out.contains("v1"));
+        assert!(!out.contains(&encoded_key(7)));
+    }
+}
@@ -6,6 +6,8 @@ use uuid::Uuid;
 pub struct GitlabProject {
     pub id: Uuid,
     pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
     pub gitlab_id: i64,
     pub name: String,
     pub path_with_namespace: String,
@@ -18,6 +20,8 @@ pub struct GitlabProject {
 pub struct GitlabMergeRequest {
     pub id: Uuid,
     pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
     pub gitlab_project_id: i64,
     pub gitlab_mr_iid: i64,
     pub title: String,
@@ -26,6 +30,7 @@ pub struct GitlabMergeRequest {
     pub labels: Vec<String>,
     pub created_at_gl: Option<DateTime<Utc>>,
     pub merged_at: Option<DateTime<Utc>>,
+    pub target_branch: Option<String>,
     pub web_url: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -35,6 +40,8 @@ pub struct GitlabMergeRequest {
 pub struct GitlabPipeline {
     pub id: Uuid,
     pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
     pub gitlab_project_id: i64,
     pub gitlab_pipeline_id: i64,
     pub status: String,
@@ -47,6 +54,236 @@ pub struct GitlabPipeline {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabPipelineJob {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
+    pub gitlab_project_id: i64,
+    pub gitlab_pipeline_id: i64,
+    pub gitlab_job_id: i64,
+    pub name: String,
+    pub stage: Option<String>,
+    pub status: String,
+    pub sha: Option<String>,
+    pub ref_name: Option<String>,
+    /// A later attempt of this job exists in the same pipeline.
+    pub retried: bool,
+    pub allow_failure: bool,
+    pub failure_reason: Option<String>,
+    pub created_at_gl: Option<DateTime<Utc>>,
+    pub started_at_gl: Option<DateTime<Utc>>,
+    pub finished_at_gl: Option<DateTime<Utc>>,
+    pub duration_secs: Option<f64>,
+    pub web_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabIssue {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub gitlab_project_id: i64,
+    pub gitlab_issue_iid: i64,
+    pub gitlab_issue_id: i64,
+    pub title: String,
+    pub state: String,
+    pub issue_type: Option<String>,
+    pub author_username: Option<String>,
+    pub assignee_usernames: Vec<String>,
+    pub labels: Vec<String>,
+    pub milestone_id: Option<i64>,
+    pub milestone_title: Option<String>,
+    pub created_at_gl: Option<DateTime<Utc>>,
+    pub updated_at_gl: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub web_url: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An issue being opened, closed or reopened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabIssueStateEvent {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub gitlab_project_id: i64,
+    pub gitlab_issue_iid: i64,
+    pub gitlab_event_id: i64,
+    pub state: String,
+    pub username: Option<String>,
+    pub created_at_gl: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabEnvironment {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub gitlab_project_id: i64,
+    pub gitlab_environment_id: i64,
+    pub name: String,
+    /// GitLab deployment tier: production, staging, testing, development or other.
+    pub tier: Option<String>,
+    pub state: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabDeployment {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub gitlab_project_id: i64,
+    pub gitlab_deployment_id: i64,
+    pub environment_name: String,
+    pub status: String,
+    pub sha: Option<String>,
+    pub ref_name: Option<String>,
+    pub deployer_username: Option<String>,
+    pub created_at_gl: Option<DateTime<Utc>>,
+    pub finished_at_gl: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A finished production deployment, with the deployer's team if known.
+#[derive(Debug, Clone)]
+pub struct DeploymentRow {
+    pub gitlab_project_id: i64,
+    pub project_path: Option<String>,
+    pub environment_name: String,
+    pub status: String,
+    pub team: Option<String>,
+    pub created_at_gl: DateTime<Utc>,
+    pub finished_at_gl: Option<DateTime<Utc>>,
+}
+
+/// Hours from an MR's first commit to the production deployment that shipped it.
+#[derive(Debug, Clone)]
+pub struct DeployLeadTimeRow {
+    pub gitlab_project_id: i64,
+    pub project_path: Option<String>,
+    pub team: Option<String>,
+    pub hours: f64,
+}
+
+/// A commit that is part of a merge request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabCommit {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
+    pub gitlab_project_id: i64,
+    pub sha: String,
+    pub gitlab_mr_iid: Option<i64>,
+    pub title: String,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub authored_at: Option<DateTime<Utc>>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub web_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Diff size of a merge request from the MR changes API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MrSize {
+    pub additions: i32,
+    pub deletions: i32,
+    pub changed_files: i32,
+}
+
+/// Size and review time of one merged MR, with the author's team if known.
+#[derive(Debug, Clone)]
+pub struct MrSizeRow {
+    pub team: Option<String>,
+    pub lines_changed: i32,
+    pub changed_files: i32,
+    pub review_hours: Option<f64>,
+}
+
+/// A note on a merge request, either a comment or a system event. The body is not kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabMrNote {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
+    pub gitlab_project_id: i64,
+    pub gitlab_mr_iid: i64,
+    pub gitlab_note_id: i64,
+    pub discussion_id: Option<String>,
+    pub author_username: Option<String>,
+    pub system: bool,
+    pub resolvable: bool,
+    pub resolved: bool,
+    pub created_at_gl: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A current approval of a merge request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabMrApproval {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    /// "gitlab" or "github"
+    pub provider: String,
+    pub gitlab_project_id: i64,
+    pub gitlab_mr_iid: i64,
+    pub approver_username: String,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Review activity of one person over a period, excluding their own MRs.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewerLoadRow {
+    /// The reviewer's account, or the first of the linked person's accounts.
+    pub username: String,
+    /// Set when the account is linked to a person.
+    pub person_id: Option<Uuid>,
+    pub display_name: Option<String>,
+    pub mrs_reviewed: i64,
+    pub comments: i64,
+    pub approvals: i64,
+}
+
+/// Reliability of one CI job of a project over a period.
+///
+/// A job is flaky on a commit when an attempt failed and a later attempt on the same
+/// sha passed. `flaky_count` is the number of such commits.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobReliabilityRow {
+    pub gitlab_project_id: i64,
+    pub project_path: Option<String>,
+    pub job_name: String,
+    pub stage: Option<String>,
+    pub runs: i64,
+    pub failures: i64,
+    pub flaky_count: i64,
+    pub success_rate: f64,
+    pub duration_p50_secs: Option<f64>,
+    /// Failed attempt of the most recent flaky commit.
+    pub last_flaky_url: Option<String>,
+    pub first_flaky_at: Option<DateTime<Utc>>,
+}
+
+/// Row returned by the lead-time query for one merged MR.
+///
+/// `merge_to_deploy_hours` is `None` when no successful pipeline ran on the
+/// target branch after the merge.
+#[derive(Debug, Clone)]
+pub struct LeadTimeRow {
+    pub first_commit_to_merge_hours: f64,
+    pub merge_to_deploy_hours: Option<f64>,
+}
+
 /// Row returned by the review-duration query (created_at_gl → merged_at in hours).
 #[derive(Debug, Clone)]
 pub struct ReviewDurationRow {
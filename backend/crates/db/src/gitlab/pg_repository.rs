@@ -3,7 +3,10 @@ use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 use crate::gitlab::models::{
-    GitlabMergeRequest, GitlabPipeline, GitlabProject, ReviewDurationRow, StaleMrRow,
+    DeployLeadTimeRow, DeploymentRow, GitlabCommit, GitlabDeployment, GitlabEnvironment,
+    GitlabIssue, GitlabIssueStateEvent, GitlabMergeRequest, GitlabMrApproval, GitlabMrNote,
+    GitlabPipeline, GitlabPipelineJob, GitlabProject, JobReliabilityRow, LeadTimeRow, MrSize,
+    MrSizeRow, ReviewDurationRow, ReviewerLoadRow, StaleMrRow,
 };
 use ovia_common::error::{OviaError, OviaResult};
 
@@ -21,9 +24,9 @@ impl PgGitlabRepository {
 
     pub async fn upsert_project(&self, p: &GitlabProject) -> OviaResult<()> {
         sqlx::query(
-            "insert into gitlab_projects (id, org_id, gitlab_id, name, path_with_namespace, web_url)
-             values ($1, $2, $3, $4, $5, $6)
-             on conflict (org_id, gitlab_id) do update set
+            "insert into gitlab_projects (id, org_id, gitlab_id, name, path_with_namespace, web_url, provider)
+             values ($1, $2, $3, $4, $5, $6, $7)
+             on conflict (org_id, provider, gitlab_id) do update set
                name = excluded.name,
                path_with_namespace = excluded.path_with_namespace,
                web_url = excluded.web_url,
@@ -35,25 +38,31 @@ impl PgGitlabRepository {
         .bind(&p.name)
         .bind(&p.path_with_namespace)
         .bind(&p.web_url)
+        .bind(&p.provider)
         .execute(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
         Ok(())
     }
 
+    /// Insert or update an MR. Author, creation and merge times are only overwritten
+    /// when known, so partial webhook payloads do not erase what polling stored.
+    /// Authors of erased people are stored under their pseudonym.
     pub async fn upsert_merge_request(&self, mr: &GitlabMergeRequest) -> OviaResult<()> {
         sqlx::query(
             "insert into gitlab_merge_requests
              (id, org_id, gitlab_project_id, gitlab_mr_iid, title, state, author_username,
-              labels, created_at_gl, merged_at, web_url)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-             on conflict (org_id, gitlab_project_id, gitlab_mr_iid) do update set
+              labels, created_at_gl, merged_at, target_branch, web_url, provider)
+             values ($1, $2, $3, $4, $5, $6, coalesce(erased_alias($2, $13, $7), $7), $8, $9,
+                     $10, $11, $12, $13)
+             on conflict (org_id, provider, gitlab_project_id, gitlab_mr_iid) do update set
                title = excluded.title,
                state = excluded.state,
-               author_username = excluded.author_username,
+               author_username = coalesce(excluded.author_username, gitlab_merge_requests.author_username),
                labels = excluded.labels,
-               created_at_gl = excluded.created_at_gl,
-               merged_at = excluded.merged_at,
+               created_at_gl = coalesce(excluded.created_at_gl, gitlab_merge_requests.created_at_gl),
+               merged_at = coalesce(excluded.merged_at, gitlab_merge_requests.merged_at),
+               target_branch = coalesce(excluded.target_branch, gitlab_merge_requests.target_branch),
                web_url = excluded.web_url,
                updated_at = now()",
         )
@@ -67,7 +76,9 @@ impl PgGitlabRepository {
         .bind(&mr.labels)
         .bind(mr.created_at_gl)
         .bind(mr.merged_at)
+        .bind(&mr.target_branch)
         .bind(&mr.web_url)
+        .bind(&mr.provider)
         .execute(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
@@ -78,9 +89,9 @@ impl PgGitlabRepository {
         sqlx::query(
             "insert into gitlab_pipelines
              (id, org_id, gitlab_project_id, gitlab_pipeline_id, status, ref_name,
-              created_at_gl, finished_at_gl, duration_secs, web_url)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-             on conflict (org_id, gitlab_pipeline_id) do update set
+              created_at_gl, finished_at_gl, duration_secs, web_url, provider)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             on conflict (org_id, provider, gitlab_pipeline_id) do update set
                status = excluded.status,
                ref_name = excluded.ref_name,
                created_at_gl = excluded.created_at_gl,
@@ -99,15 +110,370 @@ impl PgGitlabRepository {
         .bind(p.finished_at_gl)
         .bind(p.duration_secs)
         .bind(&p.web_url)
+        .bind(&p.provider)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Insert or update a commit. A commit seen again keeps its MR association
+    /// unless the new row carries one. Commits of erased people are stored under
+    /// their pseudonym, without the author email.
+    pub async fn upsert_commit(&self, c: &GitlabCommit) -> OviaResult<()> {
+        sqlx::query(
+            "with erased as (
+               select coalesce(erased_alias($2, $12, lower($8)),
+                               erased_alias($2, $12, $7)) as alias
+             )
+             insert into gitlab_commits
+             (id, org_id, gitlab_project_id, sha, gitlab_mr_iid, title, author_name,
+              author_email, authored_at, committed_at, web_url, provider)
+             select $1, $2, $3, $4, $5, $6, coalesce(e.alias, $7),
+                    case when e.alias is null then $8 end, $9, $10, $11, $12
+             from erased e
+             on conflict (org_id, provider, gitlab_project_id, sha) do update set
+               gitlab_mr_iid = coalesce(excluded.gitlab_mr_iid, gitlab_commits.gitlab_mr_iid),
+               title = excluded.title,
+               author_name = excluded.author_name,
+               author_email = excluded.author_email,
+               authored_at = excluded.authored_at,
+               committed_at = excluded.committed_at,
+               web_url = excluded.web_url,
+               updated_at = now()",
+        )
+        .bind(c.id)
+        .bind(c.org_id)
+        .bind(c.gitlab_project_id)
+        .bind(&c.sha)
+        .bind(c.gitlab_mr_iid)
+        .bind(&c.title)
+        .bind(&c.author_name)
+        .bind(&c.author_email)
+        .bind(c.authored_at)
+        .bind(c.committed_at)
+        .bind(&c.web_url)
+        .bind(&c.provider)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn upsert_pipeline_job(&self, j: &GitlabPipelineJob) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_pipeline_jobs
+             (id, org_id, gitlab_project_id, gitlab_pipeline_id, gitlab_job_id, name, stage,
+              status, sha, ref_name, retried, allow_failure, failure_reason, created_at_gl,
+              started_at_gl, finished_at_gl, duration_secs, web_url, provider)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                     $17, $18, $19)
+             on conflict (org_id, provider, gitlab_job_id) do update set
+               name = excluded.name,
+               stage = excluded.stage,
+               status = excluded.status,
+               sha = excluded.sha,
+               ref_name = excluded.ref_name,
+               retried = excluded.retried,
+               allow_failure = excluded.allow_failure,
+               failure_reason = excluded.failure_reason,
+               created_at_gl = excluded.created_at_gl,
+               started_at_gl = excluded.started_at_gl,
+               finished_at_gl = excluded.finished_at_gl,
+               duration_secs = excluded.duration_secs,
+               web_url = excluded.web_url,
+               updated_at = now()",
+        )
+        .bind(j.id)
+        .bind(j.org_id)
+        .bind(j.gitlab_project_id)
+        .bind(j.gitlab_pipeline_id)
+        .bind(j.gitlab_job_id)
+        .bind(&j.name)
+        .bind(&j.stage)
+        .bind(&j.status)
+        .bind(&j.sha)
+        .bind(&j.ref_name)
+        .bind(j.retried)
+        .bind(j.allow_failure)
+        .bind(&j.failure_reason)
+        .bind(j.created_at_gl)
+        .bind(j.started_at_gl)
+        .bind(j.finished_at_gl)
+        .bind(j.duration_secs)
+        .bind(&j.web_url)
+        .bind(&j.provider)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Upsert an issue (idempotent on org_id + project + iid). Erased people are
+    /// stored under their pseudonym.
+    pub async fn upsert_issue(&self, i: &GitlabIssue) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_issues
+             (id, org_id, gitlab_project_id, gitlab_issue_iid, gitlab_issue_id, title, state,
+              issue_type, author_username, assignee_usernames, labels, milestone_id,
+              milestone_title, created_at_gl, updated_at_gl, closed_at, web_url)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, coalesce(erased_alias($2, 'gitlab', $9), $9),
+                     array(select coalesce(erased_alias($2, 'gitlab', a.username), a.username)
+                           from unnest($10::text[]) with ordinality a(username, n)
+                           order by a.n),
+                     $11, $12, $13, $14, $15, $16, $17)
+             on conflict (org_id, gitlab_project_id, gitlab_issue_iid) do update set
+               title = excluded.title,
+               state = excluded.state,
+               issue_type = excluded.issue_type,
+               author_username = excluded.author_username,
+               assignee_usernames = excluded.assignee_usernames,
+               labels = excluded.labels,
+               milestone_id = excluded.milestone_id,
+               milestone_title = excluded.milestone_title,
+               created_at_gl = excluded.created_at_gl,
+               updated_at_gl = excluded.updated_at_gl,
+               closed_at = excluded.closed_at,
+               web_url = excluded.web_url,
+               updated_at = now()",
+        )
+        .bind(i.id)
+        .bind(i.org_id)
+        .bind(i.gitlab_project_id)
+        .bind(i.gitlab_issue_iid)
+        .bind(i.gitlab_issue_id)
+        .bind(&i.title)
+        .bind(&i.state)
+        .bind(&i.issue_type)
+        .bind(&i.author_username)
+        .bind(&i.assignee_usernames)
+        .bind(&i.labels)
+        .bind(i.milestone_id)
+        .bind(&i.milestone_title)
+        .bind(i.created_at_gl)
+        .bind(i.updated_at_gl)
+        .bind(i.closed_at)
+        .bind(&i.web_url)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Insert an issue state event. Events never change, so repeats are ignored.
+    pub async fn insert_issue_state_event(&self, e: &GitlabIssueStateEvent) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_issue_state_events
+             (id, org_id, gitlab_project_id, gitlab_issue_iid, gitlab_event_id, state, username,
+              created_at_gl)
+             values ($1, $2, $3, $4, $5, $6, coalesce(erased_alias($2, 'gitlab', $7), $7), $8)
+             on conflict (org_id, gitlab_event_id) do nothing",
+        )
+        .bind(e.id)
+        .bind(e.org_id)
+        .bind(e.gitlab_project_id)
+        .bind(e.gitlab_issue_iid)
+        .bind(e.gitlab_event_id)
+        .bind(&e.state)
+        .bind(&e.username)
+        .bind(e.created_at_gl)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn upsert_environment(&self, e: &GitlabEnvironment) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_environments
+             (id, org_id, gitlab_project_id, gitlab_environment_id, name, tier, state)
+             values ($1, $2, $3, $4, $5, $6, $7)
+             on conflict (org_id, gitlab_project_id, gitlab_environment_id) do update set
+               name = excluded.name,
+               tier = excluded.tier,
+               state = excluded.state,
+               updated_at = now()",
+        )
+        .bind(e.id)
+        .bind(e.org_id)
+        .bind(e.gitlab_project_id)
+        .bind(e.gitlab_environment_id)
+        .bind(&e.name)
+        .bind(&e.tier)
+        .bind(&e.state)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Insert or update a deployment. Deployers who were erased are stored under
+    /// their pseudonym.
+    pub async fn upsert_deployment(&self, d: &GitlabDeployment) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_deployments
+             (id, org_id, gitlab_project_id, gitlab_deployment_id, environment_name, status,
+              sha, ref_name, deployer_username, created_at_gl, finished_at_gl)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, coalesce(erased_alias($2, 'gitlab', $9), $9),
+                     $10, $11)
+             on conflict (org_id, gitlab_project_id, gitlab_deployment_id) do update set
+               environment_name = excluded.environment_name,
+               status = excluded.status,
+               sha = excluded.sha,
+               ref_name = excluded.ref_name,
+               deployer_username = excluded.deployer_username,
+               created_at_gl = excluded.created_at_gl,
+               finished_at_gl = excluded.finished_at_gl,
+               updated_at = now()",
+        )
+        .bind(d.id)
+        .bind(d.org_id)
+        .bind(d.gitlab_project_id)
+        .bind(d.gitlab_deployment_id)
+        .bind(&d.environment_name)
+        .bind(&d.status)
+        .bind(&d.sha)
+        .bind(&d.ref_name)
+        .bind(&d.deployer_username)
+        .bind(d.created_at_gl)
+        .bind(d.finished_at_gl)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Store the diff size of an MR. Sizes are not part of the MR listing, so
+    /// `upsert_merge_request` leaves these columns alone.
+    pub async fn update_mr_size(
+        &self,
+        org_id: Uuid,
+        project_id: i64,
+        mr_iid: i64,
+        size: MrSize,
+    ) -> OviaResult<()> {
+        sqlx::query(
+            "update gitlab_merge_requests
+             set additions = $4, deletions = $5, changed_files = $6, updated_at = now()
+             where org_id = $1 and provider = 'gitlab'
+               and gitlab_project_id = $2 and gitlab_mr_iid = $3",
+        )
+        .bind(org_id)
+        .bind(project_id)
+        .bind(mr_iid)
+        .bind(size.additions)
+        .bind(size.deletions)
+        .bind(size.changed_files)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Insert or update an MR note. Authors of erased people are stored under their
+    /// pseudonym.
+    pub async fn upsert_note(&self, n: &GitlabMrNote) -> OviaResult<()> {
+        sqlx::query(
+            "insert into gitlab_mr_notes
+             (id, org_id, gitlab_project_id, gitlab_mr_iid, gitlab_note_id, discussion_id,
+              author_username, system, resolvable, resolved, created_at_gl, provider)
+             values ($1, $2, $3, $4, $5, $6, coalesce(erased_alias($2, $12, $7), $7), $8, $9,
+                     $10, $11, $12)
+             on conflict (org_id, provider, gitlab_project_id, gitlab_note_id) do update set
+               discussion_id = excluded.discussion_id,
+               author_username = excluded.author_username,
+               resolvable = excluded.resolvable,
+               resolved = excluded.resolved,
+               updated_at = now()",
+        )
+        .bind(n.id)
+        .bind(n.org_id)
+        .bind(n.gitlab_project_id)
+        .bind(n.gitlab_mr_iid)
+        .bind(n.gitlab_note_id)
+        .bind(&n.discussion_id)
+        .bind(&n.author_username)
+        .bind(n.system)
+        .bind(n.resolvable)
+        .bind(n.resolved)
+        .bind(n.created_at_gl)
+        .bind(&n.provider)
         .execute(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
         Ok(())
     }
 
+    /// Replace the approvals of one MR with the current set, so revoked approvals
+    /// disappear. A kept approval retains its known `approved_at` if the new row has none.
+    pub async fn replace_approvals(
+        &self,
+        org_id: Uuid,
+        provider: &str,
+        project_id: i64,
+        mr_iid: i64,
+        approvals: &[GitlabMrApproval],
+    ) -> OviaResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let approvers: Vec<&str> = approvals
+            .iter()
+            .map(|a| a.approver_username.as_str())
+            .collect();
+        sqlx::query(
+            "delete from gitlab_mr_approvals
+             where org_id = $1 and provider = $5 and gitlab_project_id = $2 and gitlab_mr_iid = $3
+               and approver_username <> all(
+                 array(select coalesce(erased_alias($1, $5, a), a) from unnest($4::text[]) a)
+               )",
+        )
+        .bind(org_id)
+        .bind(project_id)
+        .bind(mr_iid)
+        .bind(&approvers)
+        .bind(provider)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for a in approvals {
+            sqlx::query(
+                "insert into gitlab_mr_approvals
+                 (id, org_id, gitlab_project_id, gitlab_mr_iid, approver_username, approved_at,
+                  provider)
+                 values ($1, $2, $3, $4, coalesce(erased_alias($2, $7, $5), $5), $6, $7)
+                 on conflict (org_id, provider, gitlab_project_id, gitlab_mr_iid, approver_username)
+                 do update set
+                   approved_at = coalesce(excluded.approved_at, gitlab_mr_approvals.approved_at)",
+            )
+            .bind(a.id)
+            .bind(org_id)
+            .bind(project_id)
+            .bind(mr_iid)
+            .bind(&a.approver_username)
+            .bind(a.approved_at)
+            .bind(provider)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
     // ── KPI query helpers ───────────────────────────────────────────
 
     /// Count merged MRs in [from, to].
+    ///
+    /// Merge queries skip MRs whose author had already left (`people.left_at`)
+    /// by the merge date.
     pub async fn count_merged_mrs(
         &self,
         org_id: Uuid,
@@ -115,9 +481,10 @@ impl PgGitlabRepository {
         to: NaiveDate,
     ) -> OviaResult<i64> {
         let count: i64 = sqlx::query_scalar(
-            "select count(*) from gitlab_merge_requests
+            "select count(*) from gitlab_merge_requests mr
              where org_id = $1 and state = 'merged'
-               and merged_at >= $2::date and merged_at < ($3::date + interval '1 day')",
+               and merged_at >= $2::date and merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)",
         )
         .bind(org_id)
         .bind(from)
@@ -138,9 +505,10 @@ impl PgGitlabRepository {
     ) -> OviaResult<i64> {
         let labels_vec: Vec<String> = labels.iter().map(|s| s.to_string()).collect();
         let count: i64 = sqlx::query_scalar(
-            "select count(*) from gitlab_merge_requests
+            "select count(*) from gitlab_merge_requests mr
              where org_id = $1 and state = 'merged'
                and merged_at >= $2::date and merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
                and labels && $4",
         )
         .bind(org_id)
@@ -162,9 +530,10 @@ impl PgGitlabRepository {
         label: &str,
     ) -> OviaResult<i64> {
         let count: i64 = sqlx::query_scalar(
-            "select count(*) from gitlab_merge_requests
+            "select count(*) from gitlab_merge_requests mr
              where org_id = $1 and state = 'merged'
                and merged_at >= $2::date and merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
                and $4 = any(labels)",
         )
         .bind(org_id)
@@ -186,9 +555,10 @@ impl PgGitlabRepository {
     ) -> OviaResult<Vec<ReviewDurationRow>> {
         let rows = sqlx::query(
             "select (extract(epoch from (merged_at - created_at_gl)) / 3600.0)::float8 as hours
-             from gitlab_merge_requests
+             from gitlab_merge_requests mr
              where org_id = $1 and state = 'merged'
                and merged_at >= $2::date and merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
                and created_at_gl is not null
              order by hours asc",
         )
@@ -207,6 +577,233 @@ impl PgGitlabRepository {
             .collect())
     }
 
+    /// Hours from opening to the first review for MRs merged in [from, to].
+    ///
+    /// A review is a non-system note or an approval by someone other than the author.
+    /// MRs merged without any review are left out.
+    pub async fn get_first_review_hours(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<f64>> {
+        let rows = sqlx::query(
+            "select (extract(epoch from (fr.first_review_at - mr.created_at_gl)) / 3600.0)::float8
+                      as hours
+             from gitlab_merge_requests mr
+             join lateral (
+               select min(r.at) as first_review_at
+               from (
+                 select n.created_at_gl as at
+                 from gitlab_mr_notes n
+                 where n.org_id = mr.org_id and n.provider = mr.provider
+                 and n.gitlab_project_id = mr.gitlab_project_id
+                   and n.gitlab_mr_iid = mr.gitlab_mr_iid and not n.system
+                   and n.author_username is distinct from mr.author_username
+                 union all
+                 select a.approved_at
+                 from gitlab_mr_approvals a
+                 where a.org_id = mr.org_id and a.provider = mr.provider
+                   and a.gitlab_project_id = mr.gitlab_project_id
+                   and a.gitlab_mr_iid = mr.gitlab_mr_iid
+                   and a.approver_username is distinct from mr.author_username
+               ) r
+             ) fr on fr.first_review_at is not null
+             where mr.org_id = $1 and mr.state = 'merged'
+               and mr.merged_at >= $2::date and mr.merged_at < ($3::date + interval '1 day')
+               and mr.created_at_gl is not null
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
+             order by hours asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|r| r.get::<f64, _>("hours")).collect())
+    }
+
+    /// Review rounds per reviewed MR merged in [from, to].
+    ///
+    /// A round is a run of reviewer comments ended by author activity: an author
+    /// comment or a system note the author triggered, such as pushing commits.
+    pub async fn get_review_rounds(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<i64>> {
+        let rows = sqlx::query(
+            "select count(*) filter (where ev.by_reviewer and not coalesce(ev.prev_by_reviewer, false))
+                      as rounds
+             from gitlab_merge_requests mr
+             join lateral (
+               select e.by_reviewer,
+                      lag(e.by_reviewer) over (order by e.created_at_gl, e.gitlab_note_id)
+                        as prev_by_reviewer
+               from (
+                 select n.created_at_gl, n.gitlab_note_id,
+                        n.author_username is distinct from mr.author_username as by_reviewer
+                 from gitlab_mr_notes n
+                 where n.org_id = mr.org_id and n.provider = mr.provider
+                 and n.gitlab_project_id = mr.gitlab_project_id
+                   and n.gitlab_mr_iid = mr.gitlab_mr_iid
+                   and (n.author_username = mr.author_username or not n.system)
+               ) e
+             ) ev on true
+             where mr.org_id = $1 and mr.state = 'merged'
+               and mr.merged_at >= $2::date and mr.merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
+             group by mr.id
+             having bool_or(ev.by_reviewer)
+             order by rounds asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|r| r.get::<i64, _>("rounds")).collect())
+    }
+
+    /// Comments and approvals per reviewer in [from, to], busiest first.
+    /// Activity on one's own MRs is not review work and is left out.
+    ///
+    /// Accounts linked to the same person are counted together under that person;
+    /// unlinked accounts stand alone. Activity after the reviewer left is skipped.
+    pub async fn get_reviewer_load(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<ReviewerLoadRow>> {
+        let rows = sqlx::query(
+            "with activity as (
+               select n.author_username as username, n.provider, n.gitlab_project_id, n.gitlab_mr_iid,
+                      n.created_at_gl as at, 1 as comments, 0 as approvals
+               from gitlab_mr_notes n
+               join gitlab_merge_requests mr
+                 on mr.org_id = n.org_id and mr.provider = n.provider
+                and mr.gitlab_project_id = n.gitlab_project_id
+                and mr.gitlab_mr_iid = n.gitlab_mr_iid
+               where n.org_id = $1 and not n.system and n.author_username is not null
+                 and n.author_username is distinct from mr.author_username
+                 and n.created_at_gl >= $2::date
+                 and n.created_at_gl < ($3::date + interval '1 day')
+               union all
+               select a.approver_username, a.provider, a.gitlab_project_id, a.gitlab_mr_iid,
+                      a.approved_at, 0, 1
+               from gitlab_mr_approvals a
+               join gitlab_merge_requests mr
+                 on mr.org_id = a.org_id and mr.provider = a.provider
+                and mr.gitlab_project_id = a.gitlab_project_id
+                and mr.gitlab_mr_iid = a.gitlab_mr_iid
+               where a.org_id = $1
+                 and a.approver_username is distinct from mr.author_username
+                 and a.approved_at >= $2::date
+                 and a.approved_at < ($3::date + interval '1 day')
+             )
+             select min(act.username) as username, rp.person_id, rp.display_name,
+                    count(distinct (act.provider, act.gitlab_project_id, act.gitlab_mr_iid))
+                      as mrs_reviewed,
+                    sum(act.comments)::bigint as comments,
+                    sum(act.approvals)::bigint as approvals
+             from activity act
+             left join lateral (
+               select pe.id as person_id, pe.display_name
+               from identities i
+               join person_identity_links l
+                 on l.identity_id = i.id and l.org_id = i.org_id and l.valid_to is null
+               join people pe on pe.id = l.person_id
+               where i.org_id = $1 and i.source = act.provider and i.username = act.username
+               limit 1
+             ) rp on true
+             where not is_departed($1, act.provider, act.username, act.at)
+             group by rp.person_id, rp.display_name,
+                      case when rp.person_id is null then act.username end
+             order by mrs_reviewed desc, username asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| ReviewerLoadRow {
+                username: r.get("username"),
+                person_id: r.get("person_id"),
+                display_name: r.get("display_name"),
+                mrs_reviewed: r.get("mrs_reviewed"),
+                comments: r.get("comments"),
+                approvals: r.get("approvals"),
+            })
+            .collect())
+    }
+
+    /// Lead time parts for MRs merged in [from, to] that have commits.
+    ///
+    /// The clock starts at the earliest authored commit of the MR (authored, not
+    /// committed, so rebases do not reset it). The deploy is the first successful
+    /// pipeline on the MR's target branch created after the merge. The KPI snapshot
+    /// only uses these when no MR in the period reached a production deployment
+    /// (see `get_deploy_lead_times_hours`).
+    pub async fn get_lead_times_hours(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<LeadTimeRow>> {
+        let rows = sqlx::query(
+            "select
+               (extract(epoch from (mr.merged_at - fc.first_commit_at)) / 3600.0)::float8
+                 as first_commit_to_merge_hours,
+               (extract(epoch from (dp.deployed_at - mr.merged_at)) / 3600.0)::float8
+                 as merge_to_deploy_hours
+             from gitlab_merge_requests mr
+             join lateral (
+               select min(c.authored_at) as first_commit_at
+               from gitlab_commits c
+               where c.org_id = mr.org_id and c.provider = mr.provider
+                 and c.gitlab_project_id = mr.gitlab_project_id
+                 and c.gitlab_mr_iid = mr.gitlab_mr_iid
+             ) fc on fc.first_commit_at is not null
+             left join lateral (
+               select min(p.finished_at_gl) as deployed_at
+               from gitlab_pipelines p
+               where p.org_id = mr.org_id and p.provider = mr.provider
+                 and p.gitlab_project_id = mr.gitlab_project_id
+                 and p.status = 'success' and p.ref_name = mr.target_branch
+                 and p.created_at_gl >= mr.merged_at
+             ) dp on true
+             where mr.org_id = $1 and mr.state = 'merged'
+               and mr.merged_at >= $2::date and mr.merged_at < ($3::date + interval '1 day')
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
+               and fc.first_commit_at <= mr.merged_at
+             order by mr.merged_at asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| LeadTimeRow {
+                first_commit_to_merge_hours: r.get("first_commit_to_merge_hours"),
+                merge_to_deploy_hours: r.get("merge_to_deploy_hours"),
+            })
+            .collect())
+    }
+
     /// Count pipelines with a given status in [from, to].
     pub async fn count_pipelines_by_status(
         &self,
@@ -287,20 +884,37 @@ impl PgGitlabRepository {
             .collect())
     }
 
-    /// List failed pipelines in a period for risk item generation.
-    pub async fn list_failed_pipelines(
+    /// Size of MRs merged in [from, to] that have one, with the open-to-merge hours
+    /// and the team of the person linked to the author.
+    pub async fn get_merged_mr_sizes(
         &self,
         org_id: Uuid,
         from: NaiveDate,
         to: NaiveDate,
-    ) -> OviaResult<Vec<GitlabPipeline>> {
+    ) -> OviaResult<Vec<MrSizeRow>> {
         let rows = sqlx::query(
-            "select id, org_id, gitlab_project_id, gitlab_pipeline_id, status, ref_name,
-                    created_at_gl, finished_at_gl, duration_secs, web_url, created_at, updated_at
-             from gitlab_pipelines
-             where org_id = $1 and status = 'failed'
-               and created_at_gl >= $2::date and created_at_gl < ($3::date + interval '1 day')
-             order by created_at_gl desc",
+            "select t.team,
+                    (mr.additions + mr.deletions) as lines_changed,
+                    mr.changed_files,
+                    (extract(epoch from (mr.merged_at - mr.created_at_gl)) / 3600.0)::float8
+                      as review_hours
+             from gitlab_merge_requests mr
+             left join lateral (
+               select p.team
+               from identities i
+               join person_identity_links l
+                 on l.identity_id = i.id and l.org_id = i.org_id and l.valid_to is null
+               join people p on p.id = l.person_id
+               where i.org_id = mr.org_id and i.source = mr.provider
+                 and i.username = mr.author_username
+               limit 1
+             ) t on true
+             where mr.org_id = $1 and mr.state = 'merged'
+               and mr.merged_at >= $2::date and mr.merged_at < ($3::date + interval '1 day')
+               and mr.additions is not null and mr.deletions is not null
+               and mr.changed_files is not null
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, mr.merged_at)
+             order by mr.merged_at asc",
         )
         .bind(org_id)
         .bind(from)
@@ -309,53 +923,352 @@ impl PgGitlabRepository {
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
 
-        Ok(rows.iter().map(map_pipeline_row).collect())
-    }
-}
-
-fn map_pipeline_row(row: &sqlx::postgres::PgRow) -> GitlabPipeline {
-    GitlabPipeline {
-        id: row.get("id"),
-        org_id: row.get("org_id"),
-        gitlab_project_id: row.get("gitlab_project_id"),
-        gitlab_pipeline_id: row.get("gitlab_pipeline_id"),
-        status: row.get("status"),
-        ref_name: row.get("ref_name"),
-        created_at_gl: row.get("created_at_gl"),
-        finished_at_gl: row.get("finished_at_gl"),
-        duration_secs: row.get("duration_secs"),
-        web_url: row.get("web_url"),
-        created_at: row.get("created_at"),
-        updated_at: row.get("updated_at"),
+        Ok(rows
+            .iter()
+            .map(|r| MrSizeRow {
+                team: r.get("team"),
+                lines_changed: r.get("lines_changed"),
+                changed_files: r.get("changed_files"),
+                review_hours: r.get("review_hours"),
+            })
+            .collect())
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::create_pool;
-    use chrono::{DateTime, Utc};
-
-    async fn test_repo() -> Option<(PgGitlabRepository, PgPool)> {
-        let url = std::env::var("TEST_DATABASE_URL").ok()?;
-        let pool = create_pool(&url).await.expect("db should connect");
 
-        // Apply migration inline for test isolation
-        sqlx::query(
-            "create table if not exists gitlab_projects (
-              id uuid primary key default gen_random_uuid(),
-              org_id uuid not null, gitlab_id bigint not null,
-              name text not null, path_with_namespace text not null, web_url text not null,
-              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
-            )",
+    /// List open MRs with at least `min_lines` changed lines that have been open for
+    /// `min_days` or more, for risk item generation.
+    pub async fn list_oversized_open_mrs(
+        &self,
+        org_id: Uuid,
+        min_lines: i32,
+        min_days: i32,
+    ) -> OviaResult<Vec<StaleMrRow>> {
+        let rows = sqlx::query(
+            "select gitlab_mr_iid, gitlab_project_id, title, author_username,
+                    extract(day from now() - created_at_gl)::int as age_days,
+                    web_url
+             from gitlab_merge_requests
+             where org_id = $1 and state = 'opened'
+               and additions + deletions >= $2
+               and created_at_gl < now() - ($3 || ' days')::interval
+             order by additions + deletions desc",
         )
-        .execute(&pool)
+        .bind(org_id)
+        .bind(min_lines)
+        .bind(min_days)
+        .fetch_all(&self.pool)
         .await
-        .ok()?;
-        sqlx::query(
-            "create unique index if not exists gitlab_projects_org_gl_uidx on gitlab_projects(org_id, gitlab_id)",
-        )
-        .execute(&pool)
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| StaleMrRow {
+                gitlab_mr_iid: r.get("gitlab_mr_iid"),
+                gitlab_project_id: r.get("gitlab_project_id"),
+                title: r.get("title"),
+                author_username: r.get("author_username"),
+                age_days: r.get("age_days"),
+                web_url: r.get("web_url"),
+            })
+            .collect())
+    }
+
+    /// Successful and failed production deployments started in [from, to], oldest
+    /// first. An environment counts as production by its GitLab tier, or by the name
+    /// `production`/`prod` when the environment has not been synced. Deployments
+    /// started after their deployer left are skipped.
+    pub async fn list_production_deployments(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<DeploymentRow>> {
+        let rows = sqlx::query(
+            "select d.gitlab_project_id, p.path_with_namespace as project_path,
+                    d.environment_name, d.status, t.team, d.created_at_gl, d.finished_at_gl
+             from gitlab_deployments d
+             left join gitlab_environments e
+               on e.org_id = d.org_id and e.gitlab_project_id = d.gitlab_project_id
+              and e.name = d.environment_name
+             left join gitlab_projects p
+               on p.org_id = d.org_id and p.provider = 'gitlab' and p.gitlab_id = d.gitlab_project_id
+             left join lateral (
+               select pe.team
+               from identities i
+               join person_identity_links l
+                 on l.identity_id = i.id and l.org_id = i.org_id and l.valid_to is null
+               join people pe on pe.id = l.person_id
+               where i.org_id = d.org_id and i.source = 'gitlab'
+                 and i.username = d.deployer_username
+               limit 1
+             ) t on true
+             where d.org_id = $1 and d.status in ('success', 'failed')
+               and d.created_at_gl >= $2::date and d.created_at_gl < ($3::date + interval '1 day')
+               and coalesce(e.tier, case when lower(d.environment_name) in ('production', 'prod')
+                                         then 'production' end) = 'production'
+               and not is_departed(d.org_id, 'gitlab', d.deployer_username, d.created_at_gl)
+             order by d.created_at_gl asc, d.gitlab_deployment_id asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| DeploymentRow {
+                gitlab_project_id: r.get("gitlab_project_id"),
+                project_path: r.get("project_path"),
+                environment_name: r.get("environment_name"),
+                status: r.get("status"),
+                team: r.get("team"),
+                created_at_gl: r.get("created_at_gl"),
+                finished_at_gl: r.get("finished_at_gl"),
+            })
+            .collect())
+    }
+
+    /// Success rate, flakiness and median duration of each CI job name per project, over
+    /// finished jobs created in [from, to]. Flaky jobs come first.
+    pub async fn get_job_reliability(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<JobReliabilityRow>> {
+        let rows = sqlx::query(
+            "with jobs as (
+               select j.provider, j.gitlab_project_id, j.name, j.stage, j.status, j.sha, j.created_at_gl,
+                      j.duration_secs, j.web_url
+               from gitlab_pipeline_jobs j
+               where j.org_id = $1 and j.status in ('success', 'failed')
+                 and j.created_at_gl >= $2::date
+                 and j.created_at_gl < ($3::date + interval '1 day')
+             ),
+             flaky as (
+               select f.provider, f.gitlab_project_id, f.name, f.sha, f.created_at_gl, f.web_url
+               from jobs f
+               where f.status = 'failed' and f.sha is not null
+                 and exists (
+                   select 1 from jobs s
+                   where s.provider = f.provider and s.gitlab_project_id = f.gitlab_project_id
+                     and s.name = f.name
+                     and s.sha = f.sha and s.status = 'success'
+                     and s.created_at_gl > f.created_at_gl
+                 )
+             ),
+             stats as (
+               select provider, gitlab_project_id, name, max(stage) as stage,
+                      count(*) as runs,
+                      count(*) filter (where status = 'failed') as failures,
+                      (percentile_cont(0.5) within group (order by duration_secs))::float8
+                        as duration_p50_secs
+               from jobs
+               group by provider, gitlab_project_id, name
+             )
+             select st.gitlab_project_id, p.path_with_namespace as project_path,
+                    st.name as job_name, st.stage, st.runs, st.failures,
+                    coalesce(fl.flaky_count, 0) as flaky_count,
+                    ((st.runs - st.failures)::float8 / st.runs) as success_rate,
+                    st.duration_p50_secs, fl.last_flaky_url, fl.first_flaky_at
+             from stats st
+             left join gitlab_projects p
+               on p.org_id = $1 and p.provider = st.provider and p.gitlab_id = st.gitlab_project_id
+             left join lateral (
+               select count(distinct f.sha) as flaky_count,
+                      min(f.created_at_gl) as first_flaky_at,
+                      (array_agg(f.web_url order by f.created_at_gl desc))[1] as last_flaky_url
+               from flaky f
+               where f.provider = st.provider and f.gitlab_project_id = st.gitlab_project_id
+                 and f.name = st.name
+               having count(*) > 0
+             ) fl on true
+             order by flaky_count desc, st.failures desc, st.name asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| JobReliabilityRow {
+                gitlab_project_id: r.get("gitlab_project_id"),
+                project_path: r.get("project_path"),
+                job_name: r.get("job_name"),
+                stage: r.get("stage"),
+                runs: r.get("runs"),
+                failures: r.get("failures"),
+                flaky_count: r.get("flaky_count"),
+                success_rate: r.get("success_rate"),
+                duration_p50_secs: r.get("duration_p50_secs"),
+                last_flaky_url: r.get("last_flaky_url"),
+                first_flaky_at: r.get("first_flaky_at"),
+            })
+            .collect())
+    }
+
+    /// Lead time for changes for MRs merged in [from, to] that reached production.
+    ///
+    /// The clock runs from the MR's first authored commit (or its creation when no
+    /// commits are synced) to the end of the first successful production deployment
+    /// of the MR's target branch started after the merge. Redeploys of a sha already
+    /// deployed to the environment are rollbacks and do not count. The team is the MR
+    /// author's, and MRs deployed after their author left are skipped.
+    pub async fn get_deploy_lead_times_hours(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<DeployLeadTimeRow>> {
+        let rows = sqlx::query(
+            "select mr.gitlab_project_id, p.path_with_namespace as project_path, t.team,
+                    (extract(epoch from (dp.deployed_at
+                       - coalesce(fc.first_commit_at, mr.created_at_gl))) / 3600.0)::float8
+                      as hours
+             from gitlab_merge_requests mr
+             left join lateral (
+               select min(c.authored_at) as first_commit_at
+               from gitlab_commits c
+               where c.org_id = mr.org_id and c.provider = mr.provider
+                 and c.gitlab_project_id = mr.gitlab_project_id
+                 and c.gitlab_mr_iid = mr.gitlab_mr_iid
+             ) fc on true
+             join lateral (
+               select d.finished_at_gl as deployed_at
+               from gitlab_deployments d
+               left join gitlab_environments e
+                 on e.org_id = d.org_id and e.gitlab_project_id = d.gitlab_project_id
+                and e.name = d.environment_name
+               where d.org_id = mr.org_id and mr.provider = 'gitlab'
+                 and d.gitlab_project_id = mr.gitlab_project_id
+                 and d.status = 'success' and d.finished_at_gl is not null
+                 and d.created_at_gl >= mr.merged_at
+                 and d.ref_name = mr.target_branch
+                 and not exists (
+                   select 1 from gitlab_deployments prev
+                   where prev.org_id = d.org_id
+                     and prev.gitlab_project_id = d.gitlab_project_id
+                     and prev.environment_name = d.environment_name
+                     and prev.sha = d.sha
+                     and prev.created_at_gl < d.created_at_gl
+                 )
+                 and coalesce(e.tier, case when lower(d.environment_name) in ('production', 'prod')
+                                           then 'production' end) = 'production'
+               order by d.created_at_gl asc
+               limit 1
+             ) dp on true
+             left join gitlab_projects p
+               on p.org_id = mr.org_id and p.provider = mr.provider
+                and p.gitlab_id = mr.gitlab_project_id
+             left join lateral (
+               select pe.team
+               from identities i
+               join person_identity_links l
+                 on l.identity_id = i.id and l.org_id = i.org_id and l.valid_to is null
+               join people pe on pe.id = l.person_id
+               where i.org_id = mr.org_id and i.source = mr.provider
+                 and i.username = mr.author_username
+               limit 1
+             ) t on true
+             where mr.org_id = $1 and mr.state = 'merged'
+               and mr.merged_at >= $2::date and mr.merged_at < ($3::date + interval '1 day')
+               and coalesce(fc.first_commit_at, mr.created_at_gl) is not null
+               and not is_departed(mr.org_id, mr.provider, mr.author_username, dp.deployed_at)
+             order by hours asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| DeployLeadTimeRow {
+                gitlab_project_id: r.get("gitlab_project_id"),
+                project_path: r.get("project_path"),
+                team: r.get("team"),
+                hours: r.get("hours"),
+            })
+            .collect())
+    }
+
+    /// List failed pipelines in a period for risk item generation.
+    pub async fn list_failed_pipelines(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<GitlabPipeline>> {
+        let rows = sqlx::query(
+            "select id, org_id, provider, gitlab_project_id, gitlab_pipeline_id, status, ref_name,
+                    created_at_gl, finished_at_gl, duration_secs, web_url, created_at, updated_at
+             from gitlab_pipelines
+             where org_id = $1 and status = 'failed'
+               and created_at_gl >= $2::date and created_at_gl < ($3::date + interval '1 day')
+             order by created_at_gl desc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(map_pipeline_row).collect())
+    }
+}
+
+fn map_pipeline_row(row: &sqlx::postgres::PgRow) -> GitlabPipeline {
+    GitlabPipeline {
+        id: row.get("id"),
+        org_id: row.get("org_id"),
+        provider: row.get("provider"),
+        gitlab_project_id: row.get("gitlab_project_id"),
+        gitlab_pipeline_id: row.get("gitlab_pipeline_id"),
+        status: row.get("status"),
+        ref_name: row.get("ref_name"),
+        created_at_gl: row.get("created_at_gl"),
+        finished_at_gl: row.get("finished_at_gl"),
+        duration_secs: row.get("duration_secs"),
+        web_url: row.get("web_url"),
+        created_at: row.get("created_at"),
+        updated_at: row.get("updated_at"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+    use chrono::{DateTime, Utc};
+
+    async fn test_repo() -> Option<(PgGitlabRepository, PgPool)> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        // Apply migration inline for test isolation
+        sqlx::query(
+            "create table if not exists gitlab_projects (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_id bigint not null,
+              name text not null, path_with_namespace text not null, web_url text not null,
+              provider text not null default 'gitlab',
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_projects_org_gl_uidx on gitlab_projects(org_id, provider, gitlab_id)",
+        )
+        .execute(&pool)
         .await
         .ok()?;
 
@@ -366,6 +1279,7 @@ mod tests {
               title text not null, state text not null, author_username text,
               labels text[] not null default '{}',
               created_at_gl timestamptz, merged_at timestamptz, web_url text not null,
+              provider text not null default 'gitlab',
               created_at timestamptz not null default now(), updated_at timestamptz not null default now()
             )",
         )
@@ -373,7 +1287,7 @@ mod tests {
         .await
         .ok()?;
         sqlx::query(
-            "create unique index if not exists gitlab_mrs_org_proj_iid_uidx on gitlab_merge_requests(org_id, gitlab_project_id, gitlab_mr_iid)",
+            "create unique index if not exists gitlab_mrs_org_proj_iid_uidx on gitlab_merge_requests(org_id, provider, gitlab_project_id, gitlab_mr_iid)",
         )
         .execute(&pool)
         .await
@@ -385,7 +1299,119 @@ mod tests {
               org_id uuid not null, gitlab_project_id bigint not null, gitlab_pipeline_id bigint not null,
               status text not null, ref_name text,
               created_at_gl timestamptz, finished_at_gl timestamptz, duration_secs integer,
-              web_url text not null,
+              web_url text not null, provider text not null default 'gitlab',
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_pipelines_org_gl_uidx on gitlab_pipelines(org_id, provider, gitlab_pipeline_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        sqlx::query(
+            "alter table gitlab_merge_requests add column if not exists target_branch text",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create table if not exists gitlab_commits (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null, sha text not null,
+              gitlab_mr_iid bigint, title text not null, author_name text, author_email text,
+              authored_at timestamptz, committed_at timestamptz, web_url text,
+              provider text not null default 'gitlab',
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_commits_org_proj_sha_uidx on gitlab_commits(org_id, provider, gitlab_project_id, sha)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        for stmt in [
+            "alter table gitlab_merge_requests add column if not exists additions integer",
+            "alter table gitlab_merge_requests add column if not exists deletions integer",
+            "alter table gitlab_merge_requests add column if not exists changed_files integer",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+        sqlx::query(
+            "create table if not exists gitlab_environments (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null,
+              gitlab_environment_id bigint not null, name text not null, tier text, state text,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_environments_org_proj_env_uidx on gitlab_environments(org_id, gitlab_project_id, gitlab_environment_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create table if not exists gitlab_deployments (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null,
+              gitlab_deployment_id bigint not null, environment_name text not null,
+              status text not null, sha text, ref_name text, deployer_username text,
+              created_at_gl timestamptz, finished_at_gl timestamptz,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_deployments_org_proj_dep_uidx on gitlab_deployments(org_id, gitlab_project_id, gitlab_deployment_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create table if not exists gitlab_pipeline_jobs (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null,
+              gitlab_pipeline_id bigint not null, gitlab_job_id bigint not null,
+              name text not null, stage text, status text not null, sha text, ref_name text,
+              retried boolean not null default false, allow_failure boolean not null default false,
+              failure_reason text, created_at_gl timestamptz, started_at_gl timestamptz,
+              finished_at_gl timestamptz, duration_secs double precision, web_url text,
+              provider text not null default 'gitlab',
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_pipeline_jobs_org_job_uidx on gitlab_pipeline_jobs(org_id, provider, gitlab_job_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create table if not exists gitlab_mr_notes (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null, gitlab_mr_iid bigint not null,
+              gitlab_note_id bigint not null, discussion_id text, author_username text,
+              system boolean not null default false, resolvable boolean not null default false,
+              resolved boolean not null default false, created_at_gl timestamptz,
+              provider text not null default 'gitlab',
               created_at timestamptz not null default now(), updated_at timestamptz not null default now()
             )",
         )
@@ -393,7 +1419,71 @@ mod tests {
         .await
         .ok()?;
         sqlx::query(
-            "create unique index if not exists gitlab_pipelines_org_gl_uidx on gitlab_pipelines(org_id, gitlab_pipeline_id)",
+            "create unique index if not exists gitlab_mr_notes_org_proj_note_uidx on gitlab_mr_notes(org_id, provider, gitlab_project_id, gitlab_note_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create table if not exists gitlab_mr_approvals (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null, gitlab_mr_iid bigint not null,
+              approver_username text not null, approved_at timestamptz,
+              provider text not null default 'gitlab',
+              created_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists gitlab_mr_approvals_org_proj_mr_user_uidx on gitlab_mr_approvals(org_id, provider, gitlab_project_id, gitlab_mr_iid, approver_username)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        sqlx::query("alter table people add column if not exists left_at timestamptz")
+            .execute(&pool)
+            .await
+            .ok()?;
+        sqlx::query(
+            "create or replace view departed_identities as
+             select distinct i.org_id, i.source, i.external_id, i.username, p.left_at
+             from people p
+             join person_identity_links pil on pil.person_id = p.id and pil.org_id = p.org_id
+             join identities i on i.id = pil.identity_id
+             where p.left_at is not null
+               and pil.status not in ('rejected', 'ignored')",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        for stmt in [
+            "create table if not exists erased_accounts (
+              org_id uuid not null, source text not null, account_hash text not null,
+              alias text not null, created_at timestamptz not null default now(),
+              primary key (org_id, source, account_hash)
+            )",
+            "create or replace function erased_alias(p_org_id uuid, p_source text, p_account text)
+             returns text language sql stable as $$
+               select alias from erased_accounts
+               where org_id = p_org_id and source = p_source
+                 and account_hash = encode(sha256(convert_to(p_account, 'UTF8')), 'hex')
+             $$",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+        sqlx::query(
+            "create or replace function is_departed(p_org_id uuid, p_source text, p_account text, p_at timestamptz)
+             returns boolean language sql stable as $$
+               select exists (
+                 select 1 from departed_identities d
+                 where d.org_id = p_org_id and d.source = p_source
+                   and (case when p_source = 'jira' then d.external_id else d.username end) = p_account
+                   and d.left_at <= p_at
+               )
+             $$",
         )
         .execute(&pool)
         .await
@@ -407,6 +1497,7 @@ mod tests {
         GitlabProject {
             id: Uuid::new_v4(),
             org_id,
+            provider: "gitlab".to_string(),
             gitlab_id,
             name: format!("project-{gitlab_id}"),
             path_with_namespace: format!("group/project-{gitlab_id}"),
@@ -429,6 +1520,7 @@ mod tests {
         GitlabMergeRequest {
             id: Uuid::new_v4(),
             org_id,
+            provider: "gitlab".to_string(),
             gitlab_project_id: project_id,
             gitlab_mr_iid: iid,
             title: format!("MR !{iid}"),
@@ -437,6 +1529,7 @@ mod tests {
             labels,
             created_at_gl: Some(created),
             merged_at: merged,
+            target_branch: Some("main".to_string()),
             web_url: format!("https://gitlab.example.com/group/project/merge_requests/{iid}"),
             created_at: now,
             updated_at: now,
@@ -530,4 +1623,970 @@ mod tests {
             .expect("bugs");
         assert_eq!(bugs, 1);
     }
+
+    #[tokio::test]
+    async fn count_merged_mrs_excludes_departed_author() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+        let yesterday = now - chrono::Duration::hours(12);
+
+        let person_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into people (id, org_id, display_name, left_at) values ($1, $2, 'gone', $3)",
+        )
+        .bind(person_id)
+        .bind(org)
+        .bind(yesterday)
+        .execute(&pool)
+        .await
+        .expect("insert person");
+        let identity_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into identities (id, org_id, source, username) values ($1, $2, 'gitlab', 'dev')",
+        )
+        .bind(identity_id)
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("insert identity");
+        sqlx::query(
+            "insert into person_identity_links (id, org_id, person_id, identity_id, status, valid_to)
+             values ($1, $2, $3, $4, 'verified', $5)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org)
+        .bind(person_id)
+        .bind(identity_id)
+        .bind(yesterday)
+        .execute(&pool)
+        .await
+        .expect("insert link");
+
+        let left_early = yesterday - chrono::Duration::minutes(5);
+        let before = make_mr(org, 1, 20, "merged", vec![], left_early, Some(left_early));
+        let after = make_mr(org, 1, 21, "merged", vec![], yesterday, Some(now));
+        repo.upsert_merge_request(&before).await.expect("before");
+        repo.upsert_merge_request(&after).await.expect("after");
+
+        let from = (now - chrono::Duration::days(1)).date_naive();
+        let count = repo
+            .count_merged_mrs(org, from, now.date_naive())
+            .await
+            .expect("count");
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn departed_filter_matches_the_mr_provider() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+        let left = now - chrono::Duration::hours(12);
+
+        // "dev" left on GitLab only, "octo" left on GitHub.
+        let person_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into people (id, org_id, display_name, left_at) values ($1, $2, 'gone', $3)",
+        )
+        .bind(person_id)
+        .bind(org)
+        .bind(left)
+        .execute(&pool)
+        .await
+        .expect("insert person");
+        for (source, username) in [("gitlab", "dev"), ("github", "octo")] {
+            let identity_id = Uuid::new_v4();
+            sqlx::query(
+                "insert into identities (id, org_id, source, username) values ($1, $2, $3, $4)",
+            )
+            .bind(identity_id)
+            .bind(org)
+            .bind(source)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .expect("insert identity");
+            sqlx::query(
+                "insert into person_identity_links (id, org_id, person_id, identity_id, status)
+                 values ($1, $2, $3, $4, 'verified')",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org)
+            .bind(person_id)
+            .bind(identity_id)
+            .execute(&pool)
+            .await
+            .expect("insert link");
+        }
+
+        for (iid, author) in [(1, "dev"), (2, "octo")] {
+            let mut mr = make_mr(org, 1, iid, "merged", vec![], left, Some(now));
+            mr.provider = "github".to_string();
+            mr.author_username = Some(author.to_string());
+            repo.upsert_merge_request(&mr).await.expect("mr");
+        }
+
+        let from = (now - chrono::Duration::days(1)).date_naive();
+        let count = repo
+            .count_merged_mrs(org, from, now.date_naive())
+            .await
+            .expect("count");
+        assert_eq!(count, 1);
+    }
+
+    fn make_commit(
+        org_id: Uuid,
+        project_id: i64,
+        iid: i64,
+        sha: &str,
+        authored: DateTime<Utc>,
+    ) -> GitlabCommit {
+        let now = Utc::now();
+        GitlabCommit {
+            id: Uuid::new_v4(),
+            org_id,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: project_id,
+            sha: sha.to_string(),
+            gitlab_mr_iid: Some(iid),
+            title: format!("commit {sha}"),
+            author_name: Some("Dev".to_string()),
+            author_email: Some("dev@example.com".to_string()),
+            authored_at: Some(authored),
+            committed_at: Some(authored),
+            web_url: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn make_pipeline(
+        org_id: Uuid,
+        project_id: i64,
+        status: &str,
+        ref_name: &str,
+        created: DateTime<Utc>,
+    ) -> GitlabPipeline {
+        let now = Utc::now();
+        GitlabPipeline {
+            id: Uuid::new_v4(),
+            org_id,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: project_id,
+            gitlab_pipeline_id: (Uuid::new_v4().as_u128() % 1_000_000_000) as i64,
+            status: status.to_string(),
+            ref_name: Some(ref_name.to_string()),
+            created_at_gl: Some(created),
+            finished_at_gl: Some(created + chrono::Duration::hours(1)),
+            duration_secs: Some(3600),
+            web_url: "https://gitlab.example.com/group/project/-/pipelines/1".to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn lead_times_span_first_commit_merge_and_deploy() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let merged = Utc::now() - chrono::Duration::hours(6);
+        let opened = merged - chrono::Duration::hours(20);
+
+        let deployed_mr = make_mr(org, 5, 1, "merged", vec![], opened, Some(merged));
+        repo.upsert_merge_request(&deployed_mr).await.expect("mr1");
+        for (sha, hours_before_merge) in [("a1", 30), ("a2", 10)] {
+            let authored = merged - chrono::Duration::hours(hours_before_merge);
+            repo.upsert_commit(&make_commit(org, 5, 1, sha, authored))
+                .await
+                .expect("commit");
+        }
+        // failed and other-branch pipelines do not count as the deploy
+        for (status, branch, offset) in [("failed", "main", 1), ("success", "dev", 1)] {
+            let p = make_pipeline(
+                org,
+                5,
+                status,
+                branch,
+                merged + chrono::Duration::hours(offset),
+            );
+            repo.upsert_pipeline(&p).await.expect("pipeline");
+        }
+        let deploy = make_pipeline(
+            org,
+            5,
+            "success",
+            "main",
+            merged + chrono::Duration::hours(2),
+        );
+        repo.upsert_pipeline(&deploy).await.expect("deploy");
+
+        let undeployed_mr = make_mr(org, 6, 2, "merged", vec![], opened, Some(merged));
+        repo.upsert_merge_request(&undeployed_mr)
+            .await
+            .expect("mr2");
+        repo.upsert_commit(&make_commit(
+            org,
+            6,
+            2,
+            "b1",
+            merged - chrono::Duration::hours(4),
+        ))
+        .await
+        .expect("commit");
+
+        // merged MR without commits is skipped
+        let no_commits = make_mr(org, 7, 3, "merged", vec![], opened, Some(merged));
+        repo.upsert_merge_request(&no_commits).await.expect("mr3");
+
+        let from = (merged - chrono::Duration::days(1)).date_naive();
+        let mut rows = repo
+            .get_lead_times_hours(org, from, Utc::now().date_naive())
+            .await
+            .expect("lead times");
+        rows.sort_by(|a, b| {
+            a.first_commit_to_merge_hours
+                .total_cmp(&b.first_commit_to_merge_hours)
+        });
+
+        assert_eq!(rows.len(), 2);
+        assert!((rows[0].first_commit_to_merge_hours - 4.0).abs() < 0.01);
+        assert!(rows[0].merge_to_deploy_hours.is_none());
+        assert!((rows[1].first_commit_to_merge_hours - 30.0).abs() < 0.01);
+        assert!((rows[1].merge_to_deploy_hours.unwrap() - 3.0).abs() < 0.01);
+    }
+
+    fn make_note(
+        org_id: Uuid,
+        project_id: i64,
+        iid: i64,
+        note_id: i64,
+        author: &str,
+        system: bool,
+        at: DateTime<Utc>,
+    ) -> GitlabMrNote {
+        let now = Utc::now();
+        GitlabMrNote {
+            id: Uuid::new_v4(),
+            org_id,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: project_id,
+            gitlab_mr_iid: iid,
+            gitlab_note_id: note_id,
+            discussion_id: None,
+            author_username: Some(author.to_string()),
+            system,
+            resolvable: !system,
+            resolved: false,
+            created_at_gl: Some(at),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn make_approval(
+        org_id: Uuid,
+        project_id: i64,
+        iid: i64,
+        approver: &str,
+        at: Option<DateTime<Utc>>,
+    ) -> GitlabMrApproval {
+        GitlabMrApproval {
+            id: Uuid::new_v4(),
+            org_id,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: project_id,
+            gitlab_mr_iid: iid,
+            approver_username: approver.to_string(),
+            approved_at: at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn review_metrics_from_notes_and_approvals() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let opened = Utc::now() - chrono::Duration::hours(48);
+        let h = |n: i64| opened + chrono::Duration::hours(n);
+
+        // MR 1 by alice: bob reviews at +2h, alice pushes, bob again, then carol approves
+        let mut mr = make_mr(org, 9, 1, "merged", vec![], opened, Some(h(30)));
+        mr.author_username = Some("alice".to_string());
+        repo.upsert_merge_request(&mr).await.expect("mr1");
+        let notes = [
+            (1, "alice", false, 1),
+            (2, "bob", false, 2),
+            (3, "bob", false, 3),
+            (4, "alice", true, 10),
+            (5, "bob", false, 12),
+            (6, "carol", true, 20),
+        ];
+        for (id, author, system, at) in notes {
+            repo.upsert_note(&make_note(org, 9, 1, id, author, system, h(at)))
+                .await
+                .expect("note");
+        }
+        repo.replace_approvals(
+            org,
+            "gitlab",
+            9,
+            1,
+            &[make_approval(org, 9, 1, "carol", Some(h(20)))],
+        )
+        .await
+        .expect("approvals");
+
+        // MR 2 by bob: only an approval from alice at +5h
+        let mut mr = make_mr(org, 9, 2, "merged", vec![], opened, Some(h(30)));
+        mr.author_username = Some("bob".to_string());
+        repo.upsert_merge_request(&mr).await.expect("mr2");
+        repo.replace_approvals(
+            org,
+            "gitlab",
+            9,
+            2,
+            &[make_approval(org, 9, 2, "alice", Some(h(5)))],
+        )
+        .await
+        .expect("approvals");
+
+        let from = opened.date_naive();
+        let to = Utc::now().date_naive();
+
+        let first_review = repo.get_first_review_hours(org, from, to).await.unwrap();
+        assert_eq!(first_review.len(), 2);
+        assert!((first_review[0] - 2.0).abs() < 0.01);
+        assert!((first_review[1] - 5.0).abs() < 0.01);
+
+        // MR 2 has no reviewer comments, so only MR 1 has rounds
+        let rounds = repo.get_review_rounds(org, from, to).await.unwrap();
+        assert_eq!(rounds, vec![2]);
+
+        let load = repo.get_reviewer_load(org, from, to).await.unwrap();
+        let by_name = |name: &str| load.iter().find(|r| r.username == name).unwrap();
+        assert_eq!(load.len(), 3);
+        assert_eq!(by_name("bob").comments, 3);
+        assert_eq!(by_name("bob").mrs_reviewed, 1);
+        assert_eq!(by_name("carol").approvals, 1);
+        assert_eq!(by_name("alice").approvals, 1);
+        assert_eq!(by_name("alice").comments, 0);
+    }
+
+    #[tokio::test]
+    async fn reviewer_load_groups_linked_accounts_and_skips_departed() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let opened = Utc::now() - chrono::Duration::hours(48);
+        let h = |n: i64| opened + chrono::Duration::hours(n);
+
+        // bob reviews from two accounts; dave leaves at +10h
+        let bob = Uuid::new_v4();
+        let dave = Uuid::new_v4();
+        for (person_id, name, left_at) in [(bob, "Bob", None), (dave, "Dave", Some(h(10)))] {
+            sqlx::query(
+                "insert into people (id, org_id, display_name, left_at) values ($1, $2, $3, $4)",
+            )
+            .bind(person_id)
+            .bind(org)
+            .bind(name)
+            .bind(left_at)
+            .execute(&pool)
+            .await
+            .expect("insert person");
+        }
+        for (person_id, username) in [(bob, "bob"), (bob, "bob-alt"), (dave, "dave")] {
+            let identity_id = Uuid::new_v4();
+            sqlx::query(
+                "insert into identities (id, org_id, source, username) values ($1, $2, 'gitlab', $3)",
+            )
+            .bind(identity_id)
+            .bind(org)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .expect("insert identity");
+            sqlx::query(
+                "insert into person_identity_links (id, org_id, person_id, identity_id, status)
+                 values ($1, $2, $3, $4, 'verified')",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org)
+            .bind(person_id)
+            .bind(identity_id)
+            .execute(&pool)
+            .await
+            .expect("insert link");
+        }
+
+        let mut mr = make_mr(org, 9, 1, "merged", vec![], opened, Some(h(30)));
+        mr.author_username = Some("alice".to_string());
+        repo.upsert_merge_request(&mr).await.expect("mr");
+        let notes = [
+            (1, "bob", 2),
+            (2, "bob-alt", 3),
+            (3, "dave", 4),
+            (4, "dave", 12),
+            (5, "carol", 14),
+        ];
+        for (id, author, at) in notes {
+            repo.upsert_note(&make_note(org, 9, 1, id, author, false, h(at)))
+                .await
+                .expect("note");
+        }
+
+        let from = opened.date_naive();
+        let to = Utc::now().date_naive();
+        let load = repo.get_reviewer_load(org, from, to).await.unwrap();
+        assert_eq!(load.len(), 3);
+        let bob_load = load.iter().find(|r| r.person_id == Some(bob)).unwrap();
+        assert_eq!(bob_load.username, "bob");
+        assert_eq!(bob_load.display_name.as_deref(), Some("Bob"));
+        assert_eq!(bob_load.comments, 2);
+        assert_eq!(bob_load.mrs_reviewed, 1);
+        // only the comment from before dave left counts
+        let dave_load = load.iter().find(|r| r.person_id == Some(dave)).unwrap();
+        assert_eq!(dave_load.comments, 1);
+        let carol = load.iter().find(|r| r.username == "carol").unwrap();
+        assert_eq!(carol.person_id, None);
+        assert_eq!(carol.comments, 1);
+    }
+
+    #[tokio::test]
+    async fn replace_approvals_drops_revoked_and_keeps_known_time() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let approved = Utc::now() - chrono::Duration::hours(3);
+
+        repo.replace_approvals(
+            org,
+            "gitlab",
+            4,
+            1,
+            &[
+                make_approval(org, 4, 1, "bob", Some(approved)),
+                make_approval(org, 4, 1, "carol", None),
+            ],
+        )
+        .await
+        .unwrap();
+        repo.replace_approvals(
+            org,
+            "gitlab",
+            4,
+            1,
+            &[make_approval(org, 4, 1, "bob", None)],
+        )
+        .await
+        .unwrap();
+
+        let rows = sqlx::query(
+            "select approver_username, approved_at from gitlab_mr_approvals
+             where org_id = $1 and gitlab_project_id = 4 and gitlab_mr_iid = 1",
+        )
+        .bind(org)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<String, _>("approver_username"), "bob");
+        let kept: Option<DateTime<Utc>> = rows[0].get("approved_at");
+        assert!((kept.unwrap() - approved).num_seconds().abs() < 1);
+    }
+
+    #[tokio::test]
+    async fn github_and_gitlab_children_with_same_ids_are_kept_apart() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let at = Utc::now() - chrono::Duration::hours(1);
+
+        for provider in ["gitlab", "github"] {
+            let mut commit = make_commit(org, 5, 1, "abc123", at);
+            commit.provider = provider.to_string();
+            repo.upsert_commit(&commit).await.expect("commit");
+
+            let mut note = make_note(org, 5, 1, 77, "bob", false, at);
+            note.provider = provider.to_string();
+            repo.upsert_note(&note).await.expect("note");
+
+            let mut approval = make_approval(org, 5, 1, "bob", Some(at));
+            approval.provider = provider.to_string();
+            repo.replace_approvals(org, provider, 5, 1, &[approval])
+                .await
+                .expect("approvals");
+        }
+        // Clearing the GitLab approvals leaves the GitHub one alone.
+        repo.replace_approvals(org, "gitlab", 5, 1, &[])
+            .await
+            .expect("clear approvals");
+
+        for (table, expected) in [
+            ("gitlab_commits", vec!["github", "gitlab"]),
+            ("gitlab_mr_notes", vec!["github", "gitlab"]),
+            ("gitlab_mr_approvals", vec!["github"]),
+        ] {
+            let providers: Vec<String> = sqlx::query_scalar(&format!(
+                "select provider from {table} where org_id = $1 order by provider"
+            ))
+            .bind(org)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+            assert_eq!(providers, expected, "{table}");
+        }
+    }
+
+    #[tokio::test]
+    async fn mr_sizes_feed_size_queries_and_oversized_risks() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+        let size = |additions, deletions, changed_files| MrSize {
+            additions,
+            deletions,
+            changed_files,
+        };
+
+        let merged = make_mr(
+            org,
+            3,
+            1,
+            "merged",
+            vec![],
+            now - chrono::Duration::hours(10),
+            Some(now - chrono::Duration::hours(4)),
+        );
+        repo.upsert_merge_request(&merged).await.unwrap();
+        repo.update_mr_size(org, 3, 1, size(120, 30, 4))
+            .await
+            .unwrap();
+        // re-syncing the MR listing keeps the stored size
+        repo.upsert_merge_request(&merged).await.unwrap();
+
+        let no_size = make_mr(org, 3, 2, "merged", vec![], now, Some(now));
+        repo.upsert_merge_request(&no_size).await.unwrap();
+
+        let big_open = make_mr(
+            org,
+            3,
+            3,
+            "opened",
+            vec![],
+            now - chrono::Duration::days(5),
+            None,
+        );
+        repo.upsert_merge_request(&big_open).await.unwrap();
+        repo.update_mr_size(org, 3, 3, size(1500, 200, 40))
+            .await
+            .unwrap();
+        let new_big_open = make_mr(org, 3, 4, "opened", vec![], now, None);
+        repo.upsert_merge_request(&new_big_open).await.unwrap();
+        repo.update_mr_size(org, 3, 4, size(2000, 0, 12))
+            .await
+            .unwrap();
+
+        let today = now.date_naive();
+        let rows = repo
+            .get_merged_mr_sizes(org, today - chrono::Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].lines_changed, 150);
+        assert_eq!(rows[0].changed_files, 4);
+        assert!((rows[0].review_hours.unwrap() - 6.0).abs() < 0.01);
+
+        let oversized = repo.list_oversized_open_mrs(org, 1000, 3).await.unwrap();
+        assert_eq!(oversized.len(), 1);
+        assert_eq!(oversized[0].gitlab_mr_iid, 3);
+    }
+
+    fn make_deployment(
+        org_id: Uuid,
+        project_id: i64,
+        deployment_id: i64,
+        environment: &str,
+        status: &str,
+        started: DateTime<Utc>,
+    ) -> GitlabDeployment {
+        let now = Utc::now();
+        GitlabDeployment {
+            id: Uuid::new_v4(),
+            org_id,
+            gitlab_project_id: project_id,
+            gitlab_deployment_id: deployment_id,
+            environment_name: environment.to_string(),
+            status: status.to_string(),
+            sha: Some(format!("sha{deployment_id}")),
+            ref_name: Some("main".to_string()),
+            deployer_username: Some("deployer".to_string()),
+            created_at_gl: Some(started),
+            finished_at_gl: Some(started + chrono::Duration::minutes(30)),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn production_deployments_use_tier_or_name() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+        let env = |id: i64, name: &str, tier: &str| GitlabEnvironment {
+            id: Uuid::new_v4(),
+            org_id: org,
+            gitlab_project_id: 8,
+            gitlab_environment_id: id,
+            name: name.to_string(),
+            tier: Some(tier.to_string()),
+            state: Some("available".to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        repo.upsert_environment(&env(1, "live", "production"))
+            .await
+            .unwrap();
+        repo.upsert_environment(&env(2, "production", "staging"))
+            .await
+            .unwrap();
+
+        let started = now - chrono::Duration::hours(5);
+        for (id, environment, status) in [
+            (1, "live", "success"),
+            (2, "production", "success"), // tier says staging
+            (3, "prod", "failed"),        // unsynced environment, matched by name
+            (4, "live", "running"),
+            (5, "review/feature", "success"),
+        ] {
+            repo.upsert_deployment(&make_deployment(org, 8, id, environment, status, started))
+                .await
+                .unwrap();
+        }
+
+        let today = now.date_naive();
+        let rows = repo
+            .list_production_deployments(org, today - chrono::Duration::days(1), today)
+            .await
+            .unwrap();
+        let envs: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|r| (r.environment_name.as_str(), r.status.as_str()))
+            .collect();
+        assert_eq!(envs, vec![("live", "success"), ("prod", "failed")]);
+    }
+
+    #[tokio::test]
+    async fn deploy_lead_time_runs_to_first_production_deploy() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let merged = Utc::now() - chrono::Duration::hours(10);
+
+        let mr = make_mr(
+            org,
+            11,
+            1,
+            "merged",
+            vec![],
+            merged - chrono::Duration::hours(20),
+            Some(merged),
+        );
+        repo.upsert_merge_request(&mr).await.unwrap();
+        repo.upsert_commit(&make_commit(
+            org,
+            11,
+            1,
+            "c1",
+            merged - chrono::Duration::hours(30),
+        ))
+        .await
+        .unwrap();
+
+        // before the merge, so it did not ship the change
+        repo.upsert_deployment(&make_deployment(
+            org,
+            11,
+            1,
+            "production",
+            "success",
+            merged - chrono::Duration::hours(1),
+        ))
+        .await
+        .unwrap();
+        repo.upsert_deployment(&make_deployment(
+            org,
+            11,
+            2,
+            "production",
+            "success",
+            merged + chrono::Duration::hours(2),
+        ))
+        .await
+        .unwrap();
+
+        // merged but never deployed
+        let undeployed = make_mr(org, 12, 1, "merged", vec![], merged, Some(merged));
+        repo.upsert_merge_request(&undeployed).await.unwrap();
+
+        let today = Utc::now().date_naive();
+        let rows = repo
+            .get_deploy_lead_times_hours(org, today - chrono::Duration::days(2), today)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].gitlab_project_id, 11);
+        // 30h before merge + 2h wait + 30 min deploy
+        assert!((rows[0].hours - 32.5).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn deploy_lead_time_ignores_other_branches_and_rollbacks() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let merged = Utc::now() - chrono::Duration::hours(10);
+
+        let mr = make_mr(org, 13, 1, "merged", vec![], merged, Some(merged));
+        repo.upsert_merge_request(&mr).await.unwrap();
+
+        let released = make_deployment(
+            org,
+            13,
+            1,
+            "production",
+            "success",
+            merged - chrono::Duration::hours(1),
+        );
+        repo.upsert_deployment(&released).await.unwrap();
+        // a hotfix branch deployed after the merge does not contain the MR
+        repo.upsert_deployment(&GitlabDeployment {
+            ref_name: Some("hotfix/login".to_string()),
+            ..make_deployment(
+                org,
+                13,
+                2,
+                "production",
+                "success",
+                merged + chrono::Duration::hours(1),
+            )
+        })
+        .await
+        .unwrap();
+        // neither does a rollback to the sha released before the merge
+        repo.upsert_deployment(&GitlabDeployment {
+            sha: released.sha.clone(),
+            ..make_deployment(
+                org,
+                13,
+                3,
+                "production",
+                "success",
+                merged + chrono::Duration::hours(2),
+            )
+        })
+        .await
+        .unwrap();
+        repo.upsert_deployment(&make_deployment(
+            org,
+            13,
+            4,
+            "production",
+            "success",
+            merged + chrono::Duration::hours(4),
+        ))
+        .await
+        .unwrap();
+
+        let today = Utc::now().date_naive();
+        let rows = repo
+            .get_deploy_lead_times_hours(org, today - chrono::Duration::days(2), today)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        // created at the merge, 4h wait + 30 min deploy
+        assert!((rows[0].hours - 4.5).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn dora_queries_exclude_departed_people() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let merged = Utc::now() - chrono::Duration::hours(10);
+        let left_at = merged + chrono::Duration::hours(3);
+
+        let person_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into people (id, org_id, display_name, left_at) values ($1, $2, 'gone', $3)",
+        )
+        .bind(person_id)
+        .bind(org)
+        .bind(left_at)
+        .execute(&pool)
+        .await
+        .expect("insert person");
+        for username in ["dev", "deployer"] {
+            let identity_id = Uuid::new_v4();
+            sqlx::query(
+                "insert into identities (id, org_id, source, username) values ($1, $2, 'gitlab', $3)",
+            )
+            .bind(identity_id)
+            .bind(org)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .expect("insert identity");
+            sqlx::query(
+                "insert into person_identity_links (id, org_id, person_id, identity_id, status, valid_to)
+                 values ($1, $2, $3, $4, 'verified', $5)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(org)
+            .bind(person_id)
+            .bind(identity_id)
+            .bind(left_at)
+            .execute(&pool)
+            .await
+            .expect("insert link");
+        }
+
+        let early = make_mr(org, 14, 1, "merged", vec![], merged, Some(merged));
+        let late_merge = merged + chrono::Duration::hours(2);
+        let late = make_mr(org, 14, 2, "merged", vec![], late_merge, Some(late_merge));
+        repo.upsert_merge_request(&early).await.unwrap();
+        repo.upsert_merge_request(&late).await.unwrap();
+        // deployed before the author left, then after
+        for (id, started) in [
+            (1, merged + chrono::Duration::hours(1)),
+            (2, merged + chrono::Duration::hours(4)),
+        ] {
+            repo.upsert_deployment(&make_deployment(
+                org,
+                14,
+                id,
+                "production",
+                "success",
+                started,
+            ))
+            .await
+            .unwrap();
+        }
+
+        let today = Utc::now().date_naive();
+        let from = today - chrono::Duration::days(2);
+        let lead_times = repo
+            .get_deploy_lead_times_hours(org, from, today)
+            .await
+            .unwrap();
+        assert_eq!(lead_times.len(), 1);
+        assert!((lead_times[0].hours - 1.5).abs() < 0.01);
+
+        let deployments = repo
+            .list_production_deployments(org, from, today)
+            .await
+            .unwrap();
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(
+            deployments[0].created_at_gl.timestamp(),
+            (merged + chrono::Duration::hours(1)).timestamp()
+        );
+    }
+
+    #[tokio::test]
+    async fn job_reliability_flags_jobs_passing_on_retry() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+        let started = now - chrono::Duration::hours(3);
+        let job = |id: i64, name: &str, sha: &str, status: &str, minute: i64| GitlabPipelineJob {
+            id: Uuid::new_v4(),
+            org_id: org,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: 9,
+            gitlab_pipeline_id: 100,
+            gitlab_job_id: id,
+            name: name.to_string(),
+            stage: Some("test".to_string()),
+            status: status.to_string(),
+            sha: Some(sha.to_string()),
+            ref_name: Some("main".to_string()),
+            retried: false,
+            allow_failure: false,
+            failure_reason: (status == "failed").then(|| "script_failure".to_string()),
+            created_at_gl: Some(started + chrono::Duration::minutes(minute)),
+            started_at_gl: None,
+            finished_at_gl: None,
+            duration_secs: Some(60.0 * (id as f64)),
+            web_url: Some(format!("https://gitlab.example.com/jobs/{id}")),
+            created_at: now,
+            updated_at: now,
+        };
+        for j in [
+            job(1, "integration", "aaa", "failed", 0),
+            job(2, "integration", "aaa", "success", 5), // retry passed: flaky
+            job(3, "integration", "bbb", "success", 10),
+            job(4, "lint", "ccc", "success", 20), // passed, then failed: not flaky
+            job(5, "lint", "ccc", "failed", 25),
+            job(6, "lint", "ddd", "canceled", 30),
+        ] {
+            repo.upsert_pipeline_job(&j).await.unwrap();
+        }
+
+        let today = now.date_naive();
+        let rows = repo
+            .get_job_reliability(org, today - chrono::Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let integration = &rows[0];
+        assert_eq!(integration.job_name, "integration");
+        assert_eq!((integration.runs, integration.failures), (3, 1));
+        assert_eq!(integration.flaky_count, 1);
+        assert_eq!(
+            integration.last_flaky_url.as_deref(),
+            Some("https://gitlab.example.com/jobs/1")
+        );
+        assert_eq!(integration.duration_p50_secs, Some(120.0));
+
+        let lint = &rows[1];
+        assert_eq!((lint.runs, lint.failures, lint.flaky_count), (2, 1, 0));
+        assert!((lint.success_rate - 0.5).abs() < f64::EPSILON);
+        assert!(lint.first_flaky_at.is_none());
+    }
 }
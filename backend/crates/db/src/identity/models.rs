@@ -51,6 +51,9 @@ pub struct Person {
     pub team: Option<String>,
     pub role: Option<String>,
     pub status: String,
+    /// Departure date; links are closed and activity after this date is
+    /// excluded from team metrics.
+    pub left_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -74,6 +77,10 @@ pub struct Identity {
     pub email: Option<String>,
     pub display_name: Option<String>,
     pub is_service_account: bool,
+    /// Account state in the source system (e.g. GitLab `blocked`, Jira `active: false`).
+    pub is_active: bool,
+    /// When `is_active` last flipped; `None` if it never changed since first seen.
+    pub active_changed_at: Option<DateTime<Utc>>,
     pub first_seen_at: Option<DateTime<Utc>>,
     pub last_seen_at: Option<DateTime<Utc>>,
     pub raw_ref: Option<serde_json::Value>,
@@ -102,6 +109,7 @@ pub struct IdentityEvent {
     pub link_id: Uuid,
     pub action: String,
     pub actor: Option<String>,
+    pub payload: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
 }
 
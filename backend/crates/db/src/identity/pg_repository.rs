@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::{postgres::PgRow, PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
 
@@ -28,7 +28,7 @@ impl PgIdentityRepository {
         &self.pool
     }
 
-    fn map_person_row(row: PgRow) -> Person {
+    pub(crate) fn map_person_row(row: PgRow) -> Person {
         Person {
             id: row.get("id"),
             org_id: row.get("org_id"),
@@ -38,12 +38,98 @@ impl PgIdentityRepository {
             team: row.get("team"),
             role: row.get("role"),
             status: row.get("status"),
+            left_at: row.get("left_at"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         }
     }
 
-    fn map_link_row(row: PgRow) -> OviaResult<PersonIdentityLink> {
+    /// Write the mutable fields of a person. `left_at` sets a departure date;
+    /// without one, moving an inactive person back to `active` clears it.
+    async fn save_person<'e, E>(
+        executor: E,
+        person: &Person,
+        left_at: Option<DateTime<Utc>>,
+    ) -> OviaResult<Option<PgRow>>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query(
+            "update people
+             set display_name = $1, primary_email = $2, avatar_url = $3,
+                 team = $4, role = $5, status = $6,
+                 left_at = case
+                     when $9::timestamptz is not null then $9
+                     when $6 = 'active' and status <> 'active' then null
+                     else left_at
+                 end,
+                 updated_at = now()
+             where id = $7 and org_id = $8
+             returning id, org_id, display_name, primary_email, avatar_url, team, role, status,
+                       left_at, created_at, updated_at",
+        )
+        .bind(&person.display_name)
+        .bind(&person.primary_email)
+        .bind(&person.avatar_url)
+        .bind(&person.team)
+        .bind(&person.role)
+        .bind(&person.status)
+        .bind(person.id)
+        .bind(person.org_id)
+        .bind(left_at)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))
+    }
+
+    /// The stored identity of an erased account, left untouched. An account erased
+    /// before its identity was stored gets a pseudonymized copy that is not saved.
+    async fn erased_identity(&self, identity: Identity, alias: String) -> OviaResult<Identity> {
+        let row = sqlx::query(
+            "select id, org_id, source, external_id, username, email, display_name,
+                    is_service_account, is_active, active_changed_at, first_seen_at,
+                    last_seen_at, raw_ref
+             from identities where org_id = $1 and source = $2 and external_id = $3",
+        )
+        .bind(identity.org_id)
+        .bind(&identity.source)
+        .bind(&alias)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(match row {
+            Some(r) => Self::map_identity_row(r),
+            None => Identity {
+                external_id: identity.external_id.as_ref().map(|_| alias.clone()),
+                username: identity.username.as_ref().map(|_| alias.clone()),
+                email: None,
+                display_name: None,
+                raw_ref: None,
+                ..identity
+            },
+        })
+    }
+
+    pub(crate) fn map_identity_row(row: PgRow) -> Identity {
+        Identity {
+            id: row.get("id"),
+            org_id: row.get("org_id"),
+            source: row.get("source"),
+            external_id: row.get("external_id"),
+            username: row.get("username"),
+            email: row.get("email"),
+            display_name: row.get("display_name"),
+            is_service_account: row.get("is_service_account"),
+            is_active: row.get("is_active"),
+            active_changed_at: row.get("active_changed_at"),
+            first_seen_at: row.get("first_seen_at"),
+            last_seen_at: row.get("last_seen_at"),
+            raw_ref: row.get("raw_ref"),
+        }
+    }
+
+    pub(crate) fn map_link_row(row: PgRow) -> OviaResult<PersonIdentityLink> {
         let status_raw: String = row.get("status");
         let status = LinkStatus::from_str(&status_raw).map_err(OviaError::Internal)?;
 
@@ -95,7 +181,7 @@ impl PersonRepository for PgIdentityRepository {
     async fn get_by_id(&self, org_id: Uuid, id: Uuid) -> OviaResult<Option<Person>> {
         let row = sqlx::query(
             "select id, org_id, display_name, primary_email, avatar_url, team, role, status,
-                    created_at, updated_at
+                    left_at, created_at, updated_at
              from people where org_id = $1 and id = $2",
         )
         .bind(org_id)
@@ -115,7 +201,7 @@ impl PersonRepository for PgIdentityRepository {
             "insert into people (id, org_id, display_name, primary_email, avatar_url, team, role, status)
              values ($1, $2, $3, $4, $5, $6, $7, $8)
              returning id, org_id, display_name, primary_email, avatar_url, team, role, status,
-                       created_at, updated_at",
+                       left_at, created_at, updated_at",
         )
         .bind(person.id)
         .bind(person.org_id)
@@ -143,33 +229,10 @@ impl PersonRepository for PgIdentityRepository {
     }
 
     async fn update(&self, person: Person) -> OviaResult<Person> {
-        let row = sqlx::query(
-            "update people
-             set display_name = $1, primary_email = $2, avatar_url = $3,
-                 team = $4, role = $5, status = $6, updated_at = now()
-             where id = $7 and org_id = $8
-             returning id, org_id, display_name, primary_email, avatar_url, team, role, status,
-                       created_at, updated_at",
-        )
-        .bind(&person.display_name)
-        .bind(&person.primary_email)
-        .bind(&person.avatar_url)
-        .bind(&person.team)
-        .bind(&person.role)
-        .bind(&person.status)
-        .bind(person.id)
-        .bind(person.org_id)
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(|e| OviaError::Database(e.to_string()))?;
-
-        match row {
-            Some(r) => Ok(Self::map_person_row(r)),
-            None => Err(OviaError::NotFound(format!(
-                "person not found: {}",
-                person.id
-            ))),
-        }
+        let row = Self::save_person(&self.pool, &person, None)
+            .await?
+            .ok_or_else(|| OviaError::NotFound(format!("person not found: {}", person.id)))?;
+        Ok(Self::map_person_row(row))
     }
 
     async fn list(&self, org_id: Uuid, filter: PersonFilter) -> OviaResult<(Vec<Person>, i64)> {
@@ -177,7 +240,7 @@ impl PersonRepository for PgIdentityRepository {
 
         let mut qb = QueryBuilder::new(
             "select id, org_id, display_name, primary_email, avatar_url, team, role, status, \
-             created_at, updated_at from people where org_id = ",
+             left_at, created_at, updated_at from people where org_id = ",
         );
         qb.push_bind(org_id);
         qb.push(" and status = ").push_bind(status_filter);
@@ -239,7 +302,7 @@ impl PersonRepository for PgIdentityRepository {
         }
         let rows = sqlx::query(
             "select id, org_id, display_name, primary_email, avatar_url, team, role, status,
-                    created_at, updated_at
+                    left_at, created_at, updated_at
              from people where org_id = $1 and id = any($2)",
         )
         .bind(org_id)
@@ -267,6 +330,87 @@ impl PersonRepository for PgIdentityRepository {
         }
         Ok(())
     }
+
+    async fn mark_left(
+        &self,
+        person: Person,
+        left_at: DateTime<Utc>,
+        actor: &str,
+    ) -> OviaResult<Person> {
+        let (org_id, id) = (person.org_id, person.id);
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let person = Person {
+            status: "inactive".to_string(),
+            ..person
+        };
+        let row = Self::save_person(&mut *tx, &person, Some(left_at))
+            .await?
+            .ok_or_else(|| OviaError::NotFound(format!("person not found: {id}")))?;
+
+        let closed = sqlx::query(
+            "update person_identity_links
+             set valid_to = $1, updated_at = now()
+             where org_id = $2 and person_id = $3 and valid_to is null
+             returning id, identity_id",
+        )
+        .bind(left_at)
+        .bind(org_id)
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for link in closed {
+            let link_id: Uuid = link.get("id");
+            let identity_id: Uuid = link.get("identity_id");
+            Self::append_event(
+                &mut tx,
+                org_id,
+                link_id,
+                "offboard",
+                actor,
+                Some(serde_json::json!({
+                    "person_id": id,
+                    "identity_id": identity_id,
+                    "left_at": left_at,
+                })),
+            )
+            .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(Self::map_person_row(row))
+    }
+
+    async fn list_offboarding_candidates(&self, org_id: Uuid) -> OviaResult<Vec<Person>> {
+        let rows = sqlx::query(
+            "select p.id, p.org_id, p.display_name, p.primary_email, p.avatar_url, p.team, p.role,
+                    p.status, p.left_at, p.created_at, p.updated_at
+             from people p
+             join person_identity_links pil
+               on pil.person_id = p.id and pil.org_id = p.org_id and pil.valid_to is null
+              and pil.status not in ('rejected', 'ignored')
+             join identities i on i.id = pil.identity_id
+             where p.org_id = $1 and p.status = 'active' and p.left_at is null
+             group by p.id
+             having bool_and(not i.is_active)
+             order by p.display_name asc",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Self::map_person_row).collect())
+    }
 }
 
 #[async_trait]
@@ -274,7 +418,8 @@ impl IdentityRepository for PgIdentityRepository {
     async fn get_by_id(&self, org_id: Uuid, id: Uuid) -> OviaResult<Option<Identity>> {
         let row = sqlx::query(
             "select id, org_id, source, external_id, username, email, display_name,
-                    is_service_account, first_seen_at, last_seen_at, raw_ref
+                    is_service_account, is_active, active_changed_at, first_seen_at,
+                    last_seen_at, raw_ref
              from identities where org_id = $1 and id = $2",
         )
         .bind(org_id)
@@ -284,19 +429,7 @@ impl IdentityRepository for PgIdentityRepository {
         .map_err(|e| OviaError::Database(e.to_string()))?;
 
         match row {
-            Some(r) => Ok(Some(Identity {
-                id: r.get("id"),
-                org_id: r.get("org_id"),
-                source: r.get("source"),
-                external_id: r.get("external_id"),
-                username: r.get("username"),
-                email: r.get("email"),
-                display_name: r.get("display_name"),
-                is_service_account: r.get("is_service_account"),
-                first_seen_at: r.get("first_seen_at"),
-                last_seen_at: r.get("last_seen_at"),
-                raw_ref: r.get("raw_ref"),
-            })),
+            Some(r) => Ok(Some(Self::map_identity_row(r))),
             None => Ok(None),
         }
     }
@@ -304,10 +437,12 @@ impl IdentityRepository for PgIdentityRepository {
     async fn create(&self, identity: Identity) -> OviaResult<Identity> {
         let row = sqlx::query(
             "insert into identities (id, org_id, source, external_id, username, email, display_name,
-                                     is_service_account, first_seen_at, last_seen_at, raw_ref)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                                     is_service_account, is_active, active_changed_at,
+                                     first_seen_at, last_seen_at, raw_ref)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
              returning id, org_id, source, external_id, username, email, display_name,
-                       is_service_account, first_seen_at, last_seen_at, raw_ref",
+                       is_service_account, is_active, active_changed_at, first_seen_at,
+                       last_seen_at, raw_ref",
         )
         .bind(identity.id)
         .bind(identity.org_id)
@@ -317,6 +452,8 @@ impl IdentityRepository for PgIdentityRepository {
         .bind(&identity.email)
         .bind(&identity.display_name)
         .bind(identity.is_service_account)
+        .bind(identity.is_active)
+        .bind(identity.active_changed_at)
         .bind(identity.first_seen_at)
         .bind(identity.last_seen_at)
         .bind(&identity.raw_ref)
@@ -324,29 +461,21 @@ impl IdentityRepository for PgIdentityRepository {
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
 
-        Ok(Identity {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            source: row.get("source"),
-            external_id: row.get("external_id"),
-            username: row.get("username"),
-            email: row.get("email"),
-            display_name: row.get("display_name"),
-            is_service_account: row.get("is_service_account"),
-            first_seen_at: row.get("first_seen_at"),
-            last_seen_at: row.get("last_seen_at"),
-            raw_ref: row.get("raw_ref"),
-        })
+        Ok(Self::map_identity_row(row))
     }
 
     async fn update(&self, identity: Identity) -> OviaResult<Identity> {
         let row = sqlx::query(
             "update identities
              set username = $1, email = $2, display_name = $3, is_service_account = $4,
-                 last_seen_at = $5, raw_ref = $6, updated_at = now()
-             where id = $7 and org_id = $8
+                 last_seen_at = $5, raw_ref = $6,
+                 active_changed_at = case when is_active is distinct from $7 then now()
+                                          else active_changed_at end,
+                 is_active = $7, updated_at = now()
+             where id = $8 and org_id = $9
              returning id, org_id, source, external_id, username, email, display_name,
-                       is_service_account, first_seen_at, last_seen_at, raw_ref",
+                       is_service_account, is_active, active_changed_at, first_seen_at,
+                       last_seen_at, raw_ref",
         )
         .bind(&identity.username)
         .bind(&identity.email)
@@ -354,44 +483,55 @@ impl IdentityRepository for PgIdentityRepository {
         .bind(identity.is_service_account)
         .bind(identity.last_seen_at)
         .bind(&identity.raw_ref)
+        .bind(identity.is_active)
         .bind(identity.id)
         .bind(identity.org_id)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
 
-        Ok(Identity {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            source: row.get("source"),
-            external_id: row.get("external_id"),
-            username: row.get("username"),
-            email: row.get("email"),
-            display_name: row.get("display_name"),
-            is_service_account: row.get("is_service_account"),
-            first_seen_at: row.get("first_seen_at"),
-            last_seen_at: row.get("last_seen_at"),
-            raw_ref: row.get("raw_ref"),
-        })
+        Ok(Self::map_identity_row(row))
     }
 
     async fn upsert_by_external_id(&self, identity: Identity) -> OviaResult<Identity> {
+        let erased: Option<String> = sqlx::query_scalar(
+            "select coalesce(erased_alias($1, $2, $3), erased_alias($1, $2, $4))",
+        )
+        .bind(identity.org_id)
+        .bind(&identity.source)
+        .bind(&identity.external_id)
+        .bind(&identity.username)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        if let Some(alias) = erased {
+            return self.erased_identity(identity, alias).await;
+        }
+
         let now = Utc::now();
         let row = sqlx::query(
             "insert into identities (id, org_id, source, external_id, username, email, display_name,
-                                     is_service_account, first_seen_at, last_seen_at, raw_ref, created_at, updated_at)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+                                     is_service_account, is_active, active_changed_at,
+                                     first_seen_at, last_seen_at, raw_ref, created_at, updated_at)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $14)
              on conflict (org_id, source, external_id) where external_id is not null
              do update set
                email = excluded.email,
                display_name = excluded.display_name,
                username = excluded.username,
                is_service_account = excluded.is_service_account,
+               active_changed_at = case
+                 when identities.is_active is distinct from excluded.is_active
+                   then excluded.updated_at
+                 else identities.active_changed_at
+               end,
+               is_active = excluded.is_active,
                last_seen_at = excluded.last_seen_at,
                raw_ref = excluded.raw_ref,
                updated_at = excluded.updated_at
              returning id, org_id, source, external_id, username, email, display_name,
-                       is_service_account, first_seen_at, last_seen_at, raw_ref",
+                       is_service_account, is_active, active_changed_at, first_seen_at,
+                       last_seen_at, raw_ref",
         )
         .bind(identity.id)
         .bind(identity.org_id)
@@ -401,6 +541,8 @@ impl IdentityRepository for PgIdentityRepository {
         .bind(&identity.email)
         .bind(&identity.display_name)
         .bind(identity.is_service_account)
+        .bind(identity.is_active)
+        .bind(identity.active_changed_at)
         .bind(identity.first_seen_at.unwrap_or(now))
         .bind(identity.last_seen_at.unwrap_or(now))
         .bind(&identity.raw_ref)
@@ -409,19 +551,7 @@ impl IdentityRepository for PgIdentityRepository {
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
 
-        Ok(Identity {
-            id: row.get("id"),
-            org_id: row.get("org_id"),
-            source: row.get("source"),
-            external_id: row.get("external_id"),
-            username: row.get("username"),
-            email: row.get("email"),
-            display_name: row.get("display_name"),
-            is_service_account: row.get("is_service_account"),
-            first_seen_at: row.get("first_seen_at"),
-            last_seen_at: row.get("last_seen_at"),
-            raw_ref: row.get("raw_ref"),
-        })
+        Ok(Self::map_identity_row(row))
     }
 }
 
@@ -1490,4 +1620,145 @@ mod tests {
         assert!((avg - 0.5).abs() < 0.01);
         assert!(stats.oldest_created_at.is_some());
     }
+
+    // ── Departure / offboarding ──────────────────────────────────
+
+    async fn set_identity_active(pool: &PgPool, identity_id: Uuid, active: bool) {
+        sqlx::query("update identities set is_active = $1 where id = $2")
+            .bind(active)
+            .bind(identity_id)
+            .execute(pool)
+            .await
+            .expect("set identity active");
+    }
+
+    #[tokio::test]
+    async fn mark_left_closes_links_and_emits_events() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let person = insert_person(&pool, org).await;
+        let i1 = insert_identity(&pool, org).await;
+        let i2 = insert_identity(&pool, org).await;
+        let l1 = insert_link(&pool, org, person, i1, "auto", 0.9).await;
+        let l2 = insert_link(&pool, org, person, i2, "verified", 1.0).await;
+
+        let left_at = Utc::now() - chrono::Duration::days(3);
+        let existing = PersonRepository::get_by_id(&repo, org, person)
+            .await
+            .unwrap()
+            .unwrap();
+        let updated = repo
+            .mark_left(existing, left_at, "tester")
+            .await
+            .expect("mark_left should succeed");
+        assert!(updated.left_at.is_some());
+        assert_eq!(updated.status, "inactive");
+
+        for link in [l1, l2] {
+            let row = fetch_link_row(&pool, link).await;
+            let valid_to: Option<chrono::DateTime<Utc>> = row.get("valid_to");
+            assert!(valid_to.is_some());
+            assert_eq!(count_events(&pool, link).await, 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn mark_left_not_found() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let person = Person {
+            id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            display_name: "nobody".to_string(),
+            primary_email: None,
+            avatar_url: None,
+            team: None,
+            role: None,
+            status: "active".to_string(),
+            left_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let result = repo.mark_left(person, Utc::now(), "tester").await;
+        assert!(matches!(result, Err(OviaError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn reactivating_person_clears_left_at() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let person = insert_person(&pool, org).await;
+        let existing = PersonRepository::get_by_id(&repo, org, person)
+            .await
+            .unwrap()
+            .unwrap();
+        let left = repo
+            .mark_left(existing, Utc::now(), "tester")
+            .await
+            .unwrap();
+        assert!(left.left_at.is_some());
+
+        // other edits of a departed person keep the departure date
+        let renamed = PersonRepository::update(
+            &repo,
+            Person {
+                display_name: "renamed".to_string(),
+                ..left.clone()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(renamed.left_at.is_some());
+
+        let reactivated = PersonRepository::update(
+            &repo,
+            Person {
+                status: "active".to_string(),
+                ..left
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(reactivated.status, "active");
+        assert!(reactivated.left_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn offboarding_candidates_require_all_identities_inactive() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        let gone = insert_person(&pool, org).await;
+        let g1 = insert_identity(&pool, org).await;
+        let g2 = insert_identity(&pool, org).await;
+        insert_link(&pool, org, gone, g1, "auto", 0.9).await;
+        insert_link(&pool, org, gone, g2, "auto", 0.9).await;
+        set_identity_active(&pool, g1, false).await;
+        set_identity_active(&pool, g2, false).await;
+
+        let partial = insert_person(&pool, org).await;
+        let p1 = insert_identity(&pool, org).await;
+        let p2 = insert_identity(&pool, org).await;
+        insert_link(&pool, org, partial, p1, "auto", 0.9).await;
+        insert_link(&pool, org, partial, p2, "auto", 0.9).await;
+        set_identity_active(&pool, p1, false).await;
+
+        let candidates = repo
+            .list_offboarding_candidates(org)
+            .await
+            .expect("should succeed");
+        let ids: Vec<Uuid> = candidates.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![gone]);
+    }
 }
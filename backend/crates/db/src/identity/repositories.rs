@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::identity::models::{
@@ -11,10 +12,25 @@ use ovia_common::error::OviaResult;
 pub trait PersonRepository: Send + Sync {
     async fn get_by_id(&self, org_id: Uuid, id: Uuid) -> OviaResult<Option<Person>>;
     async fn create(&self, person: Person) -> OviaResult<Person>;
+    /// Setting the status back to `active` on an inactive person clears `left_at`.
     async fn update(&self, person: Person) -> OviaResult<Person>;
     async fn list(&self, org_id: Uuid, filter: PersonFilter) -> OviaResult<(Vec<Person>, i64)>;
     async fn list_by_ids(&self, org_id: Uuid, ids: &[Uuid]) -> OviaResult<Vec<Person>>;
     async fn soft_delete(&self, org_id: Uuid, id: Uuid) -> OviaResult<()>;
+
+    /// Save the person as inactive with a departure date and close all active links
+    /// of the person at that date, in one transaction. Each closed link gets an
+    /// `offboard` audit event.
+    async fn mark_left(
+        &self,
+        person: Person,
+        left_at: DateTime<Utc>,
+        actor: &str,
+    ) -> OviaResult<Person>;
+
+    /// Active people with at least one linked identity, where every linked identity
+    /// is inactive in its source system.
+    async fn list_offboarding_candidates(&self, org_id: Uuid) -> OviaResult<Vec<Person>>;
 }
 
 #[async_trait]
@@ -25,6 +41,9 @@ pub trait IdentityRepository: Send + Sync {
 
     /// Insert or update an identity keyed by (org_id, source, external_id).
     /// On conflict, updates mutable fields (email, display_name, etc.) but preserves first_seen_at.
+    /// `active_changed_at` is stamped only when `is_active` actually flips.
+    /// Accounts of erased people are not written back; their pseudonymized identity
+    /// is returned instead.
     async fn upsert_by_external_id(&self, identity: Identity) -> OviaResult<Identity>;
 }
 
@@ -2,11 +2,30 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Roles a Jira field can be mapped to.
+pub const JIRA_FIELD_ROLES: &[&str] = &[
+    "story_points",
+    "sprint",
+    "team",
+    "epic_link",
+    "extra",
+    "changelog",
+];
+
+/// Roles that hold a list of fields; the others hold at most one field.
+pub const JIRA_FIELD_LIST_ROLES: &[&str] = &["extra", "changelog"];
+
+/// Changelog fields whose values are Jira users. Their transitions hold account ids
+/// rather than display names, so erasure can pseudonymize them.
+pub const USER_CHANGELOG_FIELDS: &[&str] = &["assignee", "reporter"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraIssue {
     pub id: Uuid,
     pub org_id: Uuid,
     pub jira_key: String,
+    /// Jira's numeric issue id, which worklogs refer to
+    pub jira_issue_id: Option<i64>,
     pub project_key: String,
     pub issue_type: Option<String>,
     pub summary: String,
@@ -19,6 +38,8 @@ pub struct JiraIssue {
     pub sprint_id: Option<i64>,
     pub team_name: Option<String>,
     pub labels: Vec<String>,
+    /// Values of the org's "extra" mapped fields, keyed by field id
+    pub extra_fields: Option<serde_json::Value>,
     pub created_at_jira: Option<DateTime<Utc>>,
     pub updated_at_jira: Option<DateTime<Utc>>,
     pub resolved_at: Option<DateTime<Utc>>,
@@ -39,3 +60,162 @@ pub struct JiraIssueTransition {
     pub transitioned_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoard {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub board_id: i64,
+    pub name: String,
+    pub board_type: String,
+    pub project_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraSprint {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub sprint_id: i64,
+    pub board_id: Option<i64>,
+    pub name: String,
+    pub state: String,
+    pub goal: Option<String>,
+    pub start_at: Option<DateTime<Utc>>,
+    pub end_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A stretch of time an issue spent in a sprint. `added_at` is `None` when the issue
+/// was in the sprint before its changelog starts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JiraIssueSprint {
+    pub org_id: Uuid,
+    pub jira_key: String,
+    pub sprint_id: i64,
+    pub added_at: Option<DateTime<Utc>>,
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+/// What an issue's changelog yields: its transitions and sprint history, stored
+/// together with the changelog cursor (the issue's Jira `updated` time they were
+/// fetched at).
+#[derive(Debug, Clone)]
+pub struct JiraIssueHistory {
+    pub jira_key: String,
+    pub changelog_cursor: Option<DateTime<Utc>>,
+    pub transitions: Vec<JiraIssueTransition>,
+    pub sprints: Vec<JiraIssueSprint>,
+}
+
+/// Issues a closed sprint held when it was completed, and how many were unresolved.
+#[derive(Debug, Clone, Serialize)]
+pub struct SprintSpilloverRow {
+    pub sprint_id: i64,
+    pub name: String,
+    pub board_id: Option<i64>,
+    pub completed_at: DateTime<Utc>,
+    pub committed: i64,
+    pub unresolved: i64,
+}
+
+/// A link between two issues, in the outward direction of its type: for "Blocks",
+/// `source_key` blocks `target_key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JiraIssueLink {
+    pub org_id: Uuid,
+    pub jira_link_id: i64,
+    pub link_type: String,
+    pub outward: Option<String>,
+    pub inward: Option<String>,
+    pub source_key: String,
+    pub target_key: String,
+}
+
+/// The parent of an issue: the epic of a story, or the issue a subtask belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JiraIssueParent {
+    pub org_id: Uuid,
+    pub jira_key: String,
+    pub parent_key: String,
+    pub parent_issue_type: Option<String>,
+}
+
+/// An issue upstream of another in a chain of "Blocks" links. `depth` 1 blocks the
+/// starting issue directly.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyChainRow {
+    pub jira_key: String,
+    pub blocks_key: String,
+    pub depth: i32,
+    pub summary: Option<String>,
+    pub status: Option<String>,
+    pub team_name: Option<String>,
+    pub is_done: bool,
+}
+
+/// An open issue blocked by an open issue of another team.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrossTeamBlockRow {
+    pub blocked_key: String,
+    pub blocked_team: String,
+    pub blocker_key: String,
+    pub blocker_team: String,
+    pub blocker_summary: String,
+    pub blocker_status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraWorklog {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub worklog_id: i64,
+    pub jira_issue_id: i64,
+    pub author_account_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub time_spent_secs: i64,
+    pub updated_at_jira: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Hours logged in a period on one group of issues (an issue type or a team).
+#[derive(Debug, Clone, Serialize)]
+pub struct EffortRow {
+    pub group: String,
+    pub hours: f64,
+    pub issues: i64,
+    pub authors: i64,
+}
+
+/// Hours logged per issue for resolved issues of one story point estimate.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoryPointEffortRow {
+    pub story_points: f64,
+    pub issues: i64,
+    pub hours_avg: f64,
+    pub hours_p50: f64,
+}
+
+/// A custom field mapped to a role for an org.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraFieldMapping {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub role: String,
+    pub field_id: String,
+    pub field_name: Option<String>,
+    pub is_manual: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Key and Jira id of a stored issue, for reconciliation against Jira.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiraIssueRef {
+    pub jira_key: String,
+    pub jira_issue_id: Option<i64>,
+}
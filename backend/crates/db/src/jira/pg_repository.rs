@@ -1,8 +1,15 @@
-use chrono::NaiveDate;
-use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row, Transaction};
 use uuid::Uuid;
 
-use crate::jira::models::{JiraIssue, JiraIssueTransition};
+use crate::jira::models::{
+    CrossTeamBlockRow, DependencyChainRow, EffortRow, JiraBoard, JiraFieldMapping, JiraIssue,
+    JiraIssueHistory, JiraIssueLink, JiraIssueParent, JiraIssueRef, JiraIssueSprint,
+    JiraIssueTransition, JiraSprint, JiraWorklog, SprintSpilloverRow, StoryPointEffortRow,
+    JIRA_FIELD_LIST_ROLES, USER_CHANGELOG_FIELDS,
+};
 use ovia_common::error::{OviaError, OviaResult};
 
 #[derive(Clone)]
@@ -15,16 +22,71 @@ impl PgJiraRepository {
         Self { pool }
     }
 
-    /// Upsert a Jira issue (idempotent on org_id + jira_key).
+    /// Upsert a Jira issue (idempotent on org_id + jira_key). An update carrying an
+    /// older Jira `updated` time than the stored row is ignored, so late webhook
+    /// deliveries cannot roll an issue back.
     pub async fn upsert_issue(&self, issue: &JiraIssue) -> OviaResult<()> {
-        sqlx::query(
+        self.upsert_issues(std::slice::from_ref(issue)).await
+    }
+
+    /// Upsert several issues in one statement, like `upsert_issue`. Keys must be
+    /// unique within the batch. Assignees and reporters of erased people are stored
+    /// under their pseudonym, without the raw payload that names them.
+    pub async fn upsert_issues(&self, issues: &[JiraIssue]) -> OviaResult<()> {
+        if issues.is_empty() {
+            return Ok(());
+        }
+        let mut query = QueryBuilder::<Postgres>::new(
             "insert into jira_issues
              (id, org_id, jira_key, project_key, issue_type, summary, status,
               assignee_account_id, reporter_account_id, priority,
               story_points, sprint_name, sprint_id, team_name,
-              labels, created_at_jira, updated_at_jira, resolved_at, raw_ref)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)
+              labels, created_at_jira, updated_at_jira, resolved_at, raw_ref, jira_issue_id,
+              extra_fields)
+             select v.id, v.org_id, v.jira_key, v.project_key, v.issue_type, v.summary, v.status,
+                    coalesce(erased_alias(v.org_id, 'jira', v.assignee), v.assignee),
+                    coalesce(erased_alias(v.org_id, 'jira', v.reporter), v.reporter),
+                    v.priority, v.story_points, v.sprint_name, v.sprint_id, v.team_name,
+                    v.labels, v.created_at_jira, v.updated_at_jira, v.resolved_at,
+                    case
+                      when erased_alias(v.org_id, 'jira', v.assignee) is not null
+                        or erased_alias(v.org_id, 'jira', v.reporter) is not null then null
+                      else v.raw_ref
+                    end,
+                    v.jira_issue_id, v.extra_fields
+             from (",
+        );
+        query.push_values(issues, |mut row, issue| {
+            row.push_bind(issue.id)
+                .push_bind(issue.org_id)
+                .push_bind(&issue.jira_key)
+                .push_bind(&issue.project_key)
+                .push_bind(&issue.issue_type)
+                .push_bind(&issue.summary)
+                .push_bind(&issue.status)
+                .push_bind(&issue.assignee_account_id)
+                .push_bind(&issue.reporter_account_id)
+                .push_bind(&issue.priority)
+                .push_bind(issue.story_points)
+                .push_bind(&issue.sprint_name)
+                .push_bind(issue.sprint_id)
+                .push_bind(&issue.team_name)
+                .push_bind(&issue.labels)
+                .push_bind(issue.created_at_jira)
+                .push_bind(issue.updated_at_jira)
+                .push_bind(issue.resolved_at)
+                .push_bind(&issue.raw_ref)
+                .push_bind(issue.jira_issue_id)
+                .push_bind(&issue.extra_fields);
+        });
+        query.push(
+            ") as v(id, org_id, jira_key, project_key, issue_type, summary, status, assignee,
+                    reporter, priority, story_points, sprint_name, sprint_id, team_name, labels,
+                    created_at_jira, updated_at_jira, resolved_at, raw_ref, jira_issue_id,
+                    extra_fields)
              on conflict (org_id, jira_key) do update set
+               jira_issue_id = coalesce(excluded.jira_issue_id, jira_issues.jira_issue_id),
+               extra_fields = excluded.extra_fields,
                issue_type = excluded.issue_type,
                summary = excluded.summary,
                status = excluded.status,
@@ -40,40 +102,187 @@ impl PgJiraRepository {
                updated_at_jira = excluded.updated_at_jira,
                resolved_at = excluded.resolved_at,
                raw_ref = excluded.raw_ref,
-               updated_at = now()",
+               updated_at = now()
+             where jira_issues.updated_at_jira is null
+               or excluded.updated_at_jira is null
+               or excluded.updated_at_jira >= jira_issues.updated_at_jira",
+        );
+        query
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Changelog cursors of the org's issues, by key. Issues whose changelog was
+    /// never stored are absent.
+    pub async fn list_changelog_cursors(
+        &self,
+        org_id: Uuid,
+    ) -> OviaResult<HashMap<String, DateTime<Utc>>> {
+        let rows = sqlx::query(
+            "select jira_key, changelog_cursor from jira_issues
+             where org_id = $1 and changelog_cursor is not null",
         )
-        .bind(issue.id)
-        .bind(issue.org_id)
-        .bind(&issue.jira_key)
-        .bind(&issue.project_key)
-        .bind(&issue.issue_type)
-        .bind(&issue.summary)
-        .bind(&issue.status)
-        .bind(&issue.assignee_account_id)
-        .bind(&issue.reporter_account_id)
-        .bind(&issue.priority)
-        .bind(issue.story_points)
-        .bind(&issue.sprint_name)
-        .bind(issue.sprint_id)
-        .bind(&issue.team_name)
-        .bind(&issue.labels)
-        .bind(issue.created_at_jira)
-        .bind(issue.updated_at_jira)
-        .bind(issue.resolved_at)
-        .bind(&issue.raw_ref)
-        .execute(&self.pool)
+        .bind(org_id)
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(rows
+            .into_iter()
+            .map(|r| (r.get("jira_key"), r.get("changelog_cursor")))
+            .collect())
+    }
+
+    /// Store the changelog-derived history of several issues in one transaction:
+    /// each issue's sprint history and transitions are replaced, and its changelog
+    /// cursor is set.
+    pub async fn replace_issue_histories(
+        &self,
+        org_id: Uuid,
+        histories: &[JiraIssueHistory],
+    ) -> OviaResult<()> {
+        if histories.is_empty() {
+            return Ok(());
+        }
+        let keys: Vec<&str> = histories.iter().map(|h| h.jira_key.as_str()).collect();
+        let cursors: Vec<Option<DateTime<Utc>>> =
+            histories.iter().map(|h| h.changelog_cursor).collect();
+        let transitions: Vec<&JiraIssueTransition> =
+            histories.iter().flat_map(|h| &h.transitions).collect();
+        let sprints: Vec<&JiraIssueSprint> = histories.iter().flat_map(|h| &h.sprints).collect();
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query("delete from jira_issue_sprints where org_id = $1 and jira_key = any($2)")
+            .bind(org_id)
+            .bind(&keys)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        sqlx::query("delete from jira_issue_transitions where org_id = $1 and jira_key = any($2)")
+            .bind(org_id)
+            .bind(&keys)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "insert into jira_issue_transitions
+             (id, org_id, jira_key, field, from_value, to_value, author_account_id, transitioned_at)
+             select t.id, t.org_id, t.jira_key, t.field,
+                    case when t.field = any($9)
+                         then coalesce(erased_alias(t.org_id, 'jira', t.from_value), t.from_value)
+                         else t.from_value end,
+                    case when t.field = any($9)
+                         then coalesce(erased_alias(t.org_id, 'jira', t.to_value), t.to_value)
+                         else t.to_value end,
+                    coalesce(erased_alias(t.org_id, 'jira', t.author), t.author), t.transitioned_at
+             from unnest($1::uuid[], $2::uuid[], $3::text[], $4::text[], $5::text[],
+                         $6::text[], $7::text[], $8::timestamptz[])
+                  as t(id, org_id, jira_key, field, from_value, to_value, author, transitioned_at)
+             on conflict (org_id, jira_key, field, transitioned_at, from_value, to_value)
+             do nothing",
+        )
+        .bind(transitions.iter().map(|t| t.id).collect::<Vec<_>>())
+        .bind(transitions.iter().map(|t| t.org_id).collect::<Vec<_>>())
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.jira_key.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.field.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.from_value.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.to_value.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.author_account_id.as_deref())
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            transitions
+                .iter()
+                .map(|t| t.transitioned_at)
+                .collect::<Vec<_>>(),
+        )
+        .bind(USER_CHANGELOG_FIELDS)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "insert into jira_issue_sprints (org_id, jira_key, sprint_id, added_at, removed_at)
+             select * from unnest($1::uuid[], $2::text[], $3::bigint[], $4::timestamptz[],
+                                  $5::timestamptz[])",
+        )
+        .bind(sprints.iter().map(|h| h.org_id).collect::<Vec<_>>())
+        .bind(
+            sprints
+                .iter()
+                .map(|h| h.jira_key.as_str())
+                .collect::<Vec<_>>(),
+        )
+        .bind(sprints.iter().map(|h| h.sprint_id).collect::<Vec<_>>())
+        .bind(sprints.iter().map(|h| h.added_at).collect::<Vec<_>>())
+        .bind(sprints.iter().map(|h| h.removed_at).collect::<Vec<_>>())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "update jira_issues i set changelog_cursor = c.cursor
+             from unnest($2::text[], $3::timestamptz[]) as c(jira_key, cursor)
+             where i.org_id = $1 and i.jira_key = c.jira_key",
+        )
+        .bind(org_id)
+        .bind(&keys)
+        .bind(&cursors)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
         Ok(())
     }
 
-    /// Insert a transition row. Not idempotent — caller should avoid duplicates.
+    /// Insert a transition row. A transition already stored (same field, time and
+    /// values) is ignored, so webhook retries and syncs do not duplicate it.
     pub async fn insert_transition(&self, t: &JiraIssueTransition) -> OviaResult<()> {
         sqlx::query(
             "insert into jira_issue_transitions
              (id, org_id, jira_key, field, from_value, to_value, author_account_id, transitioned_at)
-             values ($1, $2, $3, $4, $5, $6, $7, $8)
-             on conflict do nothing",
+             values ($1, $2, $3, $4,
+                     case when $4 = any($9) then coalesce(erased_alias($2, 'jira', $5), $5)
+                          else $5 end,
+                     case when $4 = any($9) then coalesce(erased_alias($2, 'jira', $6), $6)
+                          else $6 end,
+                     coalesce(erased_alias($2, 'jira', $7), $7), $8)
+             on conflict (org_id, jira_key, field, transitioned_at, from_value, to_value)
+             do nothing",
         )
         .bind(t.id)
         .bind(t.org_id)
@@ -83,12 +292,559 @@ impl PgJiraRepository {
         .bind(&t.to_value)
         .bind(&t.author_account_id)
         .bind(t.transitioned_at)
+        .bind(USER_CHANGELOG_FIELDS)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn upsert_board(&self, b: &JiraBoard) -> OviaResult<()> {
+        sqlx::query(
+            "insert into jira_boards (id, org_id, board_id, name, board_type, project_key)
+             values ($1, $2, $3, $4, $5, $6)
+             on conflict (org_id, board_id) do update set
+               name = excluded.name,
+               board_type = excluded.board_type,
+               project_key = excluded.project_key,
+               updated_at = now()",
+        )
+        .bind(b.id)
+        .bind(b.org_id)
+        .bind(b.board_id)
+        .bind(&b.name)
+        .bind(&b.board_type)
+        .bind(&b.project_key)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    pub async fn upsert_sprint(&self, sp: &JiraSprint) -> OviaResult<()> {
+        sqlx::query(
+            "insert into jira_sprints
+             (id, org_id, sprint_id, board_id, name, state, goal, start_at, end_at, completed_at)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             on conflict (org_id, sprint_id) do update set
+               board_id = coalesce(excluded.board_id, jira_sprints.board_id),
+               name = excluded.name,
+               state = excluded.state,
+               goal = excluded.goal,
+               start_at = excluded.start_at,
+               end_at = excluded.end_at,
+               completed_at = excluded.completed_at,
+               updated_at = now()",
+        )
+        .bind(sp.id)
+        .bind(sp.org_id)
+        .bind(sp.sprint_id)
+        .bind(sp.board_id)
+        .bind(&sp.name)
+        .bind(&sp.state)
+        .bind(&sp.goal)
+        .bind(sp.start_at)
+        .bind(sp.end_at)
+        .bind(sp.completed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Replace the sprint history of an issue.
+    pub async fn replace_issue_sprints(
+        &self,
+        org_id: Uuid,
+        jira_key: &str,
+        history: &[JiraIssueSprint],
+    ) -> OviaResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query("delete from jira_issue_sprints where org_id = $1 and jira_key = $2")
+            .bind(org_id)
+            .bind(jira_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for h in history {
+            sqlx::query(
+                "insert into jira_issue_sprints (org_id, jira_key, sprint_id, added_at, removed_at)
+                 values ($1, $2, $3, $4, $5)",
+            )
+            .bind(h.org_id)
+            .bind(&h.jira_key)
+            .bind(h.sprint_id)
+            .bind(h.added_at)
+            .bind(h.removed_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Replace the links of an issue and its place in the hierarchy.
+    ///
+    /// An issue lists every link it is on, so links on either side of `jira_key` are
+    /// replaced. `parents` holds the issue's own parent, if any, and one row per
+    /// subtask; subtask rows are upserted and never removed here.
+    pub async fn replace_issue_relations(
+        &self,
+        org_id: Uuid,
+        jira_key: &str,
+        links: &[JiraIssueLink],
+        parents: &[JiraIssueParent],
+    ) -> OviaResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        sqlx::query(
+            "delete from jira_issue_links
+             where org_id = $1 and (source_key = $2 or target_key = $2)",
+        )
+        .bind(org_id)
+        .bind(jira_key)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        sqlx::query("delete from jira_issue_parents where org_id = $1 and jira_key = $2")
+            .bind(org_id)
+            .bind(jira_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        for l in links {
+            sqlx::query(
+                "insert into jira_issue_links
+                 (org_id, jira_link_id, link_type, outward, inward, source_key, target_key)
+                 values ($1, $2, $3, $4, $5, $6, $7)
+                 on conflict (org_id, jira_link_id) do update set
+                   link_type = excluded.link_type, outward = excluded.outward,
+                   inward = excluded.inward, source_key = excluded.source_key,
+                   target_key = excluded.target_key, updated_at = now()",
+            )
+            .bind(l.org_id)
+            .bind(l.jira_link_id)
+            .bind(&l.link_type)
+            .bind(&l.outward)
+            .bind(&l.inward)
+            .bind(&l.source_key)
+            .bind(&l.target_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        for p in parents {
+            sqlx::query(
+                "insert into jira_issue_parents (org_id, jira_key, parent_key, parent_issue_type)
+                 values ($1, $2, $3, $4)
+                 on conflict (org_id, jira_key) do update set
+                   parent_key = excluded.parent_key,
+                   parent_issue_type = excluded.parent_issue_type, updated_at = now()",
+            )
+            .bind(p.org_id)
+            .bind(&p.jira_key)
+            .bind(&p.parent_key)
+            .bind(&p.parent_issue_type)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Walk "Blocks" links upstream from an issue: what blocks it, what blocks those,
+    /// and so on up to `max_depth` levels. Each issue appears once, at its shortest
+    /// distance.
+    pub async fn get_dependency_chain(
+        &self,
+        org_id: Uuid,
+        jira_key: &str,
+        max_depth: i32,
+    ) -> OviaResult<Vec<DependencyChainRow>> {
+        let rows = sqlx::query(
+            "with recursive chain(jira_key, blocks_key, depth) as (
+               select l.source_key, l.target_key, 1
+               from jira_issue_links l
+               where l.org_id = $1 and l.link_type = 'Blocks' and l.target_key = $2
+               union
+               select l.source_key, l.target_key, c.depth + 1
+               from chain c
+               join jira_issue_links l
+                 on l.org_id = $1 and l.link_type = 'Blocks' and l.target_key = c.jira_key
+               where c.depth < $3
+             )
+             select distinct on (c.jira_key)
+                    c.jira_key, c.blocks_key, c.depth, ji.summary, ji.status, ji.team_name,
+                    coalesce(ji.status in ('Done', 'Closed', 'Resolved'), false) as is_done
+             from chain c
+             left join jira_issues ji on ji.org_id = $1 and ji.jira_key = c.jira_key
+             where c.jira_key <> $2
+             order by c.jira_key, c.depth",
+        )
+        .bind(org_id)
+        .bind(jira_key)
+        .bind(max_depth)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let mut chain: Vec<DependencyChainRow> = rows
+            .iter()
+            .map(|r| DependencyChainRow {
+                jira_key: r.get("jira_key"),
+                blocks_key: r.get("blocks_key"),
+                depth: r.get("depth"),
+                summary: r.get("summary"),
+                status: r.get("status"),
+                team_name: r.get("team_name"),
+                is_done: r.get("is_done"),
+            })
+            .collect();
+        chain.sort_by(|a, b| (a.depth, &a.jira_key).cmp(&(b.depth, &b.jira_key)));
+        Ok(chain)
+    }
+
+    /// Open issues blocked by an open issue of another team. Issues without a team
+    /// are left out, as their owner is unknown.
+    pub async fn list_cross_team_blocks(&self, org_id: Uuid) -> OviaResult<Vec<CrossTeamBlockRow>> {
+        let rows = sqlx::query(
+            "select blocked.jira_key as blocked_key, blocked.team_name as blocked_team,
+                    blocker.jira_key as blocker_key, blocker.team_name as blocker_team,
+                    blocker.summary as blocker_summary, blocker.status as blocker_status
+             from jira_issue_links l
+             join jira_issues blocker
+               on blocker.org_id = l.org_id and blocker.jira_key = l.source_key
+             join jira_issues blocked
+               on blocked.org_id = l.org_id and blocked.jira_key = l.target_key
+             where l.org_id = $1 and l.link_type = 'Blocks'
+               and blocker.status not in ('Done', 'Closed', 'Resolved')
+               and blocked.status not in ('Done', 'Closed', 'Resolved')
+               and blocker.team_name is not null and blocked.team_name is not null
+               and blocker.team_name <> blocked.team_name
+             order by blocked.jira_key, blocker.jira_key",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| CrossTeamBlockRow {
+                blocked_key: r.get("blocked_key"),
+                blocked_team: r.get("blocked_team"),
+                blocker_key: r.get("blocker_key"),
+                blocker_team: r.get("blocker_team"),
+                blocker_summary: r.get("blocker_summary"),
+                blocker_status: r.get("blocker_status"),
+            })
+            .collect())
+    }
+
+    // ── Custom field mappings ────────────────────────────────────
+
+    /// Field mappings of an org, by role then field id.
+    pub async fn list_field_mappings(&self, org_id: Uuid) -> OviaResult<Vec<JiraFieldMapping>> {
+        let rows = sqlx::query(
+            "select id, org_id, role, field_id, field_name, is_manual, created_at, updated_at
+             from jira_field_mappings
+             where org_id = $1
+             order by role, field_id",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(map_field_mapping_row).collect())
+    }
+
+    /// Record a discovered field for a single-field role. A manual mapping of the
+    /// role is left alone.
+    pub async fn upsert_discovered_field(
+        &self,
+        org_id: Uuid,
+        role: &str,
+        field_id: &str,
+        field_name: &str,
+    ) -> OviaResult<()> {
+        sqlx::query(
+            "insert into jira_field_mappings (org_id, role, field_id, field_name, is_manual)
+             values ($1, $2, $3, $4, false)
+             on conflict (org_id, role) where role not in ('extra', 'changelog') do update set
+               field_id = excluded.field_id,
+               field_name = excluded.field_name,
+               updated_at = now()
+             where not jira_field_mappings.is_manual",
+        )
+        .bind(org_id)
+        .bind(role)
+        .bind(field_id)
+        .bind(field_name)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Map a field by hand. For single-field roles this replaces the current field
+    /// and stops discovery from changing it; fields of list roles are added.
+    pub async fn set_manual_field(
+        &self,
+        org_id: Uuid,
+        role: &str,
+        field_id: &str,
+        field_name: Option<&str>,
+    ) -> OviaResult<JiraFieldMapping> {
+        let conflict_target = if JIRA_FIELD_LIST_ROLES.contains(&role) {
+            "(org_id, role, field_id) where role in ('extra', 'changelog')"
+        } else {
+            "(org_id, role) where role not in ('extra', 'changelog')"
+        };
+        let row = sqlx::query(&format!(
+            "insert into jira_field_mappings (org_id, role, field_id, field_name, is_manual)
+             values ($1, $2, $3, $4, true)
+             on conflict {conflict_target} do update set
+               field_id = excluded.field_id,
+               field_name = excluded.field_name,
+               is_manual = true,
+               updated_at = now()
+             returning id, org_id, role, field_id, field_name, is_manual, created_at, updated_at"
+        ))
+        .bind(org_id)
+        .bind(role)
+        .bind(field_id)
+        .bind(field_name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(map_field_mapping_row(&row))
+    }
+
+    /// Remove a field mapping. A single-field role falls back to discovery on the
+    /// next sync. Returns `false` if there was no such mapping.
+    pub async fn delete_field_mapping(
+        &self,
+        org_id: Uuid,
+        role: &str,
+        field_id: &str,
+    ) -> OviaResult<bool> {
+        let result = sqlx::query(
+            "delete from jira_field_mappings
+             where org_id = $1 and role = $2 and field_id = $3",
+        )
+        .bind(org_id)
+        .bind(role)
+        .bind(field_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Upsert a worklog (idempotent on org_id + worklog_id). Authors of erased people
+    /// are stored under their pseudonym.
+    pub async fn upsert_worklog(&self, w: &JiraWorklog) -> OviaResult<()> {
+        sqlx::query(
+            "insert into jira_worklogs
+             (id, org_id, worklog_id, jira_issue_id, author_account_id, started_at,
+              time_spent_secs, updated_at_jira)
+             values ($1, $2, $3, $4, coalesce(erased_alias($2, 'jira', $5), $5), $6, $7, $8)
+             on conflict (org_id, worklog_id) do update set
+               jira_issue_id = excluded.jira_issue_id,
+               author_account_id = excluded.author_account_id,
+               started_at = excluded.started_at,
+               time_spent_secs = excluded.time_spent_secs,
+               updated_at_jira = excluded.updated_at_jira,
+               updated_at = now()",
+        )
+        .bind(w.id)
+        .bind(w.org_id)
+        .bind(w.worklog_id)
+        .bind(w.jira_issue_id)
+        .bind(&w.author_account_id)
+        .bind(w.started_at)
+        .bind(w.time_spent_secs)
+        .bind(w.updated_at_jira)
         .execute(&self.pool)
         .await
         .map_err(|e| OviaError::Database(e.to_string()))?;
         Ok(())
     }
 
+    /// Jira issue ids of the org's synced issues.
+    pub async fn list_jira_issue_ids(&self, org_id: Uuid) -> OviaResult<Vec<i64>> {
+        let rows = sqlx::query(
+            "select jira_issue_id from jira_issues
+             where org_id = $1 and jira_issue_id is not null",
+        )
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(rows.iter().map(|r| r.get("jira_issue_id")).collect())
+    }
+
+    /// Delete worklogs removed in Jira. Returns the number of rows deleted.
+    pub async fn delete_worklogs(&self, org_id: Uuid, worklog_ids: &[i64]) -> OviaResult<u64> {
+        let result =
+            sqlx::query("delete from jira_worklogs where org_id = $1 and worklog_id = any($2)")
+                .bind(org_id)
+                .bind(worklog_ids)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(result.rows_affected())
+    }
+
+    // ── Jira effort queries ──────────────────────────────────────
+
+    /// Hours logged in [from, to] per issue type. Issues without a type are grouped
+    /// under "Unknown". Here and in the other effort queries, time logged after its
+    /// author left is skipped.
+    pub async fn get_effort_by_issue_type(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<EffortRow>> {
+        self.get_effort_by("coalesce(ji.issue_type, 'Unknown')", org_id, from, to)
+            .await
+    }
+
+    /// Hours logged in [from, to] per team. A worklog counts for its author's team,
+    /// else for the issue's Jira team; the rest is grouped under "Unassigned".
+    pub async fn get_effort_by_team(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<EffortRow>> {
+        self.get_effort_by(
+            "coalesce(author_team.team, ji.team_name, 'Unassigned')",
+            org_id,
+            from,
+            to,
+        )
+        .await
+    }
+
+    async fn get_effort_by(
+        &self,
+        group_expr: &str,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<EffortRow>> {
+        let rows = sqlx::query(&format!(
+            "select {group_expr} as grp,
+                    (sum(w.time_spent_secs) / 3600.0)::float8 as hours,
+                    count(distinct ji.jira_key) as issues,
+                    count(distinct w.author_account_id) as authors
+             from jira_worklogs w
+             join jira_issues ji on ji.org_id = w.org_id and ji.jira_issue_id = w.jira_issue_id
+             left join lateral (
+               select p.team
+               from identities i
+               join person_identity_links l
+                 on l.identity_id = i.id and l.org_id = i.org_id and l.valid_to is null
+               join people p on p.id = l.person_id
+               where i.org_id = w.org_id and i.source = 'jira'
+                 and i.external_id = w.author_account_id and p.team is not null
+               limit 1
+             ) author_team on true
+             where w.org_id = $1
+               and w.started_at >= $2::date
+               and w.started_at < ($3::date + interval '1 day')
+               and not is_departed(w.org_id, 'jira', w.author_account_id, w.started_at)
+             group by grp
+             order by hours desc, grp"
+        ))
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| EffortRow {
+                group: r.get("grp"),
+                hours: r.get("hours"),
+                issues: r.get("issues"),
+                authors: r.get("authors"),
+            })
+            .collect())
+    }
+
+    /// Logged hours per issue against story points, for estimated issues resolved in
+    /// [from, to] that have any time logged. Smallest estimate first.
+    pub async fn get_effort_vs_story_points(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<StoryPointEffortRow>> {
+        let rows = sqlx::query(
+            "with per_issue as (
+               select ji.story_points::float8 as story_points,
+                      sum(w.time_spent_secs) / 3600.0 as hours
+               from jira_issues ji
+               join jira_worklogs w on w.org_id = ji.org_id and w.jira_issue_id = ji.jira_issue_id
+               where ji.org_id = $1
+                 and ji.story_points is not null
+                 and ji.resolved_at >= $2::date
+                 and ji.resolved_at < ($3::date + interval '1 day')
+                 and not is_departed(w.org_id, 'jira', w.author_account_id, w.started_at)
+               group by ji.jira_key, ji.story_points
+             )
+             select story_points, count(*) as issues,
+                    avg(hours)::float8 as hours_avg,
+                    (percentile_cont(0.5) within group (order by hours))::float8 as hours_p50
+             from per_issue
+             group by story_points
+             order by story_points",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| StoryPointEffortRow {
+                story_points: r.get("story_points"),
+                issues: r.get("issues"),
+                hours_avg: r.get("hours_avg"),
+                hours_p50: r.get("hours_p50"),
+            })
+            .collect())
+    }
+
     // ── Jira KPI metrics queries ─────────────────────────────────
 
     /// Count open blocker issues (priority = 'Blocker' or 'Highest', not resolved).
@@ -150,8 +906,57 @@ impl PgJiraRepository {
         Ok(unresolved as f64 / total as f64)
     }
 
+    /// Spillover of each sprint completed in [from, to]: the issues in the sprint at
+    /// completion, and those of them not resolved by then. Oldest sprint first.
+    pub async fn get_sprint_spillover(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<SprintSpilloverRow>> {
+        let rows = sqlx::query(
+            "select s.sprint_id, s.name, s.board_id, s.completed_at,
+                    count(distinct h.jira_key) as committed,
+                    count(distinct h.jira_key) filter (
+                      where ji.resolved_at is null or ji.resolved_at > s.completed_at
+                    ) as unresolved
+             from jira_sprints s
+             join jira_issue_sprints h
+               on h.org_id = s.org_id and h.sprint_id = s.sprint_id
+              and (h.added_at is null or h.added_at <= s.completed_at)
+              and (h.removed_at is null or h.removed_at >= s.completed_at)
+             join jira_issues ji on ji.org_id = h.org_id and ji.jira_key = h.jira_key
+             where s.org_id = $1 and s.state = 'closed'
+               and s.completed_at >= $2::date
+               and s.completed_at < ($3::date + interval '1 day')
+             group by s.sprint_id, s.name, s.board_id, s.completed_at
+             order by s.completed_at asc",
+        )
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| SprintSpilloverRow {
+                sprint_id: r.get("sprint_id"),
+                name: r.get("name"),
+                board_id: r.get("board_id"),
+                completed_at: r.get("completed_at"),
+                committed: r.get("committed"),
+                unresolved: r.get("unresolved"),
+            })
+            .collect())
+    }
+
     /// Get cycle times in hours for issues resolved in the given period.
     /// Cycle time = first "In Progress" transition → resolved_at.
+    ///
+    /// Resolution queries skip issues whose assignee had already left
+    /// (`people.left_at`) by the resolution date.
     pub async fn get_cycle_times_hours(
         &self,
         org_id: Uuid,
@@ -174,6 +979,7 @@ impl PgJiraRepository {
                and ji.resolved_at is not null
                and ji.resolved_at >= $2::date
                and ji.resolved_at < $3::date
+               and not is_departed(ji.org_id, 'jira', ji.assignee_account_id, ji.resolved_at)
              order by hours",
         )
         .bind(org_id)
@@ -194,10 +1000,11 @@ impl PgJiraRepository {
         to: NaiveDate,
     ) -> OviaResult<i64> {
         let row = sqlx::query(
-            "select count(*) as cnt from jira_issues
+            "select count(*) as cnt from jira_issues ji
              where org_id = $1
                and resolved_at >= $2::date
-               and resolved_at < $3::date",
+               and resolved_at < $3::date
+               and not is_departed(ji.org_id, 'jira', ji.assignee_account_id, ji.resolved_at)",
         )
         .bind(org_id)
         .bind(from)
@@ -218,10 +1025,11 @@ impl PgJiraRepository {
     ) -> OviaResult<i64> {
         let types_vec: Vec<String> = issue_types.iter().map(|s| s.to_string()).collect();
         let row = sqlx::query(
-            "select count(*) as cnt from jira_issues
+            "select count(*) as cnt from jira_issues ji
              where org_id = $1
                and resolved_at >= $2::date
                and resolved_at < $3::date
+               and not is_departed(ji.org_id, 'jira', ji.assignee_account_id, ji.resolved_at)
                and issue_type = any($4)",
         )
         .bind(org_id)
@@ -243,10 +1051,11 @@ impl PgJiraRepository {
         issue_type: &str,
     ) -> OviaResult<i64> {
         let row = sqlx::query(
-            "select count(*) as cnt from jira_issues
+            "select count(*) as cnt from jira_issues ji
              where org_id = $1
                and resolved_at >= $2::date
                and resolved_at < $3::date
+               and not is_departed(ji.org_id, 'jira', ji.assignee_account_id, ji.resolved_at)
                and issue_type = $4",
         )
         .bind(org_id)
@@ -274,6 +1083,215 @@ impl PgJiraRepository {
                 .map_err(|e| OviaError::Database(e.to_string()))?;
         Ok(result.rows_affected())
     }
+
+    /// Delete an issue with its transitions, sprint history, links, parent and
+    /// worklogs. Returns `false` if the issue was unknown.
+    pub async fn delete_issue(&self, org_id: Uuid, jira_key: &str) -> OviaResult<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let deleted = Self::delete_issue_rows(&mut tx, org_id, jira_key).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(deleted)
+    }
+
+    async fn delete_issue_rows(
+        tx: &mut Transaction<'_, Postgres>,
+        org_id: Uuid,
+        jira_key: &str,
+    ) -> OviaResult<bool> {
+        for stmt in [
+            "delete from jira_issue_transitions where org_id = $1 and jira_key = $2",
+            "delete from jira_issue_sprints where org_id = $1 and jira_key = $2",
+            "delete from jira_issue_links
+             where org_id = $1 and (source_key = $2 or target_key = $2)",
+            "delete from jira_issue_parents where org_id = $1 and jira_key = $2",
+            "delete from jira_worklogs w
+             using jira_issues ji
+             where ji.org_id = $1 and ji.jira_key = $2
+               and w.org_id = ji.org_id and w.jira_issue_id = ji.jira_issue_id",
+        ] {
+            sqlx::query(stmt)
+                .bind(org_id)
+                .bind(jira_key)
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+        let result = sqlx::query("delete from jira_issues where org_id = $1 and jira_key = $2")
+            .bind(org_id)
+            .bind(jira_key)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_tombstone(
+        tx: &mut Transaction<'_, Postgres>,
+        org_id: Uuid,
+        jira_key: &str,
+        reason: &str,
+        moved_to_key: Option<&str>,
+    ) -> OviaResult<()> {
+        sqlx::query(
+            "insert into jira_issue_tombstones
+             (org_id, jira_key, jira_issue_id, project_key, reason, moved_to_key)
+             select org_id, jira_key, jira_issue_id, project_key, $3, $4
+             from jira_issues
+             where org_id = $1 and jira_key = $2
+             on conflict (org_id, jira_key) do update set
+               reason = excluded.reason,
+               moved_to_key = excluded.moved_to_key,
+               tombstoned_at = now()",
+        )
+        .bind(org_id)
+        .bind(jira_key)
+        .bind(reason)
+        .bind(moved_to_key)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(())
+    }
+
+    // ── Reconciliation ───────────────────────────────────────────
+
+    /// Keys and Jira ids of the stored issues of a project.
+    pub async fn list_issue_refs(
+        &self,
+        org_id: Uuid,
+        project_key: &str,
+    ) -> OviaResult<Vec<JiraIssueRef>> {
+        let rows = sqlx::query(
+            "select jira_key, jira_issue_id from jira_issues
+             where org_id = $1 and project_key = $2
+             order by jira_key",
+        )
+        .bind(org_id)
+        .bind(project_key)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| JiraIssueRef {
+                jira_key: r.get("jira_key"),
+                jira_issue_id: r.get("jira_issue_id"),
+            })
+            .collect())
+    }
+
+    /// Record a tombstone for an issue that is gone from Jira (`reason` "deleted")
+    /// or from the synced projects ("moved"), then delete it like `delete_issue`.
+    /// Returns `false` if the issue was unknown.
+    pub async fn tombstone_issue(
+        &self,
+        org_id: Uuid,
+        jira_key: &str,
+        reason: &str,
+        moved_to_key: Option<&str>,
+    ) -> OviaResult<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Self::insert_tombstone(&mut tx, org_id, jira_key, reason, moved_to_key).await?;
+        let deleted = Self::delete_issue_rows(&mut tx, org_id, jira_key).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(deleted)
+    }
+
+    /// Move an issue and its history from `old_key` to `new_key` after a move in
+    /// Jira, leaving a "moved" tombstone for the old key. If `new_key` was already
+    /// synced, the old rows are dropped instead. Returns `false` if `old_key` was
+    /// unknown.
+    pub async fn rekey_issue(
+        &self,
+        org_id: Uuid,
+        old_key: &str,
+        new_key: &str,
+    ) -> OviaResult<bool> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Self::insert_tombstone(&mut tx, org_id, old_key, "moved", Some(new_key)).await?;
+
+        let new_exists: bool = sqlx::query_scalar(
+            "select exists(select 1 from jira_issues where org_id = $1 and jira_key = $2)",
+        )
+        .bind(org_id)
+        .bind(new_key)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let moved = if new_exists {
+            Self::delete_issue_rows(&mut tx, org_id, old_key).await?
+        } else {
+            let result = sqlx::query(
+                "update jira_issues
+                 set jira_key = $3, project_key = split_part($3, '-', 1), updated_at = now()
+                 where org_id = $1 and jira_key = $2",
+            )
+            .bind(org_id)
+            .bind(old_key)
+            .bind(new_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+            for stmt in [
+                "update jira_issue_transitions set jira_key = $3 where org_id = $1 and jira_key = $2",
+                "update jira_issue_sprints set jira_key = $3 where org_id = $1 and jira_key = $2",
+                "update jira_issue_parents set jira_key = $3 where org_id = $1 and jira_key = $2",
+                "update jira_issue_parents set parent_key = $3 where org_id = $1 and parent_key = $2",
+                "update jira_issue_links set source_key = $3 where org_id = $1 and source_key = $2",
+                "update jira_issue_links set target_key = $3 where org_id = $1 and target_key = $2",
+            ] {
+                sqlx::query(stmt)
+                    .bind(org_id)
+                    .bind(old_key)
+                    .bind(new_key)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+            }
+            result.rows_affected() > 0
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(moved)
+    }
+}
+
+fn map_field_mapping_row(r: &sqlx::postgres::PgRow) -> JiraFieldMapping {
+    JiraFieldMapping {
+        id: r.get("id"),
+        org_id: r.get("org_id"),
+        role: r.get("role"),
+        field_id: r.get("field_id"),
+        field_name: r.get("field_name"),
+        is_manual: r.get("is_manual"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    }
 }
 
 #[cfg(test)]
@@ -322,6 +1340,129 @@ mod tests {
         .execute(&pool)
         .await
         .ok()?;
+        sqlx::query(
+            "create unique index if not exists jira_transitions_natural_uidx
+             on jira_issue_transitions(org_id, jira_key, field, transitioned_at, from_value, to_value)
+             nulls not distinct",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        for stmt in [
+            "create table if not exists jira_sprints (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, sprint_id bigint not null, board_id bigint,
+              name text not null, state text not null, goal text,
+              start_at timestamptz, end_at timestamptz, completed_at timestamptz,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_sprints_org_sprint_uidx on jira_sprints(org_id, sprint_id)",
+            "create table if not exists jira_boards (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, board_id bigint not null, name text not null,
+              board_type text not null, project_key text,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_boards_org_board_uidx on jira_boards(org_id, board_id)",
+            "create table if not exists jira_issue_sprints (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_key text not null, sprint_id bigint not null,
+              added_at timestamptz, removed_at timestamptz,
+              created_at timestamptz not null default now()
+            )",
+            "create table if not exists jira_issue_links (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_link_id bigint not null, link_type text not null,
+              outward text, inward text, source_key text not null, target_key text not null,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_issue_links_org_link_uidx on jira_issue_links(org_id, jira_link_id)",
+            "create table if not exists jira_issue_parents (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_key text not null, parent_key text not null,
+              parent_issue_type text,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_issue_parents_org_key_uidx on jira_issue_parents(org_id, jira_key)",
+            "alter table jira_issues add column if not exists jira_issue_id bigint",
+            "alter table jira_issues add column if not exists extra_fields jsonb",
+            "alter table jira_issues add column if not exists changelog_cursor timestamptz",
+            "create table if not exists jira_issue_tombstones (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_key text not null, jira_issue_id bigint,
+              project_key text not null, reason text not null, moved_to_key text,
+              tombstoned_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_issue_tombstones_org_key_uidx
+              on jira_issue_tombstones(org_id, jira_key)",
+            "create table if not exists jira_field_mappings (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, role text not null, field_id text not null, field_name text,
+              is_manual boolean not null default false,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_field_mappings_org_role_uidx
+              on jira_field_mappings(org_id, role) where role not in ('extra', 'changelog')",
+            "create unique index if not exists jira_field_mappings_org_field_uidx
+              on jira_field_mappings(org_id, role, field_id) where role in ('extra', 'changelog')",
+            "create table if not exists jira_worklogs (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, worklog_id bigint not null, jira_issue_id bigint not null,
+              author_account_id text, started_at timestamptz not null,
+              time_spent_secs bigint not null, updated_at_jira timestamptz,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create unique index if not exists jira_worklogs_org_worklog_uidx on jira_worklogs(org_id, worklog_id)",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+
+        sqlx::query("alter table people add column if not exists left_at timestamptz")
+            .execute(&pool)
+            .await
+            .ok()?;
+        sqlx::query(
+            "create or replace view departed_identities as
+             select distinct i.org_id, i.source, i.external_id, i.username, p.left_at
+             from people p
+             join person_identity_links pil on pil.person_id = p.id and pil.org_id = p.org_id
+             join identities i on i.id = pil.identity_id
+             where p.left_at is not null
+               and pil.status not in ('rejected', 'ignored')",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        for stmt in [
+            "create table if not exists erased_accounts (
+              org_id uuid not null, source text not null, account_hash text not null,
+              alias text not null, created_at timestamptz not null default now(),
+              primary key (org_id, source, account_hash)
+            )",
+            "create or replace function erased_alias(p_org_id uuid, p_source text, p_account text)
+             returns text language sql stable as $$
+               select alias from erased_accounts
+               where org_id = p_org_id and source = p_source
+                 and account_hash = encode(sha256(convert_to(p_account, 'UTF8')), 'hex')
+             $$",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+        sqlx::query(
+            "create or replace function is_departed(p_org_id uuid, p_source text, p_account text, p_at timestamptz)
+             returns boolean language sql stable as $$
+               select exists (
+                 select 1 from departed_identities d
+                 where d.org_id = p_org_id and d.source = p_source
+                   and (case when p_source = 'jira' then d.external_id else d.username end) = p_account
+                   and d.left_at <= p_at
+               )
+             $$",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
 
         Some((PgJiraRepository::new(pool.clone()), pool))
     }
@@ -332,6 +1473,7 @@ mod tests {
             id: Uuid::new_v4(),
             org_id,
             jira_key: key.to_string(),
+            jira_issue_id: None,
             project_key: "BEE".to_string(),
             issue_type: Some("Story".to_string()),
             summary: format!("Test issue {key}"),
@@ -344,6 +1486,7 @@ mod tests {
             sprint_id: Some(100),
             team_name: Some("Team Alpha".to_string()),
             labels: vec!["backend".to_string()],
+            extra_fields: None,
             created_at_jira: Some(now),
             updated_at_jira: Some(now),
             resolved_at: None,
@@ -402,6 +1545,13 @@ mod tests {
             created_at: now,
         };
         repo.insert_transition(&t).await.expect("insert transition");
+        // The same changelog item delivered again, e.g. by a webhook retry
+        repo.insert_transition(&JiraIssueTransition {
+            id: Uuid::new_v4(),
+            ..t.clone()
+        })
+        .await
+        .expect("insert repeated transition");
 
         let deleted = repo
             .delete_transitions_for_issue(org, "BEE-3")
@@ -410,6 +1560,158 @@ mod tests {
         assert_eq!(deleted, 1);
     }
 
+    #[tokio::test]
+    async fn upsert_issue_ignores_older_updates() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let mut issue = make_issue(org, "BEE-4");
+        issue.status = "Done".to_string();
+        repo.upsert_issue(&issue).await.expect("insert");
+
+        let mut stale = issue.clone();
+        stale.status = "In Progress".to_string();
+        stale.updated_at_jira = issue.updated_at_jira.map(|t| t - Duration::hours(1));
+        repo.upsert_issue(&stale).await.expect("stale upsert");
+
+        let status: String = sqlx::query_scalar(
+            "select status from jira_issues where org_id = $1 and jira_key = $2",
+        )
+        .bind(org)
+        .bind("BEE-4")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(status, "Done");
+    }
+
+    #[tokio::test]
+    async fn delete_issue_removes_issue_and_transitions() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        repo.upsert_issue(&make_issue(org, "BEE-5"))
+            .await
+            .expect("insert");
+        let now = Utc::now();
+        repo.insert_transition(&JiraIssueTransition {
+            id: Uuid::new_v4(),
+            org_id: org,
+            jira_key: "BEE-5".to_string(),
+            field: "status".to_string(),
+            from_value: Some("To Do".to_string()),
+            to_value: Some("Done".to_string()),
+            author_account_id: None,
+            transitioned_at: now,
+            created_at: now,
+        })
+        .await
+        .expect("insert transition");
+
+        assert!(repo.delete_issue(org, "BEE-5").await.unwrap());
+        assert!(!repo.delete_issue(org, "BEE-5").await.unwrap());
+        assert_eq!(
+            repo.delete_transitions_for_issue(org, "BEE-5")
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn issue_histories_are_stored_in_batches_with_cursors() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let issues = vec![make_issue(org, "BEE-6"), make_issue(org, "BEE-7")];
+        repo.upsert_issues(&issues).await.expect("batch upsert");
+
+        let now = Utc::now();
+        let transition = |key: &str, to: &str| JiraIssueTransition {
+            id: Uuid::new_v4(),
+            org_id: org,
+            jira_key: key.to_string(),
+            field: "status".to_string(),
+            from_value: None,
+            to_value: Some(to.to_string()),
+            author_account_id: None,
+            transitioned_at: now,
+            created_at: now,
+        };
+        let sprint = |key: &str, sprint_id| JiraIssueSprint {
+            org_id: org,
+            jira_key: key.to_string(),
+            sprint_id,
+            added_at: Some(now),
+            removed_at: None,
+        };
+        repo.replace_issue_histories(
+            org,
+            &[
+                JiraIssueHistory {
+                    jira_key: "BEE-6".to_string(),
+                    changelog_cursor: issues[0].updated_at_jira,
+                    transitions: vec![
+                        transition("BEE-6", "In Progress"),
+                        transition("BEE-6", "Done"),
+                    ],
+                    sprints: vec![sprint("BEE-6", 100)],
+                },
+                JiraIssueHistory {
+                    jira_key: "BEE-7".to_string(),
+                    changelog_cursor: None,
+                    transitions: vec![transition("BEE-7", "In Progress")],
+                    sprints: vec![],
+                },
+            ],
+        )
+        .await
+        .expect("first histories");
+
+        // A changelog without allow-listed changes drops the stored transitions too
+        repo.replace_issue_histories(
+            org,
+            &[JiraIssueHistory {
+                jira_key: "BEE-6".to_string(),
+                changelog_cursor: issues[0].updated_at_jira,
+                transitions: vec![],
+                sprints: vec![sprint("BEE-6", 101), sprint("BEE-6", 102)],
+            }],
+        )
+        .await
+        .expect("second histories");
+
+        let count = |table: &'static str, key: &'static str| {
+            let pool = pool.clone();
+            async move {
+                sqlx::query_scalar::<_, i64>(&format!(
+                    "select count(*) from {table} where org_id = $1 and jira_key = $2"
+                ))
+                .bind(org)
+                .bind(key)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+            }
+        };
+        assert_eq!(count("jira_issue_transitions", "BEE-6").await, 0);
+        assert_eq!(count("jira_issue_transitions", "BEE-7").await, 1);
+        assert_eq!(count("jira_issue_sprints", "BEE-6").await, 2);
+
+        let cursors = repo.list_changelog_cursors(org).await.unwrap();
+        assert_eq!(cursors.len(), 1);
+        assert_eq!(
+            cursors.get("BEE-6").map(|t| t.timestamp_micros()),
+            issues[0].updated_at_jira.map(|t| t.timestamp_micros())
+        );
+    }
+
     // ── Jira KPI metrics tests ────────────────────────────────────
 
     #[tokio::test]
@@ -627,4 +1929,467 @@ mod tests {
             .expect("stories");
         assert_eq!(stories, 1);
     }
+
+    #[tokio::test]
+    async fn sprint_spillover_counts_issues_in_sprint_at_completion() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let completed = Utc::now() - Duration::days(1);
+        let now = Utc::now();
+
+        repo.upsert_sprint(&JiraSprint {
+            id: Uuid::new_v4(),
+            org_id: org,
+            sprint_id: 42,
+            board_id: Some(7),
+            name: "Sprint 42".to_string(),
+            state: "closed".to_string(),
+            goal: Some("Ship checkout".to_string()),
+            start_at: Some(completed - Duration::days(14)),
+            end_at: Some(completed),
+            completed_at: Some(completed),
+            created_at: now,
+            updated_at: now,
+        })
+        .await
+        .unwrap();
+
+        // key, resolved, added, removed
+        let cases = [
+            ("BEE-1", Some(completed - Duration::days(2)), None, None), // done in sprint
+            ("BEE-2", None, Some(completed - Duration::days(10)), None), // carried over
+            ("BEE-3", Some(completed + Duration::hours(2)), None, None), // done too late
+            (
+                "BEE-4",
+                None,
+                Some(completed - Duration::days(10)),
+                Some(completed - Duration::days(5)), // moved out before the end
+            ),
+            ("BEE-5", None, Some(completed + Duration::hours(1)), None), // added after
+        ];
+        for (key, resolved, added, removed) in cases {
+            let mut issue = make_issue(org, key);
+            issue.resolved_at = resolved;
+            repo.upsert_issue(&issue).await.unwrap();
+            repo.replace_issue_sprints(
+                org,
+                key,
+                &[JiraIssueSprint {
+                    org_id: org,
+                    jira_key: key.to_string(),
+                    sprint_id: 42,
+                    added_at: added,
+                    removed_at: removed,
+                }],
+            )
+            .await
+            .unwrap();
+        }
+
+        let today = now.date_naive();
+        let rows = repo
+            .get_sprint_spillover(org, today - Duration::days(7), today)
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "Sprint 42");
+        assert_eq!((rows[0].committed, rows[0].unresolved), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn dependency_chain_and_cross_team_blocks_follow_blocking_links() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        // BEE-1 (Platform) blocks BEE-2 (Checkout), which blocks BEE-3 (Checkout).
+        // BEE-4 only relates to BEE-3.
+        for (key, team, status) in [
+            ("BEE-1", "Platform", "In Progress"),
+            ("BEE-2", "Checkout", "To Do"),
+            ("BEE-3", "Checkout", "To Do"),
+            ("BEE-4", "Platform", "To Do"),
+        ] {
+            let mut issue = make_issue(org, key);
+            issue.team_name = Some(team.to_string());
+            issue.status = status.to_string();
+            repo.upsert_issue(&issue).await.unwrap();
+        }
+        let link = |id, link_type: &str, source: &str, target: &str| JiraIssueLink {
+            org_id: org,
+            jira_link_id: id,
+            link_type: link_type.to_string(),
+            outward: None,
+            inward: None,
+            source_key: source.to_string(),
+            target_key: target.to_string(),
+        };
+        repo.replace_issue_relations(
+            org,
+            "BEE-2",
+            &[
+                link(1, "Blocks", "BEE-1", "BEE-2"),
+                link(2, "Blocks", "BEE-2", "BEE-3"),
+            ],
+            &[JiraIssueParent {
+                org_id: org,
+                jira_key: "BEE-2".to_string(),
+                parent_key: "BEE-100".to_string(),
+                parent_issue_type: Some("Epic".to_string()),
+            }],
+        )
+        .await
+        .unwrap();
+        repo.replace_issue_relations(org, "BEE-4", &[link(3, "Relates", "BEE-4", "BEE-3")], &[])
+            .await
+            .unwrap();
+
+        let chain = repo.get_dependency_chain(org, "BEE-3", 5).await.unwrap();
+        let keys: Vec<_> = chain
+            .iter()
+            .map(|c| (c.jira_key.as_str(), c.depth))
+            .collect();
+        assert_eq!(keys, vec![("BEE-2", 1), ("BEE-1", 2)]);
+        assert_eq!(chain[1].team_name.as_deref(), Some("Platform"));
+        assert!(!chain[1].is_done);
+
+        let blocks = repo.list_cross_team_blocks(org).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].blocker_key, "BEE-1");
+        assert_eq!(blocks[0].blocked_key, "BEE-2");
+        assert_eq!(blocks[0].blocker_team, "Platform");
+
+        // Resolving the blocker clears the cross-team block
+        let mut done = make_issue(org, "BEE-1");
+        done.team_name = Some("Platform".to_string());
+        done.status = "Done".to_string();
+        done.updated_at_jira = Some(Utc::now() + Duration::minutes(1));
+        repo.upsert_issue(&done).await.unwrap();
+        assert!(repo.list_cross_team_blocks(org).await.unwrap().is_empty());
+
+        // Re-syncing BEE-2 without links drops both of its links
+        repo.replace_issue_relations(org, "BEE-2", &[], &[])
+            .await
+            .unwrap();
+        assert!(repo
+            .get_dependency_chain(org, "BEE-3", 5)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn effort_groups_worklogs_by_issue_type_team_and_story_points() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+
+        // key, issue id, type, story points, resolved
+        for (key, issue_id, issue_type, points, resolved) in [
+            ("BEE-1", 101, "Bug", 2.0, true),
+            ("BEE-2", 102, "Story", 3.0, true),
+            ("BEE-3", 103, "Story", 3.0, true),
+            ("BEE-4", 104, "Story", 5.0, false),
+        ] {
+            let mut issue = make_issue(org, key);
+            issue.jira_issue_id = Some(issue_id);
+            issue.issue_type = Some(issue_type.to_string());
+            issue.story_points = Some(points);
+            issue.resolved_at = resolved.then_some(now);
+            repo.upsert_issue(&issue).await.unwrap();
+        }
+        // worklog id, issue id, hours
+        for (worklog_id, issue_id, hours) in [
+            (1, 101, 1),
+            (2, 102, 2),
+            (3, 102, 2),
+            (4, 103, 6),
+            (5, 104, 8),
+        ] {
+            repo.upsert_worklog(&JiraWorklog {
+                id: Uuid::new_v4(),
+                org_id: org,
+                worklog_id,
+                jira_issue_id: issue_id,
+                author_account_id: Some(format!("acc-{worklog_id}")),
+                started_at: now - Duration::hours(1),
+                time_spent_secs: hours * 3600,
+                updated_at_jira: Some(now),
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+        }
+        // Deleted in Jira
+        assert_eq!(repo.delete_worklogs(org, &[5]).await.unwrap(), 1);
+
+        let today = now.date_naive();
+        let by_type = repo
+            .get_effort_by_issue_type(org, today - Duration::days(1), today)
+            .await
+            .unwrap();
+        let by_type: Vec<_> = by_type
+            .iter()
+            .map(|r| (r.group.as_str(), r.hours, r.issues))
+            .collect();
+        assert_eq!(by_type, vec![("Story", 10.0, 2), ("Bug", 1.0, 1)]);
+
+        // No author is linked to a person, so the issue's Jira team applies
+        let by_team = repo
+            .get_effort_by_team(org, today - Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(by_team.len(), 1);
+        assert_eq!(by_team[0].group, "Team Alpha");
+        assert_eq!(by_team[0].authors, 4);
+
+        let by_points = repo
+            .get_effort_vs_story_points(org, today - Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(by_points.len(), 2);
+        assert_eq!((by_points[0].story_points, by_points[0].issues), (2.0, 1));
+        assert_eq!((by_points[1].story_points, by_points[1].issues), (3.0, 2));
+        assert!((by_points[1].hours_avg - 5.0).abs() < 0.01);
+        assert!((by_points[1].hours_p50 - 5.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn effort_skips_time_logged_after_author_left() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+
+        let person_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into people (id, org_id, display_name, left_at) values ($1, $2, 'gone', $3)",
+        )
+        .bind(person_id)
+        .bind(org)
+        .bind(now - Duration::hours(2))
+        .execute(&pool)
+        .await
+        .expect("insert person");
+        let identity_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into identities (id, org_id, source, external_id) values ($1, $2, 'jira', 'acc-gone')",
+        )
+        .bind(identity_id)
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("insert identity");
+        sqlx::query(
+            "insert into person_identity_links (id, org_id, person_id, identity_id, status)
+             values ($1, $2, $3, $4, 'verified')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org)
+        .bind(person_id)
+        .bind(identity_id)
+        .execute(&pool)
+        .await
+        .expect("insert link");
+
+        let mut issue = make_issue(org, "BEE-1");
+        issue.jira_issue_id = Some(101);
+        issue.issue_type = Some("Story".to_string());
+        issue.story_points = Some(3.0);
+        issue.resolved_at = Some(now);
+        repo.upsert_issue(&issue).await.unwrap();
+        // logged before and after the author left
+        for (worklog_id, started) in [(1, now - Duration::hours(3)), (2, now - Duration::hours(1))]
+        {
+            repo.upsert_worklog(&JiraWorklog {
+                id: Uuid::new_v4(),
+                org_id: org,
+                worklog_id,
+                jira_issue_id: 101,
+                author_account_id: Some("acc-gone".to_string()),
+                started_at: started,
+                time_spent_secs: 3600,
+                updated_at_jira: Some(now),
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+        }
+
+        let today = now.date_naive();
+        let by_type = repo
+            .get_effort_by_issue_type(org, today - Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(by_type.len(), 1);
+        assert!((by_type[0].hours - 1.0).abs() < 0.01);
+
+        let by_points = repo
+            .get_effort_vs_story_points(org, today - Duration::days(1), today)
+            .await
+            .unwrap();
+        assert_eq!(by_points.len(), 1);
+        assert!((by_points[0].hours_avg - 1.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn discovered_fields_do_not_override_manual_mappings() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        repo.upsert_discovered_field(org, "story_points", "customfield_10016", "Story Points")
+            .await
+            .unwrap();
+        repo.upsert_discovered_field(org, "sprint", "customfield_10020", "Sprint")
+            .await
+            .unwrap();
+        // Second site: story points live elsewhere, set by hand
+        repo.set_manual_field(org, "story_points", "customfield_10106", Some("Points"))
+            .await
+            .unwrap();
+        repo.upsert_discovered_field(org, "story_points", "customfield_10016", "Story Points")
+            .await
+            .unwrap();
+        repo.set_manual_field(org, "extra", "customfield_10200", Some("Severity"))
+            .await
+            .unwrap();
+        repo.set_manual_field(org, "extra", "customfield_10201", None)
+            .await
+            .unwrap();
+        // The same field can be both an extra field and a changelog field
+        repo.set_manual_field(org, "changelog", "customfield_10200", None)
+            .await
+            .unwrap();
+
+        let rows = repo.list_field_mappings(org).await.unwrap();
+        let rows: Vec<_> = rows
+            .iter()
+            .map(|m| (m.role.as_str(), m.field_id.as_str(), m.is_manual))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("changelog", "customfield_10200", true),
+                ("extra", "customfield_10200", true),
+                ("extra", "customfield_10201", true),
+                ("sprint", "customfield_10020", false),
+                ("story_points", "customfield_10106", true),
+            ]
+        );
+
+        assert!(repo
+            .delete_field_mapping(org, "extra", "customfield_10201")
+            .await
+            .unwrap());
+        assert!(!repo
+            .delete_field_mapping(org, "extra", "customfield_10201")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn tombstone_and_rekey_issues() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let now = Utc::now();
+
+        for (key, id) in [("BEE-1", 1), ("BEE-2", 2), ("BEE-3", 3)] {
+            let mut issue = make_issue(org, key);
+            issue.jira_issue_id = Some(id);
+            repo.upsert_issue(&issue).await.unwrap();
+        }
+        repo.insert_transition(&JiraIssueTransition {
+            id: Uuid::new_v4(),
+            org_id: org,
+            jira_key: "BEE-2".to_string(),
+            field: "status".to_string(),
+            from_value: Some("To Do".to_string()),
+            to_value: Some("In Progress".to_string()),
+            author_account_id: None,
+            transitioned_at: now,
+            created_at: now,
+        })
+        .await
+        .unwrap();
+        repo.replace_issue_relations(
+            org,
+            "BEE-3",
+            &[JiraIssueLink {
+                org_id: org,
+                jira_link_id: 9,
+                link_type: "Blocks".to_string(),
+                outward: None,
+                inward: None,
+                source_key: "BEE-2".to_string(),
+                target_key: "BEE-3".to_string(),
+            }],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        assert!(repo
+            .tombstone_issue(org, "BEE-1", "deleted", None)
+            .await
+            .unwrap());
+        assert!(repo.rekey_issue(org, "BEE-2", "OPS-7").await.unwrap());
+        assert!(!repo.rekey_issue(org, "BEE-2", "OPS-7").await.unwrap());
+
+        let refs = repo.list_issue_refs(org, "BEE").await.unwrap();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].jira_key, "BEE-3");
+        let moved = repo.list_issue_refs(org, "OPS").await.unwrap();
+        assert_eq!(moved[0].jira_key, "OPS-7");
+        assert_eq!(moved[0].jira_issue_id, Some(2));
+
+        // History and links follow the new key
+        let transitions: i64 = sqlx::query_scalar(
+            "select count(*) from jira_issue_transitions where org_id = $1 and jira_key = 'OPS-7'",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(transitions, 1);
+        let chain = repo.get_dependency_chain(org, "BEE-3", 3).await.unwrap();
+        assert_eq!(chain[0].jira_key, "OPS-7");
+
+        let tombstones: Vec<(String, String, Option<String>)> = sqlx::query_as(
+            "select jira_key, reason, moved_to_key from jira_issue_tombstones
+             where org_id = $1 order by jira_key",
+        )
+        .bind(org)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            tombstones,
+            vec![
+                ("BEE-1".to_string(), "deleted".to_string(), None),
+                (
+                    "BEE-2".to_string(),
+                    "moved".to_string(),
+                    Some("OPS-7".to_string())
+                ),
+            ]
+        );
+    }
 }
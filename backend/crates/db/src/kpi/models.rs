@@ -20,10 +20,38 @@ pub struct KpiSnapshot {
     pub spillover_rate: Option<f64>,
     pub cycle_time_p50_hours: Option<f64>,
     pub cycle_time_p90_hours: Option<f64>,
+    pub lead_time_p50_hours: Option<f64>,
+    pub lead_time_p90_hours: Option<f64>,
+    pub time_to_first_review_p50_hours: Option<f64>,
+    pub time_to_first_review_p90_hours: Option<f64>,
+    pub review_rounds_avg: Option<f64>,
+    pub mr_size_stats: Option<MrSizeStats>,
     pub computed_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
+/// MR size distribution for a KPI period, stored as jsonb on the snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MrSizeStats {
+    /// All merged MRs with a known size, one entry per bucket, smallest first.
+    pub buckets: Vec<MrSizeBucketStats>,
+    /// The same split per author team. Authors without a team are under `None`.
+    pub teams: Vec<TeamMrSizeStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MrSizeBucketStats {
+    pub bucket: String,
+    pub mr_count: i32,
+    pub review_latency_median_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamMrSizeStats {
+    pub team: Option<String>,
+    pub buckets: Vec<MrSizeBucketStats>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RiskItem {
     pub id: Uuid,
@@ -39,6 +67,23 @@ pub struct RiskItem {
     pub created_at: DateTime<Utc>,
 }
 
+/// DORA metrics for one scope of a KPI snapshot: the whole org (`scope_type = "org"`,
+/// no key), a GitLab project (keyed by path) or a team (keyed by team name).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DoraMetrics {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub snapshot_id: Uuid,
+    pub scope_type: String,
+    pub scope_key: Option<String>,
+    pub deployment_count: i32,
+    pub deployments_per_day: Option<f64>,
+    pub lead_time_p50_hours: Option<f64>,
+    pub change_failure_rate: Option<f64>,
+    pub time_to_restore_p50_hours: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct KpiFilter {
     pub org_id: Option<Uuid>,
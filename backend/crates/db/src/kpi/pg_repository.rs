@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use sqlx::{PgPool, QueryBuilder, Row};
 use uuid::Uuid;
 
-use crate::kpi::models::{KpiFilter, KpiSnapshot, RiskItem};
+use crate::kpi::models::{DoraMetrics, KpiFilter, KpiSnapshot, RiskItem};
 use crate::kpi::repositories::KpiRepository;
 use ovia_common::error::{OviaError, OviaResult};
 
@@ -20,14 +20,22 @@ impl PgKpiRepository {
 #[async_trait]
 impl KpiRepository for PgKpiRepository {
     async fn save_snapshot(&self, snapshot: KpiSnapshot) -> OviaResult<KpiSnapshot> {
+        let mr_size_stats_json = snapshot
+            .mr_size_stats
+            .as_ref()
+            .map(|s| serde_json::to_value(s).unwrap_or_default());
+
         let row = sqlx::query(
             "insert into kpi_snapshots
              (id, org_id, period_start, period_end, delivery_health_score, release_risk_score,
               throughput_total, throughput_bugs, throughput_features, throughput_chores,
               review_latency_median_hours, review_latency_p90_hours,
               blocker_count, spillover_rate, cycle_time_p50_hours, cycle_time_p90_hours,
-              computed_at, created_at)
-             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18)
+              lead_time_p50_hours, lead_time_p90_hours, time_to_first_review_p50_hours,
+              time_to_first_review_p90_hours, review_rounds_avg, mr_size_stats, computed_at,
+              created_at)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18,
+                     $19, $20, $21, $22, $23, $24)
              on conflict (org_id, period_start, period_end)
              do update set
                delivery_health_score = excluded.delivery_health_score,
@@ -42,6 +50,12 @@ impl KpiRepository for PgKpiRepository {
                spillover_rate = excluded.spillover_rate,
                cycle_time_p50_hours = excluded.cycle_time_p50_hours,
                cycle_time_p90_hours = excluded.cycle_time_p90_hours,
+               lead_time_p50_hours = excluded.lead_time_p50_hours,
+               lead_time_p90_hours = excluded.lead_time_p90_hours,
+               time_to_first_review_p50_hours = excluded.time_to_first_review_p50_hours,
+               time_to_first_review_p90_hours = excluded.time_to_first_review_p90_hours,
+               review_rounds_avg = excluded.review_rounds_avg,
+               mr_size_stats = excluded.mr_size_stats,
                computed_at = excluded.computed_at
              returning id, org_id, period_start, period_end,
                        delivery_health_score::float8 as delivery_health_score,
@@ -53,6 +67,12 @@ impl KpiRepository for PgKpiRepository {
                        spillover_rate::float8 as spillover_rate,
                        cycle_time_p50_hours::float8 as cycle_time_p50_hours,
                        cycle_time_p90_hours::float8 as cycle_time_p90_hours,
+                       lead_time_p50_hours::float8 as lead_time_p50_hours,
+                       lead_time_p90_hours::float8 as lead_time_p90_hours,
+                       time_to_first_review_p50_hours::float8 as time_to_first_review_p50_hours,
+                       time_to_first_review_p90_hours::float8 as time_to_first_review_p90_hours,
+                       review_rounds_avg::float8 as review_rounds_avg,
+                       mr_size_stats,
                        computed_at, created_at",
         )
         .bind(snapshot.id)
@@ -71,6 +91,12 @@ impl KpiRepository for PgKpiRepository {
         .bind(snapshot.spillover_rate)
         .bind(snapshot.cycle_time_p50_hours)
         .bind(snapshot.cycle_time_p90_hours)
+        .bind(snapshot.lead_time_p50_hours)
+        .bind(snapshot.lead_time_p90_hours)
+        .bind(snapshot.time_to_first_review_p50_hours)
+        .bind(snapshot.time_to_first_review_p90_hours)
+        .bind(snapshot.review_rounds_avg)
+        .bind(&mr_size_stats_json)
         .bind(snapshot.computed_at)
         .bind(snapshot.created_at)
         .fetch_one(&self.pool)
@@ -92,6 +118,12 @@ impl KpiRepository for PgKpiRepository {
                     spillover_rate::float8 as spillover_rate,
                     cycle_time_p50_hours::float8 as cycle_time_p50_hours,
                     cycle_time_p90_hours::float8 as cycle_time_p90_hours,
+                    lead_time_p50_hours::float8 as lead_time_p50_hours,
+                    lead_time_p90_hours::float8 as lead_time_p90_hours,
+                    time_to_first_review_p50_hours::float8 as time_to_first_review_p50_hours,
+                    time_to_first_review_p90_hours::float8 as time_to_first_review_p90_hours,
+                    review_rounds_avg::float8 as review_rounds_avg,
+                    mr_size_stats,
                     computed_at, created_at
              from kpi_snapshots
              where org_id = $1
@@ -118,6 +150,12 @@ impl KpiRepository for PgKpiRepository {
              spillover_rate::float8 as spillover_rate, \
              cycle_time_p50_hours::float8 as cycle_time_p50_hours, \
              cycle_time_p90_hours::float8 as cycle_time_p90_hours, \
+             lead_time_p50_hours::float8 as lead_time_p50_hours, \
+             lead_time_p90_hours::float8 as lead_time_p90_hours, \
+             time_to_first_review_p50_hours::float8 as time_to_first_review_p50_hours, \
+             time_to_first_review_p90_hours::float8 as time_to_first_review_p90_hours, \
+             review_rounds_avg::float8 as review_rounds_avg, \
+             mr_size_stats, \
              computed_at, created_at \
              from kpi_snapshots where 1=1",
         );
@@ -220,9 +258,101 @@ impl KpiRepository for PgKpiRepository {
             })
             .collect())
     }
+
+    async fn save_dora_metrics(
+        &self,
+        snapshot_id: Uuid,
+        metrics: Vec<DoraMetrics>,
+    ) -> OviaResult<Vec<DoraMetrics>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        // Recomputing a period reuses its snapshot, so drop the previous run's rows.
+        sqlx::query("delete from dora_metrics where snapshot_id = $1")
+            .bind(snapshot_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let mut saved = Vec::with_capacity(metrics.len());
+        for m in metrics {
+            let row = sqlx::query(&format!(
+                "insert into dora_metrics
+                 (id, org_id, snapshot_id, scope_type, scope_key, deployment_count,
+                  deployments_per_day, lead_time_p50_hours, change_failure_rate,
+                  time_to_restore_p50_hours, created_at)
+                 values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                 returning {DORA_COLUMNS}"
+            ))
+            .bind(m.id)
+            .bind(m.org_id)
+            .bind(snapshot_id)
+            .bind(&m.scope_type)
+            .bind(&m.scope_key)
+            .bind(m.deployment_count)
+            .bind(m.deployments_per_day)
+            .bind(m.lead_time_p50_hours)
+            .bind(m.change_failure_rate)
+            .bind(m.time_to_restore_p50_hours)
+            .bind(m.created_at)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+            saved.push(map_dora_row(&row));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(saved)
+    }
+
+    async fn list_dora_metrics(&self, snapshot_id: Uuid) -> OviaResult<Vec<DoraMetrics>> {
+        let rows = sqlx::query(&format!(
+            "select {DORA_COLUMNS}
+             from dora_metrics
+             where snapshot_id = $1
+             order by case scope_type when 'org' then 0 when 'project' then 1 else 2 end,
+                      scope_key asc"
+        ))
+        .bind(snapshot_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(map_dora_row).collect())
+    }
+}
+
+const DORA_COLUMNS: &str = "id, org_id, snapshot_id, scope_type, scope_key, deployment_count,
+     deployments_per_day::float8 as deployments_per_day,
+     lead_time_p50_hours::float8 as lead_time_p50_hours,
+     change_failure_rate::float8 as change_failure_rate,
+     time_to_restore_p50_hours::float8 as time_to_restore_p50_hours,
+     created_at";
+
+fn map_dora_row(row: &sqlx::postgres::PgRow) -> DoraMetrics {
+    DoraMetrics {
+        id: row.get("id"),
+        org_id: row.get("org_id"),
+        snapshot_id: row.get("snapshot_id"),
+        scope_type: row.get("scope_type"),
+        scope_key: row.get("scope_key"),
+        deployment_count: row.get("deployment_count"),
+        deployments_per_day: row.get("deployments_per_day"),
+        lead_time_p50_hours: row.get("lead_time_p50_hours"),
+        change_failure_rate: row.get("change_failure_rate"),
+        time_to_restore_p50_hours: row.get("time_to_restore_p50_hours"),
+        created_at: row.get("created_at"),
+    }
 }
 
 fn map_snapshot_row(row: &sqlx::postgres::PgRow) -> KpiSnapshot {
+    let mr_size_stats_json: Option<serde_json::Value> = row.get("mr_size_stats");
+
     KpiSnapshot {
         id: row.get("id"),
         org_id: row.get("org_id"),
@@ -240,6 +370,12 @@ fn map_snapshot_row(row: &sqlx::postgres::PgRow) -> KpiSnapshot {
         spillover_rate: row.get("spillover_rate"),
         cycle_time_p50_hours: row.get("cycle_time_p50_hours"),
         cycle_time_p90_hours: row.get("cycle_time_p90_hours"),
+        lead_time_p50_hours: row.get("lead_time_p50_hours"),
+        lead_time_p90_hours: row.get("lead_time_p90_hours"),
+        time_to_first_review_p50_hours: row.get("time_to_first_review_p50_hours"),
+        time_to_first_review_p90_hours: row.get("time_to_first_review_p90_hours"),
+        review_rounds_avg: row.get("review_rounds_avg"),
+        mr_size_stats: mr_size_stats_json.and_then(|v| serde_json::from_value(v).ok()),
         computed_at: row.get("computed_at"),
         created_at: row.get("created_at"),
     }
@@ -249,6 +385,7 @@ fn map_snapshot_row(row: &sqlx::postgres::PgRow) -> KpiSnapshot {
 mod tests {
     use super::*;
     use crate::create_pool;
+    use crate::kpi::models::{MrSizeBucketStats, MrSizeStats};
     use chrono::{NaiveDate, Utc};
 
     async fn test_repo() -> Option<(PgKpiRepository, PgPool)> {
@@ -292,6 +429,12 @@ mod tests {
             "alter table kpi_snapshots add column if not exists spillover_rate numeric(5,4)",
             "alter table kpi_snapshots add column if not exists cycle_time_p50_hours numeric(8,2)",
             "alter table kpi_snapshots add column if not exists cycle_time_p90_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists lead_time_p50_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists lead_time_p90_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists time_to_first_review_p50_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists time_to_first_review_p90_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists review_rounds_avg numeric(6,2)",
+            "alter table kpi_snapshots add column if not exists mr_size_stats jsonb",
         ] {
             sqlx::query(stmt)
                 .execute(&pool)
@@ -325,6 +468,25 @@ mod tests {
         .await
         .expect("create risk_items index");
 
+        sqlx::query(
+            "create table if not exists dora_metrics (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null,
+              snapshot_id uuid not null references kpi_snapshots(id) on delete cascade,
+              scope_type text not null,
+              scope_key text,
+              deployment_count integer not null default 0,
+              deployments_per_day numeric(8,3),
+              lead_time_p50_hours numeric(8,2),
+              change_failure_rate numeric(5,4),
+              time_to_restore_p50_hours numeric(8,2),
+              created_at timestamptz not null default now()
+            )",
+        )
+        .execute(&pool)
+        .await
+        .expect("create dora_metrics");
+
         Some((PgKpiRepository::new(pool.clone()), pool))
     }
 
@@ -347,6 +509,19 @@ mod tests {
             spillover_rate: Some(0.15),
             cycle_time_p50_hours: Some(36.0),
             cycle_time_p90_hours: Some(72.0),
+            lead_time_p50_hours: Some(40.0),
+            lead_time_p90_hours: Some(96.0),
+            time_to_first_review_p50_hours: Some(3.0),
+            time_to_first_review_p90_hours: Some(20.0),
+            review_rounds_avg: Some(1.5),
+            mr_size_stats: Some(MrSizeStats {
+                buckets: vec![MrSizeBucketStats {
+                    bucket: "s".to_string(),
+                    mr_count: 4,
+                    review_latency_median_hours: Some(5.5),
+                }],
+                teams: vec![],
+            }),
             computed_at: now,
             created_at: now,
         }
@@ -386,6 +561,7 @@ mod tests {
         let latest = latest.unwrap();
         assert_eq!(latest.id, saved.id);
         assert!((latest.delivery_health_score.unwrap() - 75.5).abs() < 0.1);
+        assert_eq!(latest.mr_size_stats, snapshot.mr_size_stats);
     }
 
     #[tokio::test]
@@ -519,4 +695,50 @@ mod tests {
         // Should return the original id (from the existing row)
         assert_eq!(saved2.id, saved1.id);
     }
+
+    #[tokio::test]
+    async fn save_dora_metrics_replaces_previous_run() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let saved = repo.save_snapshot(make_snapshot(org)).await.expect("save");
+        let dora = |scope_type: &str, scope_key: Option<&str>, count: i32| DoraMetrics {
+            id: Uuid::new_v4(),
+            org_id: org,
+            snapshot_id: saved.id,
+            scope_type: scope_type.to_string(),
+            scope_key: scope_key.map(str::to_string),
+            deployment_count: count,
+            deployments_per_day: Some(count as f64 / 14.0),
+            lead_time_p50_hours: Some(20.0),
+            change_failure_rate: Some(0.25),
+            time_to_restore_p50_hours: None,
+            created_at: Utc::now(),
+        };
+
+        repo.save_dora_metrics(saved.id, vec![dora("org", None, 3)])
+            .await
+            .expect("first run");
+        repo.save_dora_metrics(
+            saved.id,
+            vec![
+                dora("team", Some("core"), 2),
+                dora("project", Some("acme/api"), 4),
+                dora("org", None, 4),
+            ],
+        )
+        .await
+        .expect("second run");
+
+        let listed = repo.list_dora_metrics(saved.id).await.expect("list");
+        let scopes: Vec<(&str, i32)> = listed
+            .iter()
+            .map(|m| (m.scope_type.as_str(), m.deployment_count))
+            .collect();
+        assert_eq!(scopes, vec![("org", 4), ("project", 4), ("team", 2)]);
+        assert!((listed[0].change_failure_rate.unwrap() - 0.25).abs() < 0.001);
+        assert!(listed[0].time_to_restore_p50_hours.is_none());
+    }
 }
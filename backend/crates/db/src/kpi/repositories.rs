@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::kpi::models::{KpiFilter, KpiSnapshot, RiskItem};
+use crate::kpi::models::{DoraMetrics, KpiFilter, KpiSnapshot, RiskItem};
 use ovia_common::error::OviaResult;
 
 #[async_trait]
@@ -11,4 +11,11 @@ pub trait KpiRepository: Send + Sync {
     async fn list_snapshots(&self, filter: KpiFilter) -> OviaResult<Vec<KpiSnapshot>>;
     async fn save_risk_items(&self, items: Vec<RiskItem>) -> OviaResult<Vec<RiskItem>>;
     async fn list_risk_items(&self, snapshot_id: Uuid) -> OviaResult<Vec<RiskItem>>;
+    /// Replace the DORA metrics of a snapshot with `metrics`.
+    async fn save_dora_metrics(
+        &self,
+        snapshot_id: Uuid,
+        metrics: Vec<DoraMetrics>,
+    ) -> OviaResult<Vec<DoraMetrics>>;
+    async fn list_dora_metrics(&self, snapshot_id: Uuid) -> OviaResult<Vec<DoraMetrics>>;
 }
@@ -1,9 +1,15 @@
 pub mod ask;
+pub mod confluence;
+pub mod credentials;
 pub mod gitlab;
 pub mod identity;
 pub mod jira;
 pub mod kpi;
+pub mod org;
+pub mod privacy;
 pub mod sync;
+pub mod webhooks;
+pub mod work_items;
 
 use ovia_common::error::{OviaError, OviaResult};
 use sqlx::postgres::PgPoolOptions;
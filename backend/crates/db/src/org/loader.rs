@@ -0,0 +1,101 @@
+use uuid::Uuid;
+
+use crate::org::models::Org;
+use crate::org::repositories::OrgRepository;
+use ovia_common::error::OviaResult;
+
+/// The orgs a service run covers.
+#[derive(Debug, Clone)]
+pub struct LoadedOrgs {
+    pub orgs: Vec<Org>,
+    /// The registry was empty and the single org comes from `ORG_ID`. Only that
+    /// org may fall back to env config.
+    pub from_org_id: bool,
+}
+
+/// Enabled orgs from the registry. Falls back to a single org from `ORG_ID`
+/// (configured purely from env) when the registry is empty.
+pub async fn load_orgs(repo: &dyn OrgRepository) -> OviaResult<LoadedOrgs> {
+    load_orgs_with(repo, std::env::var("ORG_ID").ok().as_deref()).await
+}
+
+async fn load_orgs_with(repo: &dyn OrgRepository, org_id: Option<&str>) -> OviaResult<LoadedOrgs> {
+    let orgs = repo.list_enabled().await?;
+    if !orgs.is_empty() {
+        return Ok(LoadedOrgs {
+            orgs,
+            from_org_id: false,
+        });
+    }
+
+    let fallback = match org_id.map(Uuid::parse_str) {
+        Some(Ok(id)) => {
+            tracing::warn!(org_id = %id, "orgs registry is empty, falling back to ORG_ID");
+            Some(Org::new(id, "default"))
+        }
+        Some(Err(e)) => {
+            tracing::error!(error = %e, "ORG_ID is not a valid uuid");
+            None
+        }
+        None => None,
+    };
+    Ok(LoadedOrgs {
+        from_org_id: fallback.is_some(),
+        orgs: fallback.into_iter().collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct StubOrgs(Vec<Org>);
+
+    #[async_trait]
+    impl OrgRepository for StubOrgs {
+        async fn list_enabled(&self) -> OviaResult<Vec<Org>> {
+            Ok(self.0.clone())
+        }
+
+        async fn get(&self, id: Uuid) -> OviaResult<Option<Org>> {
+            Ok(self.0.iter().find(|o| o.id == id).cloned())
+        }
+
+        async fn upsert(&self, org: &Org) -> OviaResult<Org> {
+            Ok(org.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn registry_orgs_win_over_org_id() {
+        let org = Org::new(Uuid::new_v4(), "acme");
+        let fallback = Uuid::new_v4().to_string();
+        let loaded = load_orgs_with(&StubOrgs(vec![org.clone()]), Some(&fallback))
+            .await
+            .unwrap();
+        assert_eq!(loaded.orgs.len(), 1);
+        assert_eq!(loaded.orgs[0].id, org.id);
+        assert!(!loaded.from_org_id);
+    }
+
+    #[tokio::test]
+    async fn empty_registry_falls_back_to_org_id() {
+        let id = Uuid::new_v4();
+        let loaded = load_orgs_with(&StubOrgs(vec![]), Some(&id.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(loaded.orgs.len(), 1);
+        assert_eq!(loaded.orgs[0].id, id);
+        assert!(loaded.from_org_id);
+    }
+
+    #[tokio::test]
+    async fn empty_registry_without_valid_org_id_loads_nothing() {
+        for org_id in [None, Some("not-a-uuid")] {
+            let loaded = load_orgs_with(&StubOrgs(vec![]), org_id).await.unwrap();
+            assert!(loaded.orgs.is_empty());
+            assert!(!loaded.from_org_id);
+        }
+    }
+}
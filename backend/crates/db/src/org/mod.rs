@@ -0,0 +1,4 @@
+pub mod loader;
+pub mod models;
+pub mod pg_repository;
+pub mod repositories;
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A tenant and the source instances ingest should pull from for it.
+///
+/// Unset base URLs and empty scope lists fall back to the service's env config only
+/// for the single org built from `ORG_ID` (`LoadedOrgs::from_org_id`, passed on as
+/// `ConnectorContext::allow_env_fallback`). A registry org must configure each
+/// source it uses; sources it leaves unset are skipped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Org {
+    pub id: Uuid,
+    pub name: String,
+    pub is_enabled: bool,
+    pub jira_base_url: Option<String>,
+    pub jira_project_keys: Vec<String>,
+    pub gitlab_base_url: Option<String>,
+    pub gitlab_groups: Vec<String>,
+    pub confluence_base_url: Option<String>,
+    pub confluence_spaces: Vec<String>,
+    /// API root of GitHub Enterprise Server; unset means github.com.
+    pub github_base_url: Option<String>,
+    /// GitHub organization logins whose members and repositories are synced.
+    pub github_orgs: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Org {
+    /// An enabled org with no source overrides. As the `ORG_ID` fallback org its
+    /// connectors use env config; as a registry org its unset sources are skipped.
+    pub fn new(id: Uuid, name: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            name: name.to_string(),
+            is_enabled: true,
+            jira_base_url: None,
+            jira_project_keys: Vec::new(),
+            gitlab_base_url: None,
+            gitlab_groups: Vec::new(),
+            confluence_base_url: None,
+            confluence_spaces: Vec::new(),
+            github_base_url: None,
+            github_orgs: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
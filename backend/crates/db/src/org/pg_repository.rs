@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::org::models::Org;
+use crate::org::repositories::OrgRepository;
+use ovia_common::error::{OviaError, OviaResult};
+
+const ORG_COLUMNS: &str = "id, name, is_enabled, jira_base_url, jira_project_keys, \
+     gitlab_base_url, gitlab_groups, confluence_base_url, confluence_spaces, \
+     github_base_url, github_orgs, created_at, updated_at";
+
+#[derive(Clone)]
+pub struct PgOrgRepository {
+    pool: PgPool,
+}
+
+impl PgOrgRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn map_row(row: sqlx::postgres::PgRow) -> Org {
+        Org {
+            id: row.get("id"),
+            name: row.get("name"),
+            is_enabled: row.get("is_enabled"),
+            jira_base_url: row.get("jira_base_url"),
+            jira_project_keys: row.get("jira_project_keys"),
+            gitlab_base_url: row.get("gitlab_base_url"),
+            gitlab_groups: row.get("gitlab_groups"),
+            confluence_base_url: row.get("confluence_base_url"),
+            confluence_spaces: row.get("confluence_spaces"),
+            github_base_url: row.get("github_base_url"),
+            github_orgs: row.get("github_orgs"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl OrgRepository for PgOrgRepository {
+    async fn list_enabled(&self) -> OviaResult<Vec<Org>> {
+        let rows = sqlx::query(&format!(
+            "select {ORG_COLUMNS} from orgs where is_enabled = true order by name, id"
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Self::map_row).collect())
+    }
+
+    async fn get(&self, id: Uuid) -> OviaResult<Option<Org>> {
+        let row = sqlx::query(&format!("select {ORG_COLUMNS} from orgs where id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(row.map(Self::map_row))
+    }
+
+    async fn upsert(&self, org: &Org) -> OviaResult<Org> {
+        let row = sqlx::query(&format!(
+            "insert into orgs (id, name, is_enabled, jira_base_url, jira_project_keys,
+               gitlab_base_url, gitlab_groups, confluence_base_url, confluence_spaces,
+               github_base_url, github_orgs)
+             values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+             on conflict (id) do update set
+               name = excluded.name,
+               is_enabled = excluded.is_enabled,
+               jira_base_url = excluded.jira_base_url,
+               jira_project_keys = excluded.jira_project_keys,
+               gitlab_base_url = excluded.gitlab_base_url,
+               gitlab_groups = excluded.gitlab_groups,
+               confluence_base_url = excluded.confluence_base_url,
+               confluence_spaces = excluded.confluence_spaces,
+               github_base_url = excluded.github_base_url,
+               github_orgs = excluded.github_orgs,
+               updated_at = now()
+             returning {ORG_COLUMNS}"
+        ))
+        .bind(org.id)
+        .bind(&org.name)
+        .bind(org.is_enabled)
+        .bind(&org.jira_base_url)
+        .bind(&org.jira_project_keys)
+        .bind(&org.gitlab_base_url)
+        .bind(&org.gitlab_groups)
+        .bind(&org.confluence_base_url)
+        .bind(&org.confluence_spaces)
+        .bind(&org.github_base_url)
+        .bind(&org.github_orgs)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(Self::map_row(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+
+    async fn test_repo() -> Option<PgOrgRepository> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        sqlx::query(
+            "create table if not exists orgs (
+               id uuid primary key,
+               name text not null,
+               is_enabled boolean not null default true,
+               jira_base_url text,
+               jira_project_keys text[] not null default '{}',
+               gitlab_base_url text,
+               gitlab_groups text[] not null default '{}',
+               confluence_base_url text,
+               confluence_spaces text[] not null default '{}',
+               github_base_url text,
+               github_orgs text[] not null default '{}',
+               created_at timestamptz not null default now(),
+               updated_at timestamptz not null default now()
+             )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        Some(PgOrgRepository::new(pool))
+    }
+
+    fn make_org(name: &str, is_enabled: bool) -> Org {
+        Org {
+            is_enabled,
+            jira_base_url: Some("https://acme.atlassian.net".to_string()),
+            jira_project_keys: vec!["DEV".to_string(), "OPS".to_string()],
+            gitlab_groups: vec!["platform".to_string()],
+            ..Org::new(Uuid::new_v4(), name)
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_and_get_roundtrip() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+
+        let org = make_org("acme", true);
+        repo.upsert(&org).await.unwrap();
+
+        let fetched = repo.get(org.id).await.unwrap().expect("org should exist");
+        assert_eq!(fetched.name, "acme");
+        assert_eq!(fetched.jira_project_keys, vec!["DEV", "OPS"]);
+        assert_eq!(fetched.gitlab_groups, vec!["platform"]);
+        assert!(fetched.confluence_spaces.is_empty());
+        assert!(fetched.github_orgs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn upsert_replaces_config() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+
+        let mut org = make_org("acme", true);
+        repo.upsert(&org).await.unwrap();
+
+        org.jira_project_keys = vec!["INFRA".to_string()];
+        org.is_enabled = false;
+        let updated = repo.upsert(&org).await.unwrap();
+
+        assert_eq!(updated.jira_project_keys, vec!["INFRA"]);
+        assert!(!updated.is_enabled);
+    }
+
+    #[tokio::test]
+    async fn list_enabled_skips_disabled_orgs() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+
+        let enabled = make_org("enabled-org", true);
+        let disabled = make_org("disabled-org", false);
+        repo.upsert(&enabled).await.unwrap();
+        repo.upsert(&disabled).await.unwrap();
+
+        let orgs = repo.list_enabled().await.unwrap();
+        assert!(orgs.iter().any(|o| o.id == enabled.id));
+        assert!(!orgs.iter().any(|o| o.id == disabled.id));
+    }
+
+    #[tokio::test]
+    async fn get_missing_returns_none() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+
+        assert!(repo.get(Uuid::new_v4()).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::org::models::Org;
+use ovia_common::error::OviaResult;
+
+#[async_trait]
+pub trait OrgRepository: Send + Sync {
+    /// All orgs with `is_enabled = true`, ordered by name.
+    async fn list_enabled(&self) -> OviaResult<Vec<Org>>;
+
+    async fn get(&self, id: Uuid) -> OviaResult<Option<Org>>;
+
+    /// Insert an org or replace its name, enabled flag and source config.
+    async fn upsert(&self, org: &Org) -> OviaResult<Org>;
+}
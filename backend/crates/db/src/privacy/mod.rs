@@ -0,0 +1,3 @@
+pub mod models;
+pub mod pg_repository;
+pub mod repositories;
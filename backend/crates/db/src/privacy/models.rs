@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ask::models::AskSession;
+use crate::identity::models::{Identity, IdentityEvent, Person, PersonIdentityLink};
+
+/// Everything Ovia stores about a single person, for data subject access requests.
+///
+/// Source activity (merge requests and their reviews, commits, deployments, GitLab and
+/// Jira issues, transitions and worklogs, Confluence pages) is exported as raw rows since
+/// the bundle is meant to be read, not re-imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonExport {
+    pub person: Person,
+    pub identities: Vec<Identity>,
+    pub links: Vec<PersonIdentityLink>,
+    pub events: Vec<IdentityEvent>,
+    pub merge_requests: Vec<serde_json::Value>,
+    /// Review comments and approvals the person left on merge requests.
+    pub mr_notes: Vec<serde_json::Value>,
+    pub mr_approvals: Vec<serde_json::Value>,
+    /// Commits whose author email or name matches one of the person's GitLab identities.
+    pub commits: Vec<serde_json::Value>,
+    pub deployments: Vec<serde_json::Value>,
+    /// GitLab issues the person opened or is assigned to.
+    pub gitlab_issues: Vec<serde_json::Value>,
+    pub gitlab_issue_state_events: Vec<serde_json::Value>,
+    pub jira_issues: Vec<serde_json::Value>,
+    pub jira_transitions: Vec<serde_json::Value>,
+    pub jira_worklogs: Vec<serde_json::Value>,
+    /// Confluence pages the person created or last edited.
+    pub confluence_pages: Vec<serde_json::Value>,
+    pub confluence_page_versions: Vec<serde_json::Value>,
+    /// Ask sessions whose query or answer mentions the person's name, email or username.
+    pub ask_sessions: Vec<AskSession>,
+    pub erasure: Option<PersonErasure>,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// Audit record written when a person's data is erased.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonErasure {
+    pub id: Uuid,
+    pub org_id: Uuid,
+    pub person_id: Uuid,
+    pub requested_by: String,
+    pub identities_erased: i32,
+    pub links_erased: i32,
+    pub events_erased: i32,
+    pub ask_sessions_redacted: i32,
+    pub created_at: DateTime<Utc>,
+}
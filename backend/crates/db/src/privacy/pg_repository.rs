@@ -0,0 +1,1722 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::ask::pg_repository::map_session_row;
+use crate::identity::models::{Identity, IdentityEvent, Person};
+use crate::identity::pg_repository::PgIdentityRepository;
+use crate::jira::models::USER_CHANGELOG_FIELDS;
+use crate::privacy::models::{PersonErasure, PersonExport};
+use crate::privacy::repositories::PrivacyRepository;
+use ovia_common::error::{OviaError, OviaResult};
+
+/// Replacement for erased mentions in free text, and the display name left on the person.
+const ERASED_MARKER: &str = "[erased]";
+const ERASED_DISPLAY_NAME: &str = "Erased person";
+
+#[derive(Clone)]
+pub struct PgPrivacyRepository {
+    pool: PgPool,
+}
+
+impl PgPrivacyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_person(
+        tx: &mut Transaction<'_, Postgres>,
+        org_id: Uuid,
+        person_id: Uuid,
+    ) -> OviaResult<Option<Person>> {
+        let row = sqlx::query(
+            "select id, org_id, display_name, primary_email, avatar_url, team, role, status,
+                    left_at, created_at, updated_at
+             from people where org_id = $1 and id = $2
+             for update",
+        )
+        .bind(org_id)
+        .bind(person_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(row.map(PgIdentityRepository::map_person_row))
+    }
+
+    /// Identities that belong to the person: linked at any time (rejected/ignored links
+    /// excluded) and not currently linked to somebody else.
+    async fn fetch_identities(
+        tx: &mut Transaction<'_, Postgres>,
+        org_id: Uuid,
+        person_id: Uuid,
+    ) -> OviaResult<Vec<Identity>> {
+        let rows = sqlx::query(
+            "select i.id, i.org_id, i.source, i.external_id, i.username, i.email, i.display_name,
+                    i.is_service_account, i.is_active, i.active_changed_at, i.first_seen_at,
+                    i.last_seen_at, i.raw_ref
+             from identities i
+             where i.org_id = $1
+               and exists (
+                 select 1 from person_identity_links pil
+                 where pil.identity_id = i.id and pil.person_id = $2
+                   and pil.status not in ('rejected', 'ignored')
+               )
+               and not exists (
+                 select 1 from person_identity_links pil
+                 where pil.identity_id = i.id and pil.person_id <> $2
+                   and pil.valid_to is null
+               )
+             order by i.source, i.first_seen_at",
+        )
+        .bind(org_id)
+        .bind(person_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(PgIdentityRepository::map_identity_row)
+            .collect())
+    }
+
+    async fn fetch_json_rows(
+        tx: &mut Transaction<'_, Postgres>,
+        sql: &str,
+        org_id: Uuid,
+        keys: &[String],
+    ) -> OviaResult<Vec<serde_json::Value>> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let rows = sqlx::query(sql)
+            .bind(org_id)
+            .bind(keys)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(rows.iter().map(|r| r.get("doc")).collect())
+    }
+
+    async fn fetch_erasure(
+        tx: &mut Transaction<'_, Postgres>,
+        org_id: Uuid,
+        person_id: Uuid,
+    ) -> OviaResult<Option<PersonErasure>> {
+        let row = sqlx::query(
+            "select id, org_id, person_id, requested_by, identities_erased, links_erased,
+                    events_erased, ask_sessions_redacted, created_at
+             from person_erasures where org_id = $1 and person_id = $2",
+        )
+        .bind(org_id)
+        .bind(person_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(row.as_ref().map(map_erasure_row))
+    }
+}
+
+fn map_erasure_row(row: &PgRow) -> PersonErasure {
+    PersonErasure {
+        id: row.get("id"),
+        org_id: row.get("org_id"),
+        person_id: row.get("person_id"),
+        requested_by: row.get("requested_by"),
+        identities_erased: row.get("identities_erased"),
+        links_erased: row.get("links_erased"),
+        events_erased: row.get("events_erased"),
+        ask_sessions_redacted: row.get("ask_sessions_redacted"),
+        created_at: row.get("created_at"),
+    }
+}
+
+/// Stable replacement for an erased identity's external id and username.
+fn pseudonym(identity_id: Uuid) -> String {
+    format!("erased-{}", identity_id.simple())
+}
+
+/// Source keys used to find a person's activity: GitLab and GitHub usernames and
+/// Jira account ids.
+fn source_keys(identities: &[Identity], source: &str) -> Vec<String> {
+    identities
+        .iter()
+        .filter(|i| i.source == source)
+        .filter_map(|i| match source {
+            "gitlab" | "github" => i.username.clone(),
+            _ => i.external_id.clone(),
+        })
+        .collect()
+}
+
+/// `provider:username` of the person's GitLab and GitHub identities. Both providers
+/// share the merge request tables, so a username only identifies someone together
+/// with its provider.
+fn provider_keys(identities: &[Identity]) -> Vec<String> {
+    identities
+        .iter()
+        .filter(|i| matches!(i.source.as_str(), "gitlab" | "github"))
+        .filter_map(|i| Some(format!("{}:{}", i.source, i.username.as_ref()?)))
+        .collect()
+}
+
+/// `provider:key` for the lowercased emails and usernames of the person's GitLab and
+/// GitHub identities, which commit authors are matched against.
+fn commit_author_keys(identities: &[Identity]) -> Vec<String> {
+    identities
+        .iter()
+        .filter(|i| matches!(i.source.as_str(), "gitlab" | "github"))
+        .flat_map(|i| {
+            [i.email.as_ref(), i.username.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(|k| format!("{}:{}", i.source, k.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Build a case-insensitive Postgres regex matching any of the person's names,
+/// emails or usernames as whole words. Terms shorter than 3 characters are ignored
+/// to avoid redacting unrelated text.
+fn mention_pattern(person: &Person, identities: &[Identity]) -> Option<String> {
+    let mut terms: Vec<String> = Vec::new();
+    let candidates = std::iter::once(Some(&person.display_name))
+        .chain(std::iter::once(person.primary_email.as_ref()))
+        .chain(identities.iter().flat_map(|i| {
+            [
+                i.username.as_ref(),
+                i.email.as_ref(),
+                i.display_name.as_ref(),
+            ]
+        }))
+        .flatten();
+
+    for term in candidates {
+        let term = term.trim();
+        if term.chars().count() < 3 || terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            continue;
+        }
+        terms.push(term.to_string());
+    }
+
+    if terms.is_empty() {
+        return None;
+    }
+
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    let escaped: Vec<String> = terms
+        .iter()
+        .map(|t| {
+            let body: String = t
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() {
+                        c.to_string()
+                    } else {
+                        format!("\\{c}")
+                    }
+                })
+                .collect();
+            // \m and \M need a word character next to them, so a term starting or
+            // ending in punctuation is left open on that side.
+            let start = if is_word(t.chars().next()) { "\\m" } else { "" };
+            let end = if is_word(t.chars().last()) { "\\M" } else { "" };
+            format!("{start}(?:{body}){end}")
+        })
+        .collect();
+    Some(escaped.join("|"))
+}
+
+#[async_trait]
+impl PrivacyRepository for PgPrivacyRepository {
+    async fn export_person(
+        &self,
+        org_id: Uuid,
+        person_id: Uuid,
+    ) -> OviaResult<Option<PersonExport>> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let person = match Self::fetch_person(&mut tx, org_id, person_id).await? {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        let identities = Self::fetch_identities(&mut tx, org_id, person_id).await?;
+
+        let link_rows = sqlx::query(
+            "select id, org_id, person_id, identity_id, status, confidence::float4 as confidence,
+                    valid_from, valid_to, verified_by, verified_at, created_at, updated_at
+             from person_identity_links
+             where org_id = $1 and person_id = $2
+             order by created_at",
+        )
+        .bind(org_id)
+        .bind(person_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        let links = link_rows
+            .into_iter()
+            .map(PgIdentityRepository::map_link_row)
+            .collect::<OviaResult<Vec<_>>>()?;
+
+        let link_ids: Vec<Uuid> = links.iter().map(|l| l.id).collect();
+        let events: Vec<IdentityEvent> = sqlx::query(
+            "select id, org_id, link_id, action, actor, payload, created_at
+             from identity_events
+             where org_id = $1 and link_id = any($2)
+             order by created_at",
+        )
+        .bind(org_id)
+        .bind(&link_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?
+        .into_iter()
+        .map(|r| IdentityEvent {
+            id: r.get("id"),
+            org_id: r.get("org_id"),
+            link_id: r.get("link_id"),
+            action: r.get("action"),
+            actor: r.get("actor"),
+            payload: r.get("payload"),
+            created_at: r.get("created_at"),
+        })
+        .collect();
+
+        let merge_requests = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(m) as doc from gitlab_merge_requests m
+             where m.org_id = $1 and m.provider || ':' || m.author_username = any($2)
+             order by m.created_at_gl",
+            org_id,
+            &provider_keys(&identities),
+        )
+        .await?;
+        let mr_notes = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(n) as doc from gitlab_mr_notes n
+             where n.org_id = $1 and n.provider || ':' || n.author_username = any($2)
+             order by n.created_at_gl",
+            org_id,
+            &provider_keys(&identities),
+        )
+        .await?;
+        let mr_approvals = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(a) as doc from gitlab_mr_approvals a
+             where a.org_id = $1 and a.provider || ':' || a.approver_username = any($2)
+             order by a.approved_at",
+            org_id,
+            &provider_keys(&identities),
+        )
+        .await?;
+        let commits = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(c) as doc from gitlab_commits c
+             where c.org_id = $1
+               and (c.provider || ':' || lower(c.author_email) = any($2)
+                    or c.provider || ':' || lower(c.author_name) = any($2))
+             order by c.authored_at",
+            org_id,
+            &commit_author_keys(&identities),
+        )
+        .await?;
+        let deployments = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(d) as doc from gitlab_deployments d
+             where d.org_id = $1 and d.deployer_username = any($2)
+             order by d.created_at_gl",
+            org_id,
+            &source_keys(&identities, "gitlab"),
+        )
+        .await?;
+        let gitlab_issues = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(gi) as doc from gitlab_issues gi
+             where gi.org_id = $1
+               and (gi.author_username = any($2) or gi.assignee_usernames && $2)
+             order by gi.created_at_gl",
+            org_id,
+            &source_keys(&identities, "gitlab"),
+        )
+        .await?;
+        let gitlab_issue_state_events = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(e) as doc from gitlab_issue_state_events e
+             where e.org_id = $1 and e.username = any($2)
+             order by e.created_at_gl",
+            org_id,
+            &source_keys(&identities, "gitlab"),
+        )
+        .await?;
+
+        let account_ids = source_keys(&identities, "jira");
+        let jira_issues = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(ji) as doc from jira_issues ji
+             where ji.org_id = $1
+               and (ji.assignee_account_id = any($2) or ji.reporter_account_id = any($2))
+             order by ji.created_at_jira",
+            org_id,
+            &account_ids,
+        )
+        .await?;
+        // Changes the person made, and changes of user fields to or from them.
+        let jira_transitions: Vec<serde_json::Value> = sqlx::query_scalar(
+            "select to_jsonb(t) as doc from jira_issue_transitions t
+             where t.org_id = $1
+               and (t.author_account_id = any($2)
+                    or (t.field = any($3)
+                        and (t.from_value = any($2) or t.to_value = any($2))))
+             order by t.transitioned_at",
+        )
+        .bind(org_id)
+        .bind(&account_ids)
+        .bind(USER_CHANGELOG_FIELDS)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        let jira_worklogs = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(w) as doc from jira_worklogs w
+             where w.org_id = $1 and w.author_account_id = any($2)
+             order by w.started_at",
+            org_id,
+            &account_ids,
+        )
+        .await?;
+
+        let confluence_account_ids = source_keys(&identities, "confluence");
+        let confluence_pages = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(p) as doc from confluence_pages p
+             where p.org_id = $1
+               and (p.author_account_id = any($2) or p.last_editor_account_id = any($2))
+             order by p.created_at_confluence",
+            org_id,
+            &confluence_account_ids,
+        )
+        .await?;
+        let confluence_page_versions = Self::fetch_json_rows(
+            &mut tx,
+            "select to_jsonb(v) as doc from confluence_page_versions v
+             where v.org_id = $1 and v.author_account_id = any($2)
+             order by v.created_at_confluence",
+            org_id,
+            &confluence_account_ids,
+        )
+        .await?;
+
+        let ask_sessions = match mention_pattern(&person, &identities) {
+            Some(pattern) => sqlx::query(
+                "select id, org_id, query, answer, confidence, assumptions, citations,
+                        filters, model, prompt_tokens, completion_tokens, latency_ms, created_at
+                 from ask_sessions
+                 where org_id = $1 and (query ~* $2 or answer ~* $2)
+                 order by created_at",
+            )
+            .bind(org_id)
+            .bind(pattern)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?
+            .iter()
+            .map(map_session_row)
+            .collect(),
+            None => vec![],
+        };
+
+        let erasure = Self::fetch_erasure(&mut tx, org_id, person_id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(Some(PersonExport {
+            person,
+            identities,
+            links,
+            events,
+            merge_requests,
+            mr_notes,
+            mr_approvals,
+            commits,
+            deployments,
+            gitlab_issues,
+            gitlab_issue_state_events,
+            jira_issues,
+            jira_transitions,
+            jira_worklogs,
+            confluence_pages,
+            confluence_page_versions,
+            ask_sessions,
+            erasure,
+            exported_at: Utc::now(),
+        }))
+    }
+
+    async fn erase_person(
+        &self,
+        org_id: Uuid,
+        person_id: Uuid,
+        requested_by: &str,
+    ) -> OviaResult<PersonErasure> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let person = Self::fetch_person(&mut tx, org_id, person_id)
+            .await?
+            .ok_or_else(|| OviaError::NotFound(format!("person not found: {person_id}")))?;
+
+        if Self::fetch_erasure(&mut tx, org_id, person_id)
+            .await?
+            .is_some()
+        {
+            return Err(OviaError::Conflict(format!(
+                "person already erased: {person_id}"
+            )));
+        }
+
+        let identities = Self::fetch_identities(&mut tx, org_id, person_id).await?;
+        // Compute before anything is anonymized.
+        let pattern = mention_pattern(&person, &identities);
+
+        // ── Identities and the source activity keyed on them ────────
+        for identity in &identities {
+            let alias = pseudonym(identity.id);
+
+            match (
+                identity.source.as_str(),
+                &identity.username,
+                &identity.external_id,
+            ) {
+                (provider @ ("gitlab" | "github"), Some(username), _) => {
+                    sqlx::query(
+                        "update gitlab_merge_requests set author_username = $1, updated_at = now()
+                         where org_id = $2 and provider = $4 and author_username = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(username)
+                    .bind(provider)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update gitlab_mr_notes set author_username = $1, updated_at = now()
+                         where org_id = $2 and provider = $4 and author_username = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(username)
+                    .bind(provider)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update gitlab_mr_approvals set approver_username = $1
+                         where org_id = $2 and provider = $4 and approver_username = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(username)
+                    .bind(provider)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update gitlab_commits set
+                           author_name = $1, author_email = null, updated_at = now()
+                         where org_id = $2 and provider = $5
+                           and (lower(author_name) = lower($3)
+                                or lower(author_email) = lower($4))",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(username)
+                    .bind(&identity.email)
+                    .bind(provider)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    // Issues and deployments are only synced from GitLab.
+                    if provider == "gitlab" {
+                        sqlx::query(
+                            "update gitlab_issues set
+                               author_username = case when author_username = $3
+                                                      then $1 else author_username end,
+                               assignee_usernames = array_replace(assignee_usernames, $3, $1),
+                               updated_at = now()
+                             where org_id = $2
+                               and (author_username = $3 or $3 = any(assignee_usernames))",
+                        )
+                        .bind(&alias)
+                        .bind(org_id)
+                        .bind(username)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                        sqlx::query(
+                            "update gitlab_issue_state_events set username = $1
+                             where org_id = $2 and username = $3",
+                        )
+                        .bind(&alias)
+                        .bind(org_id)
+                        .bind(username)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                        sqlx::query(
+                            "update gitlab_deployments set deployer_username = $1, updated_at = now()
+                             where org_id = $2 and deployer_username = $3",
+                        )
+                        .bind(&alias)
+                        .bind(org_id)
+                        .bind(username)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(|e| OviaError::Database(e.to_string()))?;
+                    }
+                }
+                ("jira", _, Some(account_id)) => {
+                    sqlx::query(
+                        "update jira_issues set
+                           assignee_account_id = case when assignee_account_id = $3
+                                                      then $1 else assignee_account_id end,
+                           reporter_account_id = case when reporter_account_id = $3
+                                                      then $1 else reporter_account_id end,
+                           raw_ref = null,
+                           updated_at = now()
+                         where org_id = $2
+                           and (assignee_account_id = $3 or reporter_account_id = $3)",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update jira_issue_transitions set author_account_id = $1
+                         where org_id = $2 and author_account_id = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update jira_issue_transitions set
+                           from_value = case when from_value = $3 then $1 else from_value end,
+                           to_value = case when to_value = $3 then $1 else to_value end
+                         where org_id = $2 and field = any($4)
+                           and (from_value = $3 or to_value = $3)",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .bind(USER_CHANGELOG_FIELDS)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update jira_worklogs set author_account_id = $1
+                         where org_id = $2 and author_account_id = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+                }
+                ("confluence", _, Some(account_id)) => {
+                    sqlx::query(
+                        "update confluence_pages set
+                           author_account_id = case when author_account_id = $3
+                                                    then $1 else author_account_id end,
+                           last_editor_account_id = case when last_editor_account_id = $3
+                                                         then $1 else last_editor_account_id end,
+                           updated_at = now()
+                         where org_id = $2
+                           and (author_account_id = $3 or last_editor_account_id = $3)",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+
+                    sqlx::query(
+                        "update confluence_page_versions set author_account_id = $1
+                         where org_id = $2 and author_account_id = $3",
+                    )
+                    .bind(&alias)
+                    .bind(org_id)
+                    .bind(account_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| OviaError::Database(e.to_string()))?;
+                }
+                _ => {}
+            }
+
+            // Later syncs look accounts up here and write the pseudonym instead.
+            // Emails are stored lowercased, as commits are matched by author email.
+            let accounts: Vec<String> = [
+                identity.external_id.clone(),
+                identity.username.clone(),
+                identity.email.as_deref().map(str::to_lowercase),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            sqlx::query(
+                "insert into erased_accounts (org_id, source, account_hash, alias)
+                 select $1, $2, encode(sha256(convert_to(a, 'UTF8')), 'hex'), $3
+                 from unnest($4::text[]) a
+                 on conflict do nothing",
+            )
+            .bind(org_id)
+            .bind(&identity.source)
+            .bind(&alias)
+            .bind(&accounts)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+            sqlx::query(
+                "update identities set
+                   external_id = case when external_id is null then null else $1 end,
+                   username = case when username is null then null else $1 end,
+                   email = null,
+                   display_name = null,
+                   raw_ref = null,
+                   updated_at = now()
+                 where id = $2 and org_id = $3",
+            )
+            .bind(&alias)
+            .bind(identity.id)
+            .bind(org_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+        }
+
+        // ── Link evidence and audit payloads ────────────────────────
+        let link_ids: Vec<Uuid> = sqlx::query_scalar(
+            "update person_identity_links set rule_trace = null, updated_at = now()
+             where org_id = $1 and person_id = $2
+             returning id",
+        )
+        .bind(org_id)
+        .bind(person_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let events_erased = sqlx::query(
+            "update identity_events set payload = null
+             where org_id = $1 and link_id = any($2)",
+        )
+        .bind(org_id)
+        .bind(&link_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?
+        .rows_affected();
+
+        // ── Free-text mentions in Ask history ───────────────────────
+        let ask_sessions_redacted = match pattern {
+            Some(ref pattern) => sqlx::query(
+                "update ask_sessions set
+                   query = regexp_replace(query, $2, $3, 'gi'),
+                   answer = regexp_replace(answer, $2, $3, 'gi')
+                 where org_id = $1 and (query ~* $2 or answer ~* $2)",
+            )
+            .bind(org_id)
+            .bind(pattern)
+            .bind(ERASED_MARKER)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?
+            .rows_affected(),
+            None => 0,
+        };
+
+        // ── The person row itself ───────────────────────────────────
+        // Team is kept so team-level aggregates do not shift after an erasure.
+        sqlx::query(
+            "update people set
+               display_name = $1, primary_email = null, avatar_url = null, role = null,
+               status = 'inactive', updated_at = now()
+             where org_id = $2 and id = $3",
+        )
+        .bind(ERASED_DISPLAY_NAME)
+        .bind(org_id)
+        .bind(person_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let row = sqlx::query(
+            "insert into person_erasures
+             (id, org_id, person_id, requested_by, identities_erased, links_erased,
+              events_erased, ask_sessions_redacted)
+             values ($1, $2, $3, $4, $5, $6, $7, $8)
+             returning id, org_id, person_id, requested_by, identities_erased, links_erased,
+                       events_erased, ask_sessions_redacted, created_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org_id)
+        .bind(person_id)
+        .bind(requested_by)
+        .bind(identities.len() as i32)
+        .bind(link_ids.len() as i32)
+        .bind(events_erased as i32)
+        .bind(ask_sessions_redacted as i32)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(map_erasure_row(&row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+
+    fn make_person(display_name: &str, email: Option<&str>) -> Person {
+        Person {
+            id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            display_name: display_name.to_string(),
+            primary_email: email.map(|s| s.to_string()),
+            avatar_url: None,
+            team: None,
+            role: None,
+            status: "active".to_string(),
+            left_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn make_identity(source: &str, username: Option<&str>, external_id: &str) -> Identity {
+        Identity {
+            id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            source: source.to_string(),
+            external_id: Some(external_id.to_string()),
+            username: username.map(|s| s.to_string()),
+            email: None,
+            display_name: None,
+            is_service_account: false,
+            is_active: true,
+            active_changed_at: None,
+            first_seen_at: None,
+            last_seen_at: None,
+            raw_ref: None,
+        }
+    }
+
+    // ── Pure helpers ─────────────────────────────────────────────
+
+    #[test]
+    fn mention_pattern_escapes_and_dedupes() {
+        let person = make_person("Jane Doe", Some("jane.doe@corp.com"));
+        let identities = vec![
+            make_identity("gitlab", Some("jdoe"), "1"),
+            make_identity("gitlab", Some("JDOE"), "2"),
+        ];
+        let pattern = mention_pattern(&person, &identities).unwrap();
+        assert_eq!(
+            pattern,
+            "\\m(?:Jane\\ Doe)\\M|\\m(?:jane\\.doe\\@corp\\.com)\\M|\\m(?:jdoe)\\M"
+        );
+
+        let short = make_person("Ann", None);
+        assert_eq!(mention_pattern(&short, &[]).unwrap(), "\\m(?:Ann)\\M");
+    }
+
+    #[test]
+    fn mention_pattern_skips_short_terms() {
+        let person = make_person("Al", None);
+        assert!(mention_pattern(&person, &[]).is_none());
+    }
+
+    #[test]
+    fn source_keys_use_username_for_gitlab_and_account_id_for_jira() {
+        let identities = vec![
+            make_identity("gitlab", Some("jdoe"), "42"),
+            make_identity("jira", None, "acc-1"),
+        ];
+        assert_eq!(source_keys(&identities, "gitlab"), vec!["jdoe"]);
+        assert_eq!(source_keys(&identities, "jira"), vec!["acc-1"]);
+    }
+
+    // ── DB-backed tests ──────────────────────────────────────────
+
+    async fn test_repo() -> Option<(PgPrivacyRepository, PgPool)> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+        Some((PgPrivacyRepository::new(pool.clone()), pool))
+    }
+
+    async fn seed_person(pool: &PgPool, org_id: Uuid) -> (Uuid, Uuid, Uuid) {
+        let person_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into people (id, org_id, display_name, primary_email)
+             values ($1, $2, 'Jane Doe', 'jane@corp.com')",
+        )
+        .bind(person_id)
+        .bind(org_id)
+        .execute(pool)
+        .await
+        .expect("insert person");
+
+        let identity_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into identities (id, org_id, source, external_id, username, email, raw_ref)
+             values ($1, $2, 'gitlab', $3, 'jdoe', 'jane@corp.com', '{\"name\": \"Jane Doe\"}')",
+        )
+        .bind(identity_id)
+        .bind(org_id)
+        .bind(identity_id.to_string())
+        .execute(pool)
+        .await
+        .expect("insert identity");
+
+        let link_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into person_identity_links
+             (id, org_id, person_id, identity_id, status, confidence, rule_trace)
+             values ($1, $2, $3, $4, 'auto', 0.9, '{\"email\": \"jane@corp.com\"}')",
+        )
+        .bind(link_id)
+        .bind(org_id)
+        .bind(person_id)
+        .bind(identity_id)
+        .execute(pool)
+        .await
+        .expect("insert link");
+
+        sqlx::query(
+            "insert into identity_events (id, org_id, link_id, action, actor, payload)
+             values ($1, $2, $3, 'manual_link', 'manual', '{\"email\": \"jane@corp.com\"}')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org_id)
+        .bind(link_id)
+        .execute(pool)
+        .await
+        .expect("insert event");
+
+        (person_id, identity_id, link_id)
+    }
+
+    async fn link_identity(
+        pool: &PgPool,
+        org_id: Uuid,
+        person_id: Uuid,
+        source: &str,
+        external_id: &str,
+    ) -> Uuid {
+        let identity_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into identities (id, org_id, source, external_id)
+             values ($1, $2, $3, $4)",
+        )
+        .bind(identity_id)
+        .bind(org_id)
+        .bind(source)
+        .bind(external_id)
+        .execute(pool)
+        .await
+        .expect("insert identity");
+        sqlx::query(
+            "insert into person_identity_links (id, org_id, person_id, identity_id, status)
+             values ($1, $2, $3, $4, 'verified')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org_id)
+        .bind(person_id)
+        .bind(identity_id)
+        .execute(pool)
+        .await
+        .expect("insert link");
+        identity_id
+    }
+
+    /// Ids of the exported rows, sorted.
+    fn exported_ids(rows: &[serde_json::Value]) -> Vec<String> {
+        let mut ids: Vec<String> = rows
+            .iter()
+            .map(|r| r["id"].as_str().unwrap().to_string())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Ids of the rows in `table` where any of `columns` holds the pseudonym, sorted.
+    async fn erased_ids(
+        pool: &PgPool,
+        org_id: Uuid,
+        table: &str,
+        columns: &[&str],
+        alias: &str,
+    ) -> Vec<String> {
+        let filter = columns
+            .iter()
+            .map(|c| format!("$2 = {c}"))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        sqlx::query_scalar(&format!(
+            "select id::text from {table} where org_id = $1 and ({filter}) order by id::text"
+        ))
+        .bind(org_id)
+        .bind(alias)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_returns_none_for_unknown_person() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let export = repo
+            .export_person(Uuid::new_v4(), Uuid::new_v4())
+            .await
+            .expect("export should succeed");
+        assert!(export.is_none());
+    }
+
+    #[tokio::test]
+    async fn export_bundles_identities_links_and_events() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, link_id) = seed_person(&pool, org).await;
+
+        let export = repo
+            .export_person(org, person_id)
+            .await
+            .expect("export should succeed")
+            .expect("person should exist");
+
+        assert_eq!(export.person.id, person_id);
+        assert_eq!(export.identities.len(), 1);
+        assert_eq!(export.identities[0].id, identity_id);
+        assert_eq!(export.links.len(), 1);
+        assert_eq!(export.links[0].id, link_id);
+        assert_eq!(export.events.len(), 1);
+        assert!(export.erasure.is_none());
+    }
+
+    #[tokio::test]
+    async fn erase_anonymizes_and_records_audit() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, link_id) = seed_person(&pool, org).await;
+
+        let erasure = repo
+            .erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        assert_eq!(erasure.identities_erased, 1);
+        assert_eq!(erasure.links_erased, 1);
+        assert_eq!(erasure.events_erased, 1);
+
+        let person = sqlx::query("select display_name, primary_email from people where id = $1")
+            .bind(person_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(person.get::<String, _>("display_name"), ERASED_DISPLAY_NAME);
+        assert!(person.get::<Option<String>, _>("primary_email").is_none());
+
+        let identity = sqlx::query("select username, email, raw_ref from identities where id = $1")
+            .bind(identity_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            identity.get::<Option<String>, _>("username"),
+            Some(pseudonym(identity_id))
+        );
+        assert!(identity.get::<Option<String>, _>("email").is_none());
+        assert!(identity
+            .get::<Option<serde_json::Value>, _>("raw_ref")
+            .is_none());
+
+        let payload: Option<serde_json::Value> =
+            sqlx::query_scalar("select payload from identity_events where link_id = $1")
+                .bind(link_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(payload.is_none());
+
+        let second = repo.erase_person(org, person_id, "dpo@corp.com").await;
+        assert!(matches!(second, Err(OviaError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn mention_pattern_matches_whole_words_only() {
+        let (_repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let person = make_person("Ann", Some("dev@corp.com"));
+        let identities = vec![make_identity("gitlab", Some("dev"), "1")];
+        let pattern = mention_pattern(&person, &identities).unwrap();
+
+        for (text, expected) in [
+            ("ask Ann about it", true),
+            ("mail dev@corp.com", true),
+            ("what did dev ship?", true),
+            ("sprint planning", false),
+            ("annual review", false),
+            ("device lab", false),
+        ] {
+            let matched: bool = sqlx::query_scalar("select $1 ~* $2")
+                .bind(text)
+                .bind(&pattern)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(matched, expected, "{text}");
+        }
+    }
+
+    #[tokio::test]
+    async fn erased_accounts_stay_pseudonymized_on_reingest() {
+        use crate::gitlab::models::{GitlabMergeRequest, GitlabMrNote};
+        use crate::gitlab::pg_repository::PgGitlabRepository;
+        use crate::identity::repositories::IdentityRepository;
+
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, _) = seed_person(&pool, org).await;
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        let alias = pseudonym(identity_id);
+
+        // The user sync sees the account again
+        let identity = PgIdentityRepository::new(pool.clone())
+            .upsert_by_external_id(Identity {
+                org_id: org,
+                email: Some("jane@corp.com".to_string()),
+                display_name: Some("Jane Doe".to_string()),
+                raw_ref: Some(serde_json::json!({"name": "Jane Doe"})),
+                ..make_identity("gitlab", Some("jdoe"), &identity_id.to_string())
+            })
+            .await
+            .expect("upsert should succeed");
+        assert_eq!(identity.id, identity_id);
+        assert_eq!(identity.username.as_deref(), Some(alias.as_str()));
+        assert!(identity.email.is_none());
+        let leaked: i64 = sqlx::query_scalar(
+            "select count(*) from identities
+             where org_id = $1 and (username = 'jdoe' or email = 'jane@corp.com')",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(leaked, 0);
+
+        // ... and the MR sync writes the author again
+        let gitlab = PgGitlabRepository::new(pool.clone());
+        let now = Utc::now();
+        gitlab
+            .upsert_merge_request(&GitlabMergeRequest {
+                id: Uuid::new_v4(),
+                org_id: org,
+                provider: "gitlab".to_string(),
+                gitlab_project_id: 1,
+                gitlab_mr_iid: 1,
+                title: "Fix".to_string(),
+                state: "merged".to_string(),
+                author_username: Some("jdoe".to_string()),
+                labels: vec![],
+                created_at_gl: Some(now),
+                merged_at: Some(now),
+                target_branch: None,
+                web_url: "https://gitlab.example.com/mr/1".to_string(),
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+        gitlab
+            .upsert_note(&GitlabMrNote {
+                id: Uuid::new_v4(),
+                org_id: org,
+                provider: "gitlab".to_string(),
+                gitlab_project_id: 1,
+                gitlab_mr_iid: 1,
+                gitlab_note_id: 1,
+                discussion_id: None,
+                author_username: Some("jdoe".to_string()),
+                system: false,
+                resolvable: false,
+                resolved: false,
+                created_at_gl: Some(now),
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+            .unwrap();
+
+        for table in ["gitlab_merge_requests", "gitlab_mr_notes"] {
+            let author: Option<String> = sqlx::query_scalar(&format!(
+                "select author_username from {table} where org_id = $1"
+            ))
+            .bind(org)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(author.as_deref(), Some(alias.as_str()), "{table}");
+        }
+    }
+
+    #[tokio::test]
+    async fn commits_are_exported_and_erased_by_author_email() {
+        use crate::gitlab::models::GitlabCommit;
+        use crate::gitlab::pg_repository::PgGitlabRepository;
+
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, _) = seed_person(&pool, org).await;
+        let gitlab = PgGitlabRepository::new(pool.clone());
+        let now = Utc::now();
+        let commit = GitlabCommit {
+            id: Uuid::new_v4(),
+            org_id: org,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: 1,
+            sha: "abc123".to_string(),
+            gitlab_mr_iid: Some(1),
+            title: "Fix checkout".to_string(),
+            author_name: Some("Jane Doe".to_string()),
+            author_email: Some("Jane@corp.com".to_string()),
+            authored_at: Some(now),
+            committed_at: Some(now),
+            web_url: None,
+            created_at: now,
+            updated_at: now,
+        };
+        gitlab.upsert_commit(&commit).await.unwrap();
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.commits.len(), 1);
+        assert_eq!(export.commits[0]["sha"], "abc123");
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        // A later sync stores the same commit again
+        gitlab.upsert_commit(&commit).await.unwrap();
+
+        let row = sqlx::query(
+            "select author_name, author_email from gitlab_commits where org_id = $1 and sha = 'abc123'",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            row.get::<Option<String>, _>("author_name"),
+            Some(pseudonym(identity_id))
+        );
+        assert!(row.get::<Option<String>, _>("author_email").is_none());
+    }
+
+    #[tokio::test]
+    async fn deployments_are_exported_and_erased() {
+        use crate::gitlab::models::GitlabDeployment;
+        use crate::gitlab::pg_repository::PgGitlabRepository;
+
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, _) = seed_person(&pool, org).await;
+        let gitlab = PgGitlabRepository::new(pool.clone());
+        let now = Utc::now();
+        let deployment = GitlabDeployment {
+            id: Uuid::new_v4(),
+            org_id: org,
+            gitlab_project_id: 1,
+            gitlab_deployment_id: 7,
+            environment_name: "production".to_string(),
+            status: "success".to_string(),
+            sha: None,
+            ref_name: Some("main".to_string()),
+            deployer_username: Some("jdoe".to_string()),
+            created_at_gl: Some(now),
+            finished_at_gl: Some(now),
+            created_at: now,
+            updated_at: now,
+        };
+        gitlab.upsert_deployment(&deployment).await.unwrap();
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.deployments.len(), 1);
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        gitlab.upsert_deployment(&deployment).await.unwrap();
+
+        let deployer: Option<String> = sqlx::query_scalar(
+            "select deployer_username from gitlab_deployments where org_id = $1",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(deployer, Some(pseudonym(identity_id)));
+    }
+
+    #[tokio::test]
+    async fn mr_notes_and_approvals_are_exported_and_erased() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, _) = seed_person(&pool, org).await;
+        for (note_id, author) in [(1, "jdoe"), (2, "someone")] {
+            sqlx::query(
+                "insert into gitlab_mr_notes
+                 (org_id, gitlab_project_id, gitlab_mr_iid, gitlab_note_id, author_username)
+                 values ($1, 1, 1, $2, $3)",
+            )
+            .bind(org)
+            .bind(note_id as i64)
+            .bind(author)
+            .execute(&pool)
+            .await
+            .expect("insert note");
+        }
+        for approver in ["jdoe", "someone"] {
+            sqlx::query(
+                "insert into gitlab_mr_approvals
+                 (org_id, gitlab_project_id, gitlab_mr_iid, approver_username, approved_at)
+                 values ($1, 1, 1, $2, now())",
+            )
+            .bind(org)
+            .bind(approver)
+            .execute(&pool)
+            .await
+            .expect("insert approval");
+        }
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.mr_notes.len(), 1);
+        assert_eq!(export.mr_approvals.len(), 1);
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+
+        let alias = pseudonym(identity_id);
+        let notes = erased_ids(&pool, org, "gitlab_mr_notes", &["author_username"], &alias).await;
+        assert_eq!(notes, exported_ids(&export.mr_notes));
+        let approvals = erased_ids(
+            &pool,
+            org,
+            "gitlab_mr_approvals",
+            &["approver_username"],
+            &alias,
+        )
+        .await;
+        assert_eq!(approvals, exported_ids(&export.mr_approvals));
+    }
+
+    #[tokio::test]
+    async fn gitlab_issues_and_state_events_are_exported_and_erased() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, identity_id, _) = seed_person(&pool, org).await;
+        for (iid, author, assignees) in [
+            (1, "jdoe", vec![]),
+            (2, "someone", vec!["someone", "jdoe"]),
+            (3, "someone", vec![]),
+        ] {
+            sqlx::query(
+                "insert into gitlab_issues
+                 (org_id, gitlab_project_id, gitlab_issue_iid, gitlab_issue_id, title, state,
+                  author_username, assignee_usernames, web_url)
+                 values ($1, 1, $2, $2, 'Bug', 'opened', $3, $4, 'https://gitlab.example.com')",
+            )
+            .bind(org)
+            .bind(iid as i64)
+            .bind(author)
+            .bind(assignees)
+            .execute(&pool)
+            .await
+            .expect("insert issue");
+        }
+        for (event_id, username) in [(1, "jdoe"), (2, "someone")] {
+            sqlx::query(
+                "insert into gitlab_issue_state_events
+                 (org_id, gitlab_project_id, gitlab_issue_iid, gitlab_event_id, state, username,
+                  created_at_gl)
+                 values ($1, 1, 1, $2, 'closed', $3, now())",
+            )
+            .bind(org)
+            .bind(event_id as i64)
+            .bind(username)
+            .execute(&pool)
+            .await
+            .expect("insert state event");
+        }
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.gitlab_issues.len(), 2);
+        assert_eq!(export.gitlab_issue_state_events.len(), 1);
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+
+        let alias = pseudonym(identity_id);
+        let issues = erased_ids(
+            &pool,
+            org,
+            "gitlab_issues",
+            &["author_username", "any(assignee_usernames)"],
+            &alias,
+        )
+        .await;
+        assert_eq!(issues, exported_ids(&export.gitlab_issues));
+        let events = erased_ids(
+            &pool,
+            org,
+            "gitlab_issue_state_events",
+            &["username"],
+            &alias,
+        )
+        .await;
+        assert_eq!(events, exported_ids(&export.gitlab_issue_state_events));
+    }
+
+    #[tokio::test]
+    async fn worklogs_are_exported_and_erased() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, _, _) = seed_person(&pool, org).await;
+        let jira_id = link_identity(&pool, org, person_id, "jira", "acc-jane").await;
+        for (worklog_id, author) in [(1, "acc-jane"), (2, "acc-jane"), (3, "acc-other")] {
+            sqlx::query(
+                "insert into jira_worklogs
+                 (org_id, worklog_id, jira_issue_id, author_account_id, started_at, time_spent_secs)
+                 values ($1, $2, 10, $3, now(), 3600)",
+            )
+            .bind(org)
+            .bind(worklog_id as i64)
+            .bind(author)
+            .execute(&pool)
+            .await
+            .expect("insert worklog");
+        }
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.jira_worklogs.len(), 2);
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+
+        let erased = erased_ids(
+            &pool,
+            org,
+            "jira_worklogs",
+            &["author_account_id"],
+            &pseudonym(jira_id),
+        )
+        .await;
+        assert_eq!(erased, exported_ids(&export.jira_worklogs));
+    }
+
+    #[tokio::test]
+    async fn assignee_transitions_are_exported_and_erased() {
+        use crate::jira::models::JiraIssueTransition;
+        use crate::jira::pg_repository::PgJiraRepository;
+
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, _, _) = seed_person(&pool, org).await;
+        let jira_id = link_identity(&pool, org, person_id, "jira", "acc-jane").await;
+        let jira = PgJiraRepository::new(pool.clone());
+        let now = Utc::now();
+        let transition = |field: &str, from: &str, to: &str, author: &str| JiraIssueTransition {
+            id: Uuid::new_v4(),
+            org_id: org,
+            jira_key: "BEE-1".to_string(),
+            field: field.to_string(),
+            from_value: Some(from.to_string()),
+            to_value: Some(to.to_string()),
+            author_account_id: Some(author.to_string()),
+            transitioned_at: now,
+            created_at: now,
+        };
+        let reassigned = transition("assignee", "acc-other", "acc-jane", "acc-lead");
+        for t in [
+            reassigned.clone(),
+            transition("assignee", "acc-other", "acc-third", "acc-lead"),
+            // Only user fields hold account ids
+            transition("labels", "acc-jane", "", "acc-lead"),
+        ] {
+            jira.insert_transition(&t).await.unwrap();
+        }
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.jira_transitions.len(), 1);
+        assert_eq!(export.jira_transitions[0]["to_value"], "acc-jane");
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        // A later webhook delivers the same change again
+        jira.insert_transition(&reassigned).await.unwrap();
+
+        let alias = pseudonym(jira_id);
+        let erased = erased_ids(
+            &pool,
+            org,
+            "jira_issue_transitions",
+            &["from_value", "to_value"],
+            &alias,
+        )
+        .await;
+        assert_eq!(erased.len(), 1);
+        assert_eq!(erased, exported_ids(&export.jira_transitions));
+        let leaked: i64 = sqlx::query_scalar(
+            "select count(*) from jira_issue_transitions
+             where org_id = $1 and field = 'assignee' and 'acc-jane' in (from_value, to_value)",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(leaked, 0);
+    }
+
+    #[tokio::test]
+    async fn confluence_pages_and_versions_are_exported_and_erased() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, _, _) = seed_person(&pool, org).await;
+        let confluence_id = link_identity(&pool, org, person_id, "confluence", "acc-jane").await;
+        for (page_id, author, editor) in [
+            ("1", "acc-jane", "acc-other"),
+            ("2", "acc-other", "acc-jane"),
+            ("3", "acc-other", "acc-other"),
+        ] {
+            sqlx::query(
+                "insert into confluence_pages
+                 (org_id, page_id, space_key, title, status, author_account_id,
+                  last_editor_account_id, version_number)
+                 values ($1, $2, 'ENG', 'Runbook', 'current', $3, $4, 1)",
+            )
+            .bind(org)
+            .bind(page_id)
+            .bind(author)
+            .bind(editor)
+            .execute(&pool)
+            .await
+            .expect("insert page");
+        }
+        for (version, author) in [(1, "acc-jane"), (2, "acc-other")] {
+            sqlx::query(
+                "insert into confluence_page_versions
+                 (org_id, page_id, version_number, author_account_id, created_at_confluence)
+                 values ($1, '1', $2, $3, now())",
+            )
+            .bind(org)
+            .bind(version)
+            .bind(author)
+            .execute(&pool)
+            .await
+            .expect("insert page version");
+        }
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.confluence_pages.len(), 2);
+        assert_eq!(export.confluence_page_versions.len(), 1);
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+
+        let alias = pseudonym(confluence_id);
+        let pages = erased_ids(
+            &pool,
+            org,
+            "confluence_pages",
+            &["author_account_id", "last_editor_account_id"],
+            &alias,
+        )
+        .await;
+        assert_eq!(pages, exported_ids(&export.confluence_pages));
+        let versions = erased_ids(
+            &pool,
+            org,
+            "confluence_page_versions",
+            &["author_account_id"],
+            &alias,
+        )
+        .await;
+        assert_eq!(versions, exported_ids(&export.confluence_page_versions));
+    }
+
+    #[tokio::test]
+    async fn github_merge_requests_are_exported_and_erased_by_provider() {
+        use crate::gitlab::models::GitlabMergeRequest;
+        use crate::gitlab::pg_repository::PgGitlabRepository;
+
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let (person_id, _, _) = seed_person(&pool, org).await;
+        let github_id = Uuid::new_v4();
+        sqlx::query(
+            "insert into identities (id, org_id, source, external_id, username)
+             values ($1, $2, 'github', '99', 'octo')",
+        )
+        .bind(github_id)
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("insert identity");
+        sqlx::query(
+            "insert into person_identity_links (id, org_id, person_id, identity_id, status)
+             values ($1, $2, $3, $4, 'verified')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org)
+        .bind(person_id)
+        .bind(github_id)
+        .execute(&pool)
+        .await
+        .expect("insert link");
+
+        // "jdoe" on GitHub is somebody else.
+        let gitlab = PgGitlabRepository::new(pool.clone());
+        let now = Utc::now();
+        let mr = |iid: i64, author: &str| GitlabMergeRequest {
+            id: Uuid::new_v4(),
+            org_id: org,
+            provider: "github".to_string(),
+            gitlab_project_id: 1,
+            gitlab_mr_iid: iid,
+            title: "Fix".to_string(),
+            state: "merged".to_string(),
+            author_username: Some(author.to_string()),
+            labels: vec![],
+            created_at_gl: Some(now),
+            merged_at: Some(now),
+            target_branch: None,
+            web_url: format!("https://github.com/acme/app/pull/{iid}"),
+            created_at: now,
+            updated_at: now,
+        };
+        gitlab.upsert_merge_request(&mr(1, "octo")).await.unwrap();
+        gitlab.upsert_merge_request(&mr(2, "jdoe")).await.unwrap();
+
+        let export = repo.export_person(org, person_id).await.unwrap().unwrap();
+        assert_eq!(export.merge_requests.len(), 1);
+        assert_eq!(export.merge_requests[0]["author_username"], "octo");
+
+        repo.erase_person(org, person_id, "dpo@corp.com")
+            .await
+            .expect("erase should succeed");
+        gitlab.upsert_merge_request(&mr(1, "octo")).await.unwrap();
+
+        let authors: Vec<Option<String>> = sqlx::query_scalar(
+            "select author_username from gitlab_merge_requests
+             where org_id = $1 order by gitlab_mr_iid",
+        )
+        .bind(org)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            authors,
+            vec![Some(pseudonym(github_id)), Some("jdoe".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn erase_not_found() {
+        let (repo, _pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let result = repo
+            .erase_person(Uuid::new_v4(), Uuid::new_v4(), "dpo")
+            .await;
+        assert!(matches!(result, Err(OviaError::NotFound(_))));
+    }
+}
@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::privacy::models::{PersonErasure, PersonExport};
+use ovia_common::error::OviaResult;
+
+#[async_trait]
+pub trait PrivacyRepository: Send + Sync {
+    /// Collect all data tied to a person. Returns `None` if the person does not exist.
+    async fn export_person(
+        &self,
+        org_id: Uuid,
+        person_id: Uuid,
+    ) -> OviaResult<Option<PersonExport>>;
+
+    /// Anonymize a person, their identities, link evidence and audit payloads in place.
+    /// Source activity is re-keyed to a stable pseudonym so aggregate KPI counts survive.
+    async fn erase_person(
+        &self,
+        org_id: Uuid,
+        person_id: Uuid,
+        requested_by: &str,
+    ) -> OviaResult<PersonErasure>;
+}
@@ -0,0 +1,144 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use ovia_common::error::{OviaError, OviaResult};
+
+#[derive(Clone)]
+pub struct PgWebhookRepository {
+    pool: PgPool,
+}
+
+impl PgWebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a webhook delivery. Returns `false` if the same delivery was already
+    /// recorded for this org and source, i.e. the event is a replay.
+    pub async fn record_delivery(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        delivery_id: &str,
+        event_type: &str,
+    ) -> OviaResult<bool> {
+        let result = sqlx::query(
+            "insert into webhook_deliveries (id, org_id, source, delivery_id, event_type)
+             values ($1, $2, $3, $4, $5)
+             on conflict (org_id, source, delivery_id) do nothing",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org_id)
+        .bind(source)
+        .bind(delivery_id)
+        .bind(event_type)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Forget a delivery so a retry of the same event is processed again.
+    /// Used when handling a recorded delivery fails part-way.
+    pub async fn forget_delivery(
+        &self,
+        org_id: Uuid,
+        source: &str,
+        delivery_id: &str,
+    ) -> OviaResult<()> {
+        sqlx::query(
+            "delete from webhook_deliveries
+             where org_id = $1 and source = $2 and delivery_id = $3",
+        )
+        .bind(org_id)
+        .bind(source)
+        .bind(delivery_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+
+    async fn test_repo() -> Option<PgWebhookRepository> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        sqlx::query(
+            "create table if not exists webhook_deliveries (
+               id uuid primary key,
+               org_id uuid not null,
+               source text not null,
+               delivery_id text not null,
+               event_type text not null,
+               received_at timestamptz not null default now()
+             )",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+        sqlx::query(
+            "create unique index if not exists webhook_deliveries_org_source_delivery_uidx
+               on webhook_deliveries(org_id, source, delivery_id)",
+        )
+        .execute(&pool)
+        .await
+        .ok()?;
+
+        Some(PgWebhookRepository::new(pool))
+    }
+
+    #[tokio::test]
+    async fn record_delivery_detects_replays() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        assert!(repo
+            .record_delivery(org, "gitlab", "evt-1", "Merge Request Hook")
+            .await
+            .unwrap());
+        assert!(!repo
+            .record_delivery(org, "gitlab", "evt-1", "Merge Request Hook")
+            .await
+            .unwrap());
+
+        // same id from another org or source is a different delivery
+        assert!(repo
+            .record_delivery(Uuid::new_v4(), "gitlab", "evt-1", "Merge Request Hook")
+            .await
+            .unwrap());
+        assert!(repo
+            .record_delivery(org, "jira", "evt-1", "jira:issue_updated")
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn forget_delivery_allows_retry() {
+        let repo = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        assert!(repo
+            .record_delivery(org, "gitlab", "evt-2", "Pipeline Hook")
+            .await
+            .unwrap());
+        repo.forget_delivery(org, "gitlab", "evt-2").await.unwrap();
+        assert!(repo
+            .record_delivery(org, "gitlab", "evt-2", "Pipeline Hook")
+            .await
+            .unwrap());
+    }
+}
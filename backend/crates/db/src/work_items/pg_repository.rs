@@ -0,0 +1,462 @@
+//! KPI queries over the `work_items` view, which puts Jira issues and GitLab issues
+//! side by side. Teams can track work in either tracker and count the same way.
+
+use chrono::NaiveDate;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use ovia_common::error::{OviaError, OviaResult};
+
+/// Skips items whose assignee had already left by the resolution date. Jira assignees
+/// are account ids, GitLab assignees are usernames.
+const NOT_DEPARTED_AT_RESOLUTION: &str =
+    "not is_departed(w.org_id, w.source, w.assignee, w.resolved_at)";
+
+/// Open items holding up other work. Jira issues count when a "Blocks" link points
+/// at an open issue. GitLab has no such links, so GitLab issues count by their
+/// blocker labels (priority 'Blocker' in the view).
+const IS_OPEN_BLOCKER: &str = "not w.is_done and (
+  (w.source = 'jira' and exists (
+     select 1 from jira_issue_links l
+     join work_items b
+       on b.org_id = l.org_id and b.source = 'jira' and b.item_key = l.target_key
+     where l.org_id = w.org_id and l.link_type = 'Blocks' and l.source_key = w.item_key
+       and not b.is_done
+  ))
+  or (w.source = 'gitlab' and w.priority = 'Blocker')
+)";
+
+#[derive(Clone)]
+pub struct PgWorkItemRepository {
+    pool: PgPool,
+}
+
+impl PgWorkItemRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Count open blockers (see `IS_OPEN_BLOCKER`).
+    pub async fn count_open_blockers(&self, org_id: Uuid) -> OviaResult<i64> {
+        let row = sqlx::query(&format!(
+            "select count(*) as cnt from work_items w
+             where w.org_id = $1
+               and {IS_OPEN_BLOCKER}"
+        ))
+        .bind(org_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(row.get::<i64, _>("cnt"))
+    }
+
+    /// List age in days for each open blocker (for release risk computation).
+    pub async fn list_open_blocker_age_days(&self, org_id: Uuid) -> OviaResult<Vec<i32>> {
+        let rows = sqlx::query(&format!(
+            "select extract(day from (now() - w.created_at))::integer as age_days
+             from work_items w
+             where w.org_id = $1
+               and {IS_OPEN_BLOCKER}
+               and w.created_at is not null"
+        ))
+        .bind(org_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|r| r.get::<i32, _>("age_days")).collect())
+    }
+
+    /// Fraction of sprint (or milestone) items that are not done.
+    /// Returns 0.0 when no item is in a sprint.
+    pub async fn spillover_rate(&self, org_id: Uuid) -> OviaResult<f64> {
+        let row = sqlx::query(
+            "select
+               count(*) as total,
+               count(*) filter (where not is_done) as unresolved
+             from work_items
+             where org_id = $1
+               and sprint_name is not null",
+        )
+        .bind(org_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        let total: i64 = row.get("total");
+        let unresolved: i64 = row.get("unresolved");
+        if total == 0 {
+            return Ok(0.0);
+        }
+        Ok(unresolved as f64 / total as f64)
+    }
+
+    /// Cycle times in hours (start → resolution) for items resolved in the period,
+    /// sorted ascending.
+    pub async fn get_cycle_times_hours(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<Vec<f64>> {
+        let rows = sqlx::query(&format!(
+            "select (extract(epoch from (w.resolved_at - w.started_at)) / 3600.0)::float8 as hours
+             from work_items w
+             where w.org_id = $1
+               and w.started_at is not null
+               and w.resolved_at >= $2::date
+               and w.resolved_at < $3::date
+               and {NOT_DEPARTED_AT_RESOLUTION}
+             order by hours"
+        ))
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+
+        Ok(rows.iter().map(|r| r.get::<f64, _>("hours")).collect())
+    }
+
+    /// Count items resolved in the period.
+    pub async fn count_resolved(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> OviaResult<i64> {
+        let row = sqlx::query(&format!(
+            "select count(*) as cnt from work_items w
+             where w.org_id = $1
+               and w.resolved_at >= $2::date
+               and w.resolved_at < $3::date
+               and {NOT_DEPARTED_AT_RESOLUTION}"
+        ))
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(row.get::<i64, _>("cnt"))
+    }
+
+    /// Count items resolved in the period that match any of `item_types`. Items
+    /// without a type (GitLab issues) match on any of `labels` instead.
+    pub async fn count_resolved_by_class(
+        &self,
+        org_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+        item_types: &[&str],
+        labels: &[&str],
+    ) -> OviaResult<i64> {
+        let types_vec: Vec<String> = item_types.iter().map(|s| s.to_string()).collect();
+        let labels_vec: Vec<String> = labels.iter().map(|s| s.to_string()).collect();
+        let row = sqlx::query(&format!(
+            "select count(*) as cnt from work_items w
+             where w.org_id = $1
+               and w.resolved_at >= $2::date
+               and w.resolved_at < $3::date
+               and {NOT_DEPARTED_AT_RESOLUTION}
+               and (w.item_type = any($4) or (w.item_type is null and w.labels && $5))"
+        ))
+        .bind(org_id)
+        .bind(from)
+        .bind(to)
+        .bind(&types_vec)
+        .bind(&labels_vec)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| OviaError::Database(e.to_string()))?;
+        Ok(row.get::<i64, _>("cnt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_pool;
+    use chrono::{DateTime, Duration, Utc};
+
+    async fn test_repo() -> Option<(PgWorkItemRepository, PgPool)> {
+        let url = std::env::var("TEST_DATABASE_URL").ok()?;
+        let pool = create_pool(&url).await.expect("db should connect");
+
+        // Create tables inline for test isolation
+        for stmt in [
+            "create table if not exists jira_issues (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_key text not null, project_key text not null,
+              issue_type text, summary text not null, status text not null,
+              assignee_account_id text, reporter_account_id text, priority text,
+              story_points real, sprint_name text, sprint_id bigint, team_name text,
+              labels text[] not null default '{}',
+              created_at_jira timestamptz, updated_at_jira timestamptz, resolved_at timestamptz,
+              raw_ref jsonb,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create table if not exists jira_issue_transitions (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_key text not null, field text not null,
+              from_value text, to_value text, author_account_id text,
+              transitioned_at timestamptz not null,
+              created_at timestamptz not null default now()
+            )",
+            "create table if not exists gitlab_projects (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_id bigint not null,
+              name text not null, path_with_namespace text not null, web_url text not null,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create table if not exists gitlab_issues (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null,
+              gitlab_issue_iid bigint not null, gitlab_issue_id bigint not null,
+              title text not null, state text not null, issue_type text, author_username text,
+              assignee_usernames text[] not null default '{}', labels text[] not null default '{}',
+              milestone_id bigint, milestone_title text,
+              created_at_gl timestamptz, updated_at_gl timestamptz, closed_at timestamptz,
+              web_url text not null,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create table if not exists gitlab_issue_state_events (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, gitlab_project_id bigint not null,
+              gitlab_issue_iid bigint not null, gitlab_event_id bigint not null,
+              state text not null, username text, created_at_gl timestamptz not null,
+              created_at timestamptz not null default now()
+            )",
+            "create table if not exists jira_issue_links (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null, jira_link_id bigint not null, link_type text not null,
+              outward text, inward text, source_key text not null, target_key text not null,
+              created_at timestamptz not null default now(), updated_at timestamptz not null default now()
+            )",
+            "create or replace view work_items as
+             select ji.org_id, 'jira'::text as source, ji.jira_key as item_key, ji.project_key,
+                    ji.issue_type as item_type, ji.summary as title, ji.status,
+                    ji.status in ('Done', 'Closed', 'Resolved') as is_done,
+                    ji.assignee_account_id as assignee, ji.priority, ji.labels, ji.sprint_name,
+                    ji.team_name, ji.created_at_jira as created_at, ji.resolved_at,
+                    (select min(t.transitioned_at) from jira_issue_transitions t
+                     where t.org_id = ji.org_id and t.jira_key = ji.jira_key
+                       and t.field = 'status' and t.to_value = 'In Progress') as started_at
+             from jira_issues ji
+             union all
+             select gi.org_id, 'gitlab'::text,
+                    coalesce(p.path_with_namespace, gi.gitlab_project_id::text) || '#' || gi.gitlab_issue_iid,
+                    coalesce(p.path_with_namespace, gi.gitlab_project_id::text),
+                    null::text, gi.title, gi.state, gi.state = 'closed', gi.assignee_usernames[1],
+                    case when exists (
+                           select 1 from unnest(gi.labels) l
+                           where lower(l) in ('blocker', 'priority::blocker', 'priority::critical')
+                         ) then 'Blocker' end,
+                    gi.labels, gi.milestone_title, null::text, gi.created_at_gl,
+                    case when gi.state = 'closed' then gi.closed_at end,
+                    coalesce(
+                      (select max(e.created_at_gl) from gitlab_issue_state_events e
+                       where e.org_id = gi.org_id and e.gitlab_project_id = gi.gitlab_project_id
+                         and e.gitlab_issue_iid = gi.gitlab_issue_iid and e.state = 'reopened'
+                         and e.created_at_gl <= gi.closed_at),
+                      gi.created_at_gl)
+             from gitlab_issues gi
+             left join gitlab_projects p
+               on p.org_id = gi.org_id and p.gitlab_id = gi.gitlab_project_id",
+        ] {
+            sqlx::query(stmt).execute(&pool).await.ok()?;
+        }
+
+        Some((PgWorkItemRepository::new(pool.clone()), pool))
+    }
+
+    async fn insert_jira_issue(
+        pool: &PgPool,
+        org: Uuid,
+        key: &str,
+        issue_type: &str,
+        status: &str,
+        started: Option<DateTime<Utc>>,
+        resolved: Option<DateTime<Utc>>,
+    ) {
+        sqlx::query(
+            "insert into jira_issues
+             (org_id, jira_key, project_key, issue_type, summary, status, created_at_jira,
+              resolved_at, sprint_name)
+             values ($1, $2, 'OV', $3, 'Issue', $4, $5, $6, 'Sprint 1')",
+        )
+        .bind(org)
+        .bind(key)
+        .bind(issue_type)
+        .bind(status)
+        .bind(resolved.map(|r| r - Duration::days(5)))
+        .bind(resolved)
+        .execute(pool)
+        .await
+        .expect("insert jira issue");
+        if let Some(started) = started {
+            sqlx::query(
+                "insert into jira_issue_transitions
+                 (org_id, jira_key, field, from_value, to_value, transitioned_at)
+                 values ($1, $2, 'status', 'To Do', 'In Progress', $3)",
+            )
+            .bind(org)
+            .bind(key)
+            .bind(started)
+            .execute(pool)
+            .await
+            .expect("insert transition");
+        }
+    }
+
+    async fn insert_gitlab_issue(
+        pool: &PgPool,
+        org: Uuid,
+        iid: i64,
+        labels: &[&str],
+        created: DateTime<Utc>,
+        closed: Option<DateTime<Utc>>,
+    ) {
+        let labels: Vec<String> = labels.iter().map(|l| l.to_string()).collect();
+        sqlx::query(
+            "insert into gitlab_issues
+             (org_id, gitlab_project_id, gitlab_issue_iid, gitlab_issue_id, title, state,
+              labels, milestone_title, created_at_gl, closed_at, web_url)
+             values ($1, 5, $2, $2 + 1000, 'Issue', $3, $4, 'M1', $5, $6, 'https://gl/issues')",
+        )
+        .bind(org)
+        .bind(iid)
+        .bind(if closed.is_some() { "closed" } else { "opened" })
+        .bind(&labels)
+        .bind(created)
+        .bind(closed)
+        .execute(pool)
+        .await
+        .expect("insert gitlab issue");
+    }
+
+    #[tokio::test]
+    async fn gitlab_issues_count_like_jira_issues() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let resolved = "2026-03-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let from = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 3, 31).unwrap();
+
+        insert_jira_issue(
+            &pool,
+            org,
+            "OV-1",
+            "Bug",
+            "Done",
+            Some(resolved - Duration::hours(10)),
+            Some(resolved),
+        )
+        .await;
+        insert_jira_issue(&pool, org, "OV-2", "Story", "In Progress", None, None).await;
+
+        // Closed bug, reopened once: its cycle restarts at the reopen
+        insert_gitlab_issue(
+            &pool,
+            org,
+            1,
+            &["bug"],
+            resolved - Duration::days(3),
+            Some(resolved),
+        )
+        .await;
+        sqlx::query(
+            "insert into gitlab_issue_state_events
+             (org_id, gitlab_project_id, gitlab_issue_iid, gitlab_event_id, state, created_at_gl)
+             values ($1, 5, 1, 1, 'reopened', $2)",
+        )
+        .bind(org)
+        .bind(resolved - Duration::hours(4))
+        .execute(&pool)
+        .await
+        .expect("insert state event");
+        insert_gitlab_issue(
+            &pool,
+            org,
+            2,
+            &["priority::blocker"],
+            resolved - Duration::days(2),
+            None,
+        )
+        .await;
+
+        assert_eq!(repo.count_resolved(org, from, to).await.unwrap(), 2);
+        let bugs = repo
+            .count_resolved_by_class(org, from, to, &["Bug"], &["bug"])
+            .await
+            .unwrap();
+        assert_eq!(bugs, 2);
+        let features = repo
+            .count_resolved_by_class(org, from, to, &["Story"], &["feature"])
+            .await
+            .unwrap();
+        assert_eq!(features, 0);
+
+        let cycle_times = repo.get_cycle_times_hours(org, from, to).await.unwrap();
+        assert_eq!(cycle_times.len(), 2);
+        assert!((cycle_times[0] - 4.0).abs() < 0.01);
+        assert!((cycle_times[1] - 10.0).abs() < 0.01);
+
+        assert_eq!(repo.count_open_blockers(org).await.unwrap(), 1);
+        // OV-2 and gitlab #2 are still open out of four sprint/milestone items
+        let spillover = repo.spillover_rate(org).await.unwrap();
+        assert!((spillover - 0.5).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn jira_blockers_come_from_blocking_links() {
+        let (repo, pool) = match test_repo().await {
+            Some(r) => r,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let done = Utc::now() - Duration::days(1);
+
+        for (key, resolved) in [
+            ("OV-10", None),
+            ("OV-11", None),
+            ("OV-12", None),
+            ("OV-13", Some(done)),
+            ("OV-14", None),
+        ] {
+            let status = if resolved.is_some() { "Done" } else { "To Do" };
+            insert_jira_issue(&pool, org, key, "Story", status, None, resolved).await;
+        }
+        // Highest priority alone no longer makes a blocker
+        sqlx::query(
+            "update jira_issues set priority = 'Highest', created_at_jira = now() - interval '3 days'
+             where org_id = $1",
+        )
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("set priority");
+        // OV-10 blocks an open issue, OV-12 only a resolved one
+        for (id, source, target) in [(1_i64, "OV-10", "OV-11"), (2, "OV-12", "OV-13")] {
+            sqlx::query(
+                "insert into jira_issue_links
+                 (org_id, jira_link_id, link_type, source_key, target_key)
+                 values ($1, $2, 'Blocks', $3, $4)",
+            )
+            .bind(org)
+            .bind(id)
+            .bind(source)
+            .bind(target)
+            .execute(&pool)
+            .await
+            .expect("insert link");
+        }
+
+        assert_eq!(repo.count_open_blockers(org).await.unwrap(), 1);
+        assert_eq!(repo.list_open_blocker_age_days(org).await.unwrap(), vec![3]);
+    }
+}
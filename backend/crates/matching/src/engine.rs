@@ -90,6 +90,7 @@ mod tests {
             team: team.map(|s| s.to_string()),
             role: None,
             status: "active".to_string(),
+            left_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -110,6 +111,8 @@ mod tests {
             email: email.map(|s| s.to_string()),
             display_name: display_name.map(|s| s.to_string()),
             is_service_account,
+            is_active: true,
+            active_changed_at: None,
             first_seen_at: None,
             last_seen_at: None,
             raw_ref: None,
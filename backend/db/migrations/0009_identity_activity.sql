@@ -0,0 +1,21 @@
+-- Source account state on identities and departure tracking on people.
+
+alter table identities add column if not exists is_active boolean not null default true;
+alter table identities add column if not exists active_changed_at timestamptz;
+
+alter table people add column if not exists left_at timestamptz;
+
+-- offboarding candidates: people whose linked identities are all inactive
+create index if not exists identities_org_inactive_idx
+  on identities(org_id)
+  where is_active = false;
+
+-- identities that belonged to a person who has left, with the departure date.
+-- KPI queries use this to drop activity attributed to departed people after left_at.
+create or replace view departed_identities as
+select distinct i.org_id, i.source, i.external_id, i.username, p.left_at
+from people p
+join person_identity_links pil on pil.person_id = p.id and pil.org_id = p.org_id
+join identities i on i.id = pil.identity_id
+where p.left_at is not null
+  and pil.status not in ('rejected', 'ignored');
@@ -0,0 +1,17 @@
+-- Audit trail for right-to-erasure requests.
+-- The person row itself is kept (anonymized) so aggregate KPI counts stay intact.
+
+create table if not exists person_erasures (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  person_id uuid not null,
+  requested_by text not null,
+  identities_erased integer not null default 0,
+  links_erased integer not null default 0,
+  events_erased integer not null default 0,
+  ask_sessions_redacted integer not null default 0,
+  created_at timestamptz not null default now()
+);
+
+create unique index if not exists person_erasures_org_person_uidx
+  on person_erasures(org_id, person_id);
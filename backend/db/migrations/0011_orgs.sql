@@ -0,0 +1,20 @@
+-- Org registry with per-org source configuration.
+-- Ingest and metrics iterate over enabled orgs instead of a single ORG_ID.
+
+create table if not exists orgs (
+  id uuid primary key,
+  name text not null,
+  is_enabled boolean not null default true,
+  jira_base_url text,
+  jira_project_keys text[] not null default '{}',
+  gitlab_base_url text,
+  gitlab_groups text[] not null default '{}',
+  confluence_base_url text,
+  confluence_spaces text[] not null default '{}',
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create index if not exists orgs_enabled_idx
+  on orgs(name)
+  where is_enabled = true;
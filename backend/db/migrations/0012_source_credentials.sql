@@ -0,0 +1,27 @@
+-- Encrypted per-org source credentials (envelope encryption).
+-- Each secret is encrypted with its own random data key (AES-256-GCM); the data key
+-- is wrapped with the master key identified by key_id. Rotating the master key only
+-- rewraps data keys, the secret ciphertext stays untouched.
+
+create table if not exists source_credentials (
+  id uuid primary key,
+  org_id uuid not null,
+  source text not null,
+  principal text,
+  secret_ciphertext bytea not null,
+  secret_nonce bytea not null,
+  wrapped_key bytea not null,
+  key_nonce bytea not null,
+  key_id text not null,
+  last_tested_at timestamptz,
+  last_test_ok boolean,
+  last_test_error text,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists source_credentials_org_source_uidx
+  on source_credentials(org_id, source);
+
+create index if not exists source_credentials_key_id_idx
+  on source_credentials(key_id);
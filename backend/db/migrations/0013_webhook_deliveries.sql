@@ -0,0 +1,13 @@
+-- Webhook deliveries already processed, used to drop replays and retries.
+
+create table if not exists webhook_deliveries (
+  id uuid primary key,
+  org_id uuid not null,
+  source text not null,
+  delivery_id text not null,
+  event_type text not null,
+  received_at timestamptz not null default now()
+);
+
+create unique index if not exists webhook_deliveries_org_source_delivery_uidx
+  on webhook_deliveries(org_id, source, delivery_id);
@@ -0,0 +1,29 @@
+-- MR commits and lead time for changes (first commit → merge → deploy).
+
+create table if not exists gitlab_commits (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  sha text not null,
+  gitlab_mr_iid bigint,
+  title text not null,
+  author_name text,
+  author_email text,
+  authored_at timestamptz,
+  committed_at timestamptz,
+  web_url text,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_commits_org_proj_sha_uidx
+  on gitlab_commits(org_id, gitlab_project_id, sha);
+
+create index if not exists gitlab_commits_org_proj_mr_idx
+  on gitlab_commits(org_id, gitlab_project_id, gitlab_mr_iid);
+
+-- Deploys are matched to MRs by the branch they were merged into.
+alter table gitlab_merge_requests add column if not exists target_branch text;
+
+alter table kpi_snapshots add column if not exists lead_time_p50_hours numeric(8,2);
+alter table kpi_snapshots add column if not exists lead_time_p90_hours numeric(8,2);
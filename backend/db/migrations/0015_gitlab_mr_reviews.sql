@@ -0,0 +1,41 @@
+-- MR notes and approvals, for time-to-first-review, review rounds and reviewer load.
+-- Note bodies are not stored; only who commented and when.
+
+create table if not exists gitlab_mr_notes (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_mr_iid bigint not null,
+  gitlab_note_id bigint not null,
+  discussion_id text,
+  author_username text,
+  system boolean not null default false,
+  resolvable boolean not null default false,
+  resolved boolean not null default false,
+  created_at_gl timestamptz,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_mr_notes_org_proj_note_uidx
+  on gitlab_mr_notes(org_id, gitlab_project_id, gitlab_note_id);
+
+create index if not exists gitlab_mr_notes_org_proj_mr_idx
+  on gitlab_mr_notes(org_id, gitlab_project_id, gitlab_mr_iid);
+
+create table if not exists gitlab_mr_approvals (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_mr_iid bigint not null,
+  approver_username text not null,
+  approved_at timestamptz,
+  created_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_mr_approvals_org_proj_mr_user_uidx
+  on gitlab_mr_approvals(org_id, gitlab_project_id, gitlab_mr_iid, approver_username);
+
+alter table kpi_snapshots add column if not exists time_to_first_review_p50_hours numeric(8,2);
+alter table kpi_snapshots add column if not exists time_to_first_review_p90_hours numeric(8,2);
+alter table kpi_snapshots add column if not exists review_rounds_avg numeric(6,2);
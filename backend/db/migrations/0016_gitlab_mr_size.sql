@@ -0,0 +1,7 @@
+-- MR size from the MR changes API, and size stats on KPI snapshots.
+
+alter table gitlab_merge_requests add column if not exists additions integer;
+alter table gitlab_merge_requests add column if not exists deletions integer;
+alter table gitlab_merge_requests add column if not exists changed_files integer;
+
+alter table kpi_snapshots add column if not exists mr_size_stats jsonb;
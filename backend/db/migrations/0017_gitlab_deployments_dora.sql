@@ -0,0 +1,55 @@
+-- GitLab environments and deployments, and DORA metrics per KPI snapshot.
+
+create table if not exists gitlab_environments (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_environment_id bigint not null,
+  name text not null,
+  tier text,
+  state text,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_environments_org_proj_env_uidx
+  on gitlab_environments(org_id, gitlab_project_id, gitlab_environment_id);
+
+create table if not exists gitlab_deployments (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_deployment_id bigint not null,
+  environment_name text not null,
+  status text not null,
+  sha text,
+  ref_name text,
+  deployer_username text,
+  created_at_gl timestamptz,
+  finished_at_gl timestamptz,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_deployments_org_proj_dep_uidx
+  on gitlab_deployments(org_id, gitlab_project_id, gitlab_deployment_id);
+
+create index if not exists gitlab_deployments_org_env_created_idx
+  on gitlab_deployments(org_id, gitlab_project_id, environment_name, created_at_gl);
+
+-- One row per scope: the whole org, each project, each team.
+create table if not exists dora_metrics (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  snapshot_id uuid not null references kpi_snapshots(id) on delete cascade,
+  scope_type text not null,
+  scope_key text,
+  deployment_count integer not null default 0,
+  deployments_per_day numeric(8,3),
+  lead_time_p50_hours numeric(8,2),
+  change_failure_rate numeric(5,4),
+  time_to_restore_p50_hours numeric(8,2),
+  created_at timestamptz not null default now()
+);
+
+create index if not exists dora_metrics_snapshot_idx on dora_metrics(snapshot_id);
@@ -0,0 +1,31 @@
+-- Jobs of GitLab pipelines, including retried attempts, for flaky-job detection.
+
+create table if not exists gitlab_pipeline_jobs (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_pipeline_id bigint not null,
+  gitlab_job_id bigint not null,
+  name text not null,
+  stage text,
+  status text not null,
+  sha text,
+  ref_name text,
+  -- true when a later attempt of the same job in the same pipeline exists
+  retried boolean not null default false,
+  allow_failure boolean not null default false,
+  failure_reason text,
+  created_at_gl timestamptz,
+  started_at_gl timestamptz,
+  finished_at_gl timestamptz,
+  duration_secs double precision,
+  web_url text,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_pipeline_jobs_org_job_uidx
+  on gitlab_pipeline_jobs(org_id, gitlab_job_id);
+
+create index if not exists gitlab_pipeline_jobs_org_proj_name_sha_idx
+  on gitlab_pipeline_jobs(org_id, gitlab_project_id, name, sha);
@@ -0,0 +1,103 @@
+-- GitLab issues with their state events, and a work_items view that normalizes them
+-- next to Jira issues so KPIs can be computed from either tracker.
+
+create table if not exists gitlab_issues (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_issue_iid bigint not null,
+  gitlab_issue_id bigint not null,
+  title text not null,
+  state text not null,              -- "opened" or "closed"
+  issue_type text,                  -- "issue", "incident", "task", ...
+  author_username text,
+  assignee_usernames text[] not null default '{}',
+  labels text[] not null default '{}',
+  milestone_id bigint,
+  milestone_title text,
+  created_at_gl timestamptz,
+  updated_at_gl timestamptz,
+  closed_at timestamptz,
+  web_url text not null,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_issues_org_proj_iid_uidx
+  on gitlab_issues(org_id, gitlab_project_id, gitlab_issue_iid);
+
+-- From resource_state_events: "opened", "closed" or "reopened".
+create table if not exists gitlab_issue_state_events (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  gitlab_project_id bigint not null,
+  gitlab_issue_iid bigint not null,
+  gitlab_event_id bigint not null,
+  state text not null,
+  username text,
+  created_at_gl timestamptz not null,
+  created_at timestamptz not null default now()
+);
+
+create unique index if not exists gitlab_issue_state_events_org_event_uidx
+  on gitlab_issue_state_events(org_id, gitlab_event_id);
+
+create index if not exists gitlab_issue_state_events_org_issue_idx
+  on gitlab_issue_state_events(org_id, gitlab_project_id, gitlab_issue_iid);
+
+-- One row per tracked work item, whichever tracker it lives in.
+--   assignee     Jira account id or GitLab username (first assignee)
+--   priority     Jira priority; GitLab issues labelled as blockers read as "Blocker"
+--   sprint_name  Jira sprint or GitLab milestone
+--   started_at   first move to "In Progress" in Jira; GitLab has no such status, so
+--                an issue starts when opened, or when last reopened before closing
+create or replace view work_items as
+select ji.org_id,
+       'jira'::text as source,
+       ji.jira_key as item_key,
+       ji.project_key,
+       ji.issue_type as item_type,
+       ji.summary as title,
+       ji.status,
+       ji.status in ('Done', 'Closed', 'Resolved') as is_done,
+       ji.assignee_account_id as assignee,
+       ji.priority,
+       ji.labels,
+       ji.sprint_name,
+       ji.team_name,
+       ji.created_at_jira as created_at,
+       ji.resolved_at,
+       (select min(t.transitioned_at)
+        from jira_issue_transitions t
+        where t.org_id = ji.org_id and t.jira_key = ji.jira_key
+          and t.field = 'status' and t.to_value = 'In Progress') as started_at
+from jira_issues ji
+union all
+select gi.org_id,
+       'gitlab'::text,
+       coalesce(p.path_with_namespace, gi.gitlab_project_id::text) || '#' || gi.gitlab_issue_iid,
+       coalesce(p.path_with_namespace, gi.gitlab_project_id::text),
+       null::text,
+       gi.title,
+       gi.state,
+       gi.state = 'closed',
+       gi.assignee_usernames[1],
+       case when exists (
+              select 1 from unnest(gi.labels) l
+              where lower(l) in ('blocker', 'priority::blocker', 'priority::critical')
+            ) then 'Blocker' end,
+       gi.labels,
+       gi.milestone_title,
+       null::text,
+       gi.created_at_gl,
+       case when gi.state = 'closed' then gi.closed_at end,
+       coalesce(
+         (select max(e.created_at_gl)
+          from gitlab_issue_state_events e
+          where e.org_id = gi.org_id and e.gitlab_project_id = gi.gitlab_project_id
+            and e.gitlab_issue_iid = gi.gitlab_issue_iid and e.state = 'reopened'
+            and e.created_at_gl <= gi.closed_at),
+         gi.created_at_gl)
+from gitlab_issues gi
+left join gitlab_projects p
+  on p.org_id = gi.org_id and p.gitlab_id = gi.gitlab_project_id;
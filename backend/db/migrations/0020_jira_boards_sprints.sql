@@ -0,0 +1,54 @@
+-- Jira Agile boards and sprints, and which sprints each issue was in over time.
+
+create table if not exists jira_boards (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  board_id bigint not null,
+  name text not null,
+  board_type text not null,         -- "scrum", "kanban" or "simple"
+  project_key text,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_boards_org_board_uidx
+  on jira_boards(org_id, board_id);
+
+create table if not exists jira_sprints (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  sprint_id bigint not null,
+  board_id bigint,                  -- origin board
+  name text not null,
+  state text not null,              -- "future", "active" or "closed"
+  goal text,
+  start_at timestamptz,
+  end_at timestamptz,
+  completed_at timestamptz,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_sprints_org_sprint_uidx
+  on jira_sprints(org_id, sprint_id);
+
+create index if not exists jira_sprints_org_completed_idx
+  on jira_sprints(org_id, completed_at);
+
+-- Derived from Sprint changes in the issue changelog. removed_at is null while the
+-- issue is still in the sprint.
+create table if not exists jira_issue_sprints (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  jira_key text not null,
+  sprint_id bigint not null,
+  added_at timestamptz,
+  removed_at timestamptz,
+  created_at timestamptz not null default now()
+);
+
+create index if not exists jira_issue_sprints_org_key_idx
+  on jira_issue_sprints(org_id, jira_key);
+
+create index if not exists jira_issue_sprints_org_sprint_idx
+  on jira_issue_sprints(org_id, sprint_id);
@@ -0,0 +1,42 @@
+-- Jira issue links (blocks, relates, duplicates, ...) and the parent of each issue
+-- (epic of a story, story of a subtask).
+
+-- Stored in the outward direction of the link type: for "Blocks", source_key blocks
+-- target_key.
+create table if not exists jira_issue_links (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  jira_link_id bigint not null,
+  link_type text not null,          -- link type name, e.g. "Blocks", "Relates", "Duplicate"
+  outward text,                     -- e.g. "blocks"
+  inward text,                      -- e.g. "is blocked by"
+  source_key text not null,
+  target_key text not null,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_issue_links_org_link_uidx
+  on jira_issue_links(org_id, jira_link_id);
+
+create index if not exists jira_issue_links_org_source_idx
+  on jira_issue_links(org_id, source_key);
+
+create index if not exists jira_issue_links_org_target_idx
+  on jira_issue_links(org_id, target_key);
+
+create table if not exists jira_issue_parents (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  jira_key text not null,
+  parent_key text not null,
+  parent_issue_type text,           -- "Epic" for stories, the parent's type for subtasks
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_issue_parents_org_key_uidx
+  on jira_issue_parents(org_id, jira_key);
+
+create index if not exists jira_issue_parents_org_parent_idx
+  on jira_issue_parents(org_id, parent_key);
@@ -0,0 +1,29 @@
+-- Time logged on Jira issues. Worklogs reference issues by Jira's numeric issue id,
+-- so jira_issues keeps that id too.
+
+alter table jira_issues add column if not exists jira_issue_id bigint;
+
+create index if not exists jira_issues_org_issue_id_idx
+  on jira_issues(org_id, jira_issue_id);
+
+create table if not exists jira_worklogs (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  worklog_id bigint not null,
+  jira_issue_id bigint not null,
+  author_account_id text,
+  started_at timestamptz not null,
+  time_spent_secs bigint not null,
+  updated_at_jira timestamptz,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_worklogs_org_worklog_uidx
+  on jira_worklogs(org_id, worklog_id);
+
+create index if not exists jira_worklogs_org_issue_idx
+  on jira_worklogs(org_id, jira_issue_id);
+
+create index if not exists jira_worklogs_org_started_idx
+  on jira_worklogs(org_id, started_at);
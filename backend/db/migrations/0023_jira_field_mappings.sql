@@ -0,0 +1,22 @@
+-- Which Jira custom field holds story points, sprint, team and epic link, per org.
+-- Rows are discovered from /rest/api/3/field on each issue sync unless set by hand
+-- (is_manual). "extra" rows name further fields copied into jira_issues.extra_fields.
+
+create table if not exists jira_field_mappings (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  role text not null,               -- "story_points", "sprint", "team", "epic_link" or "extra"
+  field_id text not null,           -- e.g. "customfield_10016"
+  field_name text,
+  is_manual boolean not null default false,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_field_mappings_org_role_uidx
+  on jira_field_mappings(org_id, role) where role <> 'extra';
+
+create unique index if not exists jira_field_mappings_org_extra_uidx
+  on jira_field_mappings(org_id, field_id) where role = 'extra';
+
+alter table jira_issues add column if not exists extra_fields jsonb;
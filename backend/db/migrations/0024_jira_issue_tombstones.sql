@@ -0,0 +1,17 @@
+-- Issues removed by reconciliation: deleted in Jira, or moved out of the synced
+-- projects. Moves between synced projects re-key the issue and leave a tombstone
+-- for the old key.
+
+create table if not exists jira_issue_tombstones (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  jira_key text not null,
+  jira_issue_id bigint,
+  project_key text not null,
+  reason text not null,             -- "deleted" or "moved"
+  moved_to_key text,
+  tombstoned_at timestamptz not null default now()
+);
+
+create unique index if not exists jira_issue_tombstones_org_key_uidx
+  on jira_issue_tombstones(org_id, jira_key);
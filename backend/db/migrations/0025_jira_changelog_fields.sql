@@ -0,0 +1,13 @@
+-- Changelog fields kept as jira_issue_transitions, per org. "changelog" rows are an
+-- allow-list like "extra": several per org, one per field. jira_issue_transitions.field
+-- now also holds "assignee", "priority", "story_points", "fix_version", "labels",
+-- "issuetype" and custom field ids.
+
+drop index if exists jira_field_mappings_org_role_uidx;
+drop index if exists jira_field_mappings_org_extra_uidx;
+
+create unique index if not exists jira_field_mappings_org_role_uidx
+  on jira_field_mappings(org_id, role) where role not in ('extra', 'changelog');
+
+create unique index if not exists jira_field_mappings_org_field_uidx
+  on jira_field_mappings(org_id, role, field_id) where role in ('extra', 'changelog');
@@ -0,0 +1,63 @@
+-- Confluence spaces, pages and page versions. Pages are synced incrementally by
+-- last-modified; bodies are kept in storage format and as plain text.
+
+create table if not exists confluence_spaces (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  space_id text not null,           -- Confluence's numeric id, as a string
+  space_key text not null,          -- e.g. "ENG"
+  name text not null,
+  space_type text,                  -- "global" or "personal"
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists confluence_spaces_org_key_uidx
+  on confluence_spaces(org_id, space_key);
+
+create table if not exists confluence_pages (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  page_id text not null,
+  space_key text not null,
+  title text not null,
+  status text not null,             -- "current", "archived", "trashed", ...
+  parent_page_id text,
+  author_account_id text,           -- creator of the page
+  last_editor_account_id text,      -- author of the current version
+  version_number integer not null,
+  body_storage text,                -- Confluence storage format (XHTML)
+  body_text text,                   -- body_storage converted to plain text
+  web_url text,
+  created_at_confluence timestamptz,
+  updated_at_confluence timestamptz,
+  created_at timestamptz not null default now(),
+  updated_at timestamptz not null default now()
+);
+
+create unique index if not exists confluence_pages_org_page_uidx
+  on confluence_pages(org_id, page_id);
+
+create index if not exists confluence_pages_org_space_idx
+  on confluence_pages(org_id, space_key);
+
+create index if not exists confluence_pages_org_updated_idx
+  on confluence_pages(org_id, updated_at_confluence);
+
+create table if not exists confluence_page_versions (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  page_id text not null,
+  version_number integer not null,
+  author_account_id text,
+  message text,
+  minor_edit boolean not null default false,
+  created_at_confluence timestamptz not null,
+  created_at timestamptz not null default now()
+);
+
+create unique index if not exists confluence_page_versions_org_page_version_uidx
+  on confluence_page_versions(org_id, page_id, version_number);
+
+create index if not exists confluence_page_versions_org_author_idx
+  on confluence_page_versions(org_id, author_account_id, created_at_confluence);
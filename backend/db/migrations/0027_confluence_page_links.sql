@@ -0,0 +1,21 @@
+-- Typed links from Confluence pages to Jira issues and GitLab projects and merge
+-- requests, extracted from page bodies. links_extracted_version records which page
+-- version the links were taken from, so changed pages are re-extracted.
+
+create table if not exists confluence_page_links (
+  id uuid primary key default gen_random_uuid(),
+  org_id uuid not null,
+  page_id text not null,
+  target_type text not null,        -- "jira_issue", "gitlab_project" or "gitlab_merge_request"
+  target_ref text not null,         -- "BEE-123", "group/project" or "group/project!42"
+  link_source text not null,        -- "jira_macro", "issue_key" or "url"
+  created_at timestamptz not null default now()
+);
+
+create unique index if not exists confluence_page_links_org_page_target_uidx
+  on confluence_page_links(org_id, page_id, target_type, target_ref);
+
+create index if not exists confluence_page_links_org_target_idx
+  on confluence_page_links(org_id, target_type, target_ref);
+
+alter table confluence_pages add column if not exists links_extracted_version integer;
@@ -0,0 +1,23 @@
+-- GitHub repositories, pull requests and Actions workflow runs are stored in the
+-- GitLab project, merge request and pipeline tables so that KPIs cover both. The
+-- provider column keeps their ids apart: "gitlab" or "github".
+
+alter table orgs add column if not exists github_base_url text;
+alter table orgs add column if not exists github_orgs text[] not null default '{}';
+
+alter table gitlab_projects add column if not exists provider text not null default 'gitlab';
+alter table gitlab_merge_requests add column if not exists provider text not null default 'gitlab';
+alter table gitlab_pipelines add column if not exists provider text not null default 'gitlab';
+
+drop index if exists gitlab_projects_org_gl_uidx;
+drop index if exists gitlab_mrs_org_proj_iid_uidx;
+drop index if exists gitlab_pipelines_org_gl_uidx;
+
+create unique index if not exists gitlab_projects_org_gl_uidx
+  on gitlab_projects(org_id, provider, gitlab_id);
+
+create unique index if not exists gitlab_mrs_org_proj_iid_uidx
+  on gitlab_merge_requests(org_id, provider, gitlab_project_id, gitlab_mr_iid);
+
+create unique index if not exists gitlab_pipelines_org_gl_uidx
+  on gitlab_pipelines(org_id, provider, gitlab_pipeline_id);
@@ -0,0 +1,5 @@
+-- Per-issue changelog cursor: the issue's Jira "updated" time when its changelog
+-- was last stored. Ingest skips the changelog of an issue that has not changed
+-- since, so re-runs and interrupted backfills do not refetch it.
+
+alter table jira_issues add column if not exists changelog_cursor timestamptz;
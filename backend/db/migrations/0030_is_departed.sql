@@ -0,0 +1,14 @@
+-- Whether an account belonged to a person who had left by `at`. KPI queries use this
+-- to drop activity of departed people. Jira accounts are matched by account id,
+-- other sources by username.
+create or replace function is_departed(p_org_id uuid, p_source text, p_account text, p_at timestamptz)
+returns boolean
+language sql stable
+as $$
+  select exists (
+    select 1 from departed_identities d
+    where d.org_id = p_org_id and d.source = p_source
+      and (case when p_source = 'jira' then d.external_id else d.username end) = p_account
+      and d.left_at <= p_at
+  )
+$$;
@@ -0,0 +1,21 @@
+-- Source accounts of erased people, so re-ingesting their activity cannot bring the
+-- PII back. Accounts are stored as a SHA-256 hash of the original external id or
+-- username, with the pseudonym their rows were rewritten to.
+create table if not exists erased_accounts (
+  org_id uuid not null,
+  source text not null,
+  account_hash text not null,
+  alias text not null,
+  created_at timestamptz not null default now(),
+  primary key (org_id, source, account_hash)
+);
+
+-- The pseudonym of an erased account, or null if the account was not erased.
+create or replace function erased_alias(p_org_id uuid, p_source text, p_account text)
+returns text
+language sql stable
+as $$
+  select alias from erased_accounts
+  where org_id = p_org_id and source = p_source
+    and account_hash = encode(sha256(convert_to(p_account, 'UTF8')), 'hex')
+$$;
@@ -0,0 +1,14 @@
+-- A changelog item is stored once, however often a webhook retry or a sync run
+-- delivers it. Existing duplicates are dropped before the key is added.
+
+delete from jira_issue_transitions t
+using jira_issue_transitions d
+where d.org_id = t.org_id and d.jira_key = t.jira_key and d.field = t.field
+  and d.transitioned_at = t.transitioned_at
+  and d.from_value is not distinct from t.from_value
+  and d.to_value is not distinct from t.to_value
+  and d.id < t.id;
+
+create unique index if not exists jira_transitions_natural_uidx
+  on jira_issue_transitions(org_id, jira_key, field, transitioned_at, from_value, to_value)
+  nulls not distinct;
@@ -0,0 +1,25 @@
+-- Commits, pipeline jobs, MR notes and approvals are keyed by GitLab ids that can
+-- collide with GitHub ones. Give them the same provider column as their parent
+-- project, merge request and pipeline rows (see 0028) and key on it.
+
+alter table gitlab_commits add column if not exists provider text not null default 'gitlab';
+alter table gitlab_pipeline_jobs add column if not exists provider text not null default 'gitlab';
+alter table gitlab_mr_notes add column if not exists provider text not null default 'gitlab';
+alter table gitlab_mr_approvals add column if not exists provider text not null default 'gitlab';
+
+drop index if exists gitlab_commits_org_proj_sha_uidx;
+drop index if exists gitlab_pipeline_jobs_org_job_uidx;
+drop index if exists gitlab_mr_notes_org_proj_note_uidx;
+drop index if exists gitlab_mr_approvals_org_proj_mr_user_uidx;
+
+create unique index if not exists gitlab_commits_org_proj_sha_uidx
+  on gitlab_commits(org_id, provider, gitlab_project_id, sha);
+
+create unique index if not exists gitlab_pipeline_jobs_org_job_uidx
+  on gitlab_pipeline_jobs(org_id, provider, gitlab_job_id);
+
+create unique index if not exists gitlab_mr_notes_org_proj_note_uidx
+  on gitlab_mr_notes(org_id, provider, gitlab_project_id, gitlab_note_id);
+
+create unique index if not exists gitlab_mr_approvals_org_proj_mr_user_uidx
+  on gitlab_mr_approvals(org_id, provider, gitlab_project_id, gitlab_mr_iid, approver_username);
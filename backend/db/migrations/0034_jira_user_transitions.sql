@@ -0,0 +1,12 @@
+-- Changes of user fields (assignee, reporter) were stored with display names. They
+-- now hold account ids so erasure can pseudonymize them: drop the old rows and reset
+-- the changelog cursor of their issues so the next sync stores them again.
+
+update jira_issues i set changelog_cursor = null
+where exists (
+  select 1 from jira_issue_transitions t
+  where t.org_id = i.org_id and t.jira_key = i.jira_key
+    and t.field in ('assignee', 'reporter')
+);
+
+delete from jira_issue_transitions where field in ('assignee', 'reporter');
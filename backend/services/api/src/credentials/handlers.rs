@@ -0,0 +1,109 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use ovia_common::error::OviaError;
+use ovia_db::credentials::models::{CREDENTIAL_SOURCES, WEBHOOK_SECRET_SOURCES};
+use ovia_db::credentials::repositories::CredentialRepository;
+use ovia_db::org::repositories::OrgRepository;
+
+use crate::credentials::probe::{probe, GITHUB_API_URL};
+use crate::credentials::requests::SetCredentialRequest;
+use crate::credentials::responses::{CredentialListResponse, CredentialResponse};
+use crate::error::ApiError;
+use crate::extractors::OrgId;
+use crate::AppState;
+
+fn validate_source(source: &str) -> Result<(), OviaError> {
+    if !CREDENTIAL_SOURCES.contains(&source) {
+        return Err(OviaError::Validation(format!(
+            "unsupported credential source: {source} (expected one of: {})",
+            CREDENTIAL_SOURCES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+pub async fn list_credentials(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+) -> Result<Json<CredentialListResponse>, ApiError> {
+    let data = state.credential_repo.list(org).await?;
+    let count = data.len();
+    Ok(Json(CredentialListResponse { data, count }))
+}
+
+pub async fn set_credential(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path(source): Path<String>,
+    Json(body): Json<SetCredentialRequest>,
+) -> Result<Json<CredentialResponse>, ApiError> {
+    validate_source(&source)?;
+    if body.secret.trim().is_empty() {
+        return Err(ApiError(OviaError::Validation(
+            "secret must not be empty".to_string(),
+        )));
+    }
+    let principal = body
+        .principal
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    // GitLab and GitHub authenticate with the token alone.
+    let needs_principal = !matches!(source.as_str(), "gitlab" | "github")
+        && !WEBHOOK_SECRET_SOURCES.contains(&source.as_str());
+    if principal.is_none() && needs_principal {
+        return Err(ApiError(OviaError::Validation(format!(
+            "principal (account email) is required for {source}"
+        ))));
+    }
+
+    let data = state
+        .credential_repo
+        .upsert(org, &source, principal, body.secret.trim())
+        .await?;
+    Ok(Json(CredentialResponse { data }))
+}
+
+pub async fn test_credential(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path(source): Path<String>,
+) -> Result<Json<CredentialResponse>, ApiError> {
+    validate_source(&source)?;
+    if WEBHOOK_SECRET_SOURCES.contains(&source.as_str()) {
+        return Err(ApiError(OviaError::Validation(format!(
+            "{source} is a webhook secret and cannot be tested"
+        ))));
+    }
+
+    let credential = state
+        .credential_repo
+        .reveal(org, &source)
+        .await?
+        .ok_or_else(|| OviaError::NotFound(format!("no {source} credential for this org")))?;
+
+    let org_config = state.org_repo.get(org).await?;
+    let base_url = org_config.and_then(|o| match source.as_str() {
+        "jira" => o.jira_base_url,
+        "gitlab" => o.gitlab_base_url,
+        "confluence" => o.confluence_base_url,
+        "github" => o.github_base_url,
+        _ => None,
+    });
+    // GitHub has a public API root; the others are per-instance
+    let base_url = base_url.or_else(|| (source == "github").then(|| GITHUB_API_URL.to_string()));
+    let base_url = base_url.ok_or_else(|| {
+        OviaError::Validation(format!("no {source} base url configured for this org"))
+    })?;
+
+    let result = probe(&base_url, &credential).await;
+    if let Err(e) = &result {
+        tracing::warn!(org_id = %org, source = %source, error = %e, "credential test failed");
+    }
+
+    let data = state
+        .credential_repo
+        .record_test(org, &source, result.is_ok(), result.err().as_deref())
+        .await?;
+    Ok(Json(CredentialResponse { data }))
+}
@@ -0,0 +1,19 @@
+pub mod handlers;
+pub mod probe;
+pub mod requests;
+pub mod responses;
+
+use axum::routing::{get, post, put};
+use axum::Router;
+
+use crate::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/team/credentials", get(handlers::list_credentials))
+        .route("/team/credentials/{source}", put(handlers::set_credential))
+        .route(
+            "/team/credentials/{source}/test",
+            post(handlers::test_credential),
+        )
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use ovia_db::credentials::models::SourceSecret;
+
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// API root used for GitHub when the org does not set one.
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Call a cheap authenticated endpoint of the source to check a credential.
+/// Error messages never include the secret.
+pub async fn probe(base_url: &str, credential: &SourceSecret) -> Result<(), String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let base = base_url.trim_end_matches('/');
+
+    let request = match credential.source.as_str() {
+        "jira" => client
+            .get(format!("{base}/rest/api/3/myself"))
+            .basic_auth(principal(credential)?, Some(&credential.secret)),
+        "confluence" => client
+            .get(format!("{base}/wiki/rest/api/user/current"))
+            .basic_auth(principal(credential)?, Some(&credential.secret)),
+        "gitlab" => client
+            .get(format!("{base}/api/v4/user"))
+            .header("PRIVATE-TOKEN", &credential.secret),
+        "github" => client
+            .get(format!("{base}/user"))
+            .bearer_auth(&credential.secret)
+            .header("User-Agent", "ovia"),
+        other => return Err(format!("unsupported credential source: {other}")),
+    };
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("request failed: {e}"))?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("HTTP {status}"))
+    }
+}
+
+fn principal(credential: &SourceSecret) -> Result<&str, String> {
+    credential
+        .principal
+        .as_deref()
+        .ok_or_else(|| format!("{} credential has no principal", credential.source))
+}
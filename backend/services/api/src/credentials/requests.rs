@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct SetCredentialRequest {
+    /// Account email for Jira/Confluence basic auth; unused for GitLab and GitHub.
+    pub principal: Option<String>,
+    pub secret: String,
+}
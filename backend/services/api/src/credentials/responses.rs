@@ -0,0 +1,13 @@
+use ovia_db::credentials::models::SourceCredential;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct CredentialResponse {
+    pub data: SourceCredential,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialListResponse {
+    pub data: Vec<SourceCredential>,
+    pub count: usize,
+}
@@ -17,6 +17,7 @@ impl IntoResponse for ApiError {
             OviaError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
             OviaError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             OviaError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
+            OviaError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
             other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
         };
 
@@ -0,0 +1,76 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use ovia_common::error::OviaError;
+use ovia_db::jira::models::JIRA_FIELD_ROLES;
+
+use crate::error::ApiError;
+use crate::extractors::OrgId;
+use crate::jira_fields::requests::SetFieldMappingRequest;
+use crate::jira_fields::responses::{FieldMappingListResponse, FieldMappingResponse};
+use crate::AppState;
+
+fn validate_role(role: &str) -> Result<(), OviaError> {
+    if !JIRA_FIELD_ROLES.contains(&role) {
+        return Err(OviaError::Validation(format!(
+            "unsupported field role: {role} (expected one of: {})",
+            JIRA_FIELD_ROLES.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Custom field mappings of the org, discovered and manual.
+pub async fn list_field_mappings(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+) -> Result<Json<FieldMappingListResponse>, ApiError> {
+    let data = state.jira_repo.list_field_mappings(org).await?;
+    let count = data.len();
+    Ok(Json(FieldMappingListResponse { data, count }))
+}
+
+/// Map a field by hand. Takes effect on the next issue sync.
+pub async fn set_field_mapping(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path(role): Path<String>,
+    Json(body): Json<SetFieldMappingRequest>,
+) -> Result<Json<FieldMappingResponse>, ApiError> {
+    validate_role(&role)?;
+    let field_id = body.field_id.trim();
+    if field_id.is_empty() {
+        return Err(ApiError(OviaError::Validation(
+            "field_id must not be empty".to_string(),
+        )));
+    }
+    let field_name = body
+        .field_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty());
+
+    let data = state
+        .jira_repo
+        .set_manual_field(org, &role, field_id, field_name)
+        .await?;
+    Ok(Json(FieldMappingResponse { data }))
+}
+
+pub async fn delete_field_mapping(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path((role, field_id)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    validate_role(&role)?;
+    if !state
+        .jira_repo
+        .delete_field_mapping(org, &role, &field_id)
+        .await?
+    {
+        return Err(ApiError(OviaError::NotFound(format!(
+            "no {role} mapping for field {field_id}"
+        ))));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,18 @@
+pub mod handlers;
+pub mod requests;
+pub mod responses;
+
+use axum::routing::{delete, get, put};
+use axum::Router;
+
+use crate::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/team/jira/fields", get(handlers::list_field_mappings))
+        .route("/team/jira/fields/{role}", put(handlers::set_field_mapping))
+        .route(
+            "/team/jira/fields/{role}/{field_id}",
+            delete(handlers::delete_field_mapping),
+        )
+}
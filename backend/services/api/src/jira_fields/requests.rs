@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct SetFieldMappingRequest {
+    /// Jira field id, e.g. `customfield_10016`.
+    pub field_id: String,
+    pub field_name: Option<String>,
+}
@@ -0,0 +1,13 @@
+use ovia_db::jira::models::JiraFieldMapping;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct FieldMappingResponse {
+    pub data: JiraFieldMapping,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldMappingListResponse {
+    pub data: Vec<JiraFieldMapping>,
+    pub count: usize,
+}
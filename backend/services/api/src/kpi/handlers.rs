@@ -1,12 +1,17 @@
 use axum::extract::{Query, State};
 use axum::Json;
+use chrono::{Datelike, NaiveDate, Utc};
 use ovia_common::error::OviaError;
 use ovia_db::kpi::models::KpiFilter;
 use ovia_db::kpi::repositories::KpiRepository;
 
 use crate::error::ApiError;
 use crate::extractors::OrgId;
-use crate::kpi::responses::{KpiHistoryResponse, KpiRisksResponse, KpiSnapshotResponse};
+use crate::kpi::requests::PeriodQuery;
+use crate::kpi::responses::{
+    DoraResponse, EffortResponse, JobReliabilityResponse, KpiHistoryResponse, KpiRisksResponse,
+    KpiSnapshotResponse, ReviewerLoadResponse,
+};
 use crate::AppState;
 
 pub async fn get_latest_kpi(
@@ -47,3 +52,110 @@ pub async fn list_kpi_risks(
     let count = data.len();
     Ok(Json(KpiRisksResponse { data, count }))
 }
+
+/// DORA metrics of the latest snapshot: the whole org first, then each project and team.
+pub async fn list_dora_metrics(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+) -> Result<Json<DoraResponse>, ApiError> {
+    let snapshot = state
+        .kpi_repo
+        .get_latest(org)
+        .await?
+        .ok_or_else(|| OviaError::NotFound("no KPI snapshot found for this org".to_string()))?;
+
+    let data = state.kpi_repo.list_dora_metrics(snapshot.id).await?;
+    let count = data.len();
+    Ok(Json(DoraResponse {
+        data,
+        count,
+        snapshot_id: snapshot.id,
+        period_start: snapshot.period_start,
+        period_end: snapshot.period_end,
+    }))
+}
+
+fn resolve_period(query: &PeriodQuery) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let period_end = query.period_end.unwrap_or_else(|| Utc::now().date_naive());
+    let period_start = query
+        .period_start
+        .unwrap_or_else(|| period_end.with_day(1).unwrap_or(period_end));
+    if period_start > period_end {
+        return Err(ApiError(OviaError::Validation(
+            "period_start must not be after period_end".to_string(),
+        )));
+    }
+    Ok((period_start, period_end))
+}
+
+/// Review comments and approvals per GitLab user, busiest reviewer first.
+pub async fn list_reviewer_load(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Query(query): Query<PeriodQuery>,
+) -> Result<Json<ReviewerLoadResponse>, ApiError> {
+    let (period_start, period_end) = resolve_period(&query)?;
+
+    let data = state
+        .gitlab_repo
+        .get_reviewer_load(org, period_start, period_end)
+        .await?;
+    let count = data.len();
+    Ok(Json(ReviewerLoadResponse {
+        data,
+        count,
+        period_start,
+        period_end,
+    }))
+}
+
+/// Success rate and flakiness of CI jobs per project, flaky jobs first.
+pub async fn list_job_reliability(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Query(query): Query<PeriodQuery>,
+) -> Result<Json<JobReliabilityResponse>, ApiError> {
+    let (period_start, period_end) = resolve_period(&query)?;
+
+    let data = state
+        .gitlab_repo
+        .get_job_reliability(org, period_start, period_end)
+        .await?;
+    let count = data.len();
+    Ok(Json(JobReliabilityResponse {
+        data,
+        count,
+        period_start,
+        period_end,
+    }))
+}
+
+/// Hours logged in Jira worklogs per issue type and per team, and logged hours
+/// against story points for issues resolved in the period.
+pub async fn get_effort(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Query(query): Query<PeriodQuery>,
+) -> Result<Json<EffortResponse>, ApiError> {
+    let (period_start, period_end) = resolve_period(&query)?;
+
+    let by_issue_type = state
+        .jira_repo
+        .get_effort_by_issue_type(org, period_start, period_end)
+        .await?;
+    let by_team = state
+        .jira_repo
+        .get_effort_by_team(org, period_start, period_end)
+        .await?;
+    let by_story_points = state
+        .jira_repo
+        .get_effort_vs_story_points(org, period_start, period_end)
+        .await?;
+    Ok(Json(EffortResponse {
+        by_issue_type,
+        by_team,
+        by_story_points,
+        period_start,
+        period_end,
+    }))
+}
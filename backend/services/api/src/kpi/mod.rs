@@ -1,4 +1,5 @@
 pub mod handlers;
+pub mod requests;
 pub mod responses;
 
 use axum::routing::get;
@@ -11,4 +12,8 @@ pub fn router() -> Router<AppState> {
         .route("/team/kpi", get(handlers::get_latest_kpi))
         .route("/team/kpi/history", get(handlers::list_kpi_history))
         .route("/team/kpi/risks", get(handlers::list_kpi_risks))
+        .route("/team/kpi/dora", get(handlers::list_dora_metrics))
+        .route("/team/kpi/reviewers", get(handlers::list_reviewer_load))
+        .route("/team/kpi/jobs", get(handlers::list_job_reliability))
+        .route("/team/kpi/effort", get(handlers::get_effort))
 }
@@ -0,0 +1,10 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PeriodQuery {
+    /// Defaults to the first day of `period_end`'s month, like the KPI snapshot period.
+    pub period_start: Option<NaiveDate>,
+    /// Defaults to today.
+    pub period_end: Option<NaiveDate>,
+}
@@ -1,5 +1,9 @@
-use ovia_db::kpi::models::{KpiSnapshot, RiskItem};
+use chrono::NaiveDate;
+use ovia_db::gitlab::models::{JobReliabilityRow, ReviewerLoadRow};
+use ovia_db::jira::models::{EffortRow, StoryPointEffortRow};
+use ovia_db::kpi::models::{DoraMetrics, KpiSnapshot, RiskItem};
 use serde::Serialize;
+use uuid::Uuid;
 
 #[derive(Debug, Serialize)]
 pub struct KpiSnapshotResponse {
@@ -17,3 +21,37 @@ pub struct KpiRisksResponse {
     pub data: Vec<RiskItem>,
     pub count: usize,
 }
+
+#[derive(Debug, Serialize)]
+pub struct ReviewerLoadResponse {
+    pub data: Vec<ReviewerLoadRow>,
+    pub count: usize,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoraResponse {
+    pub data: Vec<DoraMetrics>,
+    pub count: usize,
+    pub snapshot_id: Uuid,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JobReliabilityResponse {
+    pub data: Vec<JobReliabilityRow>,
+    pub count: usize,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EffortResponse {
+    pub by_issue_type: Vec<EffortRow>,
+    pub by_team: Vec<EffortRow>,
+    pub by_story_points: Vec<StoryPointEffortRow>,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
@@ -1,9 +1,12 @@
 mod ask;
+mod credentials;
 mod error;
 mod extractors;
 mod identity;
+mod jira_fields;
 mod kpi;
 mod people;
+mod webhooks;
 
 use axum::{
     http::{header, HeaderValue, Method, StatusCode},
@@ -14,8 +17,15 @@ use axum::{
 use ovia_common::types::ServiceInfo;
 use ovia_config::{init_tracing, AppConfig};
 use ovia_db::ask::pg_repository::PgAskRepository;
+use ovia_db::credentials::pg_repository::PgCredentialRepository;
+use ovia_db::credentials::vault::MasterKeyring;
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
 use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::jira::pg_repository::PgJiraRepository;
 use ovia_db::kpi::pg_repository::PgKpiRepository;
+use ovia_db::org::pg_repository::PgOrgRepository;
+use ovia_db::privacy::pg_repository::PgPrivacyRepository;
+use ovia_db::webhooks::pg_repository::PgWebhookRepository;
 use std::net::SocketAddr;
 use tower_http::cors::CorsLayer;
 
@@ -24,6 +34,12 @@ pub struct AppState {
     pub identity_repo: PgIdentityRepository,
     pub kpi_repo: PgKpiRepository,
     pub ask_repo: PgAskRepository,
+    pub privacy_repo: PgPrivacyRepository,
+    pub credential_repo: PgCredentialRepository,
+    pub org_repo: PgOrgRepository,
+    pub gitlab_repo: PgGitlabRepository,
+    pub jira_repo: PgJiraRepository,
+    pub webhook_repo: PgWebhookRepository,
 }
 
 async fn health() -> Json<serde_json::Value> {
@@ -74,6 +90,9 @@ fn build_router(state: AppState) -> Router {
         .merge(kpi::router())
         .merge(ask::router())
         .merge(people::router())
+        .merge(credentials::router())
+        .merge(jira_fields::router())
+        .merge(webhooks::router())
         .layer(cors)
         .with_state(state)
 }
@@ -89,10 +108,21 @@ async fn main() {
         .await
         .expect("failed to create database pool");
 
+    let keyring = MasterKeyring::from_env().expect("invalid credential vault master keys");
+    if keyring.is_none() {
+        tracing::warn!("CREDENTIALS_MASTER_KEYS not set, credential vault is read-only");
+    }
+
     let state = AppState {
         identity_repo: PgIdentityRepository::new(pool.clone()),
         kpi_repo: PgKpiRepository::new(pool.clone()),
-        ask_repo: PgAskRepository::new(pool),
+        ask_repo: PgAskRepository::new(pool.clone()),
+        privacy_repo: PgPrivacyRepository::new(pool.clone()),
+        credential_repo: PgCredentialRepository::new(pool.clone(), keyring),
+        org_repo: PgOrgRepository::new(pool.clone()),
+        gitlab_repo: PgGitlabRepository::new(pool.clone()),
+        jira_repo: PgJiraRepository::new(pool.clone()),
+        webhook_repo: PgWebhookRepository::new(pool),
     };
 
     let app = build_router(state);
@@ -114,6 +144,10 @@ mod tests {
     use tower::ServiceExt;
     use uuid::Uuid;
 
+    fn test_keyring() -> MasterKeyring {
+        MasterKeyring::parse(&format!("test:{}=", "A".repeat(43))).expect("test keyring")
+    }
+
     async fn test_state() -> Option<(AppState, PgPool)> {
         let url = std::env::var("TEST_DATABASE_URL").ok()?;
         let pool = ovia_db::create_pool(&url).await.expect("db should connect");
@@ -121,6 +155,12 @@ mod tests {
             identity_repo: PgIdentityRepository::new(pool.clone()),
             kpi_repo: PgKpiRepository::new(pool.clone()),
             ask_repo: PgAskRepository::new(pool.clone()),
+            privacy_repo: PgPrivacyRepository::new(pool.clone()),
+            credential_repo: PgCredentialRepository::new(pool.clone(), Some(test_keyring())),
+            org_repo: PgOrgRepository::new(pool.clone()),
+            gitlab_repo: PgGitlabRepository::new(pool.clone()),
+            jira_repo: PgJiraRepository::new(pool.clone()),
+            webhook_repo: PgWebhookRepository::new(pool.clone()),
         };
         Some((state, pool))
     }
@@ -817,6 +857,35 @@ mod tests {
                 .expect("alter kpi_snapshots for jira columns");
         }
 
+        // Lead time columns (migration 0014)
+        for stmt in &[
+            "alter table kpi_snapshots add column if not exists lead_time_p50_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists lead_time_p90_hours numeric(8,2)",
+        ] {
+            sqlx::query(stmt)
+                .execute(pool)
+                .await
+                .expect("alter kpi_snapshots for lead time columns");
+        }
+
+        // Review engagement columns (migration 0015)
+        for stmt in &[
+            "alter table kpi_snapshots add column if not exists time_to_first_review_p50_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists time_to_first_review_p90_hours numeric(8,2)",
+            "alter table kpi_snapshots add column if not exists review_rounds_avg numeric(6,2)",
+        ] {
+            sqlx::query(stmt)
+                .execute(pool)
+                .await
+                .expect("alter kpi_snapshots for review columns");
+        }
+
+        // MR size stats (migration 0016)
+        sqlx::query("alter table kpi_snapshots add column if not exists mr_size_stats jsonb")
+            .execute(pool)
+            .await
+            .expect("alter kpi_snapshots for mr size stats");
+
         sqlx::query(
             "create table if not exists risk_items (
               id uuid primary key default gen_random_uuid(),
@@ -835,6 +904,25 @@ mod tests {
         .execute(pool)
         .await
         .expect("create risk_items");
+
+        sqlx::query(
+            "create table if not exists dora_metrics (
+              id uuid primary key default gen_random_uuid(),
+              org_id uuid not null,
+              snapshot_id uuid not null references kpi_snapshots(id) on delete cascade,
+              scope_type text not null,
+              scope_key text,
+              deployment_count integer not null default 0,
+              deployments_per_day numeric(8,3),
+              lead_time_p50_hours numeric(8,2),
+              change_failure_rate numeric(5,4),
+              time_to_restore_p50_hours numeric(8,2),
+              created_at timestamptz not null default now()
+            )",
+        )
+        .execute(pool)
+        .await
+        .expect("create dora_metrics");
     }
 
     async fn insert_kpi_snapshot(pool: &PgPool, org_id: Uuid) -> Uuid {
@@ -976,6 +1064,12 @@ mod tests {
             "spillover_rate",
             "cycle_time_p50_hours",
             "cycle_time_p90_hours",
+            "lead_time_p50_hours",
+            "lead_time_p90_hours",
+            "time_to_first_review_p50_hours",
+            "time_to_first_review_p90_hours",
+            "review_rounds_avg",
+            "mr_size_stats",
             "computed_at",
             "created_at",
         ];
@@ -1038,6 +1132,280 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn kpi_reviewers_returns_load_per_reviewer() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        sqlx::query(
+            "insert into gitlab_merge_requests
+             (id, org_id, gitlab_project_id, gitlab_mr_iid, title, state, author_username,
+              created_at_gl, web_url)
+             values ($1, $2, 1, 1, 'Fix', 'opened', 'alice', '2026-02-02T09:00:00Z', 'https://gl/1')",
+        )
+        .bind(Uuid::new_v4())
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("insert mr");
+        for (note_id, author) in [(1_i64, "bob"), (2, "bob"), (3, "alice")] {
+            sqlx::query(
+                "insert into gitlab_mr_notes
+                 (org_id, gitlab_project_id, gitlab_mr_iid, gitlab_note_id, author_username,
+                  created_at_gl)
+                 values ($1, 1, 1, $2, $3, '2026-02-03T10:00:00Z')",
+            )
+            .bind(org)
+            .bind(note_id)
+            .bind(author)
+            .execute(&pool)
+            .await
+            .expect("insert note");
+        }
+
+        let resp = build_router(state.clone())
+            .oneshot(
+                Request::get("/team/kpi/reviewers?period_start=2026-02-01&period_end=2026-02-28")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["data"][0]["username"], "bob");
+        assert_eq!(body["data"][0]["comments"], 2);
+        assert_eq!(body["data"][0]["mrs_reviewed"], 1);
+
+        let resp = build_router(state)
+            .oneshot(
+                Request::get("/team/kpi/reviewers?period_start=2026-03-01&period_end=2026-02-01")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn kpi_dora_returns_metrics_of_latest_snapshot() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        ensure_kpi_tables(&pool).await;
+        let org = Uuid::new_v4();
+
+        let resp = build_router(state.clone())
+            .oneshot(
+                Request::get("/team/kpi/dora")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let snapshot_id = insert_kpi_snapshot(&pool, org).await;
+        for (scope_type, scope_key, count) in [("team", Some("core"), 3), ("org", None, 5)] {
+            sqlx::query(
+                "insert into dora_metrics
+                 (org_id, snapshot_id, scope_type, scope_key, deployment_count,
+                  deployments_per_day, change_failure_rate)
+                 values ($1, $2, $3, $4, $5, 0.357, 0.1667)",
+            )
+            .bind(org)
+            .bind(snapshot_id)
+            .bind(scope_type)
+            .bind(scope_key)
+            .bind(count)
+            .execute(&pool)
+            .await
+            .expect("insert dora metrics");
+        }
+
+        let resp = build_router(state)
+            .oneshot(
+                Request::get("/team/kpi/dora")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["count"], 2);
+        assert_eq!(body["snapshot_id"], snapshot_id.to_string());
+        assert_eq!(body["data"][0]["scope_type"], "org");
+        assert_eq!(body["data"][0]["deployment_count"], 5);
+        assert_eq!(body["data"][1]["scope_key"], "core");
+    }
+
+    #[tokio::test]
+    async fn kpi_jobs_returns_reliability_per_job() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        for (job_id, status, created) in [
+            (1_i64, "failed", "2026-02-05T10:00:00Z"),
+            (2, "success", "2026-02-05T10:10:00Z"),
+        ] {
+            sqlx::query(
+                "insert into gitlab_pipeline_jobs
+                 (org_id, gitlab_project_id, gitlab_pipeline_id, gitlab_job_id, name, stage,
+                  status, sha, created_at_gl, duration_secs)
+                 values ($1, 1, 10, $2, 'e2e', 'test', $3, 'abc123', $4::timestamptz, 90)",
+            )
+            .bind(org)
+            .bind(job_id)
+            .bind(status)
+            .bind(created)
+            .execute(&pool)
+            .await
+            .expect("insert job");
+        }
+
+        let resp = build_router(state)
+            .oneshot(
+                Request::get("/team/kpi/jobs?period_start=2026-02-01&period_end=2026-02-28")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["data"][0]["job_name"], "e2e");
+        assert_eq!(body["data"][0]["runs"], 2);
+        assert_eq!(body["data"][0]["flaky_count"], 1);
+    }
+
+    #[tokio::test]
+    async fn kpi_effort_groups_logged_hours() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        sqlx::query(
+            "insert into jira_issues
+             (org_id, jira_key, jira_issue_id, project_key, issue_type, summary, status,
+              story_points, team_name, resolved_at)
+             values ($1, 'EF-1', 501, 'EF', 'Story', 'Checkout', 'Done', 3, 'Payments',
+                     '2026-02-10T12:00:00Z')",
+        )
+        .bind(org)
+        .execute(&pool)
+        .await
+        .expect("insert issue");
+        for (worklog_id, secs) in [(1_i64, 7200_i64), (2, 3600)] {
+            sqlx::query(
+                "insert into jira_worklogs
+                 (org_id, worklog_id, jira_issue_id, author_account_id, started_at, time_spent_secs)
+                 values ($1, $2, 501, 'acc-ef', '2026-02-09T09:00:00Z', $3)",
+            )
+            .bind(org)
+            .bind(worklog_id)
+            .bind(secs)
+            .execute(&pool)
+            .await
+            .expect("insert worklog");
+        }
+
+        let resp = build_router(state)
+            .oneshot(
+                Request::get("/team/kpi/effort?period_start=2026-02-01&period_end=2026-02-28")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["by_issue_type"][0]["group"], "Story");
+        assert_eq!(body["by_issue_type"][0]["hours"], 3.0);
+        assert_eq!(body["by_team"][0]["group"], "Payments");
+        assert_eq!(body["by_story_points"][0]["story_points"], 3.0);
+        assert_eq!(body["by_story_points"][0]["hours_avg"], 3.0);
+    }
+
+    // ── Jira field mapping tests ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn jira_field_mappings_can_be_set_listed_and_deleted() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let put = |role: &str, body: serde_json::Value| {
+            Request::put(format!("/team/jira/fields/{role}"))
+                .header("X-Org-Id", org.to_string())
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap()
+        };
+
+        let resp = build_router(state.clone())
+            .oneshot(put(
+                "estimate",
+                serde_json::json!({ "field_id": "customfield_1" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = build_router(state.clone())
+            .oneshot(put(
+                "story_points",
+                serde_json::json!({ "field_id": "customfield_10106", "field_name": "Points" }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["data"]["is_manual"], true);
+
+        let resp = build_router(state.clone())
+            .oneshot(
+                Request::get("/team/jira/fields")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = read_body(resp).await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["data"][0]["field_id"], "customfield_10106");
+
+        for expected in [StatusCode::NO_CONTENT, StatusCode::NOT_FOUND] {
+            let resp = build_router(state.clone())
+                .oneshot(
+                    Request::delete("/team/jira/fields/story_points/customfield_10106")
+                        .header("X-Org-Id", org.to_string())
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), expected);
+        }
+    }
+
     // ── Ask endpoint tests ───────────────────────────────────────────
 
     async fn ensure_ask_tables(pool: &PgPool) {
@@ -1172,11 +1540,18 @@ mod tests {
 
     // ── People CRUD endpoint tests ──────────────────────────────────
 
-    async fn ensure_avatar_column(pool: &PgPool) {
-        sqlx::query("alter table people add column if not exists avatar_url text")
-            .execute(pool)
-            .await
-            .expect("add avatar_url column");
+    async fn ensure_people_columns(pool: &PgPool) {
+        for ddl in [
+            "alter table people add column if not exists avatar_url text",
+            "alter table people add column if not exists left_at timestamptz",
+            "alter table identities add column if not exists is_active boolean not null default true",
+            "alter table identities add column if not exists active_changed_at timestamptz",
+        ] {
+            sqlx::query(ddl)
+                .execute(pool)
+                .await
+                .expect("add people/identity column");
+        }
     }
 
     #[tokio::test]
@@ -1185,7 +1560,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let resp = app
@@ -1210,7 +1585,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let body = serde_json::json!({
@@ -1246,7 +1621,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let body = serde_json::json!({
@@ -1277,7 +1652,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let body = serde_json::json!({
@@ -1305,7 +1680,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1333,7 +1708,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let resp = app
@@ -1354,7 +1729,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
 
@@ -1385,7 +1760,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let body = serde_json::json!({
@@ -1410,7 +1785,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
 
@@ -1442,7 +1817,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let app = build_router(state);
         let org = Uuid::new_v4();
         let resp = app
@@ -1463,7 +1838,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
 
@@ -1497,7 +1872,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1528,7 +1903,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1567,7 +1942,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let identity_id = insert_identity(&pool, org).await;
 
@@ -1592,7 +1967,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
 
@@ -1617,7 +1992,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1649,7 +2024,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_a = insert_person(&pool, org).await;
         let person_b = insert_person(&pool, org).await;
@@ -1679,7 +2054,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org_a = Uuid::new_v4();
         let org_b = Uuid::new_v4();
         let person_id = insert_person(&pool, org_a).await;
@@ -1707,7 +2082,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1746,7 +2121,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let identity_id = insert_identity(&pool, org).await;
@@ -1779,7 +2154,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
 
@@ -1805,7 +2180,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let id1 = insert_identity(&pool, org).await;
@@ -1840,7 +2215,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
         let person_id = insert_person(&pool, org).await;
         let id1 = insert_identity(&pool, org).await;
@@ -1882,7 +2257,7 @@ mod tests {
             Some(s) => s,
             None => return,
         };
-        ensure_avatar_column(&pool).await;
+        ensure_people_columns(&pool).await;
         let org = Uuid::new_v4();
 
         let app = build_router(state);
@@ -1897,4 +2272,827 @@ mod tests {
             .unwrap();
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
+
+    // ── Departure / offboarding ─────────────────────────────────────
+
+    #[tokio::test]
+    async fn people_update_left_at_closes_links() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        ensure_people_columns(&pool).await;
+        let org = Uuid::new_v4();
+        let person_id = insert_person(&pool, org).await;
+        let identity_id = insert_identity(&pool, org).await;
+        let link_id = insert_link(&pool, org, person_id, identity_id).await;
+
+        let app = build_router(state);
+        let body = serde_json::json!({ "left_at": "2026-01-31T00:00:00Z" });
+        let resp = app
+            .oneshot(
+                Request::put(format!("/team/people/{person_id}"))
+                    .header("X-Org-Id", org.to_string())
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = read_body(resp).await;
+        assert!(resp_body["left_at"].is_string());
+        assert_eq!(resp_body["identity_count"], 0);
+
+        let action: String =
+            sqlx::query_scalar("select action from identity_events where link_id = $1")
+                .bind(link_id)
+                .fetch_one(&pool)
+                .await
+                .expect("offboard event should exist");
+        assert_eq!(action, "offboard");
+    }
+
+    #[tokio::test]
+    async fn people_offboarding_lists_fully_inactive_people() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        ensure_people_columns(&pool).await;
+        let org = Uuid::new_v4();
+        let gone = insert_person(&pool, org).await;
+        let gone_identity = insert_identity(&pool, org).await;
+        insert_link(&pool, org, gone, gone_identity).await;
+        let present = insert_person(&pool, org).await;
+        let present_identity = insert_identity(&pool, org).await;
+        insert_link(&pool, org, present, present_identity).await;
+
+        sqlx::query("update identities set is_active = false where id = $1")
+            .bind(gone_identity)
+            .execute(&pool)
+            .await
+            .expect("deactivate identity");
+
+        let app = build_router(state);
+        let resp = app
+            .oneshot(
+                Request::get("/team/people/offboarding")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["count"], 1);
+        assert_eq!(body["data"][0]["id"], gone.to_string());
+    }
+
+    // ── GDPR export / erasure ───────────────────────────────────────
+
+    #[tokio::test]
+    async fn people_export_returns_bundle() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        ensure_people_columns(&pool).await;
+        let org = Uuid::new_v4();
+        let person_id = insert_person(&pool, org).await;
+        let identity_id = insert_identity(&pool, org).await;
+        insert_link(&pool, org, person_id, identity_id).await;
+
+        let app = build_router(state);
+        let resp = app
+            .oneshot(
+                Request::get(format!("/team/people/{person_id}/export"))
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = read_body(resp).await;
+        assert_eq!(body["person"]["id"], person_id.to_string());
+        assert_eq!(body["identities"].as_array().unwrap().len(), 1);
+        assert_eq!(body["links"].as_array().unwrap().len(), 1);
+        assert!(body["erasure"].is_null());
+    }
+
+    #[tokio::test]
+    async fn people_export_not_found_returns_404() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let app = build_router(state);
+        let resp = app
+            .oneshot(
+                Request::get(format!("/team/people/{}/export", Uuid::new_v4()))
+                    .header("X-Org-Id", Uuid::new_v4().to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn people_erase_anonymizes_and_rejects_repeat() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        ensure_people_columns(&pool).await;
+        let org = Uuid::new_v4();
+        let person_id = insert_person(&pool, org).await;
+        let identity_id = insert_identity(&pool, org).await;
+        insert_link(&pool, org, person_id, identity_id).await;
+
+        let body = serde_json::json!({ "requested_by": "dpo" });
+        let app = build_router(state.clone());
+        let resp = app
+            .oneshot(
+                Request::post(format!("/team/people/{person_id}/erase"))
+                    .header("X-Org-Id", org.to_string())
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp_body = read_body(resp).await;
+        assert_eq!(resp_body["person_id"], person_id.to_string());
+        assert_eq!(resp_body["identities_erased"], 1);
+
+        let name: String = sqlx::query_scalar("select display_name from people where id = $1")
+            .bind(person_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(name, "Erased person");
+
+        let app = build_router(state);
+        let resp = app
+            .oneshot(
+                Request::post(format!("/team/people/{person_id}/erase"))
+                    .header("X-Org-Id", org.to_string())
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn people_erase_empty_requested_by_returns_400() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let app = build_router(state);
+        let body = serde_json::json!({ "requested_by": " " });
+        let resp = app
+            .oneshot(
+                Request::post(format!("/team/people/{}/erase", Uuid::new_v4()))
+                    .header("X-Org-Id", Uuid::new_v4().to_string())
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // ── Credentials ─────────────────────────────────────────────
+
+    async fn put_credential(
+        app: Router,
+        org: Uuid,
+        source: &str,
+        body: serde_json::Value,
+    ) -> axum::http::Response<Body> {
+        app.oneshot(
+            Request::put(format!("/team/credentials/{source}"))
+                .header("X-Org-Id", org.to_string())
+                .header("Content-Type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn org_with_gitlab(pool: &PgPool, base_url: &str) -> Uuid {
+        use ovia_db::org::models::Org;
+        use ovia_db::org::repositories::OrgRepository;
+
+        let org = Org {
+            gitlab_base_url: Some(base_url.to_string()),
+            ..Org::new(Uuid::new_v4(), "credential-test")
+        };
+        PgOrgRepository::new(pool.clone())
+            .upsert(&org)
+            .await
+            .expect("upsert org");
+        org.id
+    }
+
+    #[tokio::test]
+    async fn credentials_set_and_list_never_return_secret() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        let body =
+            serde_json::json!({ "principal": "bot@acme.com", "secret": "super-secret-token" });
+
+        let resp = put_credential(build_router(state.clone()), org, "jira", body).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let text = read_body_string(resp).await;
+        assert!(!text.contains("super-secret-token"));
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["data"]["source"], "jira");
+        assert_eq!(json["data"]["principal"], "bot@acme.com");
+
+        let resp = build_router(state)
+            .oneshot(
+                Request::get("/team/credentials")
+                    .header("X-Org-Id", org.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let text = read_body_string(resp).await;
+        assert!(!text.contains("super-secret-token"));
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(json["count"], 1);
+    }
+
+    #[tokio::test]
+    async fn credentials_set_validates_input() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+
+        let resp = put_credential(
+            build_router(state.clone()),
+            org,
+            "svn",
+            serde_json::json!({ "secret": "x" }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = put_credential(
+            build_router(state.clone()),
+            org,
+            "jira",
+            serde_json::json!({ "secret": "x" }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = put_credential(
+            build_router(state),
+            org,
+            "gitlab",
+            serde_json::json!({ "secret": "  " }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn credentials_set_github_token_without_principal() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let resp = put_credential(
+            build_router(state),
+            Uuid::new_v4(),
+            "github",
+            serde_json::json!({ "secret": "ghp_token" }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json: serde_json::Value = serde_json::from_str(&read_body_string(resp).await).unwrap();
+        assert_eq!(json["data"]["source"], "github");
+        assert!(json["data"]["principal"].is_null());
+    }
+
+    #[tokio::test]
+    async fn credentials_test_records_probe_result() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/user"))
+            .and(header("PRIVATE-TOKEN", "glpat-good"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "id": 1 })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/user"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        let org = org_with_gitlab(&pool, &server.uri()).await;
+
+        for (secret, expected_ok) in [("glpat-good", true), ("glpat-bad", false)] {
+            let resp = put_credential(
+                build_router(state.clone()),
+                org,
+                "gitlab",
+                serde_json::json!({ "secret": secret }),
+            )
+            .await;
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let resp = build_router(state.clone())
+                .oneshot(
+                    Request::post("/team/credentials/gitlab/test")
+                        .header("X-Org-Id", org.to_string())
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            let text = read_body_string(resp).await;
+            assert!(!text.contains(secret));
+            let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+            assert_eq!(json["data"]["last_test_ok"], expected_ok);
+            if !expected_ok {
+                assert!(json["data"]["last_test_error"]
+                    .as_str()
+                    .unwrap()
+                    .contains("401"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn credentials_test_missing_credential_returns_404() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let resp = build_router(state)
+            .oneshot(
+                Request::post("/team/credentials/gitlab/test")
+                    .header("X-Org-Id", Uuid::new_v4().to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    // ── GitLab webhooks ─────────────────────────────────────────────
+
+    async fn org_with_webhook_secret(state: &AppState, secret: &str) -> Uuid {
+        use ovia_db::credentials::repositories::CredentialRepository;
+
+        let org = Uuid::new_v4();
+        state
+            .credential_repo
+            .upsert(org, "gitlab_webhook", None, secret)
+            .await
+            .expect("store webhook secret");
+        org
+    }
+
+    fn mr_hook_payload(iid: i64, state: &str, action: &str) -> serde_json::Value {
+        serde_json::json!({
+            "object_kind": "merge_request",
+            "user": { "username": "alice" },
+            "project": {
+                "id": 4242,
+                "name": "api",
+                "path_with_namespace": "acme/api",
+                "web_url": "https://gitlab.example.com/acme/api"
+            },
+            "object_attributes": {
+                "iid": iid,
+                "title": "Add webhook receiver",
+                "state": state,
+                "action": action,
+                "created_at": "2024-03-01T10:00:00Z",
+                "updated_at": "2024-03-02T12:00:00Z",
+                "url": format!("https://gitlab.example.com/acme/api/-/merge_requests/{iid}")
+            },
+            "labels": [{ "title": "feature" }]
+        })
+    }
+
+    async fn post_gitlab_hook(
+        app: Router,
+        org: Uuid,
+        token: &str,
+        event_uuid: Option<&str>,
+        payload: &serde_json::Value,
+    ) -> axum::http::Response<Body> {
+        let mut req = Request::post(format!("/webhooks/gitlab/{org}"))
+            .header("content-type", "application/json")
+            .header("X-Gitlab-Token", token)
+            .header("X-Gitlab-Event", "Merge Request Hook");
+        if let Some(id) = event_uuid {
+            req = req.header("X-Gitlab-Event-UUID", id);
+        }
+        app.oneshot(req.body(Body::from(payload.to_string())).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn gitlab_webhook_rejects_invalid_token() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = org_with_webhook_secret(&state, "hook-secret").await;
+        let payload = mr_hook_payload(1, "opened", "open");
+
+        let resp =
+            post_gitlab_hook(build_router(state.clone()), org, "wrong", None, &payload).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        // an org without a configured secret accepts nothing
+        let resp = post_gitlab_hook(
+            build_router(state),
+            Uuid::new_v4(),
+            "hook-secret",
+            None,
+            &payload,
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn gitlab_webhook_upserts_merge_request_and_drops_replays() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = org_with_webhook_secret(&state, "hook-secret").await;
+
+        let opened = mr_hook_payload(7, "opened", "open");
+        let resp = post_gitlab_hook(
+            build_router(state.clone()),
+            org,
+            "hook-secret",
+            Some("evt-open"),
+            &opened,
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(read_body(resp).await["status"], "processed");
+
+        let merged = mr_hook_payload(7, "merged", "merge");
+        let resp = post_gitlab_hook(
+            build_router(state.clone()),
+            org,
+            "hook-secret",
+            Some("evt-merge"),
+            &merged,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "processed");
+
+        // a replay of the earlier open event must not reopen the MR
+        let resp = post_gitlab_hook(
+            build_router(state),
+            org,
+            "hook-secret",
+            Some("evt-open"),
+            &opened,
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(read_body(resp).await["status"], "duplicate");
+
+        let row = sqlx::query(
+            "select state, author_username, merged_at is not null as merged, labels
+             from gitlab_merge_requests
+             where org_id = $1 and gitlab_project_id = 4242 and gitlab_mr_iid = 7",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .expect("mr row");
+        assert_eq!(row.get::<String, _>("state"), "merged");
+        assert_eq!(
+            row.get::<Option<String>, _>("author_username").as_deref(),
+            Some("alice")
+        );
+        assert!(row.get::<bool, _>("merged"));
+        assert_eq!(row.get::<Vec<String>, _>("labels"), vec!["feature"]);
+    }
+
+    #[tokio::test]
+    async fn gitlab_webhook_upserts_pipeline_and_ignores_other_events() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = org_with_webhook_secret(&state, "hook-secret").await;
+        let pipeline_id = 900_000 + (Uuid::new_v4().as_u128() % 100_000) as i64;
+        let payload = serde_json::json!({
+            "object_kind": "pipeline",
+            "project": {
+                "id": 4242,
+                "name": "api",
+                "path_with_namespace": "acme/api",
+                "web_url": "https://gitlab.example.com/acme/api"
+            },
+            "object_attributes": {
+                "id": pipeline_id,
+                "ref": "main",
+                "status": "success",
+                "created_at": "2024-03-01 10:00:00 UTC",
+                "finished_at": "2024-03-01 10:05:00 UTC",
+                "duration": 300
+            }
+        });
+
+        let resp = post_gitlab_hook(
+            build_router(state.clone()),
+            org,
+            "hook-secret",
+            None,
+            &payload,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "processed");
+        // without an event UUID, replays are detected from the payload itself
+        let resp = post_gitlab_hook(
+            build_router(state.clone()),
+            org,
+            "hook-secret",
+            None,
+            &payload,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "duplicate");
+
+        let row = sqlx::query(
+            "select status, duration_secs, web_url from gitlab_pipelines
+             where org_id = $1 and gitlab_pipeline_id = $2",
+        )
+        .bind(org)
+        .bind(pipeline_id)
+        .fetch_one(&pool)
+        .await
+        .expect("pipeline row");
+        assert_eq!(row.get::<String, _>("status"), "success");
+        assert_eq!(row.get::<Option<i32>, _>("duration_secs"), Some(300));
+        assert!(row
+            .get::<String, _>("web_url")
+            .ends_with(&format!("/-/pipelines/{pipeline_id}")));
+
+        let note = serde_json::json!({ "object_kind": "note" });
+        let resp = post_gitlab_hook(build_router(state), org, "hook-secret", None, &note).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(read_body(resp).await["status"], "ignored");
+    }
+
+    // ── Jira webhooks ───────────────────────────────────────────────
+
+    fn jira_hook_payload(event: &str, key: &str, status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "webhookEvent": event,
+            "timestamp": 1_717_236_000_000_i64,
+            "user": { "accountId": "acc-actor", "displayName": "Actor" },
+            "issue": {
+                "key": key,
+                "fields": {
+                    "summary": "Webhook issue",
+                    "status": { "name": status },
+                    "issuetype": { "name": "Story" },
+                    "assignee": { "accountId": format!("acc-{key}"), "displayName": "Assignee" },
+                    "labels": [],
+                    "updated": "2024-06-01T10:00:00.000+0000"
+                }
+            },
+            "changelog": {
+                "id": "10100",
+                "items": [
+                    { "field": "status", "fromString": "To Do", "toString": status }
+                ]
+            }
+        })
+    }
+
+    fn hub_signature(secret: &str, body: &[u8]) -> String {
+        use hmac::{Hmac, Mac};
+        let mut mac = Hmac::<sha2::Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    async fn post_jira_hook(
+        app: Router,
+        org: Uuid,
+        auth: (&str, String),
+        delivery_id: &str,
+        body: String,
+    ) -> axum::http::Response<Body> {
+        app.oneshot(
+            Request::post(format!("/webhooks/jira/{org}"))
+                .header("content-type", "application/json")
+                .header(auth.0, auth.1)
+                .header("X-Atlassian-Webhook-Identifier", delivery_id)
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn jira_status(pool: &PgPool, org: Uuid, key: &str) -> Option<String> {
+        sqlx::query_scalar("select status from jira_issues where org_id = $1 and jira_key = $2")
+            .bind(org)
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+            .expect("query issue")
+    }
+
+    #[tokio::test]
+    async fn jira_webhook_rejects_bad_signature() {
+        let (state, _pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        use ovia_db::credentials::repositories::CredentialRepository;
+        let org = Uuid::new_v4();
+        state
+            .credential_repo
+            .upsert(org, "jira_webhook", None, "jira-secret")
+            .await
+            .unwrap();
+
+        let body = jira_hook_payload("jira:issue_updated", "WH-0", "Done").to_string();
+        let signature = hub_signature("wrong-secret", body.as_bytes());
+        let resp = post_jira_hook(
+            build_router(state),
+            org,
+            ("X-Hub-Signature", signature),
+            "d-0",
+            body,
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn jira_webhook_applies_issue_update_and_drops_replays() {
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        use ovia_db::credentials::repositories::CredentialRepository;
+        let org = Uuid::new_v4();
+        state
+            .credential_repo
+            .upsert(org, "jira_webhook", None, "jira-secret")
+            .await
+            .unwrap();
+
+        let body = jira_hook_payload("jira:issue_updated", "WH-1", "In Progress").to_string();
+        let signature = hub_signature("jira-secret", body.as_bytes());
+        for expected in ["processed", "duplicate"] {
+            let resp = post_jira_hook(
+                build_router(state.clone()),
+                org,
+                ("X-Hub-Signature", signature.clone()),
+                "d-1",
+                body.clone(),
+            )
+            .await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert_eq!(read_body(resp).await["status"], expected);
+        }
+        // A retry under a new delivery id is applied again without duplicating
+        let resp = post_jira_hook(
+            build_router(state.clone()),
+            org,
+            ("X-Hub-Signature", signature),
+            "d-1-retry",
+            body,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "processed");
+
+        assert_eq!(
+            jira_status(&pool, org, "WH-1").await.as_deref(),
+            Some("In Progress")
+        );
+        let transitions: i64 = sqlx::query_scalar(
+            "select count(*) from jira_issue_transitions
+             where org_id = $1 and jira_key = 'WH-1' and field = 'status'
+               and to_value = 'In Progress' and author_account_id = 'acc-actor'",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(transitions, 1);
+        let identities: i64 = sqlx::query_scalar(
+            "select count(*) from identities
+             where org_id = $1 and source = 'jira' and external_id = 'acc-WH-1'",
+        )
+        .bind(org)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(identities, 1);
+    }
+
+    #[tokio::test]
+    async fn jira_webhook_deletes_issue_with_jwt_auth() {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        use base64::Engine;
+        use hmac::{Hmac, Mac};
+        use ovia_db::credentials::repositories::CredentialRepository;
+
+        let (state, pool) = match test_state().await {
+            Some(s) => s,
+            None => return,
+        };
+        let org = Uuid::new_v4();
+        state
+            .credential_repo
+            .upsert(org, "jira_webhook", None, "jira-secret")
+            .await
+            .unwrap();
+        let jwt = {
+            let exp = chrono::Utc::now().timestamp() + 300;
+            let input = format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+                URL_SAFE_NO_PAD.encode(format!(r#"{{"iss":"jira","exp":{exp}}}"#))
+            );
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"jira-secret").unwrap();
+            mac.update(input.as_bytes());
+            format!(
+                "JWT {input}.{}",
+                URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+            )
+        };
+
+        let created = jira_hook_payload("jira:issue_created", "WH-2", "To Do").to_string();
+        let resp = post_jira_hook(
+            build_router(state.clone()),
+            org,
+            ("Authorization", jwt.clone()),
+            "d-2",
+            created,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "processed");
+        assert!(jira_status(&pool, org, "WH-2").await.is_some());
+
+        let deleted = jira_hook_payload("jira:issue_deleted", "WH-2", "To Do").to_string();
+        let resp = post_jira_hook(
+            build_router(state),
+            org,
+            ("Authorization", jwt),
+            "d-3",
+            deleted,
+        )
+        .await;
+        assert_eq!(read_body(resp).await["status"], "processed");
+        assert!(jira_status(&pool, org, "WH-2").await.is_none());
+        let reason: Option<String> = sqlx::query_scalar(
+            "select reason from jira_issue_tombstones where org_id = $1 and jira_key = 'WH-2'",
+        )
+        .bind(org)
+        .fetch_optional(&pool)
+        .await
+        .unwrap();
+        assert_eq!(reason.as_deref(), Some("deleted"));
+    }
 }
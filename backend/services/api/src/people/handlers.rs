@@ -5,14 +5,16 @@ use axum::Json;
 use ovia_common::error::OviaError;
 use ovia_db::identity::models::{Person, PersonFilter};
 use ovia_db::identity::repositories::{IdentityRepository, PersonRepository};
+use ovia_db::privacy::models::{PersonErasure, PersonExport};
+use ovia_db::privacy::repositories::PrivacyRepository;
 use sqlx::Row;
 use uuid::Uuid;
 
 use crate::error::ApiError;
 use crate::extractors::OrgId;
 use crate::people::requests::{
-    ActivityFilter, CreatePersonRequest, LinkIdentityRequest, OrphanIdentityFilter,
-    UpdatePersonRequest,
+    ActivityFilter, CreatePersonRequest, ErasePersonRequest, LinkIdentityRequest,
+    OrphanIdentityFilter, UpdatePersonRequest,
 };
 use crate::people::responses::{
     ActivityItem, ActivityListResponse, LinkResponse, LinkedIdentitiesResponse,
@@ -79,6 +81,7 @@ fn to_person_response(person: Person, identity_count: i64) -> PersonResponse {
         team: person.team,
         role: person.role,
         status: person.status,
+        left_at: person.left_at,
         identity_count,
         created_at: person.created_at,
         updated_at: person.updated_at,
@@ -146,6 +149,7 @@ pub async fn create_person(
         team: body.team,
         role: body.role,
         status: "active".to_string(),
+        left_at: None,
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
@@ -202,16 +206,44 @@ pub async fn update_person(
             existing.role
         },
         status: body.status.unwrap_or(existing.status),
+        left_at: existing.left_at,
         created_at: existing.created_at,
         updated_at: chrono::Utc::now(),
     };
 
-    let updated = PersonRepository::update(&state.identity_repo, person).await?;
+    let updated = match body.left_at {
+        Some(left_at) if existing.left_at != Some(left_at) => {
+            PersonRepository::mark_left(&state.identity_repo, person, left_at, "manual").await?
+        }
+        _ => PersonRepository::update(&state.identity_repo, person).await?,
+    };
     let pool = state.identity_repo.pool();
     let count = identity_count_for_person(pool, org, id).await;
     Ok(Json(to_person_response(updated, count)))
 }
 
+pub async fn list_offboarding_candidates(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+) -> Result<Json<ListPeopleResponse>, ApiError> {
+    let people = PersonRepository::list_offboarding_candidates(&state.identity_repo, org).await?;
+    let pool = state.identity_repo.pool();
+    let person_ids: Vec<Uuid> = people.iter().map(|p| p.id).collect();
+    let counts = identity_counts_for_people(pool, org, &person_ids).await;
+
+    let data: Vec<PersonResponse> = people
+        .into_iter()
+        .map(|p| {
+            let count = counts.get(&p.id).copied().unwrap_or(0);
+            to_person_response(p, count)
+        })
+        .collect();
+
+    let count = data.len();
+    let total = count as i64;
+    Ok(Json(ListPeopleResponse { data, count, total }))
+}
+
 pub async fn delete_person(
     State(state): State<AppState>,
     OrgId(org): OrgId,
@@ -221,6 +253,38 @@ pub async fn delete_person(
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn export_person(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PersonExport>, ApiError> {
+    let export = state
+        .privacy_repo
+        .export_person(org, id)
+        .await?
+        .ok_or_else(|| ApiError(OviaError::NotFound(format!("person not found: {id}"))))?;
+    Ok(Json(export))
+}
+
+pub async fn erase_person(
+    State(state): State<AppState>,
+    OrgId(org): OrgId,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ErasePersonRequest>,
+) -> Result<Json<PersonErasure>, ApiError> {
+    if body.requested_by.trim().is_empty() {
+        return Err(ApiError(OviaError::Validation(
+            "requested_by must not be empty".to_string(),
+        )));
+    }
+
+    let erasure = state
+        .privacy_repo
+        .erase_person(org, id, &body.requested_by)
+        .await?;
+    Ok(Json(erasure))
+}
+
 pub async fn link_identity(
     State(state): State<AppState>,
     OrgId(org): OrgId,
@@ -481,15 +545,21 @@ pub async fn person_activity(
 
     let mut items: Vec<ActivityItem> = Vec::new();
 
-    // 1. GitLab MRs — match via identity username
-    if (source == "all" || source == "gitlab")
-        && (activity_type == "all" || activity_type == "merge_request")
-        && !identity_ids.is_empty()
-    {
+    // 1. GitLab MRs and GitHub pull requests — match via the identity username
+    // of the same provider
+    for provider in ["gitlab", "github"] {
+        if !(source == "all" || source == provider)
+            || !(activity_type == "all" || activity_type == "merge_request")
+            || identity_ids.is_empty()
+        {
+            continue;
+        }
+
         let usernames: Vec<Option<String>> = sqlx::query_scalar(
-            "select username from identities where id = any($1) and source = 'gitlab'",
+            "select username from identities where id = any($1) and source = $2",
         )
         .bind(&identity_ids)
+        .bind(provider)
         .fetch_all(pool)
         .await
         .unwrap_or_default();
@@ -501,10 +571,12 @@ pub async fn person_activity(
                 sqlx::query(
                     "select id, title, web_url, state, created_at_gl, merged_at, author_username \
                      from gitlab_merge_requests \
-                     where org_id = $1 and author_username = any($2) and created_at_gl >= $3 \
+                     where org_id = $1 and provider = $2 and author_username = any($3) \
+                       and created_at_gl >= $4 \
                      order by created_at_gl desc",
                 )
                 .bind(org)
+                .bind(provider)
                 .bind(&usernames)
                 .bind(cutoff_dt)
                 .fetch_all(pool)
@@ -513,10 +585,11 @@ pub async fn person_activity(
                 sqlx::query(
                     "select id, title, web_url, state, created_at_gl, merged_at, author_username \
                      from gitlab_merge_requests \
-                     where org_id = $1 and author_username = any($2) \
+                     where org_id = $1 and provider = $2 and author_username = any($3) \
                      order by created_at_gl desc",
                 )
                 .bind(org)
+                .bind(provider)
                 .bind(&usernames)
                 .fetch_all(pool)
                 .await
@@ -534,7 +607,7 @@ pub async fn person_activity(
 
                 items.push(ActivityItem {
                     id: id.to_string(),
-                    source: "gitlab".to_string(),
+                    source: provider.to_string(),
                     activity_type: "merge_request".to_string(),
                     title,
                     url: web_url,
@@ -548,7 +621,78 @@ pub async fn person_activity(
         }
     }
 
-    // 2. Identity events (link/unlink/match)
+    // 2. Confluence page edits — match via identity account id, with the issues
+    // and projects each page links to
+    if (source == "all" || source == "confluence")
+        && (activity_type == "all" || activity_type == "page_edit")
+        && !identity_ids.is_empty()
+    {
+        let account_ids: Vec<Option<String>> = sqlx::query_scalar(
+            "select external_id from identities where id = any($1) and source = 'confluence'",
+        )
+        .bind(&identity_ids)
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default();
+
+        let account_ids: Vec<String> = account_ids.into_iter().flatten().collect();
+
+        if !account_ids.is_empty() {
+            let edits = sqlx::query(
+                "select v.id, v.page_id, v.version_number, v.message, v.minor_edit, \
+                        v.created_at_confluence, p.title, p.space_key, p.web_url, \
+                        coalesce((select jsonb_agg(jsonb_build_object( \
+                                      'target_type', l.target_type, 'target_ref', l.target_ref) \
+                                    order by l.target_type, l.target_ref) \
+                                  from confluence_page_links l \
+                                  where l.org_id = p.org_id and l.page_id = p.page_id), \
+                                 '[]'::jsonb) as links \
+                 from confluence_page_versions v \
+                 join confluence_pages p on p.org_id = v.org_id and p.page_id = v.page_id \
+                 where v.org_id = $1 and v.author_account_id = any($2) \
+                   and ($3::timestamptz is null or v.created_at_confluence >= $3) \
+                 order by v.created_at_confluence desc",
+            )
+            .bind(org)
+            .bind(&account_ids)
+            .bind(cutoff)
+            .fetch_all(pool)
+            .await
+            .unwrap_or_default();
+
+            for r in edits {
+                let id: Uuid = r.get("id");
+                let page_id: String = r.get("page_id");
+                let version: i32 = r.get("version_number");
+                let message: Option<String> = r.get("message");
+                let minor_edit: bool = r.get("minor_edit");
+                let created_at: chrono::DateTime<chrono::Utc> = r.get("created_at_confluence");
+                let title: String = r.get("title");
+                let space_key: String = r.get("space_key");
+                let web_url: Option<String> = r.get("web_url");
+                let links: serde_json::Value = r.get("links");
+
+                items.push(ActivityItem {
+                    id: id.to_string(),
+                    source: "confluence".to_string(),
+                    activity_type: "page_edit".to_string(),
+                    title,
+                    url: web_url,
+                    timestamp: created_at,
+                    metadata: serde_json::json!({
+                        "page_id": page_id,
+                        "space_key": space_key,
+                        "version": version,
+                        "message": message,
+                        "minor_edit": minor_edit,
+                        "links": links,
+                    }),
+                });
+            }
+        }
+    }
+
+    // 3. Identity events (link/unlink/match)
     if (source == "all" || source == "identity")
         && (activity_type == "all" || activity_type == "identity_event")
     {
@@ -599,7 +743,7 @@ pub async fn person_activity(
     }
 
     // Sort all items by timestamp desc
-    items.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    items.sort_by_key(|b| std::cmp::Reverse(b.timestamp));
 
     let total = items.len() as i64;
 
@@ -11,9 +11,15 @@ pub fn router() -> Router<AppState> {
     Router::new()
         .route("/team/people", get(handlers::list_people))
         .route("/team/people", post(handlers::create_person))
+        .route(
+            "/team/people/offboarding",
+            get(handlers::list_offboarding_candidates),
+        )
         .route("/team/people/{id}", get(handlers::get_person))
         .route("/team/people/{id}", put(handlers::update_person))
         .route("/team/people/{id}", delete(handlers::delete_person))
+        .route("/team/people/{id}/export", get(handlers::export_person))
+        .route("/team/people/{id}/erase", post(handlers::erase_person))
         .route(
             "/team/people/{id}/identities",
             get(handlers::list_person_identities).post(handlers::link_identity),
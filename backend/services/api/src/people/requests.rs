@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
@@ -18,6 +19,13 @@ pub struct UpdatePersonRequest {
     pub team: Option<String>,
     pub role: Option<String>,
     pub status: Option<String>,
+    /// Setting a departure date closes all active identity links of the person.
+    pub left_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ErasePersonRequest {
+    pub requested_by: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -36,9 +44,9 @@ pub struct OrphanIdentityFilter {
 #[derive(Debug, Deserialize)]
 pub struct ActivityFilter {
     pub period: Option<String>,       // 7d, 30d, 90d
-    pub source: Option<String>,       // gitlab, jira, identity, all
+    pub source: Option<String>,       // gitlab, github, jira, confluence, identity, all
     #[serde(rename = "type")]
-    pub activity_type: Option<String>, // merge_request, issue, identity_event, all
+    pub activity_type: Option<String>, // merge_request, issue, page_edit, identity_event, all
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
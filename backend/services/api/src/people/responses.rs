@@ -11,6 +11,7 @@ pub struct PersonResponse {
     pub team: Option<String>,
     pub role: Option<String>,
     pub status: String,
+    pub left_at: Option<DateTime<Utc>>,
     pub identity_count: i64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
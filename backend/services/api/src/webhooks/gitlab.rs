@@ -0,0 +1,290 @@
+//! GitLab webhook payloads and their mapping onto the polled GitLab tables.
+//!
+//! Only merge request and pipeline events are applied. Push events are ignored: the
+//! polled sync stores commits with their MR and both author and commit dates, which a
+//! push payload does not carry.
+//!
+//! Only the fields we store are deserialized. Timestamps arrive either as RFC 3339
+//! or in GitLab's older `2016-08-12 15:23:28 UTC` form, depending on event and version.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ovia_db::gitlab::models::{GitlabMergeRequest, GitlabPipeline, GitlabProject};
+use serde::Deserialize;
+use uuid::Uuid;
+
+pub const TOKEN_HEADER: &str = "x-gitlab-token";
+pub const EVENT_HEADER: &str = "x-gitlab-event";
+pub const EVENT_UUID_HEADER: &str = "x-gitlab-event-uuid";
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookProject {
+    pub id: i64,
+    pub name: String,
+    pub path_with_namespace: String,
+    pub web_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookUser {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookLabel {
+    pub title: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeRequestAttributes {
+    pub iid: i64,
+    pub title: String,
+    pub state: String,
+    pub action: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub merged_at: Option<String>,
+    pub target_branch: Option<String>,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeRequestEvent {
+    pub user: Option<WebhookUser>,
+    pub project: WebhookProject,
+    pub object_attributes: MergeRequestAttributes,
+    #[serde(default)]
+    pub labels: Vec<WebhookLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PipelineAttributes {
+    pub id: i64,
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    pub created_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub duration: Option<i64>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PipelineEvent {
+    pub project: WebhookProject,
+    pub object_attributes: PipelineAttributes,
+}
+
+/// A webhook event we know how to handle, keyed by the payload's `object_kind`.
+#[derive(Debug)]
+pub enum GitlabEvent {
+    MergeRequest(MergeRequestEvent),
+    Pipeline(PipelineEvent),
+}
+
+impl GitlabEvent {
+    /// Parse a payload. Returns `Ok(None)` for event kinds we do not consume.
+    pub fn parse(body: &[u8]) -> Result<Option<Self>, String> {
+        let value: serde_json::Value =
+            serde_json::from_slice(body).map_err(|e| format!("invalid JSON payload: {e}"))?;
+        let kind = value
+            .get("object_kind")
+            .and_then(|k| k.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let event = match kind.as_str() {
+            "merge_request" => serde_json::from_value(value).map(Self::MergeRequest),
+            "pipeline" => serde_json::from_value(value).map(Self::Pipeline),
+            _ => return Ok(None),
+        };
+        event
+            .map(Some)
+            .map_err(|e| format!("invalid {kind} payload: {e}"))
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MergeRequest(_) => "merge_request",
+            Self::Pipeline(_) => "pipeline",
+        }
+    }
+
+    pub fn project(&self) -> &WebhookProject {
+        match self {
+            Self::MergeRequest(e) => &e.project,
+            Self::Pipeline(e) => &e.project,
+        }
+    }
+
+    /// Stable key for replay detection when GitLab does not send `X-Gitlab-Event-UUID`.
+    /// Two deliveries describing the same state change produce the same key.
+    pub fn fallback_delivery_id(&self) -> String {
+        match self {
+            Self::MergeRequest(e) => {
+                let a = &e.object_attributes;
+                format!(
+                    "merge_request:{}:{}:{}:{}",
+                    e.project.id,
+                    a.iid,
+                    a.action.as_deref().unwrap_or(""),
+                    a.updated_at.as_deref().unwrap_or("")
+                )
+            }
+            Self::Pipeline(e) => {
+                let a = &e.object_attributes;
+                format!(
+                    "pipeline:{}:{}:{}",
+                    a.id,
+                    a.status,
+                    a.finished_at.as_deref().unwrap_or("")
+                )
+            }
+        }
+    }
+}
+
+pub fn project_to_db(org_id: Uuid, p: &WebhookProject) -> GitlabProject {
+    let now = Utc::now();
+    GitlabProject {
+        id: Uuid::new_v4(),
+        org_id,
+        provider: "gitlab".to_string(),
+        gitlab_id: p.id,
+        name: p.name.clone(),
+        path_with_namespace: p.path_with_namespace.clone(),
+        web_url: p.web_url.clone(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// The acting user is only known to be the author on the `open` action; otherwise the
+/// author is left unset so the stored value is kept. Merge time falls back to the event's
+/// `updated_at` on the `merge` action, since older GitLab versions omit `merged_at`.
+pub fn merge_request_to_db(org_id: Uuid, e: &MergeRequestEvent) -> GitlabMergeRequest {
+    let now = Utc::now();
+    let a = &e.object_attributes;
+    let action = a.action.as_deref();
+    let author_username = match action {
+        Some("open") => e.user.as_ref().map(|u| u.username.clone()),
+        _ => None,
+    };
+    let merged_at = parse_timestamp(a.merged_at.as_deref()).or_else(|| match action {
+        Some("merge") => parse_timestamp(a.updated_at.as_deref()),
+        _ => None,
+    });
+
+    GitlabMergeRequest {
+        id: Uuid::new_v4(),
+        org_id,
+        provider: "gitlab".to_string(),
+        gitlab_project_id: e.project.id,
+        gitlab_mr_iid: a.iid,
+        title: a.title.clone(),
+        state: a.state.clone(),
+        author_username,
+        labels: e.labels.iter().map(|l| l.title.clone()).collect(),
+        created_at_gl: parse_timestamp(a.created_at.as_deref()),
+        merged_at,
+        target_branch: a.target_branch.clone(),
+        web_url: a.url.clone(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+pub fn pipeline_to_db(org_id: Uuid, e: &PipelineEvent) -> GitlabPipeline {
+    let now = Utc::now();
+    let a = &e.object_attributes;
+    GitlabPipeline {
+        id: Uuid::new_v4(),
+        org_id,
+        provider: "gitlab".to_string(),
+        gitlab_project_id: e.project.id,
+        gitlab_pipeline_id: a.id,
+        status: a.status.clone(),
+        ref_name: a.ref_name.clone(),
+        created_at_gl: parse_timestamp(a.created_at.as_deref()),
+        finished_at_gl: parse_timestamp(a.finished_at.as_deref()),
+        duration_secs: a.duration.and_then(|d| i32::try_from(d).ok()),
+        web_url: a
+            .url
+            .clone()
+            .unwrap_or_else(|| format!("{}/-/pipelines/{}", e.project.web_url, a.id)),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn parse_timestamp(raw: Option<&str>) -> Option<DateTime<Utc>> {
+    let raw = raw?.trim();
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    if let Ok(ts) = DateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(ts.with_timezone(&Utc));
+    }
+    raw.strip_suffix(" UTC")
+        .and_then(|s| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
+        .map(|ts| ts.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_accepts_gitlab_formats() {
+        let expected = "2016-08-12T15:23:28Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            parse_timestamp(Some("2016-08-12T15:23:28Z")),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp(Some("2016-08-12 15:23:28 UTC")),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_timestamp(Some("2016-08-12 17:23:28 +0200")),
+            Some(expected)
+        );
+        assert_eq!(parse_timestamp(Some("yesterday")), None);
+        assert_eq!(parse_timestamp(None), None);
+    }
+
+    #[test]
+    fn parse_ignores_unconsumed_event_kinds() {
+        let body = br#"{"object_kind":"note","project":{"id":1}}"#;
+        assert!(GitlabEvent::parse(body).unwrap().is_none());
+        let body = br#"{"object_kind":"push","ref":"refs/heads/main","commits":[]}"#;
+        assert!(GitlabEvent::parse(body).unwrap().is_none());
+        assert!(GitlabEvent::parse(b"not json").is_err());
+    }
+
+    #[test]
+    fn merge_request_keeps_author_unset_unless_opened() {
+        let body = br#"{
+            "object_kind": "merge_request",
+            "user": {"username": "reviewer"},
+            "project": {"id": 7, "name": "api", "path_with_namespace": "acme/api",
+                        "web_url": "https://gitlab.example.com/acme/api"},
+            "object_attributes": {"iid": 3, "title": "Fix", "state": "merged",
+                                  "action": "merge", "created_at": "2024-01-01T10:00:00Z",
+                                  "updated_at": "2024-01-02 09:00:00 UTC",
+                                  "url": "https://gitlab.example.com/acme/api/-/merge_requests/3"},
+            "labels": [{"title": "bug"}]
+        }"#;
+        let event = match GitlabEvent::parse(body).unwrap() {
+            Some(GitlabEvent::MergeRequest(e)) => e,
+            other => panic!("unexpected event: {other:?}"),
+        };
+
+        let mr = merge_request_to_db(Uuid::new_v4(), &event);
+        assert_eq!(mr.author_username, None);
+        assert_eq!(mr.labels, vec!["bug"]);
+        assert_eq!(
+            mr.merged_at,
+            Some("2024-01-02T09:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum::Json;
+use chrono::Utc;
+use ovia_common::error::OviaError;
+use ovia_db::credentials::repositories::CredentialRepository;
+use ovia_db::identity::repositories::IdentityRepository;
+use ovia_ingest::jira::fields::JiraFieldMapping;
+use ovia_ingest::jira::issue_sync::{
+    api_issue_to_db, changelog_to_transitions, collect_user_refs, issue_links_to_db,
+    issue_parents_to_db, user_ref_to_identity,
+};
+use ovia_ingest::jira::models::{JiraIssue, JiraWebhookEvent};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::webhooks::gitlab::{self, GitlabEvent, EVENT_HEADER, EVENT_UUID_HEADER, TOKEN_HEADER};
+use crate::webhooks::jira::{
+    self, DELIVERY_HEADER, ISSUE_CREATED, ISSUE_DELETED, ISSUE_UPDATED, SIGNATURE_HEADER,
+};
+use crate::webhooks::responses::WebhookResponse;
+use crate::AppState;
+
+const GITLAB_SOURCE: &str = "gitlab";
+const GITLAB_SECRET_SOURCE: &str = "gitlab_webhook";
+const JIRA_SOURCE: &str = "jira";
+const JIRA_SECRET_SOURCE: &str = "jira_webhook";
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Compare two secrets without short-circuiting on the first differing byte.
+fn secrets_match(expected: &str, given: &str) -> bool {
+    let (a, b) = (expected.as_bytes(), given.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Receive a GitLab Merge Request or Pipeline hook for one org.
+///
+/// The `X-Gitlab-Token` header must match the org's `gitlab_webhook` vault secret.
+/// Replayed deliveries are acknowledged without being applied again. Other event
+/// kinds, pushes included, are acknowledged and ignored so GitLab does not disable
+/// the hook.
+pub async fn gitlab_webhook(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let secret = state
+        .credential_repo
+        .reveal(org_id, GITLAB_SECRET_SOURCE)
+        .await?;
+    let authorized = match (&secret, header(&headers, TOKEN_HEADER)) {
+        (Some(secret), Some(token)) => secrets_match(&secret.secret, token),
+        _ => false,
+    };
+    if !authorized {
+        tracing::warn!(org_id = %org_id, "rejected gitlab webhook with invalid token");
+        return Err(ApiError(OviaError::Unauthorized(
+            "invalid webhook token".to_string(),
+        )));
+    }
+
+    let event_name = header(&headers, EVENT_HEADER)
+        .unwrap_or("unknown")
+        .to_string();
+    let event = match GitlabEvent::parse(&body).map_err(OviaError::Validation)? {
+        Some(event) => event,
+        None => {
+            return Ok(Json(WebhookResponse {
+                status: "ignored",
+                event: event_name,
+            }))
+        }
+    };
+
+    let delivery_id = header(&headers, EVENT_UUID_HEADER)
+        .map(str::to_string)
+        .unwrap_or_else(|| event.fallback_delivery_id());
+    let is_new = state
+        .webhook_repo
+        .record_delivery(org_id, GITLAB_SOURCE, &delivery_id, event.kind())
+        .await?;
+    if !is_new {
+        tracing::debug!(org_id = %org_id, delivery_id = %delivery_id, "duplicate gitlab webhook");
+        return Ok(Json(WebhookResponse {
+            status: "duplicate",
+            event: event_name,
+        }));
+    }
+
+    if let Err(e) = apply_event(&state, org_id, &event).await {
+        // let GitLab's retry of this delivery go through
+        state
+            .webhook_repo
+            .forget_delivery(org_id, GITLAB_SOURCE, &delivery_id)
+            .await?;
+        return Err(ApiError(e));
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        kind = event.kind(),
+        delivery_id = %delivery_id,
+        "gitlab webhook processed"
+    );
+    Ok(Json(WebhookResponse {
+        status: "processed",
+        event: event_name,
+    }))
+}
+
+async fn apply_event(state: &AppState, org_id: Uuid, event: &GitlabEvent) -> Result<(), OviaError> {
+    state
+        .gitlab_repo
+        .upsert_project(&gitlab::project_to_db(org_id, event.project()))
+        .await?;
+
+    match event {
+        GitlabEvent::MergeRequest(e) => {
+            state
+                .gitlab_repo
+                .upsert_merge_request(&gitlab::merge_request_to_db(org_id, e))
+                .await
+        }
+        GitlabEvent::Pipeline(e) => {
+            state
+                .gitlab_repo
+                .upsert_pipeline(&gitlab::pipeline_to_db(org_id, e))
+                .await
+        }
+    }
+}
+
+fn jira_authorized(secret: &str, headers: &HeaderMap, body: &[u8]) -> bool {
+    if let Some(signature) = header(headers, SIGNATURE_HEADER) {
+        return jira::verify_signature(secret, signature, body);
+    }
+    header(headers, "authorization")
+        .and_then(|v| v.strip_prefix("JWT "))
+        .is_some_and(|token| jira::verify_jwt(secret, token.trim(), Utc::now().timestamp()))
+}
+
+/// Receive a Jira `jira:issue_created`, `jira:issue_updated` or `jira:issue_deleted` hook.
+///
+/// The request must be signed with the org's `jira_webhook` vault secret, either as an
+/// `X-Hub-Signature` body HMAC or an HS256 `Authorization: JWT` token. Issues and the
+/// embedded changelog go through the same mapping as the `jira_issues` connector.
+pub async fn jira_webhook(
+    State(state): State<AppState>,
+    Path(org_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    let secret = state
+        .credential_repo
+        .reveal(org_id, JIRA_SECRET_SOURCE)
+        .await?;
+    let authorized = secret.is_some_and(|s| jira_authorized(&s.secret, &headers, &body));
+    if !authorized {
+        tracing::warn!(org_id = %org_id, "rejected jira webhook with invalid signature");
+        return Err(ApiError(OviaError::Unauthorized(
+            "invalid webhook signature".to_string(),
+        )));
+    }
+
+    let event: JiraWebhookEvent = serde_json::from_slice(&body)
+        .map_err(|e| OviaError::Validation(format!("invalid jira webhook payload: {e}")))?;
+    let event_name = event.webhook_event.clone();
+    if ![ISSUE_CREATED, ISSUE_UPDATED, ISSUE_DELETED].contains(&event_name.as_str()) {
+        return Ok(Json(WebhookResponse {
+            status: "ignored",
+            event: event_name,
+        }));
+    }
+    let Some(issue) = &event.issue else {
+        return Err(ApiError(OviaError::Validation(format!(
+            "{event_name} payload has no issue"
+        ))));
+    };
+
+    let delivery_id = header(&headers, DELIVERY_HEADER)
+        .map(str::to_string)
+        .unwrap_or_else(|| jira::fallback_delivery_id(&event));
+    let is_new = state
+        .webhook_repo
+        .record_delivery(org_id, JIRA_SOURCE, &delivery_id, &event_name)
+        .await?;
+    if !is_new {
+        tracing::debug!(org_id = %org_id, delivery_id = %delivery_id, "duplicate jira webhook");
+        return Ok(Json(WebhookResponse {
+            status: "duplicate",
+            event: event_name,
+        }));
+    }
+
+    if let Err(e) = apply_jira_event(&state, org_id, &event, issue).await {
+        state
+            .webhook_repo
+            .forget_delivery(org_id, JIRA_SOURCE, &delivery_id)
+            .await?;
+        return Err(ApiError(e));
+    }
+
+    tracing::info!(
+        org_id = %org_id,
+        event = %event_name,
+        key = %issue.key,
+        "jira webhook processed"
+    );
+    Ok(Json(WebhookResponse {
+        status: "processed",
+        event: event_name,
+    }))
+}
+
+async fn apply_jira_event(
+    state: &AppState,
+    org_id: Uuid,
+    event: &JiraWebhookEvent,
+    issue: &JiraIssue,
+) -> Result<(), OviaError> {
+    if event.webhook_event == ISSUE_DELETED {
+        state
+            .jira_repo
+            .tombstone_issue(org_id, &issue.key, "deleted", None)
+            .await?;
+        return Ok(());
+    }
+
+    let mapping = JiraFieldMapping::from_rows(&state.jira_repo.list_field_mappings(org_id).await?);
+    state
+        .jira_repo
+        .upsert_issue(&api_issue_to_db(org_id, issue, &mapping))
+        .await?;
+    state
+        .jira_repo
+        .replace_issue_relations(
+            org_id,
+            &issue.key,
+            &issue_links_to_db(org_id, issue),
+            &issue_parents_to_db(org_id, issue, &mapping),
+        )
+        .await?;
+    for t in changelog_to_transitions(org_id, &issue.key, &event.changelog_entries(), &mapping) {
+        state.jira_repo.insert_transition(&t).await?;
+    }
+    for user in collect_user_refs(std::slice::from_ref(issue)).values() {
+        state
+            .identity_repo
+            .upsert_by_external_id(user_ref_to_identity(org_id, user))
+            .await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! Jira webhook authentication and payload parsing.
+//!
+//! Jira signs webhooks in one of two ways, both keyed by the org's `jira_webhook` secret:
+//! admin-registered webhooks with a secret send an `X-Hub-Signature: sha256=<hex>` HMAC of
+//! the body, and app-registered webhooks send `Authorization: JWT <token>` signed with HS256.
+//! Payloads map through the same conversions as the polling `jira_issues` connector.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use ovia_ingest::jira::models::JiraWebhookEvent;
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "x-hub-signature";
+pub const DELIVERY_HEADER: &str = "x-atlassian-webhook-identifier";
+
+pub const ISSUE_CREATED: &str = "jira:issue_created";
+pub const ISSUE_UPDATED: &str = "jira:issue_updated";
+pub const ISSUE_DELETED: &str = "jira:issue_deleted";
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac(secret: &str) -> HmacSha256 {
+    HmacSha256::new_from_slice(secret.as_bytes()).expect("hmac accepts keys of any length")
+}
+
+/// Check an `X-Hub-Signature` value of the form `sha256=<hex digest of the body>`.
+pub fn verify_signature(secret: &str, signature: &str, body: &[u8]) -> bool {
+    let Some(expected) = signature
+        .strip_prefix("sha256=")
+        .and_then(|h| hex::decode(h).ok())
+    else {
+        return false;
+    };
+    let mut mac = mac(secret);
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Check an HS256 JWT from an `Authorization: JWT <token>` header. The token must carry
+/// an `exp` claim that is still in the future at `now` (epoch seconds).
+pub fn verify_jwt(secret: &str, token: &str, now: i64) -> bool {
+    let parts: Vec<&str> = token.split('.').collect();
+    let [header, claims, signature] = parts[..] else {
+        return false;
+    };
+
+    let decode_json = |part: &str| {
+        URL_SAFE_NO_PAD
+            .decode(part)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+    };
+    if !decode_json(header).is_some_and(|h| h["alg"] == "HS256") {
+        return false;
+    }
+    let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+        return false;
+    };
+
+    let mut mac = mac(secret);
+    mac.update(&token.as_bytes()[..header.len() + 1 + claims.len()]);
+    if mac.verify_slice(&signature).is_err() {
+        return false;
+    }
+
+    decode_json(claims)
+        .and_then(|c| c["exp"].as_i64())
+        .is_some_and(|exp| exp > now)
+}
+
+/// Stable key for replay detection when Jira does not send a webhook identifier.
+pub fn fallback_delivery_id(event: &JiraWebhookEvent) -> String {
+    let key = event.issue.as_ref().map(|i| i.key.as_str()).unwrap_or("");
+    let change = event
+        .changelog
+        .as_ref()
+        .and_then(|c| c.id.clone())
+        .or_else(|| event.timestamp.map(|t| t.to_string()))
+        .unwrap_or_default();
+    format!("{}:{key}:{change}", event.webhook_event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign_jwt(secret: &str, header: serde_json::Value, claims: serde_json::Value) -> String {
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let mut mac = mac(secret);
+        mac.update(signing_input.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        format!("{signing_input}.{signature}")
+    }
+
+    #[test]
+    fn signature_must_match_body_and_secret() {
+        let mut m = mac("s3cret");
+        m.update(b"{\"a\":1}");
+        let signature = format!("sha256={}", hex::encode(m.finalize().into_bytes()));
+
+        assert!(verify_signature("s3cret", &signature, b"{\"a\":1}"));
+        assert!(!verify_signature("s3cret", &signature, b"{\"a\":2}"));
+        assert!(!verify_signature("other", &signature, b"{\"a\":1}"));
+        assert!(!verify_signature("s3cret", "sha1=abcd", b"{\"a\":1}"));
+    }
+
+    #[test]
+    fn jwt_checks_signature_algorithm_and_expiry() {
+        let header = serde_json::json!({ "alg": "HS256", "typ": "JWT" });
+        let valid = sign_jwt(
+            "s3cret",
+            header.clone(),
+            serde_json::json!({ "exp": 2_000 }),
+        );
+
+        assert!(verify_jwt("s3cret", &valid, 1_000));
+        assert!(!verify_jwt("s3cret", &valid, 3_000), "expired");
+        assert!(!verify_jwt("other", &valid, 1_000), "wrong secret");
+
+        let no_exp = sign_jwt("s3cret", header, serde_json::json!({ "iss": "jira" }));
+        assert!(!verify_jwt("s3cret", &no_exp, 1_000));
+
+        let none_alg = sign_jwt(
+            "s3cret",
+            serde_json::json!({ "alg": "none" }),
+            serde_json::json!({ "exp": 2_000 }),
+        );
+        assert!(!verify_jwt("s3cret", &none_alg, 1_000));
+        assert!(!verify_jwt("s3cret", "not-a-jwt", 1_000));
+    }
+}
@@ -0,0 +1,15 @@
+pub mod gitlab;
+pub mod handlers;
+pub mod jira;
+pub mod responses;
+
+use axum::routing::post;
+use axum::Router;
+
+use crate::AppState;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/webhooks/gitlab/{org_id}", post(handlers::gitlab_webhook))
+        .route("/webhooks/jira/{org_id}", post(handlers::jira_webhook))
+}
@@ -0,0 +1,8 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    /// `processed`, `duplicate` or `ignored`.
+    pub status: &'static str,
+    pub event: String,
+}
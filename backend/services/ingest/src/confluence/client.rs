@@ -1,23 +1,60 @@
-use std::time::Duration;
+use chrono::{DateTime, Utc};
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::org::models::Org;
+use serde::de::DeserializeOwned;
 
-use reqwest::{Client, StatusCode};
+use crate::http::{Auth, HttpClient, HttpClientConfig, HttpClientError};
 
-use super::models::{ConfluencePageResponse, ConfluenceUser};
+use super::models::{
+    ConfluenceContent, ConfluenceCursorPage, ConfluencePageResponse, ConfluencePageVersion,
+    ConfluenceSpace, ConfluenceUser,
+};
 
 #[derive(Debug, Clone)]
 pub struct ConfluenceClientConfig {
     pub base_url: String,
     pub email: String,
     pub api_token: String,
+    /// Space keys this org syncs. Empty means all spaces.
+    pub spaces: Vec<String>,
     pub max_retries: u32,
     pub timeout_secs: u64,
 }
 
 impl ConfluenceClientConfig {
-    pub fn from_env() -> Option<Self> {
-        let base_url = std::env::var("CONFLUENCE_BASE_URL").ok()?;
-        let email = std::env::var("CONFLUENCE_EMAIL").ok()?;
-        let api_token = std::env::var("CONFLUENCE_API_TOKEN").ok()?;
+    /// Load Confluence config for an org. The org's base URL and spaces take
+    /// precedence over `CONFLUENCE_BASE_URL` / `CONFLUENCE_SPACES`, and the vault
+    /// credential over `CONFLUENCE_EMAIL` / `CONFLUENCE_API_TOKEN`; tuning comes from
+    /// the environment. The env only stands in for org settings with `env_fallback`.
+    /// Returns `None` if not configured.
+    pub fn for_org(
+        org: &Org,
+        credential: Option<&SourceSecret>,
+        env_fallback: bool,
+    ) -> Option<Self> {
+        let env = |name: &str| env_fallback.then(|| std::env::var(name).ok()).flatten();
+        let base_url = org
+            .confluence_base_url
+            .clone()
+            .or_else(|| env("CONFLUENCE_BASE_URL"))?;
+        let email = credential
+            .and_then(|c| c.principal.clone())
+            .or_else(|| env("CONFLUENCE_EMAIL"))?;
+        let api_token = credential
+            .map(|c| c.secret.clone())
+            .or_else(|| env("CONFLUENCE_API_TOKEN"))?;
+        let spaces = if org.confluence_spaces.is_empty() {
+            env("CONFLUENCE_SPACES")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            org.confluence_spaces.clone()
+        };
         let max_retries = std::env::var("CONFLUENCE_MAX_RETRIES")
             .ok()
             .and_then(|v| v.parse().ok())
@@ -31,6 +68,7 @@ impl ConfluenceClientConfig {
             base_url,
             email,
             api_token,
+            spaces,
             max_retries,
             timeout_secs,
         })
@@ -39,28 +77,22 @@ impl ConfluenceClientConfig {
 
 #[derive(Clone)]
 pub struct ConfluenceClient {
-    client: Client,
+    http: HttpClient,
     config: ConfluenceClientConfig,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum ConfluenceClientError {
-    #[error("HTTP {status}: {body}")]
-    HttpError { status: StatusCode, body: String },
-
-    #[error("request failed: {0}")]
-    RequestError(#[from] reqwest::Error),
-
-    #[error("max retries exceeded after {attempts} attempts: {last_error}")]
-    MaxRetriesExceeded { attempts: u32, last_error: String },
-}
+pub type ConfluenceClientError = HttpClientError;
 
 impl ConfluenceClient {
     pub fn new(config: ConfluenceClientConfig) -> Result<Self, reqwest::Error> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()?;
-        Ok(Self { client, config })
+        let http = HttpClient::new(
+            HttpClientConfig::from_env(config.max_retries, config.timeout_secs),
+            Auth::Basic {
+                username: config.email.clone(),
+                password: config.api_token.clone(),
+            },
+        )?;
+        Ok(Self { http, config })
     }
 
     /// For testing: create a client pointing at a specific base URL (e.g., wiremock).
@@ -95,83 +127,105 @@ impl ConfluenceClient {
         Ok(all_users)
     }
 
-    async fn request_with_retry(
+    pub fn config(&self) -> &ConfluenceClientConfig {
+        &self.config
+    }
+
+    /// Fetch spaces by key, or all spaces when `keys` is empty.
+    pub async fn fetch_spaces(
         &self,
-        url: &str,
-    ) -> Result<ConfluencePageResponse, ConfluenceClientError> {
-        let mut last_error = String::new();
-
-        for attempt in 0..=self.config.max_retries {
-            if attempt > 0 {
-                let backoff_secs = std::cmp::min(1u64 << attempt, 30);
-                tracing::warn!(attempt, backoff_secs, "retrying after backoff");
-                tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
-            }
+        keys: &[String],
+    ) -> Result<Vec<ConfluenceSpace>, ConfluenceClientError> {
+        let mut url = format!("{}/wiki/api/v2/spaces?limit=250", self.config.base_url);
+        if !keys.is_empty() {
+            url.push_str(&format!("&keys={}", urlencoding::encode(&keys.join(","))));
+        }
+        self.fetch_cursor_pages(url).await
+    }
 
-            let response = match self
-                .client
-                .get(url)
-                .basic_auth(&self.config.email, Some(&self.config.api_token))
-                .send()
-                .await
-            {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = e.to_string();
-                    if e.is_timeout() || e.is_connect() {
-                        continue;
-                    }
-                    return Err(ConfluenceClientError::RequestError(e));
-                }
-            };
+    /// Pages last modified at or after `modified_since` (all pages when `None`),
+    /// in the given spaces (all spaces when empty), oldest change first. Bodies come
+    /// in storage format.
+    pub async fn search_pages(
+        &self,
+        spaces: &[String],
+        modified_since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ConfluenceContent>, ConfluenceClientError> {
+        let mut cql = "type = page".to_string();
+        if !spaces.is_empty() {
+            let keys: Vec<String> = spaces.iter().map(|k| format!("\"{k}\"")).collect();
+            cql.push_str(&format!(" and space in ({})", keys.join(",")));
+        }
+        if let Some(since) = modified_since {
+            cql.push_str(&format!(
+                " and lastmodified >= \"{}\"",
+                since.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        cql.push_str(" order by lastmodified asc");
+
+        let url = format!(
+            "{}/wiki/rest/api/content/search?cql={}&limit=50&expand=body.storage,version,space,history,ancestors",
+            self.config.base_url,
+            urlencoding::encode(&cql),
+        );
+        self.fetch_cursor_pages(url).await
+    }
 
-            let status = response.status();
+    /// All versions of a page.
+    pub async fn fetch_page_versions(
+        &self,
+        page_id: &str,
+    ) -> Result<Vec<ConfluencePageVersion>, ConfluenceClientError> {
+        let url = format!(
+            "{}/wiki/api/v2/pages/{}/versions?limit=50",
+            self.config.base_url,
+            urlencoding::encode(page_id)
+        );
+        self.fetch_cursor_pages(url).await
+    }
 
-            if status.is_success() {
-                return response
-                    .json::<ConfluencePageResponse>()
-                    .await
-                    .map_err(ConfluenceClientError::RequestError);
-            }
+    /// Follow `_links.next` until the last page. v2 links include the `/wiki`
+    /// context path, v1 links are relative to it.
+    async fn fetch_cursor_pages<T: DeserializeOwned>(
+        &self,
+        first_url: String,
+    ) -> Result<Vec<T>, ConfluenceClientError> {
+        let mut url = first_url;
+        let mut all_items = Vec::new();
 
-            // Honor Retry-After header for 429
-            if status == StatusCode::TOO_MANY_REQUESTS {
-                if let Some(retry_after) = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok())
-                {
-                    let wait = std::cmp::min(retry_after, 60);
-                    tracing::warn!(wait, "rate-limited, waiting Retry-After");
-                    tokio::time::sleep(Duration::from_secs(wait)).await;
+        loop {
+            let page: ConfluenceCursorPage<T> = self.request_with_retry(&url).await?;
+            let page_len = page.results.len();
+            all_items.extend(page.results);
+
+            match page.links.next {
+                Some(next) if page_len > 0 => {
+                    url = if next.starts_with("/wiki/") {
+                        format!("{}{next}", self.config.base_url)
+                    } else {
+                        format!("{}/wiki{next}", self.config.base_url)
+                    };
                 }
-                last_error = "429 Too Many Requests".to_string();
-                continue;
-            }
-
-            // Retry on 5xx
-            if status.is_server_error() {
-                let body = response.text().await.unwrap_or_default();
-                last_error = format!("{status}: {body}");
-                continue;
+                _ => break,
             }
-
-            // Fail fast on 4xx (except 429 handled above)
-            let body = response.text().await.unwrap_or_default();
-            return Err(ConfluenceClientError::HttpError { status, body });
         }
 
-        Err(ConfluenceClientError::MaxRetriesExceeded {
-            attempts: self.config.max_retries + 1,
-            last_error,
-        })
+        Ok(all_items)
+    }
+
+    async fn request_with_retry<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<T, ConfluenceClientError> {
+        Ok(self.http.get(url).await?.body)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reqwest::StatusCode;
     use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -180,6 +234,7 @@ mod tests {
             base_url: "http://localhost".to_string(),
             email: "test@example.com".to_string(),
             api_token: "fake-token".to_string(),
+            spaces: Vec::new(),
             max_retries: 2,
             timeout_secs: 5,
         }
@@ -0,0 +1,413 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use ovia_common::refs::find_jira_issue_keys;
+use ovia_db::confluence::models::{ConfluencePageBody, ConfluencePageLink};
+use ovia_db::confluence::pg_repository::PgConfluenceRepository;
+use ovia_db::org::models::Org;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use crate::connector::{Connector, SyncResult};
+use crate::jira::client::parse_csv_project_keys;
+
+const SOURCE_NAME: &str = "confluence_links";
+
+/// Pages processed per repository round trip.
+const BATCH_SIZE: i64 = 200;
+
+/// First path segments of GitLab URLs that are not projects.
+const GITLAB_RESERVED_PATHS: &[&str] = &[
+    "-",
+    "admin",
+    "dashboard",
+    "explore",
+    "groups",
+    "help",
+    "search",
+    "users",
+];
+
+/// What links are looked for in page bodies.
+#[derive(Debug, Clone, Default)]
+pub struct LinkTargets {
+    /// Issue keys in plain text are only kept for these projects, since
+    /// `UTF-8` or `SHA-256` look like keys too. Jira macros are always kept.
+    pub jira_project_keys: Vec<String>,
+    /// Host (and optional path prefix) of the org's GitLab, e.g. `gitlab.example.com`.
+    pub gitlab_host: Option<String>,
+}
+
+impl LinkTargets {
+    /// Project keys and GitLab host of an org, falling back to
+    /// `JIRA_PROJECT_KEYS` / `GITLAB_BASE_URL` with `env_fallback`.
+    pub fn for_org(org: &Org, env_fallback: bool) -> Self {
+        let jira_project_keys = if org.jira_project_keys.is_empty() {
+            if env_fallback {
+                parse_csv_project_keys("JIRA_PROJECT_KEYS").unwrap_or_default()
+            } else {
+                Vec::new()
+            }
+        } else {
+            org.jira_project_keys
+                .iter()
+                .map(|k| k.trim().to_uppercase())
+                .filter(|k| !k.is_empty())
+                .collect()
+        };
+        let gitlab_host = org
+            .gitlab_base_url
+            .clone()
+            .or_else(|| {
+                env_fallback
+                    .then(|| std::env::var("GITLAB_BASE_URL").ok())
+                    .flatten()
+            })
+            .map(|url| gitlab_host(&url))
+            .filter(|host| !host.is_empty());
+        Self {
+            jira_project_keys,
+            gitlab_host,
+        }
+    }
+}
+
+fn gitlab_host(base_url: &str) -> String {
+    let url = base_url.trim();
+    url.split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .trim_end_matches('/')
+        .to_ascii_lowercase()
+}
+
+/// Links of a page: issue keys from Jira macros (`jira_macro`), issue keys of
+/// known projects in the text (`issue_key`) and GitLab project or merge
+/// request URLs (`url`). Each target is kept once, from its first source.
+pub fn extract_page_links(
+    org_id: Uuid,
+    page: &ConfluencePageBody,
+    targets: &LinkTargets,
+) -> Vec<ConfluencePageLink> {
+    let storage = page.body_storage.as_deref().unwrap_or_default();
+    let text = page.body_text.as_deref().unwrap_or_default();
+
+    let mut found: Vec<(&str, String, &str)> = Vec::new();
+    for key in jira_macro_keys(storage) {
+        found.push(("jira_issue", key, "jira_macro"));
+    }
+    for key in find_jira_issue_keys(text) {
+        let project = key.split_once('-').map_or("", |(p, _)| p);
+        if targets.jira_project_keys.iter().any(|k| k == project) {
+            found.push(("jira_issue", key, "issue_key"));
+        }
+    }
+    if let Some(host) = &targets.gitlab_host {
+        for body in [storage, text] {
+            for (target_type, target_ref) in gitlab_refs(body, host) {
+                found.push((target_type, target_ref, "url"));
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    found
+        .into_iter()
+        .filter(|(target_type, target_ref, _)| seen.insert((*target_type, target_ref.clone())))
+        .map(
+            |(target_type, target_ref, link_source)| ConfluencePageLink {
+                org_id,
+                page_id: page.page_id.clone(),
+                target_type: target_type.to_string(),
+                target_ref,
+                link_source: link_source.to_string(),
+            },
+        )
+        .collect()
+}
+
+/// Issue keys of the `jira` macros in a storage-format body. Single-issue
+/// macros carry the key in their `key` parameter.
+fn jira_macro_keys(storage: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = storage;
+    while let Some(start) = rest.find("<ac:structured-macro") {
+        rest = &rest[start..];
+        let end = rest.find("</ac:structured-macro>").unwrap_or(rest.len());
+        let element = &rest[..end];
+        let open_tag = &element[..element.find('>').unwrap_or(element.len())];
+        if !open_tag.contains("ac:name=\"jira\"") {
+            // Only skip the opening tag so nested macros are still visited
+            rest = &rest[open_tag.len()..];
+            continue;
+        }
+        if let Some(value) = macro_parameter(element, "key") {
+            keys.extend(find_jira_issue_keys(value));
+        }
+        rest = &rest[end..];
+    }
+    keys
+}
+
+fn macro_parameter<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<ac:parameter ac:name=\"{name}\">");
+    let start = element.find(&open)? + open.len();
+    let len = element[start..].find("</ac:parameter>")?;
+    Some(element[start..start + len].trim())
+}
+
+/// `(target_type, target_ref)` of the GitLab URLs on `host` in `body`. Merge
+/// request URLs become `group/project!42`, other URLs the project path.
+fn gitlab_refs(body: &str, host: &str) -> Vec<(&'static str, String)> {
+    let mut refs = Vec::new();
+    let lower = body.to_ascii_lowercase();
+    let needle = format!("://{host}/");
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(&needle) {
+        let start = from + pos + needle.len();
+        let len = body[start..]
+            .find(|c: char| c.is_whitespace() || "\"'<>()[]?#&".contains(c))
+            .unwrap_or(body.len() - start);
+        from = start + len;
+        if let Some(target) = parse_gitlab_path(&body[start..start + len]) {
+            refs.push(target);
+        }
+    }
+    refs
+}
+
+fn parse_gitlab_path(path: &str) -> Option<(&'static str, String)> {
+    let path = path.trim_end_matches(['/', '.', ',', ';', ':']);
+    let (project, resource) = match path.split_once("/-/") {
+        Some((project, resource)) => (project, Some(resource)),
+        None => (path, None),
+    };
+    let segments: Vec<&str> = project.split('/').collect();
+    if segments.len() < 2
+        || segments.iter().any(|s| s.is_empty())
+        || GITLAB_RESERVED_PATHS.contains(&segments[0])
+    {
+        return None;
+    }
+
+    let iid = resource
+        .and_then(|r| r.strip_prefix("merge_requests/"))
+        .and_then(|r| r.split('/').next())
+        .and_then(|iid| iid.parse::<i64>().ok());
+    Some(match iid {
+        Some(iid) => ("gitlab_merge_request", format!("{project}!{iid}")),
+        None => ("gitlab_project", project.to_string()),
+    })
+}
+
+/// Extracts links from pages stored by the page sync. Pages are picked up when
+/// their version differs from the one their links were extracted from, so no
+/// cursor is kept.
+pub struct ConfluenceLinkExtractor<S> {
+    org_id: Uuid,
+    targets: LinkTargets,
+    confluence_repo: PgConfluenceRepository,
+    sync_repo: S,
+}
+
+impl<S> ConfluenceLinkExtractor<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        targets: LinkTargets,
+        confluence_repo: PgConfluenceRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            targets,
+            confluence_repo,
+            sync_repo,
+        }
+    }
+
+    fn empty_result() -> SyncResult {
+        SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted: 0,
+            skipped: 0,
+            errors: 0,
+            deleted: 0,
+            moved: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Connector for ConfluenceLinkExtractor<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "confluence link extraction already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(Self::empty_result());
+            }
+        };
+
+        let mut result = Self::empty_result();
+        loop {
+            let pages = match self
+                .confluence_repo
+                .list_pages_pending_links(self.org_id, BATCH_SIZE)
+                .await
+            {
+                Ok(pages) => pages,
+                Err(e) => {
+                    let msg = e.to_string();
+                    self.sync_repo
+                        .mark_failed(watermark.id, &msg)
+                        .await
+                        .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                    return Err(Box::new(e));
+                }
+            };
+
+            let mut stored = 0;
+            for page in &pages {
+                let links = extract_page_links(self.org_id, page, &self.targets);
+                match self
+                    .confluence_repo
+                    .replace_page_links(self.org_id, &page.page_id, page.version_number, &links)
+                    .await
+                {
+                    Ok(()) => {
+                        stored += 1;
+                        result.upserted += links.len();
+                    }
+                    Err(e) => {
+                        tracing::warn!(page_id = %page.page_id, error = %e, "failed to store confluence page links");
+                        result.errors += 1;
+                    }
+                }
+            }
+            // Failed pages stay pending; stop once a batch makes no progress
+            if stored == 0 || (pages.len() as i64) < BATCH_SIZE {
+                break;
+            }
+        }
+
+        self.sync_repo
+            .mark_completed(watermark.id, None)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        tracing::info!(?result, "confluence link extraction completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(storage: &str, text: &str) -> ConfluencePageBody {
+        ConfluencePageBody {
+            page_id: "1001".to_string(),
+            version_number: 3,
+            body_storage: Some(storage.to_string()),
+            body_text: Some(text.to_string()),
+        }
+    }
+
+    fn targets() -> LinkTargets {
+        LinkTargets {
+            jira_project_keys: vec!["BEE".to_string()],
+            gitlab_host: Some(gitlab_host("https://GitLab.example.com/")),
+        }
+    }
+
+    fn refs(links: &[ConfluencePageLink]) -> Vec<(&str, &str, &str)> {
+        links
+            .iter()
+            .map(|l| {
+                (
+                    l.target_type.as_str(),
+                    l.target_ref.as_str(),
+                    l.link_source.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn extracts_macros_issue_keys_and_gitlab_urls() {
+        let storage = concat!(
+            "<p>Design for BEE-12 and BEE-7, uses UTF-8 and OPS-3.</p>",
+            "<ac:structured-macro ac:name=\"jira\" ac:schema-version=\"1\">",
+            "<ac:parameter ac:name=\"server\">System JIRA</ac:parameter>",
+            "<ac:parameter ac:name=\"key\">OPS-9</ac:parameter>",
+            "</ac:structured-macro>",
+            "<ac:structured-macro ac:name=\"jira\">",
+            "<ac:parameter ac:name=\"key\">BEE-7</ac:parameter>",
+            "</ac:structured-macro>",
+            "<p><a href=\"https://gitlab.example.com/acme/shop/-/merge_requests/42#note_1\">MR</a> ",
+            "in <a href=\"https://gitlab.example.com/acme/platform/shop/-/tree/main?ref_type=heads\">repo</a>, ",
+            "see https://gitlab.example.com/users/sign_in and https://other.example.com/acme/x.</p>",
+        );
+        let text = "Design for BEE-12 and BEE-7, uses UTF-8 and OPS-3.\n\
+                    MR in repo, see https://gitlab.example.com/users/sign_in and \
+                    https://gitlab.example.com/acme/shop.";
+
+        let org_id = Uuid::new_v4();
+        let links = extract_page_links(org_id, &page(storage, text), &targets());
+        assert!(links
+            .iter()
+            .all(|l| l.org_id == org_id && l.page_id == "1001"));
+        assert_eq!(
+            refs(&links),
+            vec![
+                ("jira_issue", "OPS-9", "jira_macro"),
+                ("jira_issue", "BEE-7", "jira_macro"),
+                ("jira_issue", "BEE-12", "issue_key"),
+                ("gitlab_merge_request", "acme/shop!42", "url"),
+                ("gitlab_project", "acme/platform/shop", "url"),
+                ("gitlab_project", "acme/shop", "url"),
+            ]
+        );
+    }
+
+    #[test]
+    fn without_targets_only_macros_are_kept() {
+        let storage = concat!(
+            "<ac:structured-macro ac:name=\"info\"><ac:rich-text-body>",
+            "<ac:structured-macro ac:name=\"jira\">",
+            "<ac:parameter ac:name=\"key\">BEE-1</ac:parameter>",
+            "</ac:structured-macro></ac:rich-text-body></ac:structured-macro>",
+            "<p>BEE-2 https://gitlab.example.com/acme/shop</p>",
+        );
+        let links = extract_page_links(
+            Uuid::new_v4(),
+            &page(storage, "BEE-2 https://gitlab.example.com/acme/shop"),
+            &LinkTargets::default(),
+        );
+        assert_eq!(refs(&links), vec![("jira_issue", "BEE-1", "jira_macro")]);
+    }
+}
@@ -1,3 +1,65 @@
 pub mod client;
+pub mod links;
 pub mod models;
+pub mod page_sync;
+pub mod storage;
 pub mod sync;
+
+use ovia_db::confluence::pg_repository::PgConfluenceRepository;
+use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::sync::pg_repository::PgSyncRepository;
+
+use crate::registry::{ConnectorContext, ConnectorSpec, SourceRegistration};
+use client::{ConfluenceClient, ConfluenceClientConfig};
+use links::{ConfluenceLinkExtractor, LinkTargets};
+use page_sync::ConfluencePageSyncer;
+use sync::ConfluenceSyncer;
+
+inventory::submit! {
+    SourceRegistration {
+        source: "confluence",
+        factory: connectors,
+    }
+}
+
+/// Confluence users, then spaces and pages with their versions, then the links
+/// from pages to Jira issues and GitLab projects.
+pub fn connectors(ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+    let Some(config) = ConfluenceClientConfig::for_org(
+        &ctx.org,
+        ctx.credential("confluence"),
+        ctx.allow_env_fallback,
+    ) else {
+        tracing::info!(
+            org_id = %ctx.org.id,
+            "no confluence credentials found, skipping confluence sync"
+        );
+        return Ok(Vec::new());
+    };
+    tracing::info!(org_id = %ctx.org.id, spaces = ?config.spaces, "confluence connector configured");
+
+    let client = ConfluenceClient::new(config).map_err(|e| e.to_string())?;
+
+    Ok(vec![
+        ConnectorSpec::new(ConfluenceSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgIdentityRepository::new(ctx.pool.clone()),
+            PgSyncRepository::new(ctx.pool.clone()),
+        )),
+        ConnectorSpec::new(ConfluencePageSyncer::new(
+            ctx.org.id,
+            client,
+            PgConfluenceRepository::new(ctx.pool.clone()),
+            PgSyncRepository::new(ctx.pool.clone()),
+        ))
+        .depends_on("confluence"),
+        ConnectorSpec::new(ConfluenceLinkExtractor::new(
+            ctx.org.id,
+            LinkTargets::for_org(&ctx.org, ctx.allow_env_fallback),
+            PgConfluenceRepository::new(ctx.pool.clone()),
+            PgSyncRepository::new(ctx.pool.clone()),
+        ))
+        .depends_on("confluence_pages"),
+    ])
+}
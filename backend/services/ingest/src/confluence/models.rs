@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// A user record from the Confluence Cloud REST API
 /// (`/wiki/rest/api/group/confluence-users/member`).
@@ -34,6 +35,137 @@ pub struct ConfluencePageResponse {
     pub size: usize,
 }
 
+// ── Content API response types ──────────────────────────────────
+
+/// Accepts ids sent as JSON strings or numbers.
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) => Ok(s),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected string or number id, got {other}"
+        ))),
+    }
+}
+
+/// A page of results followed by `_links.next`, from both the v1 search and the v2
+/// list endpoints.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceCursorPage<T> {
+    #[serde(default = "Vec::new")]
+    pub results: Vec<T>,
+    #[serde(default, rename = "_links")]
+    pub links: ConfluenceLinks,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ConfluenceLinks {
+    /// Relative link to the next page; absent on the last page.
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Relative UI link of a page.
+    #[serde(default)]
+    pub webui: Option<String>,
+}
+
+/// A space from `/wiki/api/v2/spaces`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceSpace {
+    #[serde(deserialize_with = "string_or_number")]
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub space_type: Option<String>,
+}
+
+/// A page from the v1 content search, with `body.storage`, `version`, `space`,
+/// `history` and `ancestors` expanded.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceContent {
+    pub id: String,
+    pub status: String,
+    pub title: String,
+    pub space: Option<ConfluenceSpaceRef>,
+    pub history: Option<ConfluenceHistory>,
+    pub version: ConfluenceContentVersion,
+    #[serde(default)]
+    pub ancestors: Vec<ConfluenceAncestor>,
+    pub body: Option<ConfluenceBody>,
+    #[serde(default, rename = "_links")]
+    pub links: ConfluenceLinks,
+}
+
+impl ConfluenceContent {
+    /// Body in storage format, if expanded.
+    pub fn storage(&self) -> Option<&str> {
+        self.body
+            .as_ref()
+            .and_then(|b| b.storage.as_ref())
+            .map(|s| s.value.as_str())
+    }
+
+    /// The direct parent page: the last ancestor.
+    pub fn parent_page_id(&self) -> Option<&str> {
+        self.ancestors.last().map(|a| a.id.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceSpaceRef {
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfluenceHistory {
+    pub created_by: Option<ConfluenceUserRef>,
+    pub created_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfluenceUserRef {
+    pub account_id: Option<String>,
+}
+
+/// The current version of a content item (v1 shape).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfluenceContentVersion {
+    pub number: i32,
+    pub when: DateTime<Utc>,
+    pub by: Option<ConfluenceUserRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceAncestor {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceBody {
+    pub storage: Option<ConfluenceStorage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConfluenceStorage {
+    pub value: String,
+}
+
+/// A version from `/wiki/api/v2/pages/{id}/versions`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfluencePageVersion {
+    pub number: i32,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub minor_edit: bool,
+    pub author_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,386 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use ovia_db::confluence::models::{
+    ConfluencePage as DbPage, ConfluencePageVersion as DbPageVersion, ConfluenceSpace as DbSpace,
+};
+use ovia_db::confluence::pg_repository::PgConfluenceRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::{ConfluenceClient, ConfluenceClientError};
+use super::models::{ConfluenceContent, ConfluencePageVersion, ConfluenceSpace};
+use super::storage::storage_to_text;
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "confluence_pages";
+
+/// Overlap applied to the last-modified cursor. CQL compares dates to the minute
+/// in the API user's time zone, so a day covers any offset; re-seen pages whose
+/// version did not change are skipped.
+const CURSOR_OVERLAP_HOURS: i64 = 24;
+
+pub fn api_space_to_db(org_id: Uuid, space: &ConfluenceSpace) -> DbSpace {
+    let now = Utc::now();
+    DbSpace {
+        id: Uuid::new_v4(),
+        org_id,
+        space_id: space.id.clone(),
+        space_key: space.key.clone(),
+        name: space.name.clone(),
+        space_type: space.space_type.clone(),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Convert a searched page to a DB row. `base_url` is the site URL, used to make
+/// the page's UI link absolute.
+pub fn api_page_to_db(org_id: Uuid, base_url: &str, page: &ConfluenceContent) -> DbPage {
+    let now = Utc::now();
+    let history = page.history.as_ref();
+    DbPage {
+        id: Uuid::new_v4(),
+        org_id,
+        page_id: page.id.clone(),
+        space_key: page
+            .space
+            .as_ref()
+            .map(|s| s.key.clone())
+            .unwrap_or_default(),
+        title: page.title.clone(),
+        status: page.status.clone(),
+        parent_page_id: page.parent_page_id().map(str::to_string),
+        author_account_id: history
+            .and_then(|h| h.created_by.as_ref())
+            .and_then(|u| u.account_id.clone()),
+        last_editor_account_id: page.version.by.as_ref().and_then(|u| u.account_id.clone()),
+        version_number: page.version.number,
+        body_storage: page.storage().map(str::to_string),
+        body_text: page.storage().map(storage_to_text),
+        web_url: page
+            .links
+            .webui
+            .as_ref()
+            .map(|path| format!("{base_url}/wiki{path}")),
+        created_at_confluence: history.and_then(|h| h.created_date),
+        updated_at_confluence: Some(page.version.when),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+pub fn api_version_to_db(
+    org_id: Uuid,
+    page_id: &str,
+    version: &ConfluencePageVersion,
+) -> DbPageVersion {
+    DbPageVersion {
+        id: Uuid::new_v4(),
+        org_id,
+        page_id: page_id.to_string(),
+        version_number: version.number,
+        author_account_id: version.author_id.clone(),
+        message: version.message.clone().filter(|m| !m.trim().is_empty()),
+        minor_edit: version.minor_edit,
+        created_at_confluence: version.created_at,
+        created_at: Utc::now(),
+    }
+}
+
+/// Syncs spaces, and pages with their versions incrementally by last-modified
+/// time. The cursor is the latest page modification seen (RFC 3339).
+pub struct ConfluencePageSyncer<S> {
+    org_id: Uuid,
+    client: ConfluenceClient,
+    confluence_repo: PgConfluenceRepository,
+    sync_repo: S,
+}
+
+impl<S> ConfluencePageSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: ConfluenceClient,
+        confluence_repo: PgConfluenceRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            confluence_repo,
+            sync_repo,
+        }
+    }
+
+    /// Store a page and the versions added since the stored copy. Returns `false`
+    /// if the stored copy was already at this version.
+    async fn store_page(
+        &self,
+        page: &ConfluenceContent,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let stored = self.confluence_repo.get_page(self.org_id, &page.id).await?;
+        let known_version = match &stored {
+            Some(p) if p.version_number == page.version.number && p.status == page.status => {
+                return Ok(false)
+            }
+            Some(p) => p.version_number,
+            None => 0,
+        };
+
+        let versions = self.client.fetch_page_versions(&page.id).await?;
+        for version in versions.iter().filter(|v| v.number > known_version) {
+            self.confluence_repo
+                .upsert_page_version(&api_version_to_db(self.org_id, &page.id, version))
+                .await?;
+        }
+        self.confluence_repo
+            .upsert_page(&api_page_to_db(
+                self.org_id,
+                &self.client.config().base_url,
+                page,
+            ))
+            .await?;
+        Ok(true)
+    }
+
+    fn empty_result() -> SyncResult {
+        SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted: 0,
+            skipped: 0,
+            errors: 0,
+            deleted: 0,
+            moved: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Connector for ConfluencePageSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "confluence page sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(Self::empty_result());
+            }
+        };
+
+        let cursor: Option<DateTime<Utc>> = watermark
+            .cursor_value
+            .as_deref()
+            .and_then(|v| v.parse().ok());
+        let spaces = &self.client.config().spaces;
+
+        let fetched = async {
+            let space_list = self.client.fetch_spaces(spaces).await?;
+            let pages = self
+                .client
+                .search_pages(
+                    spaces,
+                    cursor.map(|c| c - Duration::hours(CURSOR_OVERLAP_HOURS)),
+                )
+                .await?;
+            Ok::<_, ConfluenceClientError>((space_list, pages))
+        };
+        let (space_list, pages) = match fetched.await {
+            Ok(r) => r,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "confluence content fetch failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        tracing::info!(
+            spaces = space_list.len(),
+            pages = pages.len(),
+            "fetched confluence content"
+        );
+
+        let mut result = Self::empty_result();
+
+        for space in &space_list {
+            match self
+                .confluence_repo
+                .upsert_space(&api_space_to_db(self.org_id, space))
+                .await
+            {
+                Ok(_) => result.upserted += 1,
+                Err(e) => {
+                    tracing::warn!(space = %space.key, error = %e, "failed to upsert confluence space");
+                    result.errors += 1;
+                }
+            }
+        }
+
+        let mut next_cursor = cursor;
+        for page in &pages {
+            match self.store_page(page).await {
+                Ok(true) => result.upserted += 1,
+                Ok(false) => result.skipped += 1,
+                Err(e) => {
+                    tracing::warn!(page_id = %page.id, error = %e, "failed to store confluence page");
+                    result.errors += 1;
+                    continue;
+                }
+            }
+            next_cursor = next_cursor.max(Some(page.version.when));
+        }
+
+        let cursor_value = next_cursor.map(|c| c.to_rfc3339());
+        self.sync_repo
+            .mark_completed(watermark.id, cursor_value.as_deref())
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        tracing::info!(?result, "confluence page sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::confluence::client::ConfluenceClientConfig;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn client_follows_cursors_and_maps_pages() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki/api/v2/spaces"))
+            .and(query_param("keys", "ENG"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "id": 98306, "key": "ENG", "name": "Engineering", "type": "global" }],
+                "_links": {}
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki/rest/api/content/search"))
+            .and(query_param(
+                "cql",
+                "type = page and space in (\"ENG\") and lastmodified >= \"2026-03-01 08:30\" order by lastmodified asc",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{
+                    "id": "1001", "type": "page", "status": "current", "title": "Checkout design",
+                    "space": { "key": "ENG" },
+                    "history": {
+                        "createdBy": { "accountId": "user-1" },
+                        "createdDate": "2026-02-20T10:00:00.000Z"
+                    },
+                    "version": { "number": 3, "when": "2026-03-02T09:15:00.000Z",
+                                 "by": { "accountId": "user-2" } },
+                    "ancestors": [{ "id": "900" }, { "id": "950" }],
+                    "body": { "storage": { "value": "<p>Covers <strong>BEE-1</strong></p>",
+                                           "representation": "storage" } },
+                    "_links": { "webui": "/spaces/ENG/pages/1001/Checkout+design" }
+                }],
+                "start": 0, "limit": 50, "size": 1,
+                "_links": { "next": "/rest/api/content/search?cursor=abc" }
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki/rest/api/content/search"))
+            .and(query_param("cursor", "abc"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [], "start": 1, "limit": 50, "size": 0, "_links": {}
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/wiki/api/v2/pages/1001/versions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    { "number": 3, "message": "", "minorEdit": false, "authorId": "user-2",
+                      "createdAt": "2026-03-02T09:15:00.000Z" },
+                    { "number": 2, "message": "Add rollout", "minorEdit": true, "authorId": "user-1",
+                      "createdAt": "2026-02-25T16:00:00.000Z" }
+                ],
+                "_links": {}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ConfluenceClient::new(ConfluenceClientConfig {
+            base_url: server.uri(),
+            email: "test@example.com".to_string(),
+            api_token: "token".to_string(),
+            spaces: vec!["ENG".to_string()],
+            max_retries: 1,
+            timeout_secs: 5,
+        })
+        .unwrap();
+        let org_id = Uuid::new_v4();
+
+        let spaces = client.fetch_spaces(&["ENG".to_string()]).await.unwrap();
+        let space = api_space_to_db(org_id, &spaces[0]);
+        assert_eq!(space.space_id, "98306");
+        assert_eq!(space.space_type.as_deref(), Some("global"));
+
+        let since = "2026-03-01T08:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        let pages = client
+            .search_pages(&["ENG".to_string()], Some(since))
+            .await
+            .unwrap();
+        assert_eq!(pages.len(), 1);
+
+        let page = api_page_to_db(org_id, &server.uri(), &pages[0]);
+        assert_eq!(page.space_key, "ENG");
+        assert_eq!(page.parent_page_id.as_deref(), Some("950"));
+        assert_eq!(page.author_account_id.as_deref(), Some("user-1"));
+        assert_eq!(page.last_editor_account_id.as_deref(), Some("user-2"));
+        assert_eq!(page.version_number, 3);
+        assert_eq!(page.body_text.as_deref(), Some("Covers BEE-1"));
+        assert_eq!(
+            page.web_url,
+            Some(format!(
+                "{}/wiki/spaces/ENG/pages/1001/Checkout+design",
+                server.uri()
+            ))
+        );
+
+        let versions = client.fetch_page_versions("1001").await.unwrap();
+        let latest = api_version_to_db(org_id, "1001", &versions[0]);
+        assert!(latest.message.is_none());
+        let earlier = api_version_to_db(org_id, "1001", &versions[1]);
+        assert_eq!(earlier.message.as_deref(), Some("Add rollout"));
+        assert!(earlier.minor_edit);
+    }
+}
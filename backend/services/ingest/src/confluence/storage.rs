@@ -0,0 +1,236 @@
+//! Conversion of Confluence storage format (XHTML with `ac:`/`ri:` macro
+//! elements) to plain text for search and the Ask engine.
+
+/// Elements that start a new line in the text.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "p",
+    "div",
+    "br",
+    "hr",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "pre",
+    "blockquote",
+    "table",
+    "ul",
+    "ol",
+    "ac:structured-macro",
+    "ac:task",
+];
+
+/// Elements whose content is markup configuration rather than text.
+const SKIPPED_ELEMENTS: &[&str] = &["ac:parameter", "ac:task-id", "ac:task-status"];
+
+const NAMED_ENTITIES: &[(&str, &str)] = &[
+    ("amp", "&"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("quot", "\""),
+    ("apos", "'"),
+    ("nbsp", " "),
+    ("ndash", "\u{2013}"),
+    ("mdash", "\u{2014}"),
+    ("hellip", "\u{2026}"),
+    ("lsquo", "\u{2018}"),
+    ("rsquo", "\u{2019}"),
+    ("ldquo", "\u{201c}"),
+    ("rdquo", "\u{201d}"),
+    ("laquo", "\u{ab}"),
+    ("raquo", "\u{bb}"),
+    ("bull", "\u{2022}"),
+    ("middot", "\u{b7}"),
+    ("copy", "\u{a9}"),
+    ("reg", "\u{ae}"),
+    ("trade", "\u{2122}"),
+];
+
+/// Convert a storage-format body to plain text: tags are dropped, block elements
+/// become line breaks, table cells are separated by spaces, CDATA (code macro
+/// bodies) is kept verbatim and macro parameters are left out. Runs of blank
+/// lines collapse to one.
+pub fn storage_to_text(storage: &str) -> String {
+    let mut text = String::with_capacity(storage.len() / 2);
+    let mut skip_depth = 0usize;
+    let mut rest = storage;
+
+    while let Some(start) = rest.find('<') {
+        if skip_depth == 0 {
+            push_decoded(&mut text, &rest[..start]);
+        }
+        rest = &rest[start..];
+
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").unwrap_or(cdata.len());
+            if skip_depth == 0 {
+                text.push_str(&cdata[..end]);
+            }
+            rest = cdata.get(end + 3..).unwrap_or("");
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let end = tag_end(rest);
+        let tag = &rest[1..end.saturating_sub(1).max(1)];
+        rest = &rest[end..];
+
+        let closing = tag.starts_with('/');
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            if closing {
+                skip_depth = skip_depth.saturating_sub(1);
+            } else if !self_closing {
+                skip_depth += 1;
+            }
+            continue;
+        }
+        if skip_depth > 0 {
+            continue;
+        }
+        if BLOCK_ELEMENTS.contains(&name.as_str()) {
+            text.push('\n');
+        } else if name == "td" || name == "th" {
+            text.push(' ');
+        }
+    }
+    if skip_depth == 0 {
+        push_decoded(&mut text, rest);
+    }
+
+    normalize_whitespace(&text)
+}
+
+/// Byte offset just past the `>` closing the tag at the start of `s`, ignoring
+/// `>` inside quoted attribute values.
+fn tag_end(s: &str) -> usize {
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return i + 1,
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+/// Append `raw` with character and named entity references decoded. Unknown
+/// entities are kept as written.
+fn push_decoded(out: &mut String, raw: &str) {
+    let mut rest = raw;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = if let Some(hex) = entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(dec) = entity.strip_prefix('#') {
+                dec.parse().ok().and_then(char::from_u32)
+            } else {
+                None
+            };
+            let s = match c {
+                Some(c) => c.to_string(),
+                None => NAMED_ENTITIES
+                    .iter()
+                    .find(|(name, _)| *name == entity)?
+                    .1
+                    .to_string(),
+            };
+            Some((s, end + 1))
+        });
+        match decoded {
+            Some((s, len)) => {
+                out.push_str(&s);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+}
+
+/// Collapse spaces within lines, trim lines and keep at most one blank line
+/// between paragraphs.
+fn normalize_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank_run = 0;
+    for line in text.lines() {
+        let line = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 1 { "\n\n" } else { "\n" });
+        }
+        out.push_str(&line);
+        blank_run = 0;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_blocks_tables_and_entities() {
+        let storage = concat!(
+            "<h1>Checkout &amp; payments</h1>",
+            "<p>Owner: <ac:link><ri:user ri:account-id=\"abc\" /></ac:link>",
+            "<strong>team&nbsp;Bees</strong> &ndash; see&#160;below &#x2192; done</p>",
+            "<table><tbody><tr><th>Key</th><th>State</th></tr>",
+            "<tr><td>BEE-1</td><td>Open</td></tr></tbody></table>",
+            "<ul><li>one</li><li>two &unknown; &lt;tag&gt;</li></ul>",
+        );
+        assert_eq!(
+            storage_to_text(storage),
+            "Checkout & payments\n\
+             Owner: team Bees \u{2013} see below \u{2192} done\n\n\
+             Key State\n\
+             BEE-1 Open\n\n\
+             one\n\
+             two &unknown; <tag>"
+        );
+    }
+
+    #[test]
+    fn keeps_code_bodies_and_drops_macro_parameters() {
+        let storage = concat!(
+            "<p>Before</p>",
+            "<ac:structured-macro ac:name=\"code\">",
+            "<ac:parameter ac:name=\"language\">rust</ac:parameter>",
+            "<ac:plain-text-body><![CDATA[fn main() { if a < b {} }]]></ac:plain-text-body>",
+            "</ac:structured-macro>",
+            "<!-- hidden --><p title=\"a > b\">After</p>",
+        );
+        assert_eq!(
+            storage_to_text(storage),
+            "Before\nfn main() { if a < b {} }\nAfter"
+        );
+    }
+}
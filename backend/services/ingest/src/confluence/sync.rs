@@ -41,6 +41,8 @@ where
             email: user.email.clone(),
             display_name: user.effective_display_name().map(|s| s.to_string()),
             is_service_account: user.is_service_account(),
+            is_active: true,
+            active_changed_at: None,
             first_seen_at: Some(Utc::now()),
             last_seen_at: Some(Utc::now()),
             raw_ref: serde_json::to_value(user).ok(),
@@ -84,6 +86,8 @@ where
                     upserted: 0,
                     skipped: 0,
                     errors: 0,
+                    deleted: 0,
+                    moved: 0,
                 });
             }
         };
@@ -134,6 +138,8 @@ where
             upserted,
             skipped: 0,
             errors,
+            deleted: 0,
+            moved: 0,
         };
 
         tracing::info!(?result, "confluence sync completed");
@@ -284,6 +290,7 @@ mod tests {
             base_url: base_url.to_string(),
             email: "test@example.com".to_string(),
             api_token: "token".to_string(),
+            spaces: Vec::new(),
             max_retries: 1,
             timeout_secs: 5,
         }
@@ -4,14 +4,17 @@ use async_trait::async_trait;
 pub struct SyncResult {
     pub source: String,
     pub upserted: usize,
-    #[allow(dead_code)]
     pub skipped: usize,
     pub errors: usize,
+    /// Records removed because they no longer exist at the source
+    pub deleted: usize,
+    /// Records re-keyed after moving at the source (e.g. a Jira issue moved to
+    /// another project)
+    pub moved: usize,
 }
 
 #[async_trait]
 pub trait Connector: Send + Sync {
-    #[allow(dead_code)]
     fn source_name(&self) -> &str;
     async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>>;
 }
@@ -0,0 +1,472 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::org::models::Org;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+use crate::http::{Auth, HttpClient, HttpClientConfig, HttpClientError};
+
+use super::models::{
+    GitHubPullRequest, GitHubRepo, GitHubUser, GitHubWorkflowRun, GitHubWorkflowRunPage,
+};
+
+const DEFAULT_BASE_URL: &str = "https://api.github.com";
+const API_VERSION: &str = "2022-11-28";
+
+#[derive(Debug, Clone)]
+pub struct GitHubClientConfig {
+    /// REST API root: `https://api.github.com`, or `https://host/api/v3` on
+    /// GitHub Enterprise Server.
+    pub base_url: String,
+    pub token: String,
+    /// Organization logins whose members and repositories are synced.
+    pub orgs: Vec<String>,
+    pub max_retries: u32,
+    pub timeout_secs: u64,
+}
+
+impl GitHubClientConfig {
+    /// Load GitHub config for an org. The org's API root and organizations take
+    /// precedence over `GITHUB_API_URL` / `GITHUB_ORGS`, and the vault credential
+    /// over `GITHUB_TOKEN`, the env only being consulted with `env_fallback`; tuning
+    /// comes from the environment. Returns `None` if no token or organization is
+    /// available.
+    pub fn for_org(
+        org: &Org,
+        credential: Option<&SourceSecret>,
+        env_fallback: bool,
+    ) -> Option<Self> {
+        let env = |name: &str| env_fallback.then(|| std::env::var(name).ok()).flatten();
+        let token = credential
+            .map(|c| c.secret.clone())
+            .or_else(|| env("GITHUB_TOKEN"))?;
+        let orgs = if org.github_orgs.is_empty() {
+            env("GITHUB_ORGS")
+                .map(|v| parse_csv(&v))
+                .unwrap_or_default()
+        } else {
+            org.github_orgs.clone()
+        };
+        if orgs.is_empty() {
+            return None;
+        }
+        let base_url = org
+            .github_base_url
+            .clone()
+            .or_else(|| env("GITHUB_API_URL"))
+            .filter(|url| !url.is_empty())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let max_retries = std::env::var("GITHUB_MAX_RETRIES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(3);
+        let timeout_secs = std::env::var("GITHUB_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(30);
+
+        Some(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            orgs,
+            max_retries,
+            timeout_secs,
+        })
+    }
+}
+
+fn parse_csv(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    http: HttpClient,
+    config: GitHubClientConfig,
+}
+
+pub type GitHubClientError = HttpClientError;
+
+impl GitHubClient {
+    pub fn new(config: GitHubClientConfig) -> Result<Self, reqwest::Error> {
+        let http = HttpClient::new(
+            HttpClientConfig::from_env(config.max_retries, config.timeout_secs),
+            Auth::Bearer(config.token.clone()),
+        )?
+        .with_header("Accept", "application/vnd.github+json")
+        .with_header("X-GitHub-Api-Version", API_VERSION);
+        Ok(Self { http, config })
+    }
+
+    pub fn config(&self) -> &GitHubClientConfig {
+        &self.config
+    }
+
+    /// Fetch the members of every configured organization, deduplicated by user
+    /// id, with their profiles. A member whose profile cannot be fetched is kept
+    /// as listed.
+    pub async fn fetch_all_members(&self) -> Result<Vec<GitHubUser>, GitHubClientError> {
+        let mut seen = HashSet::new();
+        let mut users = Vec::new();
+        for org in &self.config.orgs {
+            let url = format!(
+                "{}/orgs/{}/members?per_page=100",
+                self.config.base_url,
+                urlencoding::encode(org)
+            );
+            let members: Vec<GitHubUser> = self.fetch_all_pages(&url).await?;
+            for member in members.into_iter().filter(|u| seen.insert(u.id)) {
+                let url = format!(
+                    "{}/users/{}",
+                    self.config.base_url,
+                    urlencoding::encode(&member.login)
+                );
+                match self.request_with_retry::<GitHubUser>(&url).await {
+                    Ok((profile, _)) => users.push(profile),
+                    Err(e) => {
+                        tracing::warn!(login = %member.login, error = %e, "failed to fetch github profile");
+                        users.push(member);
+                    }
+                }
+            }
+        }
+        Ok(users)
+    }
+
+    /// Fetch the active (non-archived) repositories of every configured
+    /// organization, deduplicated by id.
+    pub async fn fetch_all_repos(&self) -> Result<Vec<GitHubRepo>, GitHubClientError> {
+        let mut seen = HashSet::new();
+        let mut repos = Vec::new();
+        for org in &self.config.orgs {
+            let url = format!(
+                "{}/orgs/{}/repos?per_page=100&type=all",
+                self.config.base_url,
+                urlencoding::encode(org)
+            );
+            let org_repos: Vec<GitHubRepo> = self.fetch_all_pages(&url).await?;
+            repos.extend(
+                org_repos
+                    .into_iter()
+                    .filter(|r| !r.archived && seen.insert(r.id)),
+            );
+        }
+        Ok(repos)
+    }
+
+    /// Fetch pull requests of a repository in any state, most recently updated
+    /// first. The API has no `since` filter, so paging stops at the first pull
+    /// request not updated after `updated_after`.
+    pub async fn fetch_pull_requests(
+        &self,
+        full_name: &str,
+        updated_after: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GitHubPullRequest>, GitHubClientError> {
+        let mut next = Some(format!(
+            "{}/repos/{}/pulls?state=all&sort=updated&direction=desc&per_page=100",
+            self.config.base_url, full_name
+        ));
+        let mut pulls = Vec::new();
+
+        while let Some(url) = next {
+            let (page, next_url) = self
+                .request_with_retry::<Vec<GitHubPullRequest>>(&url)
+                .await?;
+            let page_len = page.len();
+            let before = pulls.len();
+            pulls.extend(
+                page.into_iter()
+                    .take_while(|pr| match (pr.updated_at, updated_after) {
+                        (Some(updated), Some(after)) => updated >= after,
+                        _ => true,
+                    }),
+            );
+            let stopped_early = pulls.len() - before < page_len;
+            next = if stopped_early { None } else { next_url };
+        }
+        Ok(pulls)
+    }
+
+    /// Fetch the Actions workflow runs of a repository, optionally only those
+    /// created at or after `created_after`.
+    pub async fn fetch_workflow_runs(
+        &self,
+        full_name: &str,
+        created_after: Option<DateTime<Utc>>,
+    ) -> Result<Vec<GitHubWorkflowRun>, GitHubClientError> {
+        let mut url = format!(
+            "{}/repos/{}/actions/runs?per_page=100",
+            self.config.base_url, full_name
+        );
+        if let Some(after) = created_after {
+            let filter = format!(">={}", after.format("%Y-%m-%dT%H:%M:%SZ"));
+            url.push_str(&format!("&created={}", urlencoding::encode(&filter)));
+        }
+
+        let mut next = Some(url);
+        let mut runs = Vec::new();
+        while let Some(url) = next {
+            let (page, next_url) = self
+                .request_with_retry::<GitHubWorkflowRunPage>(&url)
+                .await?;
+            runs.extend(page.workflow_runs);
+            next = next_url;
+        }
+        Ok(runs)
+    }
+
+    /// Generic paginated fetch: follows `rel="next"` links collecting all items.
+    async fn fetch_all_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<Vec<T>, GitHubClientError> {
+        let mut next = Some(url.to_string());
+        let mut all_items = Vec::new();
+        while let Some(url) = next {
+            let (items, next_url) = self.request_with_retry::<Vec<T>>(&url).await?;
+            all_items.extend(items);
+            next = next_url;
+        }
+        Ok(all_items)
+    }
+
+    /// GET one page with retries. Returns the body and the `rel="next"` URL of
+    /// the `Link` header.
+    async fn request_with_retry<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<(T, Option<String>), GitHubClientError> {
+        let response = self.http.get(url).await?;
+        Ok((response.body, next_link(&response.headers)))
+    }
+}
+
+/// The `rel="next"` URL of a `Link` header, if any.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config(base_url: &str) -> GitHubClientConfig {
+        GitHubClientConfig {
+            base_url: base_url.to_string(),
+            token: "ghp-test-token".to_string(),
+            orgs: vec!["acme".to_string()],
+            max_retries: 2,
+            timeout_secs: 5,
+        }
+    }
+
+    fn make_repos(count: usize, offset: usize) -> Vec<serde_json::Value> {
+        (0..count)
+            .map(|i| {
+                serde_json::json!({
+                    "id": i + offset,
+                    "name": format!("repo-{}", i + offset),
+                    "full_name": format!("acme/repo-{}", i + offset),
+                    "html_url": format!("https://github.com/acme/repo-{}", i + offset),
+                    "archived": false
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn follows_link_header_pages() {
+        let server = MockServer::start().await;
+        let next = format!(
+            "<{}/organizations/1/repos?per_page=100&page=2>; rel=\"next\", \
+             <{}/organizations/1/repos?per_page=100&page=2>; rel=\"last\"",
+            server.uri(),
+            server.uri()
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .and(header("Authorization", "Bearer ghp-test-token"))
+            .and(header("X-GitHub-Api-Version", API_VERSION))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(make_repos(2, 0))
+                    .append_header("link", next.as_str()),
+            )
+            .mount(&server)
+            .await;
+        let mut archived = make_repos(1, 3);
+        archived[0]["archived"] = serde_json::json!(true);
+        let mut page2 = make_repos(1, 2);
+        page2.extend(archived);
+        Mock::given(method("GET"))
+            .and(path("/organizations/1/repos"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(page2))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_config(&server.uri())).unwrap();
+        let repos = client.fetch_all_repos().await.unwrap();
+        let names: Vec<&str> = repos.iter().map(|r| r.full_name.as_str()).collect();
+        assert_eq!(names, vec!["acme/repo-0", "acme/repo-1", "acme/repo-2"]);
+    }
+
+    #[tokio::test]
+    async fn waits_for_retry_after_and_retries_on_500() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .respond_with(ResponseTemplate::new(403).append_header("retry-after", "0"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(make_repos(1, 0)))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_config(&server.uri())).unwrap();
+        let repos = client.fetch_all_repos().await.unwrap();
+        assert_eq!(repos.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn fails_fast_on_forbidden_without_rate_limit() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "4999")
+                    .set_body_string("Resource not accessible by integration"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_config(&server.uri())).unwrap();
+        let err = client.fetch_all_repos().await.unwrap_err();
+        match err {
+            GitHubClientError::HttpError { status, body } => {
+                assert_eq!(status, StatusCode::FORBIDDEN);
+                assert_eq!(body, "Resource not accessible by integration");
+            }
+            other => panic!("expected HttpError, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn max_retries_exceeded_on_exhausted_quota() {
+        let server = MockServer::start().await;
+        let reset = (Utc::now().timestamp() - 5).to_string();
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/repos"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .append_header("x-ratelimit-remaining", "0")
+                    .append_header("x-ratelimit-reset", reset.as_str()),
+            )
+            .mount(&server)
+            .await;
+
+        let mut config = test_config(&server.uri());
+        config.max_retries = 1;
+        let client = GitHubClient::new(config).unwrap();
+        let err = client.fetch_all_repos().await.unwrap_err();
+        assert!(matches!(err, GitHubClientError::MaxRetriesExceeded { .. }));
+    }
+
+    #[tokio::test]
+    async fn members_are_deduped_and_enriched_with_profiles() {
+        let server = MockServer::start().await;
+
+        for org in ["acme", "acme-labs"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/orgs/{org}/members")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                    { "id": 1, "login": "octo", "type": "User" },
+                    { "id": 2, "login": "hubot", "type": "Bot" }
+                ])))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/users/octo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1, "login": "octo", "type": "User",
+                "name": "Octo Cat", "email": "octo@example.com"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/hubot"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let mut config = test_config(&server.uri());
+        config.orgs.push("acme-labs".to_string());
+        let client = GitHubClient::new(config).unwrap();
+
+        let members = client.fetch_all_members().await.unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].name.as_deref(), Some("Octo Cat"));
+        assert_eq!(members[1].login, "hubot");
+        assert!(members[1].is_service_account());
+    }
+
+    #[test]
+    fn next_link_ignores_other_relations() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            "<https://api.github.com/x?page=1>; rel=\"prev\", <https://api.github.com/x?page=3>; rel=\"next\""
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            next_link(&headers).as_deref(),
+            Some("https://api.github.com/x?page=3")
+        );
+
+        headers.insert(
+            "link",
+            "<https://api.github.com/x?page=1>; rel=\"first\""
+                .parse()
+                .unwrap(),
+        );
+        assert!(next_link(&headers).is_none());
+    }
+}
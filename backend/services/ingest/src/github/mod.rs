@@ -0,0 +1,50 @@
+pub mod client;
+pub mod models;
+pub mod pr_sync;
+pub mod sync;
+
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::sync::pg_repository::PgSyncRepository;
+
+use crate::registry::{ConnectorContext, ConnectorSpec, SourceRegistration};
+use client::{GitHubClient, GitHubClientConfig};
+use pr_sync::GitHubPrWorkflowSyncer;
+use sync::GitHubSyncer;
+
+inventory::submit! {
+    SourceRegistration {
+        source: "github",
+        factory: connectors,
+    }
+}
+
+/// GitHub org members, then pull requests and workflow runs.
+pub fn connectors(ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+    let Some(config) =
+        GitHubClientConfig::for_org(&ctx.org, ctx.credential("github"), ctx.allow_env_fallback)
+    else {
+        tracing::info!(org_id = %ctx.org.id, "no github credentials found, skipping github sync");
+        return Ok(Vec::new());
+    };
+    tracing::info!(org_id = %ctx.org.id, orgs = ?config.orgs, "github connector configured");
+
+    let client = GitHubClient::new(config).map_err(|e| e.to_string())?;
+    let pool = &ctx.pool;
+
+    Ok(vec![
+        ConnectorSpec::new(GitHubSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgIdentityRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        )),
+        ConnectorSpec::new(GitHubPrWorkflowSyncer::new(
+            ctx.org.id,
+            client,
+            PgGitlabRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("github"),
+    ])
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A user from the GitHub REST API: an org member (`GET /orgs/:org/members`) or
+/// the full profile (`GET /users/:login`), which adds the name and public email.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubUser {
+    pub id: u64,
+    pub login: String,
+    /// "User", "Bot" or "Organization"
+    #[serde(rename = "type")]
+    pub user_type: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// Set on GitHub Enterprise Server for suspended accounts.
+    pub suspended_at: Option<DateTime<Utc>>,
+}
+
+impl GitHubUser {
+    /// Returns `true` for GitHub App and other bot accounts.
+    pub fn is_service_account(&self) -> bool {
+        self.user_type.as_deref() == Some("Bot")
+    }
+
+    /// Returns `false` if the account is suspended.
+    pub fn is_active(&self) -> bool {
+        self.suspended_at.is_none()
+    }
+}
+
+/// A repository (`GET /orgs/:org/repos`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubRepo {
+    pub id: u64,
+    pub name: String,
+    /// `owner/name`
+    pub full_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// User reference embedded in pull requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubUserRef {
+    pub login: String,
+}
+
+/// Label embedded in pull requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+}
+
+/// Base or head branch of a pull request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubBranchRef {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+}
+
+/// A pull request (`GET /repos/:owner/:repo/pulls`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubPullRequest {
+    pub number: u64,
+    pub title: String,
+    /// "open" or "closed"; merged pull requests are closed with `merged_at` set.
+    pub state: String,
+    pub user: Option<GitHubUserRef>,
+    #[serde(default)]
+    pub labels: Vec<GitHubLabel>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub base: Option<GitHubBranchRef>,
+    pub html_url: String,
+}
+
+impl GitHubPullRequest {
+    /// The state in GitLab terms ("opened", "merged" or "closed"), which is what
+    /// the merge request KPIs filter on.
+    pub fn mr_state(&self) -> &'static str {
+        match (self.state.as_str(), self.merged_at) {
+            (_, Some(_)) => "merged",
+            ("open", None) => "opened",
+            _ => "closed",
+        }
+    }
+}
+
+/// The workflow runs listing wraps runs in an object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubWorkflowRunPage {
+    #[serde(default)]
+    pub workflow_runs: Vec<GitHubWorkflowRun>,
+}
+
+/// An Actions workflow run (`GET /repos/:owner/:repo/actions/runs`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubWorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+    /// "queued", "in_progress", "completed", "waiting", "requested" or "pending"
+    pub status: Option<String>,
+    /// Set once completed: "success", "failure", "cancelled", "skipped", ...
+    pub conclusion: Option<String>,
+    pub head_branch: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub run_started_at: Option<DateTime<Utc>>,
+    pub html_url: String,
+}
+
+impl GitHubWorkflowRun {
+    /// The status in GitLab pipeline terms, so pipeline KPIs cover both.
+    pub fn pipeline_status(&self) -> &'static str {
+        match (self.status.as_deref(), self.conclusion.as_deref()) {
+            (Some("completed"), Some("success")) => "success",
+            (Some("completed"), Some("failure" | "timed_out" | "startup_failure")) => "failed",
+            (Some("completed"), Some("cancelled")) => "canceled",
+            (Some("completed"), Some("action_required")) => "manual",
+            (Some("completed"), _) => "skipped",
+            (Some("in_progress"), _) => "running",
+            (Some("waiting"), _) => "waiting_for_resource",
+            _ => "pending",
+        }
+    }
+
+    /// When a completed run finished. The API has no finish time; the last
+    /// update of a completed run is when it finished.
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        (self.status.as_deref() == Some("completed"))
+            .then_some(self.updated_at)
+            .flatten()
+    }
+
+    /// Run time of a completed run, from start (or creation) to finish.
+    pub fn duration_secs(&self) -> Option<i32> {
+        let started = self.run_started_at.or(self.created_at)?;
+        let secs = (self.finished_at()? - started).num_seconds();
+        i32::try_from(secs.max(0)).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(status: &str, conclusion: Option<&str>) -> GitHubWorkflowRun {
+        GitHubWorkflowRun {
+            id: 1,
+            name: Some("CI".to_string()),
+            status: Some(status.to_string()),
+            conclusion: conclusion.map(str::to_string),
+            head_branch: Some("main".to_string()),
+            created_at: "2026-03-02T09:00:00Z".parse().ok(),
+            updated_at: "2026-03-02T09:12:30Z".parse().ok(),
+            run_started_at: "2026-03-02T09:02:00Z".parse().ok(),
+            html_url: "https://github.com/acme/shop/actions/runs/1".to_string(),
+        }
+    }
+
+    #[test]
+    fn deserialize_member_and_profile() {
+        let member: GitHubUser =
+            serde_json::from_str(r#"{"id": 7, "login": "octo", "type": "User"}"#).unwrap();
+        assert_eq!(member.login, "octo");
+        assert!(member.name.is_none());
+        assert!(!member.is_service_account());
+        assert!(member.is_active());
+
+        let profile: GitHubUser = serde_json::from_str(
+            r#"{"id": 8, "login": "ci[bot]", "type": "Bot", "name": "CI",
+                "email": null, "suspended_at": "2026-01-05T10:00:00Z"}"#,
+        )
+        .unwrap();
+        assert!(profile.is_service_account());
+        assert!(!profile.is_active());
+    }
+
+    #[test]
+    fn pull_request_state_maps_to_mr_state() {
+        let mut pr: GitHubPullRequest = serde_json::from_str(
+            r#"{"number": 5, "title": "Add cart", "state": "open",
+                "user": {"login": "octo"}, "labels": [{"name": "feature"}],
+                "created_at": "2026-03-01T10:00:00Z", "updated_at": "2026-03-02T10:00:00Z",
+                "merged_at": null, "base": {"ref": "main"},
+                "html_url": "https://github.com/acme/shop/pull/5"}"#,
+        )
+        .unwrap();
+        assert_eq!(pr.mr_state(), "opened");
+
+        pr.state = "closed".to_string();
+        assert_eq!(pr.mr_state(), "closed");
+
+        pr.merged_at = pr.updated_at;
+        assert_eq!(pr.mr_state(), "merged");
+    }
+
+    #[test]
+    fn workflow_run_maps_to_pipeline_status_and_duration() {
+        assert_eq!(
+            run("completed", Some("success")).pipeline_status(),
+            "success"
+        );
+        assert_eq!(
+            run("completed", Some("timed_out")).pipeline_status(),
+            "failed"
+        );
+        assert_eq!(
+            run("completed", Some("cancelled")).pipeline_status(),
+            "canceled"
+        );
+        assert_eq!(
+            run("completed", Some("neutral")).pipeline_status(),
+            "skipped"
+        );
+        assert_eq!(run("in_progress", None).pipeline_status(), "running");
+        assert_eq!(run("queued", None).pipeline_status(), "pending");
+
+        assert_eq!(run("completed", Some("success")).duration_secs(), Some(630));
+        assert!(run("in_progress", None).finished_at().is_none());
+        assert!(run("in_progress", None).duration_secs().is_none());
+    }
+}
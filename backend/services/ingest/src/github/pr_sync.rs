@@ -0,0 +1,407 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use ovia_db::gitlab::models::{GitlabMergeRequest, GitlabPipeline, GitlabProject};
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::GitHubClient;
+use super::models::{
+    GitHubPullRequest as ApiPr, GitHubRepo as ApiRepo, GitHubWorkflowRun as ApiRun,
+};
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "github_pr_workflow";
+const PROVIDER: &str = "github";
+
+/// Workflow runs can only be filtered by creation time, so runs still going at
+/// the last sync are picked up by re-reading this much history.
+const RUN_OVERLAP_HOURS: i64 = 24;
+
+/// Syncs repositories, pull requests and Actions workflow runs into the same
+/// tables as GitLab projects, merge requests and pipelines, with provider
+/// `github`, so the delivery KPIs cover both.
+pub struct GitHubPrWorkflowSyncer<S> {
+    org_id: Uuid,
+    client: GitHubClient,
+    gitlab_repo: PgGitlabRepository,
+    sync_repo: S,
+}
+
+impl<S> GitHubPrWorkflowSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: GitHubClient,
+        gitlab_repo: PgGitlabRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            gitlab_repo,
+            sync_repo,
+        }
+    }
+
+    fn api_repo_to_db(&self, r: &ApiRepo) -> GitlabProject {
+        let now = Utc::now();
+        GitlabProject {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: PROVIDER.to_string(),
+            gitlab_id: r.id as i64,
+            name: r.name.clone(),
+            path_with_namespace: r.full_name.clone(),
+            web_url: r.html_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn api_pr_to_db(&self, repo_id: u64, pr: &ApiPr) -> GitlabMergeRequest {
+        let now = Utc::now();
+        GitlabMergeRequest {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: PROVIDER.to_string(),
+            gitlab_project_id: repo_id as i64,
+            gitlab_mr_iid: pr.number as i64,
+            title: pr.title.clone(),
+            state: pr.mr_state().to_string(),
+            author_username: pr.user.as_ref().map(|u| u.login.clone()),
+            labels: pr.labels.iter().map(|l| l.name.clone()).collect(),
+            created_at_gl: pr.created_at,
+            merged_at: pr.merged_at,
+            target_branch: pr.base.as_ref().map(|b| b.ref_name.clone()),
+            web_url: pr.html_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn api_run_to_db(&self, repo_id: u64, run: &ApiRun) -> GitlabPipeline {
+        let now = Utc::now();
+        GitlabPipeline {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: PROVIDER.to_string(),
+            gitlab_project_id: repo_id as i64,
+            gitlab_pipeline_id: run.id as i64,
+            status: run.pipeline_status().to_string(),
+            ref_name: run.head_branch.clone(),
+            created_at_gl: run.created_at,
+            finished_at_gl: run.finished_at(),
+            duration_secs: run.duration_secs(),
+            web_url: run.html_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Connector for GitHubPrWorkflowSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Ensure watermark row exists
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        // Try to acquire lock
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "github PR/workflow sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: SOURCE_NAME.to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        // Use cursor_value as updated_after for incremental sync
+        let updated_after = watermark
+            .cursor_value
+            .as_deref()
+            .and_then(|c| DateTime::parse_from_rfc3339(c).ok())
+            .map(|c| c.with_timezone(&Utc));
+        let runs_after = updated_after.map(|c| c - Duration::hours(RUN_OVERLAP_HOURS));
+
+        // Step 1: Fetch all active repositories
+        let repos = match self.client.fetch_all_repos().await {
+            Ok(r) => r,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "github repository fetch failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        tracing::info!(count = repos.len(), "fetched github repositories");
+
+        let mut upserted: usize = 0;
+        let mut errors: usize = 0;
+
+        // Upsert repositories
+        for r in &repos {
+            let db_project = self.api_repo_to_db(r);
+            match self.gitlab_repo.upsert_project(&db_project).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(repo = %r.full_name, error = %e, "failed to upsert repository");
+                    errors += 1;
+                }
+            }
+        }
+
+        // Step 2: For each repository, fetch pull requests and workflow runs
+        for r in &repos {
+            match self
+                .client
+                .fetch_pull_requests(&r.full_name, updated_after)
+                .await
+            {
+                Ok(prs) => {
+                    for pr in &prs {
+                        let db_mr = self.api_pr_to_db(r.id, pr);
+                        match self.gitlab_repo.upsert_merge_request(&db_mr).await {
+                            Ok(_) => upserted += 1,
+                            Err(e) => {
+                                tracing::warn!(pr = pr.number, error = %e, "failed to upsert pull request");
+                                errors += 1;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(repo = %r.full_name, error = %e, "failed to fetch pull requests");
+                    errors += 1;
+                }
+            }
+
+            match self
+                .client
+                .fetch_workflow_runs(&r.full_name, runs_after)
+                .await
+            {
+                Ok(runs) => {
+                    for run in &runs {
+                        let db_pl = self.api_run_to_db(r.id, run);
+                        match self.gitlab_repo.upsert_pipeline(&db_pl).await {
+                            Ok(_) => upserted += 1,
+                            Err(e) => {
+                                tracing::warn!(run_id = run.id, error = %e, "failed to upsert workflow run");
+                                errors += 1;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(repo = %r.full_name, error = %e, "failed to fetch workflow runs");
+                    errors += 1;
+                }
+            }
+        }
+
+        // Mark completed with current timestamp as cursor for next incremental sync
+        let cursor = Utc::now().to_rfc3339();
+        self.sync_repo
+            .mark_completed(watermark.id, Some(&cursor))
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted,
+            skipped: 0,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "github PR/workflow sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::client::GitHubClientConfig;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_client_config(base_url: &str) -> GitHubClientConfig {
+        GitHubClientConfig {
+            base_url: base_url.to_string(),
+            token: "ghp-test-token".to_string(),
+            orgs: vec!["acme".to_string()],
+            max_retries: 1,
+            timeout_secs: 5,
+        }
+    }
+
+    fn make_pr(
+        number: u64,
+        state: &str,
+        updated_at: &str,
+        merged_at: Option<&str>,
+    ) -> serde_json::Value {
+        serde_json::json!({
+            "number": number,
+            "title": format!("PR {number}"),
+            "state": state,
+            "user": { "login": "octo" },
+            "labels": [{ "name": "bug" }],
+            "created_at": "2026-02-01T09:00:00Z",
+            "updated_at": updated_at,
+            "merged_at": merged_at,
+            "base": { "ref": "main" },
+            "html_url": format!("https://github.com/acme/shop/pull/{number}")
+        })
+    }
+
+    #[tokio::test]
+    async fn client_fetch_pull_requests_stops_at_cursor() {
+        let server = MockServer::start().await;
+        let next = format!(
+            "<{}/repositories/7/pulls?state=all&page=2>; rel=\"next\"",
+            server.uri()
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/shop/pulls"))
+            .and(query_param("state", "all"))
+            .and(query_param("sort", "updated"))
+            .and(query_param("direction", "desc"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(vec![
+                        make_pr(3, "open", "2026-02-12T10:00:00Z", None),
+                        make_pr(
+                            2,
+                            "closed",
+                            "2026-02-11T10:00:00Z",
+                            Some("2026-02-11T10:00:00Z"),
+                        ),
+                        make_pr(1, "closed", "2026-01-20T10:00:00Z", None),
+                    ])
+                    .append_header("link", next.as_str()),
+            )
+            .mount(&server)
+            .await;
+        // Older pull requests are never requested
+        Mock::given(method("GET"))
+            .and(path("/repositories/7/pulls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(Vec::<serde_json::Value>::new()))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let prs = client
+            .fetch_pull_requests("acme/shop", "2026-02-01T00:00:00Z".parse().ok())
+            .await
+            .unwrap();
+        let states: Vec<_> = prs.iter().map(|p| (p.number, p.mr_state())).collect();
+        assert_eq!(states, vec![(3, "opened"), (2, "merged")]);
+    }
+
+    #[tokio::test]
+    async fn client_fetch_pull_requests_without_cursor_follows_all_pages() {
+        let server = MockServer::start().await;
+        let next = format!(
+            "<{}/repositories/7/pulls?state=all&page=2>; rel=\"next\"",
+            server.uri()
+        );
+
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/shop/pulls"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(vec![make_pr(2, "open", "2026-02-12T10:00:00Z", None)])
+                    .append_header("link", next.as_str()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repositories/7/pulls"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![make_pr(
+                1,
+                "closed",
+                "2025-06-01T10:00:00Z",
+                None,
+            )]))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let prs = client.fetch_pull_requests("acme/shop", None).await.unwrap();
+        assert_eq!(prs.len(), 2);
+        assert_eq!(prs[1].mr_state(), "closed");
+    }
+
+    #[tokio::test]
+    async fn client_fetch_workflow_runs_filters_on_creation() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/shop/actions/runs"))
+            .and(query_param("created", ">=2026-02-01T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "total_count": 1,
+                "workflow_runs": [{
+                    "id": 999,
+                    "name": "CI",
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "head_branch": "main",
+                    "created_at": "2026-02-20T12:00:00Z",
+                    "updated_at": "2026-02-20T12:05:00Z",
+                    "run_started_at": "2026-02-20T12:00:00Z",
+                    "html_url": "https://github.com/acme/shop/actions/runs/999"
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let runs = client
+            .fetch_workflow_runs("acme/shop", "2026-02-01T00:00:00Z".parse().ok())
+            .await
+            .unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].pipeline_status(), "failed");
+        assert_eq!(runs[0].duration_secs(), Some(300));
+    }
+}
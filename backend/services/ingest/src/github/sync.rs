@@ -0,0 +1,425 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use ovia_db::identity::models::Identity;
+use ovia_db::identity::repositories::IdentityRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::GitHubClient;
+use super::models::GitHubUser;
+use crate::connector::{Connector, SyncResult};
+
+pub struct GitHubSyncer<I, S> {
+    org_id: Uuid,
+    client: GitHubClient,
+    identity_repo: I,
+    sync_repo: S,
+}
+
+impl<I, S> GitHubSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    pub fn new(org_id: Uuid, client: GitHubClient, identity_repo: I, sync_repo: S) -> Self {
+        Self {
+            org_id,
+            client,
+            identity_repo,
+            sync_repo,
+        }
+    }
+
+    fn github_user_to_identity(&self, user: &GitHubUser) -> Identity {
+        Identity {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            source: "github".to_string(),
+            external_id: Some(user.id.to_string()),
+            username: Some(user.login.clone()),
+            email: user.email.clone(),
+            display_name: user.name.clone(),
+            is_service_account: user.is_service_account(),
+            is_active: user.is_active(),
+            active_changed_at: None,
+            first_seen_at: Some(Utc::now()),
+            last_seen_at: Some(Utc::now()),
+            raw_ref: serde_json::to_value(user).ok(),
+        }
+    }
+}
+
+#[async_trait]
+impl<I, S> Connector for GitHubSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        "github"
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Ensure watermark row exists
+        self.sync_repo
+            .get_or_create(self.org_id, "github")
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        // Try to acquire lock
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, "github")
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "github sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: "github".to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        // Fetch org members with their profiles
+        let users = match self.client.fetch_all_members().await {
+            Ok(users) => users,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "github member fetch failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        tracing::info!(count = users.len(), "fetched github members");
+
+        // Upsert each user
+        let mut upserted = 0;
+        let mut errors = 0;
+
+        for user in &users {
+            let identity = self.github_user_to_identity(user);
+            match self.identity_repo.upsert_by_external_id(identity).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(
+                        github_user_id = %user.id,
+                        error = %e,
+                        "failed to upsert github member"
+                    );
+                    errors += 1;
+                }
+            }
+        }
+
+        // Mark completed
+        self.sync_repo
+            .mark_completed(watermark.id, None)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: "github".to_string(),
+            upserted,
+            skipped: 0,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "github sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::client::{GitHubClient, GitHubClientConfig};
+    use ovia_db::identity::models::Identity;
+    use ovia_db::sync::models::SyncWatermark;
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    // -- Mock IdentityRepository --
+
+    #[derive(Clone)]
+    struct MockIdentityRepo {
+        upserted: Arc<Mutex<Vec<Identity>>>,
+    }
+
+    impl MockIdentityRepo {
+        fn new() -> Self {
+            Self {
+                upserted: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IdentityRepository for MockIdentityRepo {
+        async fn get_by_id(
+            &self,
+            _org_id: Uuid,
+            _id: Uuid,
+        ) -> ovia_common::error::OviaResult<Option<Identity>> {
+            Ok(None)
+        }
+
+        async fn create(&self, identity: Identity) -> ovia_common::error::OviaResult<Identity> {
+            Ok(identity)
+        }
+
+        async fn update(&self, identity: Identity) -> ovia_common::error::OviaResult<Identity> {
+            Ok(identity)
+        }
+
+        async fn upsert_by_external_id(
+            &self,
+            identity: Identity,
+        ) -> ovia_common::error::OviaResult<Identity> {
+            self.upserted.lock().unwrap().push(identity.clone());
+            Ok(identity)
+        }
+    }
+
+    // -- Mock SyncWatermarkRepository --
+
+    struct MockSyncRepo {
+        lock_available: bool,
+    }
+
+    impl MockSyncRepo {
+        fn new(lock_available: bool) -> Self {
+            Self { lock_available }
+        }
+
+        fn dummy_watermark() -> SyncWatermark {
+            SyncWatermark {
+                id: Uuid::new_v4(),
+                org_id: Uuid::new_v4(),
+                source: "github".to_string(),
+                last_synced_at: None,
+                cursor_value: None,
+                status: "running".to_string(),
+                error_message: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl SyncWatermarkRepository for MockSyncRepo {
+        async fn get_or_create(
+            &self,
+            _org_id: Uuid,
+            _source: &str,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            Ok(Self::dummy_watermark())
+        }
+
+        async fn acquire_lock(
+            &self,
+            _org_id: Uuid,
+            _source: &str,
+        ) -> ovia_common::error::OviaResult<Option<SyncWatermark>> {
+            if self.lock_available {
+                Ok(Some(Self::dummy_watermark()))
+            } else {
+                Ok(None)
+            }
+        }
+
+        async fn mark_completed(
+            &self,
+            _id: Uuid,
+            _cursor_value: Option<&str>,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            Ok(Self::dummy_watermark())
+        }
+
+        async fn mark_failed(
+            &self,
+            _id: Uuid,
+            _error_message: &str,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            Ok(Self::dummy_watermark())
+        }
+    }
+
+    fn test_client_config(base_url: &str) -> GitHubClientConfig {
+        GitHubClientConfig {
+            base_url: base_url.to_string(),
+            token: "ghp-test-token".to_string(),
+            orgs: vec!["acme".to_string()],
+            max_retries: 1,
+            timeout_secs: 5,
+        }
+    }
+
+    async fn mount_members(server: &MockServer, members: Vec<serde_json::Value>) {
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/members"))
+            .and(query_param("per_page", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(members))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn sync_upserts_members_with_profiles() {
+        let server = MockServer::start().await;
+        mount_members(
+            &server,
+            vec![
+                serde_json::json!({ "id": 1, "login": "octo", "type": "User" }),
+                serde_json::json!({ "id": 2, "login": "mona", "type": "User" }),
+            ],
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/users/octo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1, "login": "octo", "type": "User",
+                "name": "Octo Cat", "email": "octo@example.com"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/mona"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let identity_repo = MockIdentityRepo::new();
+        let sync_repo = MockSyncRepo::new(true);
+
+        let syncer = GitHubSyncer::new(Uuid::new_v4(), client, identity_repo.clone(), sync_repo);
+        let result = syncer.sync().await.expect("sync should succeed");
+
+        assert_eq!(result.source, "github");
+        assert_eq!(result.upserted, 2);
+        assert_eq!(result.errors, 0);
+
+        let upserted = identity_repo.upserted.lock().unwrap();
+        let octo = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("1"))
+            .unwrap();
+        assert_eq!(octo.source, "github");
+        assert_eq!(octo.username.as_deref(), Some("octo"));
+        assert_eq!(octo.email.as_deref(), Some("octo@example.com"));
+        assert_eq!(octo.display_name.as_deref(), Some("Octo Cat"));
+
+        // Profile fetch failed; the member record is kept as listed
+        let mona = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("2"))
+            .unwrap();
+        assert_eq!(mona.username.as_deref(), Some("mona"));
+        assert!(mona.display_name.is_none());
+    }
+
+    #[tokio::test]
+    async fn sync_skips_when_lock_unavailable() {
+        let server = MockServer::start().await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let identity_repo = MockIdentityRepo::new();
+        let sync_repo = MockSyncRepo::new(false);
+
+        let syncer = GitHubSyncer::new(Uuid::new_v4(), client, identity_repo, sync_repo);
+        let result = syncer.sync().await.expect("sync should succeed");
+
+        assert_eq!(result.upserted, 0);
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[tokio::test]
+    async fn sync_marks_bots_and_suspended_members() {
+        let server = MockServer::start().await;
+        mount_members(
+            &server,
+            vec![
+                serde_json::json!({ "id": 1, "login": "release-bot", "type": "Bot" }),
+                serde_json::json!({ "id": 2, "login": "former", "type": "User" }),
+            ],
+        )
+        .await;
+        Mock::given(method("GET"))
+            .and(path("/users/release-bot"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1, "login": "release-bot", "type": "Bot"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/former"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 2, "login": "former", "type": "User",
+                "suspended_at": "2026-01-05T10:00:00Z"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let identity_repo = MockIdentityRepo::new();
+        let sync_repo = MockSyncRepo::new(true);
+
+        let syncer = GitHubSyncer::new(Uuid::new_v4(), client, identity_repo.clone(), sync_repo);
+        syncer.sync().await.expect("sync should succeed");
+
+        let upserted = identity_repo.upserted.lock().unwrap();
+        let bot = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("1"))
+            .unwrap();
+        assert!(bot.is_service_account);
+        assert!(bot.is_active);
+
+        let former = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("2"))
+            .unwrap();
+        assert!(!former.is_service_account);
+        assert!(!former.is_active);
+    }
+
+    #[tokio::test]
+    async fn sync_fails_when_members_unavailable() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/orgs/acme/members"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(test_client_config(&server.uri())).unwrap();
+        let syncer = GitHubSyncer::new(
+            Uuid::new_v4(),
+            client,
+            MockIdentityRepo::new(),
+            MockSyncRepo::new(true),
+        );
+        assert!(syncer.sync().await.is_err());
+    }
+}
@@ -1,22 +1,53 @@
-use std::time::Duration;
+use std::collections::HashSet;
 
-use reqwest::{Client, StatusCode};
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::org::models::Org;
 use serde::de::DeserializeOwned;
 
-use super::models::{GitLabMergeRequest, GitLabPipeline, GitLabProject, GitLabUser};
+use crate::http::{Auth, HttpClient, HttpClientConfig, HttpClientError};
+
+use super::models::{
+    GitLabApprovals, GitLabCommit, GitLabDeployment, GitLabDiscussion, GitLabEnvironment,
+    GitLabIssue, GitLabJob, GitLabMergeRequest, GitLabMrChanges, GitLabPipeline, GitLabProject,
+    GitLabStateEvent, GitLabUser,
+};
 
 #[derive(Debug, Clone)]
 pub struct GitLabClientConfig {
     pub base_url: String,
     pub private_token: String,
+    /// Group paths or IDs to scope users and projects to. Empty means every
+    /// user and every project the token is a member of.
+    pub groups: Vec<String>,
     pub max_retries: u32,
     pub timeout_secs: u64,
 }
 
 impl GitLabClientConfig {
-    pub fn from_env() -> Option<Self> {
-        let base_url = std::env::var("GITLAB_BASE_URL").ok()?;
-        let private_token = std::env::var("GITLAB_PRIVATE_TOKEN").ok()?;
+    /// Load GitLab config for an org. The org's base URL and groups take precedence
+    /// over `GITLAB_BASE_URL` / `GITLAB_GROUPS`, and the vault credential over
+    /// `GITLAB_PRIVATE_TOKEN`; the env is only consulted with `env_fallback`. Tuning
+    /// comes from the environment. Returns `None` if no base URL or token is available.
+    pub fn for_org(
+        org: &Org,
+        credential: Option<&SourceSecret>,
+        env_fallback: bool,
+    ) -> Option<Self> {
+        let env = |name: &str| env_fallback.then(|| std::env::var(name).ok()).flatten();
+        let base_url = org
+            .gitlab_base_url
+            .clone()
+            .or_else(|| env("GITLAB_BASE_URL"))?;
+        let private_token = credential
+            .map(|c| c.secret.clone())
+            .or_else(|| env("GITLAB_PRIVATE_TOKEN"))?;
+        let groups = if org.gitlab_groups.is_empty() {
+            env("GITLAB_GROUPS")
+                .map(|v| parse_csv(&v))
+                .unwrap_or_default()
+        } else {
+            org.gitlab_groups.clone()
+        };
         let max_retries = std::env::var("GITLAB_MAX_RETRIES")
             .ok()
             .and_then(|v| v.parse().ok())
@@ -29,36 +60,38 @@ impl GitLabClientConfig {
         Some(Self {
             base_url,
             private_token,
+            groups,
             max_retries,
             timeout_secs,
         })
     }
 }
 
+fn parse_csv(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
 #[derive(Clone)]
 pub struct GitLabClient {
-    client: Client,
+    http: HttpClient,
     config: GitLabClientConfig,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum GitLabClientError {
-    #[error("HTTP {status}: {body}")]
-    HttpError { status: StatusCode, body: String },
-
-    #[error("request failed: {0}")]
-    RequestError(#[from] reqwest::Error),
-
-    #[error("max retries exceeded after {attempts} attempts: {last_error}")]
-    MaxRetriesExceeded { attempts: u32, last_error: String },
-}
+pub type GitLabClientError = HttpClientError;
 
 impl GitLabClient {
     pub fn new(config: GitLabClientConfig) -> Result<Self, reqwest::Error> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()?;
-        Ok(Self { client, config })
+        let http = HttpClient::new(
+            HttpClientConfig::from_env(config.max_retries, config.timeout_secs),
+            Auth::Header {
+                name: "PRIVATE-TOKEN",
+                value: config.private_token.clone(),
+            },
+        )?;
+        Ok(Self { http, config })
     }
 
     /// For testing: create a client pointing at a specific base URL (e.g., wiremock).
@@ -69,18 +102,54 @@ impl GitLabClient {
     }
 
     /// Fetch all users via paginated API, retrying transient errors.
+    ///
+    /// With groups configured, returns the (inherited) members of those groups,
+    /// deduplicated by user id.
     pub async fn fetch_all_users(&self) -> Result<Vec<GitLabUser>, GitLabClientError> {
-        let url = format!("{}/api/v4/users?per_page=100", self.config.base_url);
-        self.fetch_all_pages(&url).await
+        if self.config.groups.is_empty() {
+            let url = format!("{}/api/v4/users?per_page=100", self.config.base_url);
+            return self.fetch_all_pages(&url).await;
+        }
+
+        let mut seen = HashSet::new();
+        let mut users = Vec::new();
+        for group in &self.config.groups {
+            let url = format!(
+                "{}/api/v4/groups/{}/members/all?per_page=100",
+                self.config.base_url,
+                urlencoding::encode(group)
+            );
+            let members: Vec<GitLabUser> = self.fetch_all_pages(&url).await?;
+            users.extend(members.into_iter().filter(|u| seen.insert(u.id)));
+        }
+        Ok(users)
     }
 
     /// Fetch all active (non-archived) projects.
+    ///
+    /// With groups configured, returns the projects of those groups including
+    /// subgroups, deduplicated by project id.
     pub async fn fetch_all_projects(&self) -> Result<Vec<GitLabProject>, GitLabClientError> {
-        let url = format!(
-            "{}/api/v4/projects?per_page=100&simple=true&archived=false&membership=true",
-            self.config.base_url
-        );
-        self.fetch_all_pages(&url).await
+        if self.config.groups.is_empty() {
+            let url = format!(
+                "{}/api/v4/projects?per_page=100&simple=true&archived=false&membership=true",
+                self.config.base_url
+            );
+            return self.fetch_all_pages(&url).await;
+        }
+
+        let mut seen = HashSet::new();
+        let mut projects = Vec::new();
+        for group in &self.config.groups {
+            let url = format!(
+                "{}/api/v4/groups/{}/projects?per_page=100&simple=true&archived=false&include_subgroups=true",
+                self.config.base_url,
+                urlencoding::encode(group)
+            );
+            let group_projects: Vec<GitLabProject> = self.fetch_all_pages(&url).await?;
+            projects.extend(group_projects.into_iter().filter(|p| seen.insert(p.id)));
+        }
+        Ok(projects)
     }
 
     /// Fetch merged MRs for a project, optionally filtered by `updated_after`.
@@ -111,6 +180,89 @@ impl GitLabClient {
         self.fetch_all_pages(&url).await
     }
 
+    /// Fetch issues of a project in any state, optionally filtered by `updated_after`.
+    pub async fn fetch_issues(
+        &self,
+        project_id: u64,
+        updated_after: Option<&str>,
+    ) -> Result<Vec<GitLabIssue>, GitLabClientError> {
+        let mut url = format!(
+            "{}/api/v4/projects/{}/issues?scope=all&per_page=100",
+            self.config.base_url, project_id
+        );
+        if let Some(after) = updated_after {
+            url.push_str(&format!("&updated_after={after}"));
+        }
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch the open/close/reopen history of an issue.
+    pub async fn fetch_issue_state_events(
+        &self,
+        project_id: u64,
+        issue_iid: u64,
+    ) -> Result<Vec<GitLabStateEvent>, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/issues/{}/resource_state_events?per_page=100",
+            self.config.base_url, project_id, issue_iid
+        );
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch the commits that make up a merge request.
+    pub async fn fetch_mr_commits(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+    ) -> Result<Vec<GitLabCommit>, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/merge_requests/{}/commits?per_page=100",
+            self.config.base_url, project_id, mr_iid
+        );
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch the file diffs of a merge request.
+    pub async fn fetch_mr_changes(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+    ) -> Result<GitLabMrChanges, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/merge_requests/{}/changes",
+            self.config.base_url, project_id, mr_iid
+        );
+        let (changes, _) = self.request_with_retry::<GitLabMrChanges>(&url).await?;
+        Ok(changes)
+    }
+
+    /// Fetch the discussion threads of a merge request, including system notes.
+    pub async fn fetch_mr_discussions(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+    ) -> Result<Vec<GitLabDiscussion>, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/merge_requests/{}/discussions?per_page=100",
+            self.config.base_url, project_id, mr_iid
+        );
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch who currently approves a merge request.
+    pub async fn fetch_mr_approvals(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+    ) -> Result<GitLabApprovals, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/merge_requests/{}/approvals",
+            self.config.base_url, project_id, mr_iid
+        );
+        let (approvals, _) = self.request_with_retry::<GitLabApprovals>(&url).await?;
+        Ok(approvals)
+    }
+
     /// Fetch pipelines for a project, optionally filtered by `updated_after`.
     pub async fn fetch_pipelines(
         &self,
@@ -127,6 +279,47 @@ impl GitLabClient {
         self.fetch_all_pages(&url).await
     }
 
+    /// Fetch the jobs of a pipeline, including attempts that were retried.
+    pub async fn fetch_pipeline_jobs(
+        &self,
+        project_id: u64,
+        pipeline_id: u64,
+    ) -> Result<Vec<GitLabJob>, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/pipelines/{}/jobs?per_page=100&include_retried=true",
+            self.config.base_url, project_id, pipeline_id
+        );
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch the environments of a project.
+    pub async fn fetch_environments(
+        &self,
+        project_id: u64,
+    ) -> Result<Vec<GitLabEnvironment>, GitLabClientError> {
+        let url = format!(
+            "{}/api/v4/projects/{}/environments?per_page=100",
+            self.config.base_url, project_id
+        );
+        self.fetch_all_pages(&url).await
+    }
+
+    /// Fetch deployments for a project, optionally filtered by `updated_after`.
+    pub async fn fetch_deployments(
+        &self,
+        project_id: u64,
+        updated_after: Option<&str>,
+    ) -> Result<Vec<GitLabDeployment>, GitLabClientError> {
+        let mut url = format!(
+            "{}/api/v4/projects/{}/deployments?per_page=100&order_by=updated_at",
+            self.config.base_url, project_id
+        );
+        if let Some(after) = updated_after {
+            url.push_str(&format!("&updated_after={after}"));
+        }
+        self.fetch_all_pages(&url).await
+    }
+
     /// Generic paginated fetch: follows `x-next-page` headers collecting all items.
     async fn fetch_all_pages<T: DeserializeOwned>(
         &self,
@@ -138,7 +331,7 @@ impl GitLabClient {
 
         loop {
             let url = format!("{base_url}{separator}page={page}");
-            let (items, next_page) = self.request_with_retry::<T>(&url).await?;
+            let (items, next_page) = self.request_with_retry::<Vec<T>>(&url).await?;
             all_items.extend(items);
 
             match next_page {
@@ -150,91 +343,25 @@ impl GitLabClient {
         Ok(all_items)
     }
 
+    /// GET one page with retries. Returns the body and the `x-next-page` header.
     async fn request_with_retry<T: DeserializeOwned>(
         &self,
         url: &str,
-    ) -> Result<(Vec<T>, Option<String>), GitLabClientError> {
-        let mut last_error = String::new();
-
-        for attempt in 0..=self.config.max_retries {
-            if attempt > 0 {
-                let backoff_secs = std::cmp::min(1u64 << attempt, 30);
-                tracing::warn!(attempt, backoff_secs, "retrying after backoff");
-                tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
-            }
-
-            let response = match self
-                .client
-                .get(url)
-                .header("PRIVATE-TOKEN", &self.config.private_token)
-                .send()
-                .await
-            {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = e.to_string();
-                    if e.is_timeout() || e.is_connect() {
-                        continue;
-                    }
-                    return Err(GitLabClientError::RequestError(e));
-                }
-            };
-
-            let status = response.status();
-
-            if status.is_success() {
-                let next_page = response
-                    .headers()
-                    .get("x-next-page")
-                    .and_then(|v| v.to_str().ok())
-                    .map(|s| s.to_string());
-
-                let items = response
-                    .json::<Vec<T>>()
-                    .await
-                    .map_err(GitLabClientError::RequestError)?;
-
-                return Ok((items, next_page));
-            }
-
-            // Honor Retry-After header for 429
-            if status == StatusCode::TOO_MANY_REQUESTS {
-                if let Some(retry_after) = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok())
-                {
-                    let wait = std::cmp::min(retry_after, 60);
-                    tracing::warn!(wait, "rate-limited, waiting Retry-After");
-                    tokio::time::sleep(Duration::from_secs(wait)).await;
-                }
-                last_error = "429 Too Many Requests".to_string();
-                continue;
-            }
-
-            // Retry on 5xx
-            if status.is_server_error() {
-                let body = response.text().await.unwrap_or_default();
-                last_error = format!("{status}: {body}");
-                continue;
-            }
-
-            // Fail fast on 4xx (except 429 handled above)
-            let body = response.text().await.unwrap_or_default();
-            return Err(GitLabClientError::HttpError { status, body });
-        }
-
-        Err(GitLabClientError::MaxRetriesExceeded {
-            attempts: self.config.max_retries + 1,
-            last_error,
-        })
+    ) -> Result<(T, Option<String>), GitLabClientError> {
+        let response = self.http.get(url).await?;
+        let next_page = response
+            .headers
+            .get("x-next-page")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+        Ok((response.body, next_page))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reqwest::StatusCode;
     use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -242,6 +369,7 @@ mod tests {
         GitLabClientConfig {
             base_url: "http://localhost".to_string(),
             private_token: "glpat-test-token".to_string(),
+            groups: Vec::new(),
             max_retries: 2,
             timeout_secs: 5,
         }
@@ -433,4 +561,52 @@ mod tests {
         let err = client.fetch_all_users().await.unwrap_err();
         assert!(matches!(err, GitLabClientError::MaxRetriesExceeded { .. }));
     }
+
+    #[tokio::test]
+    async fn group_scope_dedupes_members_and_projects() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/groups/platform/members/all"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(make_users(2, 0)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/groups/acme%2Fmobile/members/all"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(make_users(2, 1)))
+            .mount(&server)
+            .await;
+
+        let project = serde_json::json!({
+            "id": 7,
+            "name": "api",
+            "path_with_namespace": "platform/api",
+            "web_url": "https://gitlab.example.com/platform/api"
+        });
+        Mock::given(method("GET"))
+            .and(path("/api/v4/groups/platform/projects"))
+            .and(query_param("include_subgroups", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![project.clone()]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v4/groups/acme%2Fmobile/projects"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![project]))
+            .mount(&server)
+            .await;
+
+        let mut config = test_config();
+        config.groups = vec!["platform".to_string(), "acme/mobile".to_string()];
+        let client = GitLabClient::new(config)
+            .unwrap()
+            .with_base_url(&server.uri());
+
+        let users = client.fetch_all_users().await.unwrap();
+        let ids: Vec<u64> = users.iter().map(|u| u.id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+
+        let projects = client.fetch_all_projects().await.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].id, 7);
+    }
 }
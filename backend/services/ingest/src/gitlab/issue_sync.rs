@@ -0,0 +1,305 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use ovia_db::gitlab::models::{GitlabIssue, GitlabIssueStateEvent};
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::GitLabClient;
+use super::models::{GitLabIssue as ApiIssue, GitLabStateEvent as ApiStateEvent};
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "gitlab_issues";
+
+/// Syncs GitLab issues and their state events, for teams that track work in GitLab
+/// rather than Jira.
+pub struct GitLabIssueSyncer<S> {
+    org_id: Uuid,
+    client: GitLabClient,
+    gitlab_repo: PgGitlabRepository,
+    sync_repo: S,
+}
+
+impl<S> GitLabIssueSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: GitLabClient,
+        gitlab_repo: PgGitlabRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            gitlab_repo,
+            sync_repo,
+        }
+    }
+
+    fn api_issue_to_db(&self, project_id: u64, i: &ApiIssue) -> GitlabIssue {
+        let now = Utc::now();
+        GitlabIssue {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            gitlab_project_id: project_id as i64,
+            gitlab_issue_iid: i.iid as i64,
+            gitlab_issue_id: i.id as i64,
+            title: i.title.clone(),
+            state: i.state.clone(),
+            issue_type: i.issue_type.clone(),
+            author_username: i.author.as_ref().map(|a| a.username.clone()),
+            assignee_usernames: i.assignees.iter().map(|a| a.username.clone()).collect(),
+            labels: i.labels.clone(),
+            milestone_id: i.milestone.as_ref().map(|m| m.id as i64),
+            milestone_title: i.milestone.as_ref().map(|m| m.title.clone()),
+            created_at_gl: i.created_at,
+            updated_at_gl: i.updated_at,
+            closed_at: i.closed_at,
+            web_url: i.web_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn api_state_event_to_db(
+        &self,
+        project_id: u64,
+        issue_iid: u64,
+        e: &ApiStateEvent,
+    ) -> GitlabIssueStateEvent {
+        GitlabIssueStateEvent {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            gitlab_project_id: project_id as i64,
+            gitlab_issue_iid: issue_iid as i64,
+            gitlab_event_id: e.id as i64,
+            state: e.state.clone(),
+            username: e.user.as_ref().map(|u| u.username.clone()),
+            created_at_gl: e.created_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Store the state events of an issue. Returns `(upserted, errors)`.
+    async fn sync_state_events(&self, project_id: u64, issue_iid: u64) -> (usize, usize) {
+        let events = match self
+            .client
+            .fetch_issue_state_events(project_id, issue_iid)
+            .await
+        {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::warn!(project_id, issue_iid, error = %e, "failed to fetch issue state events");
+                return (0, 1);
+            }
+        };
+
+        let (mut upserted, mut errors) = (0, 0);
+        for e in &events {
+            let db_event = self.api_state_event_to_db(project_id, issue_iid, e);
+            match self.gitlab_repo.insert_issue_state_event(&db_event).await {
+                Ok(_) => upserted += 1,
+                Err(err) => {
+                    tracing::warn!(event_id = e.id, error = %err, "failed to insert issue state event");
+                    errors += 1;
+                }
+            }
+        }
+        (upserted, errors)
+    }
+}
+
+#[async_trait]
+impl<S> Connector for GitLabIssueSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "gitlab issue sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: SOURCE_NAME.to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        let updated_after = watermark.cursor_value.as_deref();
+
+        let projects = match self.client.fetch_all_projects().await {
+            Ok(p) => p,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "gitlab project fetch failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        let mut upserted: usize = 0;
+        let mut errors: usize = 0;
+
+        for p in &projects {
+            let issues = match self.client.fetch_issues(p.id, updated_after).await {
+                Ok(i) => i,
+                Err(e) => {
+                    tracing::warn!(project_id = p.id, error = %e, "failed to fetch issues");
+                    errors += 1;
+                    continue;
+                }
+            };
+
+            for issue in &issues {
+                let db_issue = self.api_issue_to_db(p.id, issue);
+                match self.gitlab_repo.upsert_issue(&db_issue).await {
+                    Ok(_) => upserted += 1,
+                    Err(e) => {
+                        tracing::warn!(issue_iid = issue.iid, error = %e, "failed to upsert issue");
+                        errors += 1;
+                        continue;
+                    }
+                }
+
+                // Reopen times only matter once an issue is closed (cycle time)
+                if issue.closed_at.is_some() {
+                    let (events_upserted, event_errors) =
+                        self.sync_state_events(p.id, issue.iid).await;
+                    upserted += events_upserted;
+                    errors += event_errors;
+                }
+            }
+        }
+
+        let cursor = Utc::now().to_rfc3339();
+        self.sync_repo
+            .mark_completed(watermark.id, Some(&cursor))
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted,
+            skipped: 0,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "gitlab issue sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gitlab::client::{GitLabClient, GitLabClientConfig};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_client_config(base_url: &str) -> GitLabClientConfig {
+        GitLabClientConfig {
+            base_url: base_url.to_string(),
+            private_token: "glpat-test-token".to_string(),
+            groups: Vec::new(),
+            max_retries: 1,
+            timeout_secs: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn client_fetch_issues_and_state_events() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/issues"))
+            .and(query_param("scope", "all"))
+            .and(query_param("updated_after", "2026-02-01T00:00:00Z"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(vec![serde_json::json!({
+                    "id": 8801,
+                    "iid": 12,
+                    "title": "Checkout fails on empty cart",
+                    "state": "closed",
+                    "issue_type": "issue",
+                    "author": { "username": "carol" },
+                    "assignees": [{ "username": "alice" }, { "username": "bob" }],
+                    "labels": ["bug", "priority::critical"],
+                    "milestone": { "id": 3, "title": "Sprint 7" },
+                    "created_at": "2026-02-02T09:00:00Z",
+                    "updated_at": "2026-02-05T16:00:00Z",
+                    "closed_at": "2026-02-05T16:00:00Z",
+                    "web_url": "https://gitlab.example.com/group/my-project/-/issues/12"
+                })]),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/issues/12/resource_state_events"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                serde_json::json!({
+                    "id": 501,
+                    "state": "closed",
+                    "user": { "username": "alice" },
+                    "created_at": "2026-02-03T10:00:00Z",
+                    "resource_type": "Issue",
+                    "resource_id": 8801
+                }),
+                serde_json::json!({
+                    "id": 502,
+                    "state": "reopened",
+                    "user": { "username": "carol" },
+                    "created_at": "2026-02-04T08:00:00Z",
+                    "resource_type": "Issue",
+                    "resource_id": 8801
+                }),
+            ]))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let issues = client
+            .fetch_issues(42, Some("2026-02-01T00:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].assignees.len(), 2);
+        assert_eq!(issues[0].milestone.as_ref().unwrap().title, "Sprint 7");
+        assert!(issues[0].closed_at.is_some());
+
+        let events = client.fetch_issue_state_events(42, 12).await.unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].state, "reopened");
+        assert_eq!(events[1].user.as_ref().unwrap().username, "carol");
+    }
+}
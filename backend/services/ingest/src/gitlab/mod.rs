@@ -1,4 +1,60 @@
 pub mod client;
+pub mod issue_sync;
 pub mod models;
 pub mod mr_sync;
 pub mod sync;
+
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::sync::pg_repository::PgSyncRepository;
+
+use crate::registry::{ConnectorContext, ConnectorSpec, SourceRegistration};
+use client::{GitLabClient, GitLabClientConfig};
+use issue_sync::GitLabIssueSyncer;
+use mr_sync::GitLabMrPipelineSyncer;
+use sync::GitLabSyncer;
+
+inventory::submit! {
+    SourceRegistration {
+        source: "gitlab",
+        factory: connectors,
+    }
+}
+
+/// GitLab users, then merge requests and pipelines, then issues.
+pub fn connectors(ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+    let Some(config) =
+        GitLabClientConfig::for_org(&ctx.org, ctx.credential("gitlab"), ctx.allow_env_fallback)
+    else {
+        tracing::info!(org_id = %ctx.org.id, "no gitlab credentials found, skipping gitlab sync");
+        return Ok(Vec::new());
+    };
+    tracing::info!(org_id = %ctx.org.id, groups = ?config.groups, "gitlab connector configured");
+
+    let client = GitLabClient::new(config).map_err(|e| e.to_string())?;
+    let pool = &ctx.pool;
+
+    Ok(vec![
+        ConnectorSpec::new(GitLabSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgIdentityRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        )),
+        ConnectorSpec::new(GitLabMrPipelineSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgGitlabRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("gitlab"),
+        // Issue keys use project paths, which the MR/pipeline sync stores
+        ConnectorSpec::new(GitLabIssueSyncer::new(
+            ctx.org.id,
+            client,
+            PgGitlabRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("gitlab_mr_pipeline"),
+    ])
+}
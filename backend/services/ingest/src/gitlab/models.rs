@@ -18,6 +18,17 @@ impl GitLabUser {
     pub fn is_service_account(&self) -> bool {
         self.bot == Some(true)
     }
+
+    /// Returns `false` if the account is blocked, banned or deactivated.
+    /// A missing `state` is treated as active.
+    pub fn is_active(&self) -> bool {
+        !matches!(
+            self.state.as_deref(),
+            Some(
+                "blocked" | "ldap_blocked" | "blocked_pending_approval" | "banned" | "deactivated"
+            )
+        )
+    }
 }
 
 /// A project record from the GitLab REST API (`GET /api/v4/projects`).
@@ -46,9 +57,102 @@ pub struct GitLabMergeRequest {
     pub labels: Vec<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub merged_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub target_branch: Option<String>,
     pub web_url: String,
 }
 
+/// A commit record from the MR commits API
+/// (`GET /api/v4/projects/:id/merge_requests/:iid/commits`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabCommit {
+    /// Full commit SHA.
+    pub id: String,
+    pub title: String,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub authored_date: Option<DateTime<Utc>>,
+    pub committed_date: Option<DateTime<Utc>>,
+    pub web_url: Option<String>,
+}
+
+/// One changed file in the MR changes response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabMrChange {
+    #[serde(default)]
+    pub diff: String,
+}
+
+/// The MR changes response (`GET /api/v4/projects/:id/merge_requests/:iid/changes`).
+/// Only the file diffs are read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabMrChanges {
+    #[serde(default)]
+    pub changes: Vec<GitLabMrChange>,
+}
+
+impl GitLabMrChanges {
+    /// `(additions, deletions)` counted from the unified diffs. GitLab cuts very
+    /// large diffs short, so huge MRs are undercounted rather than missed.
+    pub fn line_counts(&self) -> (i32, i32) {
+        let (mut additions, mut deletions) = (0, 0);
+        for line in self.changes.iter().flat_map(|c| c.diff.lines()) {
+            if line.starts_with('+') && !line.starts_with("+++") {
+                additions += 1;
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                deletions += 1;
+            }
+        }
+        (additions, deletions)
+    }
+}
+
+/// A note inside an MR discussion
+/// (`GET /api/v4/projects/:id/merge_requests/:iid/discussions`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabNote {
+    pub id: u64,
+    #[serde(default)]
+    pub body: String,
+    pub author: Option<GitLabMrAuthor>,
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub system: bool,
+    #[serde(default)]
+    pub resolvable: bool,
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+impl GitLabNote {
+    /// Returns `true` for the system note GitLab adds when someone approves.
+    pub fn is_approval(&self) -> bool {
+        self.system && self.body.trim() == "approved this merge request"
+    }
+}
+
+/// A discussion thread on a merge request. Single comments are one-note discussions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabDiscussion {
+    pub id: String,
+    #[serde(default)]
+    pub notes: Vec<GitLabNote>,
+}
+
+/// An entry of `approved_by` in the MR approvals response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabApprover {
+    pub user: GitLabMrAuthor,
+}
+
+/// Current approvals of a merge request
+/// (`GET /api/v4/projects/:id/merge_requests/:iid/approvals`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabApprovals {
+    #[serde(default)]
+    pub approved_by: Vec<GitLabApprover>,
+}
+
 /// A pipeline record from the GitLab REST API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitLabPipeline {
@@ -62,6 +166,122 @@ pub struct GitLabPipeline {
     pub web_url: String,
 }
 
+/// Milestone embedded in an issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabMilestone {
+    pub id: u64,
+    pub title: String,
+}
+
+/// An issue record (`GET /api/v4/projects/:id/issues`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabIssue {
+    pub id: u64,
+    pub iid: u64,
+    pub title: String,
+    pub state: String,
+    pub issue_type: Option<String>,
+    pub author: Option<GitLabMrAuthor>,
+    #[serde(default)]
+    pub assignees: Vec<GitLabMrAuthor>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub milestone: Option<GitLabMilestone>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub web_url: String,
+}
+
+/// An issue state change
+/// (`GET /api/v4/projects/:id/issues/:iid/resource_state_events`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabStateEvent {
+    pub id: u64,
+    pub state: String,
+    pub user: Option<GitLabMrAuthor>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Commit reference embedded in a job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabJobCommit {
+    pub id: String,
+}
+
+/// A job of a pipeline (`GET /api/v4/projects/:id/pipelines/:pipeline_id/jobs`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabJob {
+    pub id: u64,
+    pub name: String,
+    pub stage: Option<String>,
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    pub commit: Option<GitLabJobCommit>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub duration: Option<f64>,
+    #[serde(default)]
+    pub allow_failure: bool,
+    pub failure_reason: Option<String>,
+    #[serde(default)]
+    pub retried: bool,
+    pub web_url: Option<String>,
+}
+
+/// An environment of a project (`GET /api/v4/projects/:id/environments`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabEnvironment {
+    pub id: u64,
+    pub name: String,
+    pub tier: Option<String>,
+    pub state: Option<String>,
+}
+
+/// Environment reference embedded in a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabDeploymentEnvironment {
+    pub name: String,
+}
+
+/// The job that ran a deployment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabDeployable {
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// A deployment record (`GET /api/v4/projects/:id/deployments`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLabDeployment {
+    pub id: u64,
+    pub status: String,
+    pub sha: Option<String>,
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub environment: GitLabDeploymentEnvironment,
+    pub user: Option<GitLabMrAuthor>,
+    pub deployable: Option<GitLabDeployable>,
+}
+
+impl GitLabDeployment {
+    /// When the deployment finished. Prefers the deploy job's finish time and falls
+    /// back to the last update for finished deployments without a job.
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.deployable
+            .as_ref()
+            .and_then(|d| d.finished_at)
+            .or_else(|| {
+                matches!(self.status.as_str(), "success" | "failed" | "canceled")
+                    .then_some(self.updated_at)
+                    .flatten()
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,5 +355,21 @@ mod tests {
         assert!(user.state.is_none());
         assert!(user.bot.is_none());
         assert!(!user.is_service_account());
+        assert!(user.is_active());
+    }
+
+    #[test]
+    fn blocked_and_deactivated_users_are_inactive() {
+        for state in ["blocked", "deactivated", "banned"] {
+            let user = GitLabUser {
+                id: 4,
+                username: "gone".to_string(),
+                email: None,
+                name: None,
+                state: Some(state.to_string()),
+                bot: None,
+            };
+            assert!(!user.is_active(), "{state} should be inactive");
+        }
     }
 }
@@ -1,13 +1,20 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use ovia_db::gitlab::models::{GitlabMergeRequest, GitlabPipeline, GitlabProject};
+use ovia_db::gitlab::models::{
+    GitlabCommit, GitlabDeployment, GitlabEnvironment, GitlabMergeRequest, GitlabMrApproval,
+    GitlabMrNote, GitlabPipeline, GitlabPipelineJob, GitlabProject, MrSize,
+};
 use ovia_db::gitlab::pg_repository::PgGitlabRepository;
 use ovia_db::sync::repositories::SyncWatermarkRepository;
 
 use super::client::GitLabClient;
 use super::models::{
+    GitLabApprovals as ApiApprovals, GitLabCommit as ApiCommit, GitLabDeployment as ApiDeployment,
+    GitLabDiscussion as ApiDiscussion, GitLabEnvironment as ApiEnvironment, GitLabJob as ApiJob,
     GitLabMergeRequest as ApiMr, GitLabPipeline as ApiPipeline, GitLabProject as ApiProject,
 };
 use crate::connector::{Connector, SyncResult};
@@ -44,6 +51,7 @@ where
         GitlabProject {
             id: Uuid::new_v4(),
             org_id: self.org_id,
+            provider: "gitlab".to_string(),
             gitlab_id: p.id as i64,
             name: p.name.clone(),
             path_with_namespace: p.path_with_namespace.clone(),
@@ -58,6 +66,7 @@ where
         GitlabMergeRequest {
             id: Uuid::new_v4(),
             org_id: self.org_id,
+            provider: "gitlab".to_string(),
             gitlab_project_id: project_id as i64,
             gitlab_mr_iid: mr.iid as i64,
             title: mr.title.clone(),
@@ -66,17 +75,206 @@ where
             labels: mr.labels.clone(),
             created_at_gl: mr.created_at,
             merged_at: mr.merged_at,
+            target_branch: mr.target_branch.clone(),
             web_url: mr.web_url.clone(),
             created_at: now,
             updated_at: now,
         }
     }
 
+    fn api_commit_to_db(&self, project_id: u64, mr_iid: u64, c: &ApiCommit) -> GitlabCommit {
+        let now = Utc::now();
+        GitlabCommit {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: "gitlab".to_string(),
+            gitlab_project_id: project_id as i64,
+            sha: c.id.clone(),
+            gitlab_mr_iid: Some(mr_iid as i64),
+            title: c.title.clone(),
+            author_name: c.author_name.clone(),
+            author_email: c.author_email.clone(),
+            authored_at: c.authored_date,
+            committed_at: c.committed_date,
+            web_url: c.web_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Store the commits of a merged MR. Returns `(upserted, errors)`.
+    async fn sync_mr_commits(&self, project_id: u64, mr_iid: u64) -> (usize, usize) {
+        let commits = match self.client.fetch_mr_commits(project_id, mr_iid).await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(project_id, mr_iid, error = %e, "failed to fetch MR commits");
+                return (0, 1);
+            }
+        };
+
+        let (mut upserted, mut errors) = (0, 0);
+        for c in &commits {
+            let db_commit = self.api_commit_to_db(project_id, mr_iid, c);
+            match self.gitlab_repo.upsert_commit(&db_commit).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(sha = %c.id, error = %e, "failed to upsert commit");
+                    errors += 1;
+                }
+            }
+        }
+        (upserted, errors)
+    }
+
+    /// Fetch the diff of an MR and store its size. Returns `(upserted, errors)`.
+    async fn sync_mr_size(&self, project_id: u64, mr_iid: u64) -> (usize, usize) {
+        let changes = match self.client.fetch_mr_changes(project_id, mr_iid).await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::warn!(project_id, mr_iid, error = %e, "failed to fetch MR changes");
+                return (0, 1);
+            }
+        };
+
+        let (additions, deletions) = changes.line_counts();
+        let size = MrSize {
+            additions,
+            deletions,
+            changed_files: changes.changes.len() as i32,
+        };
+        match self
+            .gitlab_repo
+            .update_mr_size(self.org_id, project_id as i64, mr_iid as i64, size)
+            .await
+        {
+            Ok(_) => (1, 0),
+            Err(e) => {
+                tracing::warn!(project_id, mr_iid, error = %e, "failed to store MR size");
+                (0, 1)
+            }
+        }
+    }
+
+    fn api_discussions_to_db(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+        discussions: &[ApiDiscussion],
+    ) -> Vec<GitlabMrNote> {
+        let now = Utc::now();
+        discussions
+            .iter()
+            .flat_map(|d| {
+                d.notes.iter().map(move |n| GitlabMrNote {
+                    id: Uuid::new_v4(),
+                    org_id: self.org_id,
+                    provider: "gitlab".to_string(),
+                    gitlab_project_id: project_id as i64,
+                    gitlab_mr_iid: mr_iid as i64,
+                    gitlab_note_id: n.id as i64,
+                    discussion_id: Some(d.id.clone()),
+                    author_username: n.author.as_ref().map(|a| a.username.clone()),
+                    system: n.system,
+                    resolvable: n.resolvable,
+                    resolved: n.resolved,
+                    created_at_gl: n.created_at,
+                    created_at: now,
+                    updated_at: now,
+                })
+            })
+            .collect()
+    }
+
+    /// The approvals endpoint carries no timestamps, so each approval takes the time
+    /// of the approver's latest "approved this merge request" system note.
+    fn api_approvals_to_db(
+        &self,
+        project_id: u64,
+        mr_iid: u64,
+        approvals: &ApiApprovals,
+        discussions: &[ApiDiscussion],
+    ) -> Vec<GitlabMrApproval> {
+        let mut approved_at = HashMap::new();
+        for n in discussions.iter().flat_map(|d| &d.notes) {
+            if let (true, Some(author), Some(at)) = (n.is_approval(), &n.author, n.created_at) {
+                let latest = approved_at.entry(author.username.as_str()).or_insert(at);
+                *latest = (*latest).max(at);
+            }
+        }
+
+        let now = Utc::now();
+        approvals
+            .approved_by
+            .iter()
+            .map(|a| GitlabMrApproval {
+                id: Uuid::new_v4(),
+                org_id: self.org_id,
+                provider: "gitlab".to_string(),
+                gitlab_project_id: project_id as i64,
+                gitlab_mr_iid: mr_iid as i64,
+                approver_username: a.user.username.clone(),
+                approved_at: approved_at.get(a.user.username.as_str()).copied(),
+                created_at: now,
+            })
+            .collect()
+    }
+
+    /// Store the notes and current approvals of an MR. Returns `(upserted, errors)`.
+    async fn sync_mr_reviews(&self, project_id: u64, mr_iid: u64) -> (usize, usize) {
+        let discussions = match self.client.fetch_mr_discussions(project_id, mr_iid).await {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::warn!(project_id, mr_iid, error = %e, "failed to fetch MR discussions");
+                return (0, 1);
+            }
+        };
+
+        let (mut upserted, mut errors) = (0, 0);
+        for note in self.api_discussions_to_db(project_id, mr_iid, &discussions) {
+            match self.gitlab_repo.upsert_note(&note).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(note_id = note.gitlab_note_id, error = %e, "failed to upsert MR note");
+                    errors += 1;
+                }
+            }
+        }
+
+        match self.client.fetch_mr_approvals(project_id, mr_iid).await {
+            Ok(approvals) => {
+                let rows = self.api_approvals_to_db(project_id, mr_iid, &approvals, &discussions);
+                match self
+                    .gitlab_repo
+                    .replace_approvals(
+                        self.org_id,
+                        "gitlab",
+                        project_id as i64,
+                        mr_iid as i64,
+                        &rows,
+                    )
+                    .await
+                {
+                    Ok(_) => upserted += rows.len(),
+                    Err(e) => {
+                        tracing::warn!(project_id, mr_iid, error = %e, "failed to store MR approvals");
+                        errors += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(project_id, mr_iid, error = %e, "failed to fetch MR approvals");
+                errors += 1;
+            }
+        }
+        (upserted, errors)
+    }
+
     fn api_pipeline_to_db(&self, project_id: u64, p: &ApiPipeline) -> GitlabPipeline {
         let now = Utc::now();
         GitlabPipeline {
             id: Uuid::new_v4(),
             org_id: self.org_id,
+            provider: "gitlab".to_string(),
             gitlab_project_id: project_id as i64,
             gitlab_pipeline_id: p.id as i64,
             status: p.status.clone(),
@@ -89,6 +287,171 @@ where
             updated_at: now,
         }
     }
+
+    fn api_jobs_to_db(
+        &self,
+        project_id: u64,
+        pipeline_id: u64,
+        jobs: &[ApiJob],
+    ) -> Vec<GitlabPipelineJob> {
+        // Retrying a job adds a new job with the same name; the older attempts are retried
+        let mut latest_attempt: HashMap<&str, u64> = HashMap::new();
+        for j in jobs {
+            let latest = latest_attempt.entry(j.name.as_str()).or_insert(j.id);
+            *latest = (*latest).max(j.id);
+        }
+
+        let now = Utc::now();
+        jobs.iter()
+            .map(|j| GitlabPipelineJob {
+                id: Uuid::new_v4(),
+                org_id: self.org_id,
+                provider: "gitlab".to_string(),
+                gitlab_project_id: project_id as i64,
+                gitlab_pipeline_id: pipeline_id as i64,
+                gitlab_job_id: j.id as i64,
+                name: j.name.clone(),
+                stage: j.stage.clone(),
+                status: j.status.clone(),
+                sha: j.commit.as_ref().map(|c| c.id.clone()),
+                ref_name: j.ref_name.clone(),
+                retried: j.retried || latest_attempt.get(j.name.as_str()) != Some(&j.id),
+                allow_failure: j.allow_failure,
+                failure_reason: j.failure_reason.clone(),
+                created_at_gl: j.created_at,
+                started_at_gl: j.started_at,
+                finished_at_gl: j.finished_at,
+                duration_secs: j.duration,
+                web_url: j.web_url.clone(),
+                created_at: now,
+                updated_at: now,
+            })
+            .collect()
+    }
+
+    /// Store the jobs of a pipeline. Returns `(upserted, errors)`.
+    async fn sync_pipeline_jobs(&self, project_id: u64, pipeline_id: u64) -> (usize, usize) {
+        let jobs = match self
+            .client
+            .fetch_pipeline_jobs(project_id, pipeline_id)
+            .await
+        {
+            Ok(j) => j,
+            Err(e) => {
+                tracing::warn!(project_id, pipeline_id, error = %e, "failed to fetch pipeline jobs");
+                return (0, 1);
+            }
+        };
+
+        let (mut upserted, mut errors) = (0, 0);
+        for job in self.api_jobs_to_db(project_id, pipeline_id, &jobs) {
+            match self.gitlab_repo.upsert_pipeline_job(&job).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(job_id = job.gitlab_job_id, error = %e, "failed to upsert pipeline job");
+                    errors += 1;
+                }
+            }
+        }
+        (upserted, errors)
+    }
+
+    fn api_environment_to_db(&self, project_id: u64, e: &ApiEnvironment) -> GitlabEnvironment {
+        let now = Utc::now();
+        GitlabEnvironment {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            gitlab_project_id: project_id as i64,
+            gitlab_environment_id: e.id as i64,
+            name: e.name.clone(),
+            tier: e.tier.clone(),
+            state: e.state.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn api_deployment_to_db(&self, project_id: u64, d: &ApiDeployment) -> GitlabDeployment {
+        let now = Utc::now();
+        GitlabDeployment {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            gitlab_project_id: project_id as i64,
+            gitlab_deployment_id: d.id as i64,
+            environment_name: d.environment.name.clone(),
+            status: d.status.clone(),
+            sha: d.sha.clone(),
+            ref_name: d.ref_name.clone(),
+            deployer_username: d.user.as_ref().map(|u| u.username.clone()),
+            created_at_gl: d.created_at,
+            finished_at_gl: d.finished_at(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Store the environments and deployments of a project. Environments are few and
+    /// carry the tier, so they are refreshed every run. Returns `(upserted, errors)`.
+    async fn sync_deployments(
+        &self,
+        project_id: u64,
+        updated_after: Option<&str>,
+    ) -> (usize, usize) {
+        let (mut upserted, mut errors) = (0, 0);
+
+        match self.client.fetch_environments(project_id).await {
+            Ok(environments) => {
+                for e in &environments {
+                    let db_env = self.api_environment_to_db(project_id, e);
+                    match self.gitlab_repo.upsert_environment(&db_env).await {
+                        Ok(_) => upserted += 1,
+                        Err(e) => {
+                            tracing::warn!(project_id, error = %e, "failed to upsert environment");
+                            errors += 1;
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(project_id, error = %e, "failed to fetch environments");
+                errors += 1;
+            }
+        }
+
+        match self
+            .client
+            .fetch_deployments(project_id, updated_after)
+            .await
+        {
+            Ok(deployments) => {
+                for d in &deployments {
+                    let db_deploy = self.api_deployment_to_db(project_id, d);
+                    match self.gitlab_repo.upsert_deployment(&db_deploy).await {
+                        Ok(_) => upserted += 1,
+                        Err(e) => {
+                            tracing::warn!(deployment_id = d.id, error = %e, "failed to upsert deployment");
+                            errors += 1;
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(project_id, error = %e, "failed to fetch deployments");
+                errors += 1;
+            }
+        }
+        (upserted, errors)
+    }
+}
+
+/// Whether an MR changed after the sync cursor. Without a cursor or timestamps,
+/// assume it did.
+fn updated_since(mr: &ApiMr, cursor: Option<&str>) -> bool {
+    let cursor = cursor.and_then(|c| DateTime::parse_from_rfc3339(c).ok());
+    match (mr.updated_at, cursor) {
+        (Some(updated), Some(cursor)) => updated >= cursor,
+        _ => true,
+    }
 }
 
 #[async_trait]
@@ -126,6 +489,8 @@ where
                     upserted: 0,
                     skipped: 0,
                     errors: 0,
+                    deleted: 0,
+                    moved: 0,
                 });
             }
         };
@@ -176,8 +541,24 @@ where
                             Err(e) => {
                                 tracing::warn!(mr_iid = mr.iid, error = %e, "failed to upsert merged MR");
                                 errors += 1;
+                                continue;
                             }
                         }
+
+                        // Commits are only fetched once merged, when the set is final
+                        let (commits_upserted, commit_errors) =
+                            self.sync_mr_commits(p.id, mr.iid).await;
+                        upserted += commits_upserted;
+                        errors += commit_errors;
+
+                        let (reviews_upserted, review_errors) =
+                            self.sync_mr_reviews(p.id, mr.iid).await;
+                        upserted += reviews_upserted;
+                        errors += review_errors;
+
+                        let (size_upserted, size_errors) = self.sync_mr_size(p.id, mr.iid).await;
+                        upserted += size_upserted;
+                        errors += size_errors;
                     }
                 }
                 Err(e) => {
@@ -196,8 +577,22 @@ where
                             Err(e) => {
                                 tracing::warn!(mr_iid = mr.iid, error = %e, "failed to upsert open MR");
                                 errors += 1;
+                                continue;
                             }
                         }
+
+                        let (reviews_upserted, review_errors) =
+                            self.sync_mr_reviews(p.id, mr.iid).await;
+                        upserted += reviews_upserted;
+                        errors += review_errors;
+
+                        // Diffs are large; only re-fetch them for MRs changed since last sync
+                        if updated_since(mr, updated_after) {
+                            let (size_upserted, size_errors) =
+                                self.sync_mr_size(p.id, mr.iid).await;
+                            upserted += size_upserted;
+                            errors += size_errors;
+                        }
                     }
                 }
                 Err(e) => {
@@ -216,8 +611,14 @@ where
                             Err(e) => {
                                 tracing::warn!(pipeline_id = pl.id, error = %e, "failed to upsert pipeline");
                                 errors += 1;
+                                continue;
                             }
                         }
+
+                        let (jobs_upserted, job_errors) =
+                            self.sync_pipeline_jobs(p.id, pl.id).await;
+                        upserted += jobs_upserted;
+                        errors += job_errors;
                     }
                 }
                 Err(e) => {
@@ -225,6 +626,11 @@ where
                     errors += 1;
                 }
             }
+
+            let (deploys_upserted, deploy_errors) =
+                self.sync_deployments(p.id, updated_after).await;
+            upserted += deploys_upserted;
+            errors += deploy_errors;
         }
 
         // Mark completed with current timestamp as cursor for next incremental sync
@@ -239,6 +645,8 @@ where
             upserted,
             skipped: 0,
             errors,
+            deleted: 0,
+            moved: 0,
         };
 
         tracing::info!(?result, "gitlab MR/pipeline sync completed");
@@ -323,6 +731,7 @@ mod tests {
         GitLabClientConfig {
             base_url: base_url.to_string(),
             private_token: "glpat-test-token".to_string(),
+            groups: Vec::new(),
             max_retries: 1,
             timeout_secs: 5,
         }
@@ -346,6 +755,7 @@ mod tests {
             "labels": ["bug"],
             "created_at": "2026-02-10T10:00:00Z",
             "merged_at": "2026-02-11T14:00:00Z",
+            "target_branch": "main",
             "web_url": "https://gitlab.example.com/group/my-project/merge_requests/1"
         })]
     }
@@ -470,6 +880,132 @@ mod tests {
         assert_eq!(mrs[0].title, "Fix bug");
     }
 
+    #[tokio::test]
+    async fn client_fetch_mr_commits() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/merge_requests/1/commits"))
+            .and(query_param("per_page", "100"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(vec![serde_json::json!({
+                    "id": "ed899a2f4b50b4370feeea94676502b42383c746",
+                    "short_id": "ed899a2f",
+                    "title": "Fix bug",
+                    "author_name": "Alice",
+                    "author_email": "alice@example.com",
+                    "authored_date": "2026-02-09T08:00:00Z",
+                    "committed_date": "2026-02-10T09:30:00+01:00",
+                    "web_url": "https://gitlab.example.com/group/my-project/-/commit/ed899a2f"
+                })]),
+            )
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let commits = client.fetch_mr_commits(42, 1).await.unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].id, "ed899a2f4b50b4370feeea94676502b42383c746");
+        assert_eq!(
+            commits[0].author_email.as_deref(),
+            Some("alice@example.com")
+        );
+        assert_eq!(
+            commits[0].committed_date,
+            Some("2026-02-10T08:30:00Z".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn client_fetch_mr_discussions_and_approvals() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/merge_requests/1/discussions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                serde_json::json!({
+                    "id": "6a9c1750b37d513a43987b574953fceb50b03ce7",
+                    "notes": [
+                        {"id": 1126, "body": "Could this be a constant?", "author": {"username": "bob"},
+                         "created_at": "2026-02-10T12:00:00Z", "system": false,
+                         "resolvable": true, "resolved": true},
+                        {"id": 1127, "body": "Done", "author": {"username": "alice"},
+                         "created_at": "2026-02-10T13:00:00Z", "system": false,
+                         "resolvable": true, "resolved": true}
+                    ]
+                }),
+                serde_json::json!({
+                    "id": "87805b7c09016a7058e91bdbe7b29d1f284a39e6",
+                    "notes": [
+                        {"id": 1130, "body": "approved this merge request", "author": {"username": "bob"},
+                         "created_at": "2026-02-11T09:00:00Z", "system": true}
+                    ]
+                }),
+            ]))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/merge_requests/1/approvals"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "approved": true,
+                "approved_by": [{"user": {"id": 2, "username": "bob"}}]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let discussions = client.fetch_mr_discussions(42, 1).await.unwrap();
+        assert_eq!(discussions.len(), 2);
+        assert_eq!(discussions[0].notes.len(), 2);
+        assert!(discussions[0].notes[0].resolvable);
+        assert!(discussions[1].notes[0].is_approval());
+        assert!(!discussions[0].notes[0].is_approval());
+
+        let approvals = client.fetch_mr_approvals(42, 1).await.unwrap();
+        assert_eq!(approvals.approved_by.len(), 1);
+        assert_eq!(approvals.approved_by[0].user.username, "bob");
+    }
+
+    #[tokio::test]
+    async fn client_fetch_mr_changes_counts_lines() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/merge_requests/1/changes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "iid": 1,
+                "changes_count": "2",
+                "changes": [
+                    {"old_path": "src/a.rs", "new_path": "src/a.rs",
+                     "diff": "@@ -1,3 +1,4 @@\n fn a() {\n-    1\n+    2\n+    3\n }\n"},
+                    {"old_path": "README.md", "new_path": "README.md",
+                     "diff": "--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n"}
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let changes = client.fetch_mr_changes(42, 1).await.unwrap();
+        assert_eq!(changes.changes.len(), 2);
+        assert_eq!(changes.line_counts(), (3, 2));
+    }
+
+    #[test]
+    fn updated_since_compares_against_cursor() {
+        let mut mr: ApiMr = serde_json::from_value(make_open_mrs()[0].clone()).unwrap();
+        assert!(updated_since(&mr, None));
+        assert!(
+            updated_since(&mr, Some("2026-02-01T00:00:00Z")),
+            "no updated_at"
+        );
+
+        mr.updated_at = Some("2026-02-05T00:00:00Z".parse().unwrap());
+        assert!(updated_since(&mr, Some("2026-02-01T00:00:00+00:00")));
+        assert!(!updated_since(&mr, Some("2026-02-06T00:00:00+00:00")));
+    }
+
     #[tokio::test]
     async fn client_fetch_pipelines() {
         let server = MockServer::start().await;
@@ -485,4 +1021,121 @@ mod tests {
         assert_eq!(pipelines.len(), 1);
         assert_eq!(pipelines[0].status, "failed");
     }
+
+    #[tokio::test]
+    async fn client_fetch_environments_and_deployments() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/environments"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(vec![serde_json::json!({
+                    "id": 7,
+                    "name": "production",
+                    "tier": "production",
+                    "state": "available"
+                })]),
+            )
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/deployments"))
+            .and(query_param("order_by", "updated_at"))
+            .and(query_param("updated_after", "2026-02-01T00:00:00Z"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                serde_json::json!({
+                    "id": 501,
+                    "status": "success",
+                    "sha": "ed899a2f4b50b4370feeea94676502b42383c746",
+                    "ref": "main",
+                    "created_at": "2026-02-12T10:00:00Z",
+                    "updated_at": "2026-02-12T10:20:00Z",
+                    "environment": { "id": 7, "name": "production" },
+                    "user": { "username": "alice" },
+                    "deployable": { "finished_at": "2026-02-12T10:15:00Z" }
+                }),
+                serde_json::json!({
+                    "id": 502,
+                    "status": "failed",
+                    "ref": "main",
+                    "created_at": "2026-02-13T10:00:00Z",
+                    "updated_at": "2026-02-13T10:05:00Z",
+                    "environment": { "id": 7, "name": "production" },
+                    "user": null,
+                    "deployable": null
+                }),
+            ]))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let environments = client.fetch_environments(42).await.unwrap();
+        assert_eq!(environments.len(), 1);
+        assert_eq!(environments[0].tier.as_deref(), Some("production"));
+
+        let deployments = client
+            .fetch_deployments(42, Some("2026-02-01T00:00:00Z"))
+            .await
+            .unwrap();
+        assert_eq!(deployments.len(), 2);
+        assert_eq!(deployments[0].environment.name, "production");
+        assert_eq!(deployments[0].user.as_ref().unwrap().username, "alice");
+        assert_eq!(
+            deployments[0].finished_at(),
+            Some("2026-02-12T10:15:00Z".parse().unwrap())
+        );
+        // Without a deploy job, a finished deployment ends at its last update
+        assert_eq!(
+            deployments[1].finished_at(),
+            Some("2026-02-13T10:05:00Z".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn client_fetch_pipeline_jobs_includes_retried() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/projects/42/pipelines/999/jobs"))
+            .and(query_param("include_retried", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![
+                serde_json::json!({
+                    "id": 7001,
+                    "name": "integration",
+                    "stage": "test",
+                    "status": "failed",
+                    "ref": "main",
+                    "commit": { "id": "ed899a2f4b50b4370feeea94676502b42383c746" },
+                    "created_at": "2026-02-20T12:00:00Z",
+                    "started_at": "2026-02-20T12:00:10Z",
+                    "finished_at": "2026-02-20T12:03:10Z",
+                    "duration": 180.4,
+                    "allow_failure": false,
+                    "failure_reason": "script_failure",
+                    "web_url": "https://gitlab.example.com/group/my-project/-/jobs/7001"
+                }),
+                serde_json::json!({
+                    "id": 7002,
+                    "name": "integration",
+                    "stage": "test",
+                    "status": "success",
+                    "ref": "main",
+                    "commit": { "id": "ed899a2f4b50b4370feeea94676502b42383c746" },
+                    "created_at": "2026-02-20T12:04:00Z",
+                    "duration": 175.0,
+                    "web_url": "https://gitlab.example.com/group/my-project/-/jobs/7002"
+                }),
+            ]))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let jobs = client.fetch_pipeline_jobs(42, 999).await.unwrap();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].failure_reason.as_deref(), Some("script_failure"));
+        assert_eq!(jobs[0].duration, Some(180.4));
+        assert!(jobs[1].failure_reason.is_none());
+        assert!(!jobs[1].allow_failure);
+    }
 }
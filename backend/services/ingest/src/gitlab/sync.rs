@@ -41,6 +41,8 @@ where
             email: user.email.clone(),
             display_name: user.name.clone(),
             is_service_account: user.is_service_account(),
+            is_active: user.is_active(),
+            active_changed_at: None,
             first_seen_at: Some(Utc::now()),
             last_seen_at: Some(Utc::now()),
             raw_ref: serde_json::to_value(user).ok(),
@@ -84,6 +86,8 @@ where
                     upserted: 0,
                     skipped: 0,
                     errors: 0,
+                    deleted: 0,
+                    moved: 0,
                 });
             }
         };
@@ -134,6 +138,8 @@ where
             upserted,
             skipped: 0,
             errors,
+            deleted: 0,
+            moved: 0,
         };
 
         tracing::info!(?result, "gitlab sync completed");
@@ -277,6 +283,7 @@ mod tests {
         GitLabClientConfig {
             base_url: base_url.to_string(),
             private_token: "glpat-test-token".to_string(),
+            groups: Vec::new(),
             max_retries: 1,
             timeout_secs: 5,
         }
@@ -370,6 +377,41 @@ mod tests {
         assert!(bot.is_service_account);
     }
 
+    #[tokio::test]
+    async fn sync_maps_blocked_state_to_inactive() {
+        let server = MockServer::start().await;
+        let users = vec![
+            serde_json::json!({ "id": 1, "username": "present", "state": "active" }),
+            serde_json::json!({ "id": 2, "username": "former", "state": "blocked" }),
+        ];
+
+        Mock::given(method("GET"))
+            .and(path("/api/v4/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&users))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new(test_client_config(&server.uri())).unwrap();
+        let identity_repo = MockIdentityRepo::new();
+        let sync_repo = MockSyncRepo::new(true);
+
+        let syncer = GitLabSyncer::new(Uuid::new_v4(), client, identity_repo.clone(), sync_repo);
+        syncer.sync().await.expect("sync should succeed");
+
+        let upserted = identity_repo.upserted.lock().unwrap();
+        let present = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("1"))
+            .unwrap();
+        assert!(present.is_active);
+
+        let former = upserted
+            .iter()
+            .find(|i| i.external_id.as_deref() == Some("2"))
+            .unwrap();
+        assert!(!former.is_active);
+    }
+
     #[tokio::test]
     async fn sync_sets_raw_ref() {
         let server = MockServer::start().await;
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use super::HttpClientConfig;
+
+/// State shared by every client talking to one host (scheme, host and port), so
+/// e.g. Jira and Confluence on the same Atlassian site share its limits. The
+/// first client to reach a host sets its concurrency limit and breaker
+/// thresholds.
+pub(crate) struct HostState {
+    host: String,
+    permits: Semaphore,
+    throttled_until: Mutex<Option<Instant>>,
+    breaker: Mutex<Breaker>,
+    counters: Counters,
+}
+
+/// Opens after `threshold` consecutive failures and rejects requests for
+/// `cooldown`. The first request after that is a trial: success closes the
+/// breaker, failure opens it again.
+struct Breaker {
+    threshold: u32,
+    cooldown: Duration,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Counters {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    rate_limited: AtomicU64,
+    failures: AtomicU64,
+    circuit_opens: AtomicU64,
+    rejected: AtomicU64,
+    latency_ms: AtomicU64,
+}
+
+/// Request counters of one host since the process started.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostMetrics {
+    pub host: String,
+    /// Requests sent, retries included
+    pub requests: u64,
+    pub retries: u64,
+    /// Responses that were rate limits (429, or GitHub's 403)
+    pub rate_limited: u64,
+    /// 5xx responses, timeouts and connection errors
+    pub failures: u64,
+    pub circuit_opens: u64,
+    /// Requests refused locally because the circuit was open
+    pub rejected: u64,
+    pub avg_latency_ms: u64,
+}
+
+/// Host states by `scheme://host:port`.
+#[derive(Default)]
+pub(crate) struct Registry {
+    hosts: Mutex<HashMap<String, Arc<HostState>>>,
+}
+
+impl Registry {
+    /// The registry shared by every client of the process.
+    pub(crate) fn global() -> Arc<Registry> {
+        static GLOBAL: OnceLock<Arc<Registry>> = OnceLock::new();
+        GLOBAL.get_or_init(Default::default).clone()
+    }
+
+    /// The state of the host of `url`, created on first use.
+    pub(crate) fn state_for(&self, url: &str, config: &HttpClientConfig) -> Arc<HostState> {
+        let key = host_key(url);
+        let mut hosts = self.hosts.lock().unwrap();
+        hosts
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(HostState::new(key, config)))
+            .clone()
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<HostMetrics> {
+        let hosts = self.hosts.lock().unwrap();
+        let mut metrics: Vec<HostMetrics> = hosts.values().map(|h| h.metrics()).collect();
+        metrics.sort_by(|a, b| a.host.cmp(&b.host));
+        metrics
+    }
+}
+
+/// `scheme://host:port` of a URL, or the URL itself if it does not parse.
+pub(super) fn host_key(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(u) => format!(
+            "{}://{}:{}",
+            u.scheme(),
+            u.host_str().unwrap_or_default(),
+            u.port_or_known_default().unwrap_or_default()
+        ),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Counters of every host contacted so far, sorted by host.
+pub fn metrics_snapshot() -> Vec<HostMetrics> {
+    Registry::global().snapshot()
+}
+
+impl HostState {
+    fn new(host: String, config: &HttpClientConfig) -> Self {
+        Self {
+            host,
+            permits: Semaphore::new(config.max_concurrency_per_host.max(1)),
+            throttled_until: Mutex::new(None),
+            breaker: Mutex::new(Breaker {
+                threshold: config.circuit_failure_threshold.max(1),
+                cooldown: config.circuit_cooldown,
+                consecutive_failures: 0,
+                open_until: None,
+            }),
+            counters: Counters::default(),
+        }
+    }
+
+    pub(crate) fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Wait for a request slot on the host.
+    pub(crate) async fn acquire(&self) -> SemaphorePermit<'_> {
+        // The semaphore is never closed
+        self.permits.acquire().await.expect("host semaphore closed")
+    }
+
+    /// Hold requests to the host for `wait`, unless already held longer.
+    pub(crate) fn throttle(&self, wait: Duration) {
+        let until = Instant::now() + wait;
+        let mut throttled = self.throttled_until.lock().unwrap();
+        *throttled = Some(throttled.map_or(until, |t| t.max(until)));
+    }
+
+    /// Sleep until the host is no longer throttled, at most `max_wait`.
+    pub(crate) async fn wait_for_throttle(&self, max_wait: Duration) {
+        let until = *self.throttled_until.lock().unwrap();
+        if let Some(wait) = until.and_then(|t| t.checked_duration_since(Instant::now())) {
+            let wait = wait.min(max_wait);
+            tracing::warn!(host = %self.host, wait_ms = wait.as_millis() as u64, "throttling requests to rate-limited host");
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Whether a request may be sent. Counts a rejection if the circuit is open.
+    pub(crate) fn admit(&self) -> bool {
+        let breaker = self.breaker.lock().unwrap();
+        let open = breaker.open_until.is_some_and(|t| Instant::now() < t);
+        if open {
+            self.counters.rejected.fetch_add(1, Ordering::Relaxed);
+        }
+        !open
+    }
+
+    pub(crate) fn record_request(&self, latency: Duration) {
+        self.counters.requests.fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .latency_ms
+            .fetch_add(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.counters.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_rate_limited(&self) {
+        self.counters.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_success(&self) {
+        let mut breaker = self.breaker.lock().unwrap();
+        breaker.consecutive_failures = 0;
+        breaker.open_until = None;
+    }
+
+    pub(crate) fn record_failure(&self) {
+        self.counters.failures.fetch_add(1, Ordering::Relaxed);
+        let mut breaker = self.breaker.lock().unwrap();
+        breaker.consecutive_failures += 1;
+        if breaker.consecutive_failures >= breaker.threshold {
+            breaker.open_until = Some(Instant::now() + breaker.cooldown);
+            self.counters.circuit_opens.fetch_add(1, Ordering::Relaxed);
+            tracing::error!(
+                host = %self.host,
+                failures = breaker.consecutive_failures,
+                cooldown_secs = breaker.cooldown.as_secs(),
+                "circuit opened"
+            );
+        }
+    }
+
+    fn metrics(&self) -> HostMetrics {
+        let load = |c: &AtomicU64| c.load(Ordering::Relaxed);
+        let requests = load(&self.counters.requests);
+        HostMetrics {
+            host: self.host.clone(),
+            requests,
+            retries: load(&self.counters.retries),
+            rate_limited: load(&self.counters.rate_limited),
+            failures: load(&self.counters.failures),
+            circuit_opens: load(&self.counters.circuit_opens),
+            rejected: load(&self.counters.rejected),
+            avg_latency_ms: load(&self.counters.latency_ms)
+                .checked_div(requests)
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_key_includes_default_port() {
+        assert_eq!(
+            host_key("https://acme.atlassian.net/rest/api/3/search?jql=x"),
+            "https://acme.atlassian.net:443"
+        );
+        assert_eq!(
+            host_key("http://127.0.0.1:8080/api/v4/users"),
+            "http://127.0.0.1:8080"
+        );
+    }
+
+    #[test]
+    fn breaker_opens_after_threshold_and_closes_on_success() {
+        let config = HttpClientConfig {
+            circuit_failure_threshold: 2,
+            circuit_cooldown: Duration::from_secs(60),
+            ..HttpClientConfig::new(0, 5)
+        };
+        let host = HostState::new("http://breaker.test:80".to_string(), &config);
+
+        host.record_failure();
+        assert!(host.admit());
+        host.record_failure();
+        assert!(!host.admit());
+
+        host.record_success();
+        assert!(host.admit());
+
+        let metrics = host.metrics();
+        assert_eq!(metrics.failures, 2);
+        assert_eq!(metrics.circuit_opens, 1);
+        assert_eq!(metrics.rejected, 1);
+    }
+}
@@ -0,0 +1,474 @@
+//! Shared HTTP client of the connectors. Every request goes through
+//! [`HttpClient`], which adds what each connector used to reimplement or lack:
+//!
+//! - retries with jittered exponential backoff on 5xx, timeouts and connection
+//!   errors;
+//! - throttling driven by `Retry-After` and the rate-limit headers of GitLab,
+//!   GitHub and Atlassian, shared by all clients of a host;
+//! - a per-host concurrency limit;
+//! - a per-host circuit breaker that fails requests fast while a host is down;
+//! - per-host request metrics, see [`metrics_snapshot`].
+
+mod host;
+mod rate_limit;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::{Client, Method, StatusCode};
+use serde::de::DeserializeOwned;
+
+pub use host::{metrics_snapshot, HostMetrics};
+pub use rate_limit::{backoff, RateLimit};
+
+#[derive(Debug, Clone)]
+pub struct HttpClientConfig {
+    pub max_retries: u32,
+    pub timeout_secs: u64,
+    /// Requests in flight to one host at a time, across all clients
+    pub max_concurrency_per_host: usize,
+    /// Consecutive failures (5xx, timeouts, connection errors) that open a host's
+    /// circuit
+    pub circuit_failure_threshold: u32,
+    /// How long an open circuit rejects requests
+    pub circuit_cooldown: Duration,
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Longest rate-limit wait honoured before retrying
+    pub max_throttle_wait: Duration,
+}
+
+impl HttpClientConfig {
+    /// Defaults for the given connector's retry and timeout settings.
+    pub fn new(max_retries: u32, timeout_secs: u64) -> Self {
+        Self {
+            max_retries,
+            timeout_secs,
+            max_concurrency_per_host: 4,
+            circuit_failure_threshold: 5,
+            circuit_cooldown: Duration::from_secs(30),
+            base_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            max_throttle_wait: Duration::from_secs(60),
+        }
+    }
+
+    /// Like [`new`](Self::new), with the host limits read from
+    /// `HTTP_MAX_CONCURRENCY_PER_HOST`, `HTTP_CIRCUIT_FAILURE_THRESHOLD`,
+    /// `HTTP_CIRCUIT_COOLDOWN_SECS` and `HTTP_BACKOFF_BASE_MS` when set.
+    pub fn from_env(max_retries: u32, timeout_secs: u64) -> Self {
+        fn env<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+
+        let defaults = Self::new(max_retries, timeout_secs);
+        Self {
+            max_concurrency_per_host: env("HTTP_MAX_CONCURRENCY_PER_HOST")
+                .unwrap_or(defaults.max_concurrency_per_host),
+            circuit_failure_threshold: env("HTTP_CIRCUIT_FAILURE_THRESHOLD")
+                .unwrap_or(defaults.circuit_failure_threshold),
+            circuit_cooldown: env("HTTP_CIRCUIT_COOLDOWN_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.circuit_cooldown),
+            base_backoff: env("HTTP_BACKOFF_BASE_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.base_backoff),
+            ..defaults
+        }
+    }
+}
+
+/// How requests authenticate.
+#[derive(Debug, Clone)]
+pub enum Auth {
+    Basic { username: String, password: String },
+    Bearer(String),
+    Header { name: &'static str, value: String },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HttpClientError {
+    #[error("HTTP {status}: {body}")]
+    HttpError { status: StatusCode, body: String },
+
+    #[error("request failed: {0}")]
+    RequestError(#[from] reqwest::Error),
+
+    #[error("max retries exceeded after {attempts} attempts: {last_error}")]
+    MaxRetriesExceeded { attempts: u32, last_error: String },
+
+    #[error("circuit open for {host}, request not sent")]
+    CircuitOpen { host: String },
+}
+
+/// A decoded response body with the response headers, for pagination.
+#[derive(Debug)]
+pub struct HttpResponse<T> {
+    pub body: T,
+    pub headers: HeaderMap,
+}
+
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    config: HttpClientConfig,
+    auth: Auth,
+    headers: HeaderMap,
+    hosts: Arc<host::Registry>,
+}
+
+impl HttpClient {
+    pub fn new(config: HttpClientConfig, auth: Auth) -> Result<Self, reqwest::Error> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent("ovia-ingest")
+            .build()?;
+        Ok(Self {
+            client,
+            config,
+            auth,
+            headers: HeaderMap::new(),
+            hosts: Self::registry(),
+        })
+    }
+
+    #[cfg(not(test))]
+    fn registry() -> Arc<host::Registry> {
+        host::Registry::global()
+    }
+
+    /// Tests get a registry per client: wiremock reuses server ports across
+    /// tests, which would otherwise share breaker and throttle state.
+    #[cfg(test)]
+    fn registry() -> Arc<host::Registry> {
+        Default::default()
+    }
+
+    /// Send `name: value` with every request.
+    pub fn with_header(mut self, name: &'static str, value: &'static str) -> Self {
+        self.headers.insert(name, HeaderValue::from_static(value));
+        self
+    }
+
+    pub fn config(&self) -> &HttpClientConfig {
+        &self.config
+    }
+
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<HttpResponse<T>, HttpClientError> {
+        self.send(Method::GET, url, None).await
+    }
+
+    pub async fn post<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<HttpResponse<T>, HttpClientError> {
+        self.send(Method::POST, url, Some(body)).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<HttpResponse<T>, HttpClientError> {
+        let host = self.hosts.state_for(url, &self.config);
+        let mut last_error = String::new();
+        let mut rate_limited = false;
+
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                host.record_retry();
+                // A rate limit already throttled the host; otherwise back off
+                if !rate_limited {
+                    let wait = backoff(attempt, self.config.base_backoff, self.config.max_backoff);
+                    tracing::warn!(
+                        host = host.host(),
+                        attempt,
+                        backoff_ms = wait.as_millis() as u64,
+                        "retrying after backoff"
+                    );
+                    tokio::time::sleep(wait).await;
+                }
+            }
+            host.wait_for_throttle(self.config.max_throttle_wait).await;
+
+            if !host.admit() {
+                return Err(HttpClientError::CircuitOpen {
+                    host: host.host().to_string(),
+                });
+            }
+            let _permit = host.acquire().await;
+
+            let started = Instant::now();
+            let sent = self.request(method.clone(), url, body).send().await;
+            host.record_request(started.elapsed());
+
+            let response = match sent {
+                Ok(resp) => resp,
+                Err(e) => {
+                    last_error = e.to_string();
+                    if e.is_timeout() || e.is_connect() {
+                        host.record_failure();
+                        rate_limited = false;
+                        continue;
+                    }
+                    return Err(HttpClientError::RequestError(e));
+                }
+            };
+
+            let status = response.status();
+            let limit = RateLimit::from_headers(response.headers(), Utc::now());
+            if let Some(pause) = limit.pause() {
+                host.throttle(pause);
+            }
+
+            if status.is_success() {
+                host.record_success();
+                let headers = response.headers().clone();
+                let body = response.json::<T>().await?;
+                return Ok(HttpResponse { body, headers });
+            }
+
+            rate_limited = limit.is_rate_limited(status);
+            if rate_limited {
+                let wait = limit
+                    .wait_hint()
+                    .unwrap_or_else(|| {
+                        backoff(
+                            attempt + 1,
+                            self.config.base_backoff,
+                            self.config.max_backoff,
+                        )
+                    })
+                    .min(self.config.max_throttle_wait);
+                host.record_rate_limited();
+                host.throttle(wait);
+                tracing::warn!(
+                    host = host.host(),
+                    %status,
+                    wait_ms = wait.as_millis() as u64,
+                    "rate-limited, waiting before retry"
+                );
+                last_error = format!("{status}: rate limited");
+                continue;
+            }
+
+            let text = response.text().await.unwrap_or_default();
+
+            // Retry on 5xx
+            if status.is_server_error() {
+                host.record_failure();
+                last_error = format!("{status}: {text}");
+                continue;
+            }
+
+            // Fail fast on other 4xx; the host itself is healthy
+            host.record_success();
+            return Err(HttpClientError::HttpError { status, body: text });
+        }
+
+        Err(HttpClientError::MaxRetriesExceeded {
+            attempts: self.config.max_retries + 1,
+            last_error,
+        })
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        url: &str,
+        body: Option<&serde_json::Value>,
+    ) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .request(method, url)
+            .headers(self.headers.clone());
+        request = match &self.auth {
+            Auth::Basic { username, password } => request.basic_auth(username, Some(password)),
+            Auth::Bearer(token) => request.bearer_auth(token),
+            Auth::Header { name, value } => request.header(*name, value),
+        };
+        match body {
+            Some(body) => request.json(body),
+            None => request,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config() -> HttpClientConfig {
+        HttpClientConfig {
+            base_backoff: Duration::from_millis(10),
+            ..HttpClientConfig::new(2, 5)
+        }
+    }
+
+    fn client(config: HttpClientConfig) -> HttpClient {
+        HttpClient::new(config, Auth::Bearer("secret".to_string())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_then_succeeds() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/items"))
+            .and(header("Authorization", "Bearer secret"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!([1, 2]))
+                    .append_header("x-next-page", "2"),
+            )
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/items", server.uri());
+        let client = client(test_config());
+        let resp = client.get::<Vec<u32>>(&url).await.unwrap();
+        assert_eq!(resp.body, vec![1, 2]);
+        assert_eq!(resp.headers.get("x-next-page").unwrap(), "2");
+
+        let metrics = metrics_for(&client, &server);
+        assert_eq!(metrics.requests, 3);
+        assert_eq!(metrics.retries, 2);
+        assert_eq!(metrics.failures, 2);
+    }
+
+    #[tokio::test]
+    async fn honours_retry_after_on_429() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(429).append_header("retry-after", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let started = Instant::now();
+        let url = format!("{}/search", server.uri());
+        let client = client(test_config());
+        let resp = client
+            .post::<serde_json::Value>(&url, &serde_json::json!({"q": 1}))
+            .await
+            .unwrap();
+        assert_eq!(resp.body["ok"], true);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+
+        let metrics = metrics_for(&client, &server);
+        assert_eq!(metrics.rate_limited, 1);
+        assert_eq!(metrics.failures, 0);
+    }
+
+    #[tokio::test]
+    async fn fails_fast_on_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404).set_body_string("missing"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/missing", server.uri());
+        match client(test_config()).get::<serde_json::Value>(&url).await {
+            Err(HttpClientError::HttpError { status, body }) => {
+                assert_eq!(status, StatusCode::NOT_FOUND);
+                assert_eq!(body, "missing");
+            }
+            other => panic!("expected HttpError, got: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_after_consecutive_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = client(HttpClientConfig {
+            max_retries: 5,
+            circuit_failure_threshold: 3,
+            ..test_config()
+        });
+        let url = format!("{}/flaky", server.uri());
+
+        let err = client.get::<serde_json::Value>(&url).await.unwrap_err();
+        assert!(
+            matches!(err, HttpClientError::CircuitOpen { .. }),
+            "{err:?}"
+        );
+        let err = client.get::<serde_json::Value>(&url).await.unwrap_err();
+        assert!(
+            matches!(err, HttpClientError::CircuitOpen { .. }),
+            "{err:?}"
+        );
+
+        let metrics = metrics_for(&client, &server);
+        assert_eq!(metrics.circuit_opens, 1);
+        assert_eq!(metrics.rejected, 2);
+    }
+
+    #[tokio::test]
+    async fn limits_concurrent_requests_per_host() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({}))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .mount(&server)
+            .await;
+
+        let client = client(HttpClientConfig {
+            max_concurrency_per_host: 2,
+            ..test_config()
+        });
+        let url = format!("{}/slow", server.uri());
+        let started = Instant::now();
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                let (client, url) = (client.clone(), url.clone());
+                tokio::spawn(async move { client.get::<serde_json::Value>(&url).await })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+        // Six requests, two at a time
+        assert!(started.elapsed() >= Duration::from_millis(300));
+    }
+
+    fn metrics_for(client: &HttpClient, server: &MockServer) -> HostMetrics {
+        let key = host::host_key(&server.uri());
+        client
+            .hosts
+            .snapshot()
+            .into_iter()
+            .find(|m| m.host == key)
+            .expect("host metrics")
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+/// How long to hold requests to a host that reports it is close to its limit.
+const NEAR_LIMIT_PAUSE: Duration = Duration::from_secs(1);
+
+/// Epoch seconds start here; smaller reset values are a number of seconds.
+const MIN_EPOCH_SECS: i64 = 1_000_000_000;
+
+/// Rate-limit hints of a response, from whichever header family the server
+/// uses:
+///
+/// - `Retry-After`: seconds or an HTTP date (all).
+/// - `RateLimit-Remaining` / `RateLimit-Reset`: epoch seconds (GitLab).
+/// - `X-RateLimit-Remaining` / `X-RateLimit-Reset`: epoch seconds (GitHub), or an
+///   ISO timestamp (Atlassian).
+/// - `X-RateLimit-NearLimit` (Atlassian).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RateLimit {
+    pub retry_after: Option<Duration>,
+    pub remaining: Option<u64>,
+    pub reset_in: Option<Duration>,
+    pub near_limit: bool,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::trim)
+        };
+        let either = |a: &str, b: &str| header(a).or_else(|| header(b));
+
+        Self {
+            retry_after: header("retry-after").and_then(|v| parse_retry_after(v, now)),
+            remaining: either("ratelimit-remaining", "x-ratelimit-remaining")
+                .and_then(|v| v.parse().ok()),
+            reset_in: either("ratelimit-reset", "x-ratelimit-reset")
+                .and_then(|v| parse_reset(v, now)),
+            near_limit: header("x-ratelimit-nearlimit") == Some("true"),
+        }
+    }
+
+    fn exhausted(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    /// How long the server asks us to wait: `Retry-After`, else the time to the
+    /// reset of an exhausted quota.
+    pub fn wait_hint(&self) -> Option<Duration> {
+        self.retry_after
+            .or_else(|| self.exhausted().then_some(self.reset_in).flatten())
+    }
+
+    /// How long to hold further requests to the host, whatever the status.
+    pub fn pause(&self) -> Option<Duration> {
+        if self.exhausted() {
+            Some(self.reset_in.unwrap_or(NEAR_LIMIT_PAUSE))
+        } else if self.near_limit {
+            Some(NEAR_LIMIT_PAUSE)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a failed response was a rate limit rather than an error. GitHub
+    /// answers 403 when a quota is exhausted or a secondary limit is hit.
+    pub fn is_rate_limited(&self, status: StatusCode) -> bool {
+        status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::FORBIDDEN && (self.retry_after.is_some() || self.exhausted()))
+    }
+}
+
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    until(at.with_timezone(&Utc), now)
+}
+
+fn parse_reset(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    if let Ok(n) = value.parse::<i64>() {
+        return if n >= MIN_EPOCH_SECS {
+            until(DateTime::from_timestamp(n, 0)?, now)
+        } else {
+            Some(Duration::from_secs(n.max(0) as u64))
+        };
+    }
+    let at = DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%MZ").map(|t| t.and_utc()))
+        .ok()?;
+    until(at, now)
+}
+
+fn until(at: DateTime<Utc>, now: DateTime<Utc>) -> Option<Duration> {
+    Some((at - now).to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Exponential backoff with equal jitter: a random delay between half and all of
+/// `base * 2^attempt`, capped at `max`, so clients retrying together spread out.
+pub fn backoff(attempt: u32, base: Duration, max: Duration) -> Duration {
+    let ceiling = base
+        .saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX))
+        .min(max);
+    let half = ceiling / 2;
+    half + half.mul_f64(fastrand::f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, value.parse().unwrap());
+        }
+        map
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-02T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let limit = RateLimit::from_headers(&headers(&[("retry-after", "7")]), now());
+        assert_eq!(limit.retry_after, Some(Duration::from_secs(7)));
+
+        let limit = RateLimit::from_headers(
+            &headers(&[("retry-after", "Mon, 02 Mar 2026 12:00:30 GMT")]),
+            now(),
+        );
+        assert_eq!(limit.retry_after, Some(Duration::from_secs(30)));
+        assert_eq!(limit.wait_hint(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn reset_accepts_epoch_delta_and_iso_timestamps() {
+        let epoch = (now().timestamp() + 45).to_string();
+        let gitlab = RateLimit::from_headers(
+            &headers(&[("ratelimit-remaining", "0"), ("ratelimit-reset", &epoch)]),
+            now(),
+        );
+        assert_eq!(gitlab.reset_in, Some(Duration::from_secs(45)));
+        assert_eq!(gitlab.pause(), Some(Duration::from_secs(45)));
+
+        let delta = RateLimit::from_headers(&headers(&[("x-ratelimit-reset", "12")]), now());
+        assert_eq!(delta.reset_in, Some(Duration::from_secs(12)));
+
+        let atlassian = RateLimit::from_headers(
+            &headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "2026-03-02T12:02Z"),
+            ]),
+            now(),
+        );
+        assert_eq!(atlassian.wait_hint(), Some(Duration::from_secs(120)));
+
+        let past = RateLimit::from_headers(
+            &headers(&[("x-ratelimit-reset", "2026-03-02T11:00:00Z")]),
+            now(),
+        );
+        assert_eq!(past.reset_in, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn pause_and_rate_limit_classification() {
+        let plenty = RateLimit::from_headers(
+            &headers(&[
+                ("x-ratelimit-remaining", "4999"),
+                ("x-ratelimit-reset", "60"),
+            ]),
+            now(),
+        );
+        assert_eq!(plenty.pause(), None);
+        assert_eq!(plenty.wait_hint(), None);
+        assert!(!plenty.is_rate_limited(StatusCode::FORBIDDEN));
+        assert!(plenty.is_rate_limited(StatusCode::TOO_MANY_REQUESTS));
+
+        let near = RateLimit::from_headers(&headers(&[("x-ratelimit-nearlimit", "true")]), now());
+        assert_eq!(near.pause(), Some(NEAR_LIMIT_PAUSE));
+
+        let secondary = RateLimit::from_headers(&headers(&[("retry-after", "3")]), now());
+        assert!(secondary.is_rate_limited(StatusCode::FORBIDDEN));
+        assert!(!RateLimit::default().is_rate_limited(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn backoff_grows_with_jitter_and_is_capped() {
+        let base = Duration::from_millis(500);
+        let max = Duration::from_secs(30);
+        for _ in 0..50 {
+            let first = backoff(1, base, max);
+            assert!(first >= Duration::from_millis(500) && first <= Duration::from_secs(1));
+            let third = backoff(3, base, max);
+            assert!(third >= Duration::from_secs(2) && third <= Duration::from_secs(4));
+            let capped = backoff(40, base, max);
+            assert!(capped >= Duration::from_secs(15) && capped <= max);
+        }
+    }
+}
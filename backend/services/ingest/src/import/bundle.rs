@@ -0,0 +1,336 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+
+use super::models::{
+    IdentityRecord, ImportRecord, IssueRecord, MergeRequestRecord, PipelineRecord, ProjectRecord,
+    TransitionRecord,
+};
+
+/// A row that could not be parsed or failed validation. Rows are numbered from
+/// 1: the array index plus one in JSON, the line number in CSV.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    pub file: String,
+    pub row: u64,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} row {}: {}", self.file, self.row, self.message)
+    }
+}
+
+/// Every valid record of an import directory, plus the rows that were rejected.
+#[derive(Debug, Default)]
+pub struct Bundle {
+    pub identities: Vec<IdentityRecord>,
+    pub issues: Vec<IssueRecord>,
+    pub transitions: Vec<TransitionRecord>,
+    pub projects: Vec<ProjectRecord>,
+    pub merge_requests: Vec<MergeRequestRecord>,
+    pub pipelines: Vec<PipelineRecord>,
+    pub row_errors: Vec<RowError>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+    #[error("{path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("{path}: {message}")]
+    Malformed { path: PathBuf, message: String },
+}
+
+impl Bundle {
+    /// Read every known file of `dir`. Each record type is optional and may be
+    /// given as `<stem>.json` (an array of objects) or `<stem>.csv` (with a
+    /// header row), not both. A file that cannot be read at all fails the load;
+    /// a bad row only lands in `row_errors`.
+    pub fn load(dir: &Path) -> Result<Self, BundleError> {
+        let mut bundle = Self::default();
+        bundle.identities = read_records(dir, &mut bundle.row_errors)?;
+        bundle.issues = read_records(dir, &mut bundle.row_errors)?;
+        bundle.transitions = read_records(dir, &mut bundle.row_errors)?;
+        bundle.projects = read_records(dir, &mut bundle.row_errors)?;
+        bundle.merge_requests = read_records(dir, &mut bundle.row_errors)?;
+        bundle.pipelines = read_records(dir, &mut bundle.row_errors)?;
+        Ok(bundle)
+    }
+
+    /// Number of valid records.
+    pub fn len(&self) -> usize {
+        self.identities.len()
+            + self.issues.len()
+            + self.transitions.len()
+            + self.projects.len()
+            + self.merge_requests.len()
+            + self.pipelines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Latest modification time of the bundle's files, used to skip an import when
+/// nothing changed. `None` if the directory holds no bundle file.
+pub fn last_modified(dir: &Path) -> Result<Option<DateTime<Utc>>, BundleError> {
+    let stems = [
+        IdentityRecord::FILE_STEM,
+        IssueRecord::FILE_STEM,
+        TransitionRecord::FILE_STEM,
+        ProjectRecord::FILE_STEM,
+        MergeRequestRecord::FILE_STEM,
+        PipelineRecord::FILE_STEM,
+    ];
+    let mut latest: Option<SystemTime> = None;
+    for stem in stems {
+        for ext in ["json", "csv"] {
+            let path = dir.join(format!("{stem}.{ext}"));
+            let modified = match fs::metadata(&path).and_then(|m| m.modified()) {
+                Ok(t) => t,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(source) => return Err(BundleError::Io { path, source }),
+            };
+            latest = latest.max(Some(modified));
+        }
+    }
+    Ok(latest.map(DateTime::<Utc>::from))
+}
+
+fn read_records<T: ImportRecord>(
+    dir: &Path,
+    row_errors: &mut Vec<RowError>,
+) -> Result<Vec<T>, BundleError> {
+    let json = dir.join(format!("{}.json", T::FILE_STEM));
+    let csv = dir.join(format!("{}.csv", T::FILE_STEM));
+
+    let parsed = match (json.is_file(), csv.is_file()) {
+        (true, true) => {
+            return Err(BundleError::Malformed {
+                path: dir.to_path_buf(),
+                message: format!("both {0}.json and {0}.csv present", T::FILE_STEM),
+            })
+        }
+        (true, false) => read_json::<T>(&json)?,
+        (false, true) => read_csv::<T>(&csv)?,
+        (false, false) => return Ok(Vec::new()),
+    };
+
+    let file = if json.is_file() { json } else { csv };
+    let file = file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let mut records = Vec::new();
+    for (row, result) in parsed {
+        match result.and_then(|r: T| r.validate().map(|_| r)) {
+            Ok(record) => records.push(record),
+            Err(message) => row_errors.push(RowError {
+                file: file.clone(),
+                row,
+                message,
+            }),
+        }
+    }
+    Ok(records)
+}
+
+type ParsedRows<T> = Vec<(u64, Result<T, String>)>;
+
+fn read_json<T: ImportRecord>(path: &Path) -> Result<ParsedRows<T>, BundleError> {
+    let text = fs::read_to_string(path).map_err(|source| BundleError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let rows: Vec<serde_json::Value> =
+        serde_json::from_str(&text).map_err(|e| BundleError::Malformed {
+            path: path.to_path_buf(),
+            message: format!("expected a JSON array of objects: {e}"),
+        })?;
+    Ok(rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            (
+                i as u64 + 1,
+                serde_json::from_value(value).map_err(|e| e.to_string()),
+            )
+        })
+        .collect())
+}
+
+fn read_csv<T: ImportRecord>(path: &Path) -> Result<ParsedRows<T>, BundleError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .map_err(|e| BundleError::Malformed {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    Ok(reader
+        .deserialize::<T>()
+        .enumerate()
+        .map(|(i, result)| match result {
+            Ok(record) => (i as u64 + 2, Ok(record)),
+            Err(e) => {
+                let line = e.position().map_or(i as u64 + 2, |p| p.line());
+                (line, Err(csv_error_message(&e)))
+            }
+        })
+        .collect())
+}
+
+/// The csv error without its position, which `RowError` already carries.
+fn csv_error_message(e: &csv::Error) -> String {
+    match e.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => match err.field() {
+            Some(field) => format!("field {}: {}", field + 1, err.kind()),
+            None => err.kind().to_string(),
+        },
+        _ => e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("ovia-import-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, name: &str, contents: &str) {
+            fs::write(self.0.join(name), contents).unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn load_reads_json_and_csv_files() {
+        let dir = TempDir::new();
+        dir.write(
+            "identities.json",
+            r#"[{"source": "jira", "external_id": "acc-1", "display_name": "Alice"},
+                {"source": "gitlab", "external_id": "7", "username": "alice", "is_active": false}]"#,
+        );
+        dir.write(
+            "issues.csv",
+            "key,summary,status,story_points,labels,created_at\n\
+             SHOP-1,Checkout,Done,3,backend;payments,2026-02-01T09:00:00Z\n\
+             SHOP-2,Cart,In Progress,,,\n",
+        );
+        dir.write(
+            "merge_requests.csv",
+            "provider,project_id,iid,title,state,author_username,merged_at\n\
+             github,42,5,Add cart,merged,alice,2026-02-03T10:00:00Z\n",
+        );
+
+        let bundle = Bundle::load(&dir.0).unwrap();
+        assert!(bundle.row_errors.is_empty(), "{:?}", bundle.row_errors);
+        assert_eq!(bundle.len(), 5);
+
+        assert!(bundle.identities[0].is_active);
+        assert!(!bundle.identities[1].is_active);
+
+        assert_eq!(bundle.issues[0].project_key(), "SHOP");
+        assert_eq!(bundle.issues[0].story_points, Some(3.0));
+        assert_eq!(bundle.issues[0].labels, vec!["backend", "payments"]);
+        assert!(bundle.issues[1].labels.is_empty());
+        assert!(bundle.issues[1].created_at.is_none());
+
+        assert_eq!(bundle.merge_requests[0].provider, "github");
+        assert!(bundle.transitions.is_empty());
+        assert!(bundle.pipelines.is_empty());
+    }
+
+    #[test]
+    fn load_reports_bad_rows_and_keeps_the_rest() {
+        let dir = TempDir::new();
+        dir.write(
+            "transitions.json",
+            r#"[{"issue_key": "SHOP-1", "to_value": "Done", "transitioned_at": "2026-02-02T10:00:00Z"},
+                {"issue_key": "SHOP-1", "to_value": "Done", "transitioned_at": "yesterday"},
+                {"issue_key": " ", "transitioned_at": "2026-02-02T10:00:00Z"}]"#,
+        );
+        dir.write(
+            "pipelines.csv",
+            "project_id,pipeline_id,status,ref,duration_secs\n\
+             42,999,failed,main,300\n\
+             42,abc,success,main,10\n\
+             42,1000,success,main,-5\n",
+        );
+
+        let bundle = Bundle::load(&dir.0).unwrap();
+        assert_eq!(bundle.transitions.len(), 1);
+        assert_eq!(bundle.transitions[0].field, "status");
+        assert_eq!(bundle.pipelines.len(), 1);
+        assert_eq!(bundle.pipelines[0].provider, "gitlab");
+
+        let rows: Vec<_> = bundle
+            .row_errors
+            .iter()
+            .map(|e| (e.file.as_str(), e.row))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("transitions.json", 2),
+                ("transitions.json", 3),
+                ("pipelines.csv", 3),
+                ("pipelines.csv", 4),
+            ]
+        );
+        assert_eq!(
+            bundle.row_errors[1].to_string(),
+            "transitions.json row 3: issue_key must not be empty"
+        );
+        assert!(bundle.row_errors[2].message.starts_with("field 2:"));
+    }
+
+    #[test]
+    fn load_rejects_unreadable_files() {
+        let dir = TempDir::new();
+        dir.write("issues.json", r#"{"key": "SHOP-1"}"#);
+        assert!(matches!(
+            Bundle::load(&dir.0),
+            Err(BundleError::Malformed { .. })
+        ));
+
+        let dir = TempDir::new();
+        dir.write("issues.json", "[]");
+        dir.write("issues.csv", "key,summary,status\n");
+        assert!(matches!(
+            Bundle::load(&dir.0),
+            Err(BundleError::Malformed { .. })
+        ));
+    }
+
+    #[test]
+    fn last_modified_ignores_unknown_files() {
+        let dir = TempDir::new();
+        dir.write("README.md", "notes");
+        assert!(last_modified(&dir.0).unwrap().is_none());
+
+        dir.write("projects.json", "[]");
+        assert!(last_modified(&dir.0).unwrap().is_some());
+    }
+}
@@ -0,0 +1,44 @@
+pub mod bundle;
+pub mod models;
+pub mod sync;
+
+use std::path::PathBuf;
+
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::pg_repository::PgSyncRepository;
+
+use crate::registry::{ConnectorContext, ConnectorSpec, SourceRegistration};
+use sync::FileImportSyncer;
+
+inventory::submit! {
+    SourceRegistration {
+        source: "file_import",
+        factory: connectors,
+    }
+}
+
+/// A file import from `IMPORT_DIR/<org id>`, if that directory exists. The bundle
+/// schema is documented in `docs/17-file-import.md`.
+pub fn connectors(ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+    let Some(root) = std::env::var("IMPORT_DIR").ok().filter(|d| !d.is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let dir = PathBuf::from(root).join(ctx.org.id.to_string());
+    if !dir.is_dir() {
+        tracing::info!(org_id = %ctx.org.id, dir = %dir.display(), "no import directory for org, skipping file import");
+        return Ok(Vec::new());
+    }
+    tracing::info!(org_id = %ctx.org.id, dir = %dir.display(), "file import configured");
+
+    let pool = &ctx.pool;
+    Ok(vec![ConnectorSpec::new(FileImportSyncer::new(
+        ctx.org.id,
+        dir,
+        PgIdentityRepository::new(pool.clone()),
+        PgJiraRepository::new(pool.clone()),
+        PgGitlabRepository::new(pool.clone()),
+        PgSyncRepository::new(pool.clone()),
+    ))])
+}
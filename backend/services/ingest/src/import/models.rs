@@ -0,0 +1,278 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+/// A record type of an import bundle, stored in `<FILE_STEM>.json` or
+/// `<FILE_STEM>.csv`.
+pub trait ImportRecord: for<'de> Deserialize<'de> {
+    const FILE_STEM: &'static str;
+
+    /// Checks what deserialization cannot, e.g. that required text is not blank.
+    fn validate(&self) -> Result<(), String>;
+}
+
+fn require(field: &str, value: &str) -> Result<(), String> {
+    if value.trim().is_empty() {
+        Err(format!("{field} must not be empty"))
+    } else {
+        Ok(())
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_provider() -> String {
+    "gitlab".to_string()
+}
+
+fn default_field() -> String {
+    "status".to_string()
+}
+
+/// A list given as a JSON array, or as `;`-separated text in CSV.
+fn list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ListVisitor;
+
+    impl<'de> Visitor<'de> for ListVisitor {
+        type Value = Vec<String>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a list of strings or ';'-separated text")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(v.split(';')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect())
+        }
+
+        // CSV infers numbers from unquoted fields
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            Ok(vec![v.to_string()])
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            Ok(vec![v.to_string()])
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(Vec::new())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut items = Vec::new();
+            while let Some(item) = seq.next_element::<String>()? {
+                items.push(item);
+            }
+            Ok(items)
+        }
+    }
+
+    deserializer.deserialize_any(ListVisitor)
+}
+
+/// `identities.*` — one account in a source system.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityRecord {
+    /// e.g. "jira", "gitlab", "github", "confluence"
+    pub source: String,
+    pub external_id: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub is_service_account: bool,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+impl ImportRecord for IdentityRecord {
+    const FILE_STEM: &'static str = "identities";
+
+    fn validate(&self) -> Result<(), String> {
+        require("source", &self.source)?;
+        require("external_id", &self.external_id)
+    }
+}
+
+/// `issues.*` — a Jira issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueRecord {
+    pub key: String,
+    /// Defaults to the part of `key` before the dash.
+    pub project_key: Option<String>,
+    pub summary: String,
+    pub status: String,
+    pub issue_type: Option<String>,
+    pub assignee_account_id: Option<String>,
+    pub reporter_account_id: Option<String>,
+    pub priority: Option<String>,
+    pub story_points: Option<f32>,
+    pub sprint_name: Option<String>,
+    pub team_name: Option<String>,
+    #[serde(default, deserialize_with = "list")]
+    pub labels: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl IssueRecord {
+    pub fn project_key(&self) -> String {
+        self.project_key
+            .clone()
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| {
+                self.key
+                    .split_once('-')
+                    .map_or(self.key.as_str(), |(project, _)| project)
+                    .to_string()
+            })
+    }
+}
+
+impl ImportRecord for IssueRecord {
+    const FILE_STEM: &'static str = "issues";
+
+    fn validate(&self) -> Result<(), String> {
+        require("key", &self.key)?;
+        require("summary", &self.summary)?;
+        require("status", &self.status)?;
+        if self.story_points.is_some_and(|p| p < 0.0) {
+            return Err("story_points must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// `transitions.*` — a change of an issue field, by default its status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionRecord {
+    pub issue_key: String,
+    #[serde(default = "default_field")]
+    pub field: String,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+    pub author_account_id: Option<String>,
+    pub transitioned_at: DateTime<Utc>,
+}
+
+impl ImportRecord for TransitionRecord {
+    const FILE_STEM: &'static str = "transitions";
+
+    fn validate(&self) -> Result<(), String> {
+        require("issue_key", &self.issue_key)?;
+        require("field", &self.field)
+    }
+}
+
+/// `projects.*` — a GitLab project or GitHub repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectRecord {
+    #[serde(default = "default_provider")]
+    pub provider: String,
+    pub project_id: i64,
+    pub name: String,
+    /// `group/project` or `owner/repo`
+    pub path: String,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+impl ImportRecord for ProjectRecord {
+    const FILE_STEM: &'static str = "projects";
+
+    fn validate(&self) -> Result<(), String> {
+        validate_provider(&self.provider)?;
+        require("name", &self.name)?;
+        require("path", &self.path)
+    }
+}
+
+/// `merge_requests.*` — a merge request or pull request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestRecord {
+    #[serde(default = "default_provider")]
+    pub provider: String,
+    pub project_id: i64,
+    /// Project-scoped number (GitLab iid, GitHub pull request number)
+    pub iid: i64,
+    pub title: String,
+    /// "opened", "merged" or "closed"
+    pub state: String,
+    pub author_username: Option<String>,
+    #[serde(default, deserialize_with = "list")]
+    pub labels: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub target_branch: Option<String>,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+impl ImportRecord for MergeRequestRecord {
+    const FILE_STEM: &'static str = "merge_requests";
+
+    fn validate(&self) -> Result<(), String> {
+        validate_provider(&self.provider)?;
+        require("title", &self.title)?;
+        match self.state.as_str() {
+            "opened" | "merged" | "closed" => {}
+            other => return Err(format!("unknown state {other:?}")),
+        }
+        if self.state == "merged" && self.merged_at.is_none() {
+            return Err("merged_at is required for merged merge requests".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// `pipelines.*` — a CI pipeline or workflow run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PipelineRecord {
+    #[serde(default = "default_provider")]
+    pub provider: String,
+    pub project_id: i64,
+    pub pipeline_id: i64,
+    /// GitLab pipeline status, e.g. "success", "failed", "running"
+    pub status: String,
+    #[serde(rename = "ref")]
+    pub ref_name: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub duration_secs: Option<i32>,
+    #[serde(default)]
+    pub web_url: String,
+}
+
+impl ImportRecord for PipelineRecord {
+    const FILE_STEM: &'static str = "pipelines";
+
+    fn validate(&self) -> Result<(), String> {
+        validate_provider(&self.provider)?;
+        require("status", &self.status)?;
+        if self.duration_secs.is_some_and(|d| d < 0) {
+            return Err("duration_secs must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn validate_provider(provider: &str) -> Result<(), String> {
+    match provider {
+        "gitlab" | "github" => Ok(()),
+        other => Err(format!("unknown provider {other:?}")),
+    }
+}
@@ -0,0 +1,381 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use ovia_db::gitlab::models::{GitlabMergeRequest, GitlabPipeline, GitlabProject};
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::identity::models::Identity;
+use ovia_db::identity::repositories::IdentityRepository;
+use ovia_db::jira::models::{JiraIssue, JiraIssueTransition};
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::bundle::{self, Bundle};
+use super::models::{
+    IdentityRecord, IssueRecord, MergeRequestRecord, PipelineRecord, ProjectRecord,
+    TransitionRecord,
+};
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "file_import";
+
+/// Imports a bundle of JSON/CSV files into the same tables the live connectors
+/// write, for air-gapped sources, manual exports and demo datasets.
+pub struct FileImportSyncer<I, S> {
+    org_id: Uuid,
+    dir: PathBuf,
+    identity_repo: I,
+    jira_repo: PgJiraRepository,
+    gitlab_repo: PgGitlabRepository,
+    sync_repo: S,
+}
+
+impl<I, S> FileImportSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        dir: PathBuf,
+        identity_repo: I,
+        jira_repo: PgJiraRepository,
+        gitlab_repo: PgGitlabRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            dir,
+            identity_repo,
+            jira_repo,
+            gitlab_repo,
+            sync_repo,
+        }
+    }
+
+    fn identity_to_db(&self, r: &IdentityRecord) -> Identity {
+        Identity {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            source: r.source.clone(),
+            external_id: Some(r.external_id.clone()),
+            username: r.username.clone(),
+            email: r.email.clone(),
+            display_name: r.display_name.clone(),
+            is_service_account: r.is_service_account,
+            is_active: r.is_active,
+            active_changed_at: None,
+            first_seen_at: Some(Utc::now()),
+            last_seen_at: Some(Utc::now()),
+            raw_ref: serde_json::to_value(r).ok(),
+        }
+    }
+
+    fn issue_to_db(&self, r: &IssueRecord) -> JiraIssue {
+        let now = Utc::now();
+        JiraIssue {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            jira_key: r.key.clone(),
+            jira_issue_id: None,
+            project_key: r.project_key(),
+            issue_type: r.issue_type.clone(),
+            summary: r.summary.clone(),
+            status: r.status.clone(),
+            assignee_account_id: r.assignee_account_id.clone(),
+            reporter_account_id: r.reporter_account_id.clone(),
+            priority: r.priority.clone(),
+            story_points: r.story_points,
+            sprint_name: r.sprint_name.clone(),
+            sprint_id: None,
+            team_name: r.team_name.clone(),
+            labels: r.labels.clone(),
+            extra_fields: None,
+            created_at_jira: r.created_at,
+            updated_at_jira: r.updated_at,
+            resolved_at: r.resolved_at,
+            raw_ref: serde_json::to_value(r).ok(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn transition_to_db(&self, r: &TransitionRecord) -> JiraIssueTransition {
+        JiraIssueTransition {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            jira_key: r.issue_key.clone(),
+            field: r.field.clone(),
+            from_value: r.from_value.clone(),
+            to_value: r.to_value.clone(),
+            author_account_id: r.author_account_id.clone(),
+            transitioned_at: r.transitioned_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn project_to_db(&self, r: &ProjectRecord) -> GitlabProject {
+        let now = Utc::now();
+        GitlabProject {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: r.provider.clone(),
+            gitlab_id: r.project_id,
+            name: r.name.clone(),
+            path_with_namespace: r.path.clone(),
+            web_url: r.web_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn merge_request_to_db(&self, r: &MergeRequestRecord) -> GitlabMergeRequest {
+        let now = Utc::now();
+        GitlabMergeRequest {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: r.provider.clone(),
+            gitlab_project_id: r.project_id,
+            gitlab_mr_iid: r.iid,
+            title: r.title.clone(),
+            state: r.state.clone(),
+            author_username: r.author_username.clone(),
+            labels: r.labels.clone(),
+            created_at_gl: r.created_at,
+            merged_at: r.merged_at,
+            target_branch: r.target_branch.clone(),
+            web_url: r.web_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn pipeline_to_db(&self, r: &PipelineRecord) -> GitlabPipeline {
+        let now = Utc::now();
+        GitlabPipeline {
+            id: Uuid::new_v4(),
+            org_id: self.org_id,
+            provider: r.provider.clone(),
+            gitlab_project_id: r.project_id,
+            gitlab_pipeline_id: r.pipeline_id,
+            status: r.status.clone(),
+            ref_name: r.ref_name.clone(),
+            created_at_gl: r.created_at,
+            finished_at_gl: r.finished_at,
+            duration_secs: r.duration_secs,
+            web_url: r.web_url.clone(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Write every record of the bundle. Returns `(upserted, errors)`.
+    async fn import(&self, bundle: &Bundle) -> (usize, usize) {
+        let (mut upserted, mut errors) = (0, 0);
+
+        for r in &bundle.identities {
+            match self
+                .identity_repo
+                .upsert_by_external_id(self.identity_to_db(r))
+                .await
+            {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(source = %r.source, external_id = %r.external_id, error = %e, "failed to import identity");
+                    errors += 1;
+                }
+            }
+        }
+
+        for r in &bundle.issues {
+            match self.jira_repo.upsert_issue(&self.issue_to_db(r)).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(key = %r.key, error = %e, "failed to import issue");
+                    errors += 1;
+                }
+            }
+        }
+
+        // The bundle is the full history of every issue it has transitions for,
+        // so re-importing replaces rather than duplicates it
+        let mut transitions: BTreeMap<&str, Vec<&TransitionRecord>> = BTreeMap::new();
+        for r in &bundle.transitions {
+            transitions.entry(r.issue_key.as_str()).or_default().push(r);
+        }
+        for (key, rows) in transitions {
+            if let Err(e) = self
+                .jira_repo
+                .delete_transitions_for_issue(self.org_id, key)
+                .await
+            {
+                tracing::warn!(key, error = %e, "failed to clear transitions");
+                errors += rows.len();
+                continue;
+            }
+            for r in rows {
+                match self
+                    .jira_repo
+                    .insert_transition(&self.transition_to_db(r))
+                    .await
+                {
+                    Ok(_) => upserted += 1,
+                    Err(e) => {
+                        tracing::warn!(key, error = %e, "failed to import transition");
+                        errors += 1;
+                    }
+                }
+            }
+        }
+
+        for r in &bundle.projects {
+            match self
+                .gitlab_repo
+                .upsert_project(&self.project_to_db(r))
+                .await
+            {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(path = %r.path, error = %e, "failed to import project");
+                    errors += 1;
+                }
+            }
+        }
+
+        for r in &bundle.merge_requests {
+            match self
+                .gitlab_repo
+                .upsert_merge_request(&self.merge_request_to_db(r))
+                .await
+            {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(project_id = r.project_id, iid = r.iid, error = %e, "failed to import merge request");
+                    errors += 1;
+                }
+            }
+        }
+
+        for r in &bundle.pipelines {
+            match self
+                .gitlab_repo
+                .upsert_pipeline(&self.pipeline_to_db(r))
+                .await
+            {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(pipeline_id = r.pipeline_id, error = %e, "failed to import pipeline");
+                    errors += 1;
+                }
+            }
+        }
+
+        (upserted, errors)
+    }
+}
+
+#[async_trait]
+impl<I, S> Connector for FileImportSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        // Ensure watermark row exists
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        // Try to acquire lock
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "file import already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: SOURCE_NAME.to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        // The cursor is the newest file's modification time; an unchanged bundle
+        // is not imported again
+        let loaded = bundle::last_modified(&self.dir).and_then(|modified| {
+            let cursor = modified.map(|m| m.to_rfc3339());
+            if cursor.is_none() || cursor == watermark.cursor_value {
+                return Ok((cursor, None));
+            }
+            Bundle::load(&self.dir).map(|b| (cursor, Some(b)))
+        });
+        let (cursor, bundle) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "import bundle could not be read");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        let (upserted, errors) = match &bundle {
+            Some(bundle) => {
+                tracing::info!(
+                    dir = %self.dir.display(),
+                    records = bundle.len(),
+                    invalid_rows = bundle.row_errors.len(),
+                    "loaded import bundle"
+                );
+                for e in &bundle.row_errors {
+                    tracing::warn!(file = %e.file, row = e.row, error = %e.message, "invalid import row");
+                }
+                let (upserted, errors) = self.import(bundle).await;
+                (upserted, errors + bundle.row_errors.len())
+            }
+            None => {
+                tracing::info!(dir = %self.dir.display(), "import bundle unchanged, skipping");
+                (0, 0)
+            }
+        };
+
+        self.sync_repo
+            .mark_completed(watermark.id, cursor.as_deref())
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted,
+            skipped: 0,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "file import completed");
+        Ok(result)
+    }
+}
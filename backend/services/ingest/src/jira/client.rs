@@ -1,9 +1,20 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use reqwest::{Client, StatusCode};
+use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
 
-use super::models::{JiraChangelogResponse, JiraIssue, JiraSearchResponse, JiraUser};
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::org::models::Org;
+
+use super::fields::JiraFieldMapping;
+use super::models::{
+    JiraAgilePage, JiraAgileSprint, JiraBoard, JiraBulkChangelogResponse, JiraChangelogEntry,
+    JiraChangelogResponse, JiraField, JiraIssue, JiraIssueKey, JiraIssueKeyPage,
+    JiraSearchResponse, JiraUser, JiraWorklog, JiraWorklogChange, JiraWorklogChangePage,
+};
+use crate::http::{Auth, HttpClient, HttpClientConfig, HttpClientError};
 
 #[derive(Debug, Clone)]
 pub struct JiraClientConfig {
@@ -17,27 +28,54 @@ pub struct JiraClientConfig {
 }
 
 impl JiraClientConfig {
-    /// Load Jira config from environment.
+    /// Load Jira config for an org. The org's base URL and project keys and the
+    /// vault credential take precedence. With `env_fallback` (only for the org built
+    /// from `ORG_ID`) any of them left unset comes from the environment; tuning always
+    /// does.
     ///
     /// Returns `Ok(None)` if Jira is not configured (base URL / email / token missing).
-    /// Returns `Err` if Jira IS configured but `JIRA_PROJECT_KEYS` is missing or empty
-    /// (fail-fast on misconfiguration).
-    pub fn from_env() -> Result<Option<Self>, String> {
-        let base_url = match std::env::var("JIRA_BASE_URL").ok() {
+    /// Returns `Err` if Jira IS configured but has no project keys, neither on the org
+    /// nor in `JIRA_PROJECT_KEYS` (fail-fast on misconfiguration).
+    pub fn for_org(
+        org: &Org,
+        credential: Option<&SourceSecret>,
+        env_fallback: bool,
+    ) -> Result<Option<Self>, String> {
+        let env = |name: &str| env_fallback.then(|| std::env::var(name).ok()).flatten();
+        let base_url = match org.jira_base_url.clone().or_else(|| env("JIRA_BASE_URL")) {
             Some(v) => v,
             None => return Ok(None),
         };
-        let email = match std::env::var("JIRA_EMAIL").ok() {
+        let email = match credential
+            .and_then(|c| c.principal.clone())
+            .or_else(|| env("JIRA_EMAIL"))
+        {
             Some(v) => v,
             None => return Ok(None),
         };
-        let api_token = match std::env::var("JIRA_API_TOKEN").ok() {
+        let api_token = match credential
+            .map(|c| c.secret.clone())
+            .or_else(|| env("JIRA_API_TOKEN"))
+        {
             Some(v) => v,
             None => return Ok(None),
         };
 
-        // Jira IS configured — JIRA_PROJECT_KEYS is now mandatory
-        let project_keys = parse_csv_project_keys("JIRA_PROJECT_KEYS")?;
+        // Jira IS configured — project keys are now mandatory
+        let project_keys = if !org.jira_project_keys.is_empty() {
+            org.jira_project_keys
+                .iter()
+                .map(|k| k.trim().to_uppercase())
+                .filter(|k| !k.is_empty())
+                .collect()
+        } else if env_fallback {
+            parse_csv_project_keys("JIRA_PROJECT_KEYS")?
+        } else {
+            Vec::new()
+        };
+        if project_keys.is_empty() {
+            return Err(format!("org {} has no valid Jira project keys", org.id));
+        }
 
         let sync_window_days = std::env::var("JIRA_SYNC_WINDOW_DAYS")
             .ok()
@@ -86,30 +124,33 @@ pub fn parse_csv_project_keys(env_key: &str) -> Result<Vec<String>, String> {
     Ok(keys)
 }
 
+/// Most issues one bulk changelog request may name.
+pub const BULK_CHANGELOG_MAX_ISSUES: usize = 1000;
+
 #[derive(Clone)]
 pub struct JiraClient {
-    client: Client,
+    http: HttpClient,
     config: JiraClientConfig,
+    /// Cleared once the site turns out to lack the bulk changelog API
+    bulk_changelog: Arc<AtomicBool>,
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum JiraClientError {
-    #[error("HTTP {status}: {body}")]
-    HttpError { status: StatusCode, body: String },
-
-    #[error("request failed: {0}")]
-    RequestError(#[from] reqwest::Error),
-
-    #[error("max retries exceeded after {attempts} attempts: {last_error}")]
-    MaxRetriesExceeded { attempts: u32, last_error: String },
-}
+pub type JiraClientError = HttpClientError;
 
 impl JiraClient {
     pub fn new(config: JiraClientConfig) -> Result<Self, reqwest::Error> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_secs))
-            .build()?;
-        Ok(Self { client, config })
+        let http = HttpClient::new(
+            HttpClientConfig::from_env(config.max_retries, config.timeout_secs),
+            Auth::Basic {
+                username: config.email.clone(),
+                password: config.api_token.clone(),
+            },
+        )?;
+        Ok(Self {
+            http,
+            config,
+            bulk_changelog: Arc::new(AtomicBool::new(true)),
+        })
     }
 
     /// For testing: create a client pointing at a specific base URL (e.g., wiremock).
@@ -153,10 +194,14 @@ impl JiraClient {
     /// Uses the `/rest/api/3/search/jql` endpoint (the legacy `/rest/api/3/search`
     /// was removed by Atlassian in August 2025). Pagination is token-based via
     /// `nextPageToken`.
-    pub async fn search_issues(&self, jql: &str) -> Result<Vec<JiraIssue>, JiraClientError> {
+    pub async fn search_issues(
+        &self,
+        jql: &str,
+        mapping: &JiraFieldMapping,
+    ) -> Result<Vec<JiraIssue>, JiraClientError> {
         let max_results = 50;
         let mut all_issues = Vec::new();
-        let fields = "summary,status,issuetype,assignee,reporter,priority,labels,created,updated,resolutiondate,customfield_10016,customfield_10020,customfield_10001";
+        let fields = mapping.search_fields();
         let mut next_page_token: Option<String> = None;
 
         loop {
@@ -185,11 +230,69 @@ impl JiraClient {
         Ok(all_issues)
     }
 
+    /// Ids and keys of every issue currently in a project.
+    pub async fn list_issue_keys(
+        &self,
+        project_key: &str,
+    ) -> Result<Vec<JiraIssueKey>, JiraClientError> {
+        let jql = format!("project = \"{project_key}\" order by key asc");
+        let mut all_keys = Vec::new();
+        let mut next_page_token: Option<String> = None;
+
+        loop {
+            let mut url = format!(
+                "{}/rest/api/3/search/jql?jql={}&maxResults=1000&fields=id",
+                self.config.base_url,
+                urlencoding::encode(&jql),
+            );
+            if let Some(ref token) = next_page_token {
+                url.push_str(&format!("&nextPageToken={}", urlencoding::encode(token)));
+            }
+
+            let page: JiraIssueKeyPage = self.request_with_retry(&url).await?;
+            let page_len = page.issues.len();
+            all_keys.extend(page.issues);
+
+            match page.next_page_token {
+                Some(token) if page_len > 0 => next_page_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(all_keys)
+    }
+
+    /// Look up an issue by key. Returns its current id and key (which differs
+    /// after a move), or `None` if the issue no longer exists.
+    pub async fn fetch_issue_key(
+        &self,
+        issue_key: &str,
+    ) -> Result<Option<JiraIssueKey>, JiraClientError> {
+        let url = format!(
+            "{}/rest/api/3/issue/{}?fields=id",
+            self.config.base_url,
+            urlencoding::encode(issue_key)
+        );
+        match self.request_with_retry(&url).await {
+            Ok(issue) => Ok(Some(issue)),
+            Err(JiraClientError::HttpError { status, .. }) if status == StatusCode::NOT_FOUND => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fetch all system and custom fields of the site.
+    pub async fn fetch_fields(&self) -> Result<Vec<JiraField>, JiraClientError> {
+        let url = format!("{}/rest/api/3/field", self.config.base_url);
+        self.request_with_retry(&url).await
+    }
+
     /// Fetch the full changelog for a single issue.
     pub async fn fetch_issue_changelog(
         &self,
         issue_key: &str,
-    ) -> Result<Vec<super::models::JiraChangelogEntry>, JiraClientError> {
+    ) -> Result<Vec<JiraChangelogEntry>, JiraClientError> {
         let max_results = 100;
         let mut start_at: usize = 0;
         let mut all_entries = Vec::new();
@@ -213,77 +316,171 @@ impl JiraClient {
         Ok(all_entries)
     }
 
-    async fn request_with_retry<T: DeserializeOwned>(
+    /// Fetch the changelogs of up to [`BULK_CHANGELOG_MAX_ISSUES`] issues at once
+    /// through the bulk changelog API, keyed by issue id. Returns `Ok(None)` if the
+    /// site has no such endpoint (Jira Data Center); the client then remembers
+    /// that, and callers fall back to `fetch_issue_changelog`.
+    pub async fn fetch_bulk_changelogs(
         &self,
-        url: &str,
-    ) -> Result<T, JiraClientError> {
-        let mut last_error = String::new();
+        issue_ids: &[String],
+    ) -> Result<Option<HashMap<String, Vec<JiraChangelogEntry>>>, JiraClientError> {
+        if !self.bulk_changelog.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let url = format!("{}/rest/api/3/changelog/bulkfetch", self.config.base_url);
+        let mut changelogs: HashMap<String, Vec<JiraChangelogEntry>> = HashMap::new();
+        let mut next_page_token: Option<String> = None;
 
-        for attempt in 0..=self.config.max_retries {
-            if attempt > 0 {
-                let backoff_secs = std::cmp::min(1u64 << attempt, 30);
-                tracing::warn!(attempt, backoff_secs, "retrying after backoff");
-                tokio::time::sleep(Duration::from_secs(backoff_secs)).await;
+        loop {
+            let mut body = serde_json::json!({
+                "issueIdsOrKeys": issue_ids,
+                "maxResults": 1000,
+            });
+            if let Some(token) = &next_page_token {
+                body["nextPageToken"] = serde_json::json!(token);
             }
 
-            let response = match self
-                .client
-                .get(url)
-                .basic_auth(&self.config.email, Some(&self.config.api_token))
-                .send()
-                .await
-            {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = e.to_string();
-                    if e.is_timeout() || e.is_connect() {
-                        continue;
-                    }
-                    return Err(JiraClientError::RequestError(e));
+            let page: JiraBulkChangelogResponse = match self.http.post(&url, &body).await {
+                Ok(resp) => resp.body,
+                Err(JiraClientError::HttpError { status, .. })
+                    if changelogs.is_empty()
+                        && (status == StatusCode::NOT_FOUND
+                            || status == StatusCode::METHOD_NOT_ALLOWED) =>
+                {
+                    tracing::info!(%status, "jira bulk changelog unavailable, fetching per issue");
+                    self.bulk_changelog.store(false, Ordering::Relaxed);
+                    return Ok(None);
                 }
+                Err(e) => return Err(e),
             };
 
-            let status = response.status();
-
-            if status.is_success() {
-                return response
-                    .json::<T>()
-                    .await
-                    .map_err(JiraClientError::RequestError);
+            let page_len = page.issue_change_logs.len();
+            for log in page.issue_change_logs {
+                changelogs
+                    .entry(log.issue_id)
+                    .or_default()
+                    .extend(log.change_histories);
             }
 
-            // Honor Retry-After header for 429
-            if status == StatusCode::TOO_MANY_REQUESTS {
-                if let Some(retry_after) = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok())
-                {
-                    let wait = std::cmp::min(retry_after, 60);
-                    tracing::warn!(wait, "rate-limited, waiting Retry-After");
-                    tokio::time::sleep(Duration::from_secs(wait)).await;
-                }
-                last_error = "429 Too Many Requests".to_string();
-                continue;
+            match page.next_page_token {
+                Some(token) if page_len > 0 => next_page_token = Some(token),
+                _ => break,
             }
+        }
 
-            // Retry on 5xx
-            if status.is_server_error() {
-                let body = response.text().await.unwrap_or_default();
-                last_error = format!("{status}: {body}");
-                continue;
+        Ok(Some(changelogs))
+    }
+
+    /// Ids of worklogs created or updated since `since_ms` (epoch milliseconds).
+    /// Returns the changes and the `until` of the last page, the cursor for the next
+    /// call.
+    pub async fn fetch_updated_worklog_ids(
+        &self,
+        since_ms: i64,
+    ) -> Result<(Vec<JiraWorklogChange>, i64), JiraClientError> {
+        self.fetch_worklog_changes("updated", since_ms).await
+    }
+
+    /// Ids of worklogs deleted since `since_ms`, with the cursor for the next call.
+    pub async fn fetch_deleted_worklog_ids(
+        &self,
+        since_ms: i64,
+    ) -> Result<(Vec<JiraWorklogChange>, i64), JiraClientError> {
+        self.fetch_worklog_changes("deleted", since_ms).await
+    }
+
+    async fn fetch_worklog_changes(
+        &self,
+        kind: &str,
+        since_ms: i64,
+    ) -> Result<(Vec<JiraWorklogChange>, i64), JiraClientError> {
+        let mut since = since_ms;
+        let mut all_changes = Vec::new();
+
+        loop {
+            let url = format!(
+                "{}/rest/api/3/worklog/{}?since={}",
+                self.config.base_url, kind, since
+            );
+            let page: JiraWorklogChangePage = self.request_with_retry(&url).await?;
+            let page_len = page.values.len();
+            all_changes.extend(page.values);
+            since = page.until.max(page.since);
+
+            if page.last_page || page_len == 0 {
+                break;
             }
+        }
+
+        Ok((all_changes, since))
+    }
+
+    /// Fetch worklogs by id, in batches of 1000 (the API's limit).
+    pub async fn fetch_worklogs(&self, ids: &[i64]) -> Result<Vec<JiraWorklog>, JiraClientError> {
+        let url = format!("{}/rest/api/3/worklog/list", self.config.base_url);
+        let mut all_worklogs = Vec::new();
 
-            // Fail fast on 4xx (except 429 handled above)
-            let body = response.text().await.unwrap_or_default();
-            return Err(JiraClientError::HttpError { status, body });
+        for batch in ids.chunks(1000) {
+            let body = serde_json::json!({ "ids": batch });
+            let worklogs: Vec<JiraWorklog> = self.http.post(&url, &body).await?.body;
+            all_worklogs.extend(worklogs);
         }
 
-        Err(JiraClientError::MaxRetriesExceeded {
-            attempts: self.config.max_retries + 1,
-            last_error,
-        })
+        Ok(all_worklogs)
+    }
+
+    /// Fetch the boards of a project from the Agile API.
+    pub async fn fetch_boards(&self, project_key: &str) -> Result<Vec<JiraBoard>, JiraClientError> {
+        let url = format!(
+            "{}/rest/agile/1.0/board?projectKeyOrId={}",
+            self.config.base_url,
+            urlencoding::encode(project_key)
+        );
+        self.fetch_agile_pages(&url).await
+    }
+
+    /// Fetch all sprints of a scrum board.
+    pub async fn fetch_board_sprints(
+        &self,
+        board_id: i64,
+    ) -> Result<Vec<JiraAgileSprint>, JiraClientError> {
+        let url = format!(
+            "{}/rest/agile/1.0/board/{}/sprint",
+            self.config.base_url, board_id
+        );
+        self.fetch_agile_pages(&url).await
+    }
+
+    /// Follow `startAt` paging of the Agile API until `isLast`.
+    async fn fetch_agile_pages<T: DeserializeOwned>(
+        &self,
+        base_url: &str,
+    ) -> Result<Vec<T>, JiraClientError> {
+        let max_results = 50;
+        let mut start_at: usize = 0;
+        let mut all_items = Vec::new();
+        let separator = if base_url.contains('?') { '&' } else { '?' };
+
+        loop {
+            let url = format!("{base_url}{separator}startAt={start_at}&maxResults={max_results}");
+            let page: JiraAgilePage<T> = self.request_with_retry(&url).await?;
+            let page_len = page.values.len();
+            all_items.extend(page.values);
+
+            if page.is_last || page_len == 0 {
+                break;
+            }
+            start_at += page_len;
+        }
+
+        Ok(all_items)
+    }
+
+    async fn request_with_retry<T: DeserializeOwned>(
+        &self,
+        url: &str,
+    ) -> Result<T, JiraClientError> {
+        Ok(self.http.get(url).await?.body)
     }
 }
 
@@ -552,6 +749,10 @@ mod tests {
         std::env::remove_var("_TEST_KEYS4");
     }
 
+    fn env_only_org() -> Org {
+        Org::new(uuid::Uuid::new_v4(), "test")
+    }
+
     #[test]
     fn from_env_returns_none_when_no_jira_creds() {
         let _g = ENV_LOCK.lock().unwrap();
@@ -559,7 +760,7 @@ mod tests {
         std::env::remove_var("JIRA_EMAIL");
         std::env::remove_var("JIRA_API_TOKEN");
         std::env::remove_var("JIRA_PROJECT_KEYS");
-        let result = JiraClientConfig::from_env().unwrap();
+        let result = JiraClientConfig::for_org(&env_only_org(), None, true).unwrap();
         assert!(result.is_none());
     }
 
@@ -570,7 +771,7 @@ mod tests {
         std::env::set_var("JIRA_EMAIL", "a@b.com");
         std::env::set_var("JIRA_API_TOKEN", "tok");
         std::env::remove_var("JIRA_PROJECT_KEYS");
-        let err = JiraClientConfig::from_env().unwrap_err();
+        let err = JiraClientConfig::for_org(&env_only_org(), None, true).unwrap_err();
         assert!(err.contains("JIRA_PROJECT_KEYS"), "got: {err}");
         std::env::remove_var("JIRA_BASE_URL");
         std::env::remove_var("JIRA_EMAIL");
@@ -585,7 +786,9 @@ mod tests {
         std::env::set_var("JIRA_API_TOKEN", "tok");
         std::env::set_var("JIRA_PROJECT_KEYS", "DEV,OPS");
         std::env::set_var("JIRA_SYNC_WINDOW_DAYS", "14");
-        let cfg = JiraClientConfig::from_env().unwrap().unwrap();
+        let cfg = JiraClientConfig::for_org(&env_only_org(), None, true)
+            .unwrap()
+            .unwrap();
         assert_eq!(cfg.project_keys, vec!["DEV", "OPS"]);
         assert_eq!(cfg.sync_window_days, 14);
         std::env::remove_var("JIRA_BASE_URL");
@@ -594,4 +797,117 @@ mod tests {
         std::env::remove_var("JIRA_PROJECT_KEYS");
         std::env::remove_var("JIRA_SYNC_WINDOW_DAYS");
     }
+
+    #[test]
+    fn for_org_overrides_base_url_and_project_keys() {
+        let _g = ENV_LOCK.lock().unwrap();
+        std::env::set_var("JIRA_BASE_URL", "https://env.atlassian.net");
+        std::env::set_var("JIRA_EMAIL", "a@b.com");
+        std::env::set_var("JIRA_API_TOKEN", "tok");
+        std::env::remove_var("JIRA_PROJECT_KEYS");
+        let org = Org {
+            jira_base_url: Some("https://acme.atlassian.net".to_string()),
+            jira_project_keys: vec!["dev ".to_string(), "ops".to_string()],
+            ..env_only_org()
+        };
+        let cfg = JiraClientConfig::for_org(&org, None, true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cfg.base_url, "https://acme.atlassian.net");
+        assert_eq!(cfg.project_keys, vec!["DEV", "OPS"]);
+        std::env::remove_var("JIRA_BASE_URL");
+        std::env::remove_var("JIRA_EMAIL");
+        std::env::remove_var("JIRA_API_TOKEN");
+    }
+
+    #[test]
+    fn for_org_returns_none_without_credentials() {
+        let _g = ENV_LOCK.lock().unwrap();
+        std::env::remove_var("JIRA_EMAIL");
+        std::env::remove_var("JIRA_API_TOKEN");
+        let org = Org {
+            jira_base_url: Some("https://acme.atlassian.net".to_string()),
+            jira_project_keys: vec!["DEV".to_string()],
+            ..env_only_org()
+        };
+        assert!(JiraClientConfig::for_org(&org, None, true)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn for_org_prefers_vault_credential() {
+        let _g = ENV_LOCK.lock().unwrap();
+        std::env::set_var("JIRA_EMAIL", "env@b.com");
+        std::env::set_var("JIRA_API_TOKEN", "env-token");
+        let org = Org {
+            jira_base_url: Some("https://acme.atlassian.net".to_string()),
+            jira_project_keys: vec!["DEV".to_string()],
+            ..env_only_org()
+        };
+        let credential = SourceSecret {
+            source: "jira".to_string(),
+            principal: Some("vault@acme.com".to_string()),
+            secret: "vault-token".to_string(),
+        };
+        let cfg = JiraClientConfig::for_org(&org, Some(&credential), true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cfg.email, "vault@acme.com");
+        assert_eq!(cfg.api_token, "vault-token");
+        std::env::remove_var("JIRA_EMAIL");
+        std::env::remove_var("JIRA_API_TOKEN");
+    }
+
+    #[test]
+    fn registry_orgs_do_not_fall_back_to_env() {
+        let _g = ENV_LOCK.lock().unwrap();
+        std::env::set_var("JIRA_BASE_URL", "https://env.atlassian.net");
+        std::env::set_var("JIRA_EMAIL", "env@b.com");
+        std::env::set_var("JIRA_API_TOKEN", "env-token");
+        std::env::set_var("JIRA_PROJECT_KEYS", "ENV");
+        let credential = SourceSecret {
+            source: "jira".to_string(),
+            principal: Some("vault@acme.com".to_string()),
+            secret: "vault-token".to_string(),
+        };
+        let acme = Org {
+            jira_base_url: Some("https://acme.atlassian.net".to_string()),
+            jira_project_keys: vec!["DEV".to_string()],
+            ..env_only_org()
+        };
+        let globex = Org {
+            jira_base_url: Some("https://globex.atlassian.net".to_string()),
+            jira_project_keys: vec!["OPS".to_string()],
+            ..env_only_org()
+        };
+
+        let cfg = JiraClientConfig::for_org(&acme, Some(&credential), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(cfg.api_token, "vault-token");
+        // Own base URL but no vault credential: the env token must not be used
+        assert!(JiraClientConfig::for_org(&globex, None, false)
+            .unwrap()
+            .is_none());
+        // Nothing configured: the env tenant must not be ingested into this org
+        assert!(JiraClientConfig::for_org(&env_only_org(), None, false)
+            .unwrap()
+            .is_none());
+        // A vault credential without project keys does not borrow JIRA_PROJECT_KEYS
+        let no_keys = Org {
+            jira_project_keys: vec![],
+            ..acme
+        };
+        assert!(JiraClientConfig::for_org(&no_keys, Some(&credential), false).is_err());
+
+        for var in [
+            "JIRA_BASE_URL",
+            "JIRA_EMAIL",
+            "JIRA_API_TOKEN",
+            "JIRA_PROJECT_KEYS",
+        ] {
+            std::env::remove_var(var);
+        }
+    }
 }
@@ -0,0 +1,310 @@
+//! Which custom fields hold story points, sprint, team and epic link differs per
+//! Jira site. The mapping is discovered from `/rest/api/3/field` and can be
+//! overridden per org (`jira_field_mappings`). The same table holds the
+//! allow-list of changelog fields kept as transitions.
+
+use ovia_db::jira::models::JiraFieldMapping as DbFieldMapping;
+
+use super::models::{JiraChangelogItem, JiraField};
+
+/// Standard fields requested from the search API on top of the mapped ones.
+const STANDARD_FIELDS: &str = "summary,status,issuetype,assignee,reporter,priority,labels,created,updated,resolutiondate,issuelinks,parent,subtasks";
+
+const SPRINT_TYPE: &str = "com.pyxis.greenhopper.jira:gh-sprint";
+const EPIC_LINK_TYPE: &str = "com.pyxis.greenhopper.jira:gh-epic-link";
+const STORY_POINTS_TYPE: &str = "com.pyxis.greenhopper.jira:jsw-story-points";
+const TEAM_TYPE: &str = "com.atlassian.jira.plugin.system.customfieldtypes:atlassian-team";
+
+const STORY_POINTS_NAMES: &[&str] = &["story points", "story point estimate"];
+
+/// Changelog fields every org keeps: cycle time and sprint history depend on them.
+const REQUIRED_CHANGELOG_FIELDS: &[&str] = &["status", "sprint"];
+
+/// Changelog fields kept on top of the required ones while an org has no
+/// "changelog" rows.
+const DEFAULT_CHANGELOG_FIELDS: &[&str] = &[
+    "assignee",
+    "priority",
+    "story_points",
+    "fix_version",
+    "labels",
+    "issuetype",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiraFieldMapping {
+    pub story_points: Option<String>,
+    pub sprint: Option<String>,
+    pub team: Option<String>,
+    pub epic_link: Option<String>,
+    pub extra: Vec<String>,
+    /// Allow-listed changelog fields, by canonical name or custom field id.
+    pub changelog: Vec<String>,
+}
+
+/// The field ids of a default Jira Cloud site, used until discovery has run.
+impl Default for JiraFieldMapping {
+    fn default() -> Self {
+        Self {
+            story_points: Some("customfield_10016".to_string()),
+            sprint: Some("customfield_10020".to_string()),
+            team: Some("customfield_10001".to_string()),
+            epic_link: None,
+            extra: Vec::new(),
+            changelog: REQUIRED_CHANGELOG_FIELDS
+                .iter()
+                .chain(DEFAULT_CHANGELOG_FIELDS)
+                .map(|f| f.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// A field found for a role by discovery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredField {
+    pub role: &'static str,
+    pub field_id: String,
+    pub field_name: String,
+}
+
+impl JiraFieldMapping {
+    /// Build the mapping from stored rows. Roles without a row keep the default.
+    /// "changelog" rows replace the default changelog fields, not the required ones.
+    pub fn from_rows(rows: &[DbFieldMapping]) -> Self {
+        let mut mapping = Self::default();
+        if rows.iter().any(|r| r.role == "changelog") {
+            mapping.changelog.truncate(REQUIRED_CHANGELOG_FIELDS.len());
+        }
+        for row in rows {
+            let field_id = Some(row.field_id.clone());
+            match row.role.as_str() {
+                "story_points" => mapping.story_points = field_id,
+                "sprint" => mapping.sprint = field_id,
+                "team" => mapping.team = field_id,
+                "epic_link" => mapping.epic_link = field_id,
+                "extra" => mapping.extra.push(row.field_id.clone()),
+                "changelog" if !mapping.changelog.contains(&row.field_id) => {
+                    mapping.changelog.push(row.field_id.clone())
+                }
+                _ => {}
+            }
+        }
+        mapping
+    }
+
+    /// Comma-separated `fields` parameter for the search API.
+    pub fn search_fields(&self) -> String {
+        let mut fields = vec![STANDARD_FIELDS.to_string()];
+        fields.extend(
+            [
+                &self.story_points,
+                &self.sprint,
+                &self.team,
+                &self.epic_link,
+            ]
+            .into_iter()
+            .flatten()
+            .chain(&self.extra)
+            .cloned(),
+        );
+        fields.join(",")
+    }
+
+    /// Canonical name of the field a changelog item changed: "story_points" and
+    /// "sprint" for the mapped custom fields, "fix_version", the field id of other
+    /// custom fields, else the lowercased field name.
+    pub fn changelog_field(&self, item: &JiraChangelogItem) -> String {
+        let field_id = item.field_id.as_deref();
+        let name = item.field.to_lowercase();
+        if field_id.is_some() && field_id == self.story_points.as_deref()
+            || STORY_POINTS_NAMES.contains(&name.as_str())
+        {
+            return "story_points".to_string();
+        }
+        if field_id.is_some() && field_id == self.sprint.as_deref() || name == "sprint" {
+            return "sprint".to_string();
+        }
+        match field_id {
+            Some("fixVersions") => "fix_version".to_string(),
+            Some(id) if id.starts_with("customfield_") => id.to_string(),
+            _ if name == "fix version" => "fix_version".to_string(),
+            _ => name,
+        }
+    }
+
+    /// Canonical field name of a changelog item, if the allow-list keeps it.
+    pub fn kept_changelog_field(&self, item: &JiraChangelogItem) -> Option<String> {
+        let field = self.changelog_field(item);
+        self.changelog.contains(&field).then_some(field)
+    }
+}
+
+/// Find the fields for each role among a site's fields, by custom field type and
+/// then by name. The first match of a role wins.
+pub fn discover_fields(fields: &[JiraField]) -> Vec<DiscoveredField> {
+    let custom_type = |f: &JiraField| f.schema.as_ref().and_then(|s| s.custom.clone());
+    let find =
+        |pred: &dyn Fn(&JiraField) -> bool| fields.iter().filter(|f| f.custom).find(|f| pred(f));
+
+    let candidates: [(&'static str, Option<&JiraField>); 4] = [
+        (
+            "story_points",
+            find(&|f| custom_type(f).as_deref() == Some(STORY_POINTS_TYPE)).or_else(|| {
+                find(&|f| STORY_POINTS_NAMES.contains(&f.name.to_lowercase().as_str()))
+            }),
+        ),
+        (
+            "sprint",
+            find(&|f| custom_type(f).as_deref() == Some(SPRINT_TYPE)),
+        ),
+        (
+            "team",
+            find(&|f| custom_type(f).as_deref() == Some(TEAM_TYPE))
+                .or_else(|| find(&|f| f.name.eq_ignore_ascii_case("team"))),
+        ),
+        (
+            "epic_link",
+            find(&|f| custom_type(f).as_deref() == Some(EPIC_LINK_TYPE)),
+        ),
+    ];
+
+    candidates
+        .into_iter()
+        .filter_map(|(role, field)| {
+            field.map(|f| DiscoveredField {
+                role,
+                field_id: f.id.clone(),
+                field_name: f.name.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(id: &str, name: &str, custom_type: Option<&str>) -> JiraField {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "custom": true,
+            "schema": { "type": "any", "custom": custom_type }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn discovers_fields_by_type_then_name() {
+        let fields = vec![
+            serde_json::from_value(serde_json::json!({
+                "id": "summary", "name": "Summary", "custom": false
+            }))
+            .unwrap(),
+            field(
+                "customfield_10106",
+                "Story Points",
+                Some("com.atlassian.jira.plugin.system.customfieldtypes:float"),
+            ),
+            field("customfield_10104", "Sprint", Some(SPRINT_TYPE)),
+            field("customfield_10102", "Epic Link", Some(EPIC_LINK_TYPE)),
+            field("customfield_10300", "Team", Some(TEAM_TYPE)),
+        ];
+
+        let found = discover_fields(&fields);
+        let found: Vec<_> = found
+            .iter()
+            .map(|d| (d.role, d.field_id.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("story_points", "customfield_10106"),
+                ("sprint", "customfield_10104"),
+                ("team", "customfield_10300"),
+                ("epic_link", "customfield_10102"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mapping_from_rows_overrides_defaults_and_drives_search_fields() {
+        let row = |role: &str, field_id: &str| DbFieldMapping {
+            id: uuid::Uuid::new_v4(),
+            org_id: uuid::Uuid::new_v4(),
+            role: role.to_string(),
+            field_id: field_id.to_string(),
+            field_name: None,
+            is_manual: false,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let mapping = JiraFieldMapping::from_rows(&[
+            row("story_points", "customfield_10106"),
+            row("extra", "customfield_10200"),
+        ]);
+        assert_eq!(mapping.story_points.as_deref(), Some("customfield_10106"));
+        assert_eq!(mapping.sprint.as_deref(), Some("customfield_10020"));
+        assert!(mapping.epic_link.is_none());
+        assert_eq!(mapping.changelog, JiraFieldMapping::default().changelog);
+        assert!(mapping.search_fields().ends_with(
+            ",subtasks,customfield_10106,customfield_10020,customfield_10001,customfield_10200"
+        ));
+    }
+
+    #[test]
+    fn changelog_allow_list_uses_canonical_names() {
+        let item = |field: &str, field_id: Option<&str>| JiraChangelogItem {
+            field: field.to_string(),
+            field_id: field_id.map(str::to_string),
+            from_string: None,
+            to_string: None,
+            from: None,
+            to: None,
+        };
+        let default = JiraFieldMapping::default();
+        assert_eq!(
+            default.kept_changelog_field(&item("Story point estimate", Some("customfield_10016"))),
+            Some("story_points".to_string())
+        );
+        assert_eq!(
+            default.kept_changelog_field(&item("Fix Version", Some("fixVersions"))),
+            Some("fix_version".to_string())
+        );
+        assert_eq!(
+            default.kept_changelog_field(&item("Assignee", Some("assignee"))),
+            Some("assignee".to_string())
+        );
+        assert!(default
+            .kept_changelog_field(&item("description", Some("description")))
+            .is_none());
+
+        let row = DbFieldMapping {
+            id: uuid::Uuid::new_v4(),
+            org_id: uuid::Uuid::new_v4(),
+            role: "changelog".to_string(),
+            field_id: "customfield_10200".to_string(),
+            field_name: Some("Severity".to_string()),
+            is_manual: true,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+        let mapping = JiraFieldMapping::from_rows(&[row]);
+        assert_eq!(
+            mapping.changelog,
+            vec!["status", "sprint", "customfield_10200"]
+        );
+        assert_eq!(
+            mapping.kept_changelog_field(&item("Severity", Some("customfield_10200"))),
+            Some("customfield_10200".to_string())
+        );
+        assert!(mapping
+            .kept_changelog_field(&item("priority", Some("priority")))
+            .is_none());
+        assert_eq!(
+            mapping.kept_changelog_field(&item("Sprint", Some("customfield_10020"))),
+            Some("sprint".to_string())
+        );
+    }
+}
@@ -1,32 +1,43 @@
 use async_trait::async_trait;
-use chrono::{Duration, Utc};
-use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use ovia_db::identity::models::Identity;
 use ovia_db::identity::repositories::IdentityRepository;
-use ovia_db::jira::models::{JiraIssue as DbJiraIssue, JiraIssueTransition};
+use ovia_db::jira::models::{
+    JiraIssue as DbJiraIssue, JiraIssueHistory, JiraIssueLink, JiraIssueParent, JiraIssueSprint,
+    JiraIssueTransition, USER_CHANGELOG_FIELDS,
+};
 use ovia_db::jira::pg_repository::PgJiraRepository;
 use ovia_db::sync::repositories::SyncWatermarkRepository;
 
-use super::client::JiraClient;
+use super::client::{JiraClient, JiraClientError};
+use super::fields::{discover_fields, JiraFieldMapping};
 use super::models::{JiraChangelogEntry, JiraIssue as ApiIssue, JiraUserRef};
 use super::query::build_issue_search_jql;
 use crate::connector::{Connector, SyncResult};
 
 const SOURCE_NAME: &str = "jira_issues";
 
-/// Convert an API issue to a DB issue row.
-fn api_issue_to_db(org_id: Uuid, issue: &ApiIssue) -> DbJiraIssue {
+/// Issues per batch: one bulk changelog request and one batch of DB writes.
+const ISSUE_BATCH_SIZE: usize = 100;
+
+const DEFAULT_CHANGELOG_CONCURRENCY: usize = 4;
+
+/// Convert an API issue to a DB issue row, reading custom fields through `mapping`.
+pub fn api_issue_to_db(org_id: Uuid, issue: &ApiIssue, mapping: &JiraFieldMapping) -> DbJiraIssue {
     let now = Utc::now();
     let f = &issue.fields;
     let project_key = issue.key.split('-').next().unwrap_or("").to_string();
-    let latest_sprint = f.latest_sprint();
+    let latest_sprint = f.latest_sprint(mapping);
 
     DbJiraIssue {
         id: Uuid::new_v4(),
         org_id,
         jira_key: issue.key.clone(),
+        jira_issue_id: issue.id.as_deref().and_then(|id| id.parse().ok()),
         project_key,
         issue_type: f.issuetype.as_ref().map(|t| t.name.clone()),
         summary: f.summary.clone(),
@@ -34,11 +45,12 @@ fn api_issue_to_db(org_id: Uuid, issue: &ApiIssue) -> DbJiraIssue {
         assignee_account_id: f.assignee.as_ref().map(|a| a.account_id.clone()),
         reporter_account_id: f.reporter.as_ref().map(|r| r.account_id.clone()),
         priority: f.priority.as_ref().map(|p| p.name.clone()),
-        story_points: f.story_points.map(|sp| sp as f32),
-        sprint_name: latest_sprint.map(|s| s.name.clone()),
-        sprint_id: latest_sprint.map(|s| s.id),
-        team_name: f.team_name(),
+        story_points: f.story_points(mapping).map(|sp| sp as f32),
+        sprint_name: latest_sprint.as_ref().map(|s| s.name.clone()),
+        sprint_id: latest_sprint.as_ref().map(|s| s.id),
+        team_name: f.team_name(mapping),
         labels: f.labels.clone(),
+        extra_fields: f.extra_fields(mapping),
         created_at_jira: f.created,
         updated_at_jira: f.updated,
         resolved_at: f.resolution_date,
@@ -48,29 +60,35 @@ fn api_issue_to_db(org_id: Uuid, issue: &ApiIssue) -> DbJiraIssue {
     }
 }
 
-/// Extract status and sprint transitions from a changelog.
-fn changelog_to_transitions(
+/// Extract transitions of the allow-listed fields of `mapping` from a changelog.
+/// User fields keep the account ids, other fields the displayed values.
+pub fn changelog_to_transitions(
     org_id: Uuid,
     issue_key: &str,
     entries: &[JiraChangelogEntry],
+    mapping: &JiraFieldMapping,
 ) -> Vec<JiraIssueTransition> {
     let now = Utc::now();
     let mut transitions = Vec::new();
 
     for entry in entries {
         for item in &entry.items {
-            let field = item.field.to_lowercase();
-            if field != "status" && field != "sprint" {
+            let Some(field) = mapping.kept_changelog_field(item) else {
                 continue;
-            }
+            };
+            let (from_value, to_value) = if USER_CHANGELOG_FIELDS.contains(&field.as_str()) {
+                (item.from.clone(), item.to.clone())
+            } else {
+                (item.from_string.clone(), item.to_string.clone())
+            };
 
             transitions.push(JiraIssueTransition {
                 id: Uuid::new_v4(),
                 org_id,
                 jira_key: issue_key.to_string(),
-                field: field.clone(),
-                from_value: item.from_string.clone(),
-                to_value: item.to_string.clone(),
+                field,
+                from_value,
+                to_value,
                 author_account_id: entry.author.as_ref().map(|a| a.account_id.clone()),
                 transitioned_at: entry.created,
                 created_at: now,
@@ -81,8 +99,196 @@ fn changelog_to_transitions(
     transitions
 }
 
+/// The transitions and sprint history an issue's changelog yields, with the
+/// issue's `updated` time as its changelog cursor.
+pub fn issue_history(
+    org_id: Uuid,
+    issue: &ApiIssue,
+    entries: &[JiraChangelogEntry],
+    mapping: &JiraFieldMapping,
+) -> JiraIssueHistory {
+    let current_sprints: Vec<i64> = issue.fields.sprints(mapping).iter().map(|s| s.id).collect();
+    JiraIssueHistory {
+        jira_key: issue.key.clone(),
+        changelog_cursor: issue.fields.updated,
+        transitions: changelog_to_transitions(org_id, &issue.key, entries, mapping),
+        sprints: sprint_history(org_id, &issue.key, &current_sprints, entries),
+    }
+}
+
+/// Whether an issue's changelog must be fetched: the issue changed since its
+/// changelog was stored, or either time is unknown.
+pub fn changelog_is_stale(issue: &ApiIssue, cursors: &HashMap<String, DateTime<Utc>>) -> bool {
+    match (issue.fields.updated, cursors.get(&issue.key)) {
+        (Some(updated), Some(cursor)) => updated > *cursor,
+        _ => true,
+    }
+}
+
+/// Fetch the changelogs of a batch of `(key, id)` issues: in one bulk request
+/// when every issue has an id and the site supports it, otherwise one issue at a
+/// time.
+async fn fetch_changelogs(
+    client: JiraClient,
+    batch: Vec<(String, Option<String>)>,
+) -> Vec<(String, Result<Vec<JiraChangelogEntry>, JiraClientError>)> {
+    let ids: Option<Vec<String>> = batch.iter().map(|(_, id)| id.clone()).collect();
+    if let Some(ids) = ids {
+        match client.fetch_bulk_changelogs(&ids).await {
+            // Issues without any change are left out of the response
+            Ok(Some(mut by_id)) => {
+                return batch
+                    .into_iter()
+                    .map(|(key, id)| {
+                        let entries = id.and_then(|id| by_id.remove(&id)).unwrap_or_default();
+                        (key, Ok(entries))
+                    })
+                    .collect();
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(error = %e, "bulk changelog fetch failed, fetching per issue")
+            }
+        }
+    }
+
+    let mut results = Vec::with_capacity(batch.len());
+    for (key, _) in batch {
+        let result = client.fetch_issue_changelog(&key).await;
+        results.push((key, result));
+    }
+    results
+}
+
+/// Changelog batches fetched at once: `JIRA_CHANGELOG_CONCURRENCY`, default 4.
+fn changelog_concurrency() -> usize {
+    std::env::var("JIRA_CHANGELOG_CONCURRENCY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_CHANGELOG_CONCURRENCY)
+}
+
+fn parse_sprint_ids(raw: Option<&str>) -> BTreeSet<i64> {
+    raw.unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect()
+}
+
+/// Derive which sprints an issue was in, and when, from the Sprint changes in its
+/// changelog. Before the first change the issue was in that change's "from" sprints;
+/// without any change it has only ever been in `current_sprint_ids`.
+pub fn sprint_history(
+    org_id: Uuid,
+    issue_key: &str,
+    current_sprint_ids: &[i64],
+    entries: &[JiraChangelogEntry],
+) -> Vec<JiraIssueSprint> {
+    let mut changes: Vec<_> = entries
+        .iter()
+        .flat_map(|e| {
+            e.items
+                .iter()
+                .filter(|i| i.field.eq_ignore_ascii_case("sprint"))
+                .map(move |i| (e.created, i))
+        })
+        .collect();
+    changes.sort_by_key(|(at, _)| *at);
+
+    let initial = match changes.first() {
+        Some((_, item)) => parse_sprint_ids(item.from.as_deref()),
+        None => current_sprint_ids.iter().copied().collect(),
+    };
+    let mut open: BTreeMap<i64, Option<chrono::DateTime<Utc>>> =
+        initial.into_iter().map(|id| (id, None)).collect();
+    let mut history = Vec::new();
+    let entry = |sprint_id, added_at, removed_at| JiraIssueSprint {
+        org_id,
+        jira_key: issue_key.to_string(),
+        sprint_id,
+        added_at,
+        removed_at,
+    };
+
+    for (at, item) in changes {
+        let to = parse_sprint_ids(item.to.as_deref());
+        let removed: Vec<i64> = open.keys().filter(|id| !to.contains(id)).copied().collect();
+        for id in removed {
+            let added_at = open.remove(&id).flatten();
+            history.push(entry(id, added_at, Some(at)));
+        }
+        for id in to {
+            open.entry(id).or_insert(Some(at));
+        }
+    }
+    history.extend(
+        open.into_iter()
+            .map(|(id, added_at)| entry(id, added_at, None)),
+    );
+    history.sort_by_key(|h| (h.sprint_id, h.added_at));
+    history
+}
+
+/// Links of an issue, turned to their outward direction. Links with an id that is
+/// not numeric are skipped.
+pub fn issue_links_to_db(org_id: Uuid, issue: &ApiIssue) -> Vec<JiraIssueLink> {
+    issue
+        .fields
+        .issuelinks
+        .iter()
+        .filter_map(|link| {
+            let (source_key, target_key) = match (&link.outward_issue, &link.inward_issue) {
+                (Some(outward), _) => (issue.key.clone(), outward.key.clone()),
+                (None, Some(inward)) => (inward.key.clone(), issue.key.clone()),
+                (None, None) => return None,
+            };
+            Some(JiraIssueLink {
+                org_id,
+                jira_link_id: link.id.parse().ok()?,
+                link_type: link.link_type.name.clone(),
+                outward: link.link_type.outward.clone(),
+                inward: link.link_type.inward.clone(),
+                source_key,
+                target_key,
+            })
+        })
+        .collect()
+}
+
+/// The issue's own parent, if any, followed by one row per subtask. Without a
+/// `parent` the legacy Epic Link field, when mapped, names the epic.
+pub fn issue_parents_to_db(
+    org_id: Uuid,
+    issue: &ApiIssue,
+    mapping: &JiraFieldMapping,
+) -> Vec<JiraIssueParent> {
+    let f = &issue.fields;
+    let own = match &f.parent {
+        Some(p) => Some(JiraIssueParent {
+            org_id,
+            jira_key: issue.key.clone(),
+            parent_key: p.key.clone(),
+            parent_issue_type: p.issue_type().map(str::to_string),
+        }),
+        None => f.epic_key(mapping).map(|epic| JiraIssueParent {
+            org_id,
+            jira_key: issue.key.clone(),
+            parent_key: epic,
+            parent_issue_type: Some("Epic".to_string()),
+        }),
+    };
+    let subtasks = f.subtasks.iter().map(|s| JiraIssueParent {
+        org_id,
+        jira_key: s.key.clone(),
+        parent_key: issue.key.clone(),
+        parent_issue_type: f.issuetype.as_ref().map(|t| t.name.clone()),
+    });
+    own.into_iter().chain(subtasks).collect()
+}
+
 /// Extract unique user refs from issue assignees/reporters for identity ingest.
-fn collect_user_refs(issues: &[ApiIssue]) -> HashMap<String, &JiraUserRef> {
+pub fn collect_user_refs(issues: &[ApiIssue]) -> HashMap<String, &JiraUserRef> {
     let mut users: HashMap<String, &JiraUserRef> = HashMap::new();
     for issue in issues {
         if let Some(ref a) = issue.fields.assignee {
@@ -96,7 +302,7 @@ fn collect_user_refs(issues: &[ApiIssue]) -> HashMap<String, &JiraUserRef> {
 }
 
 /// Convert a JiraUserRef (from issue fields) to an Identity.
-fn user_ref_to_identity(org_id: Uuid, user: &JiraUserRef) -> Identity {
+pub fn user_ref_to_identity(org_id: Uuid, user: &JiraUserRef) -> Identity {
     let now = Utc::now();
     let is_service = matches!(user.account_type.as_deref(), Some("app"));
     Identity {
@@ -108,6 +314,8 @@ fn user_ref_to_identity(org_id: Uuid, user: &JiraUserRef) -> Identity {
         email: user.email_address.clone(),
         display_name: user.display_name.clone(),
         is_service_account: is_service,
+        is_active: user.active.unwrap_or(true),
+        active_changed_at: None,
         first_seen_at: Some(now),
         last_seen_at: Some(now),
         raw_ref: serde_json::to_value(user).ok(),
@@ -144,6 +352,113 @@ where
     }
 }
 
+impl<I, S> JiraIssueSyncer<I, S> {
+    /// Upsert a batch of issues in one statement. If that fails, upsert them one by
+    /// one so a bad issue does not take the rest down. Returns the stored issues
+    /// and the number of failures.
+    async fn upsert_issue_batch<'a>(
+        &self,
+        batch: &[&'a ApiIssue],
+        mapping: &JiraFieldMapping,
+    ) -> (Vec<&'a ApiIssue>, usize) {
+        let rows: Vec<DbJiraIssue> = batch
+            .iter()
+            .map(|i| api_issue_to_db(self.org_id, i, mapping))
+            .collect();
+        match self.jira_repo.upsert_issues(&rows).await {
+            Ok(()) => return (batch.to_vec(), 0),
+            Err(e) => tracing::warn!(error = %e, "batch issue upsert failed, upserting one by one"),
+        }
+
+        let mut stored = Vec::with_capacity(batch.len());
+        let mut failed = 0;
+        for (issue, row) in batch.iter().zip(&rows) {
+            match self.jira_repo.upsert_issue(row).await {
+                Ok(()) => stored.push(*issue),
+                Err(e) => {
+                    tracing::warn!(
+                        key = %issue.key,
+                        error = %e,
+                        "failed to upsert jira issue"
+                    );
+                    failed += 1;
+                }
+            }
+        }
+        (stored, failed)
+    }
+
+    /// Store the histories of a fetched changelog batch in one transaction, or one
+    /// issue at a time if that fails. Returns the number of failures, fetch
+    /// failures included.
+    async fn store_histories(
+        &self,
+        results: Vec<(String, Result<Vec<JiraChangelogEntry>, JiraClientError>)>,
+        by_key: &HashMap<&str, &ApiIssue>,
+        mapping: &JiraFieldMapping,
+    ) -> usize {
+        let mut errors = 0;
+        let mut histories = Vec::with_capacity(results.len());
+        for (key, result) in results {
+            let Some(issue) = by_key.get(key.as_str()) else {
+                continue;
+            };
+            match result {
+                Ok(entries) => histories.push(issue_history(self.org_id, issue, &entries, mapping)),
+                Err(e) => {
+                    tracing::warn!(key = %key, error = %e, "failed to fetch changelog");
+                    errors += 1;
+                }
+            }
+        }
+
+        if let Err(e) = self
+            .jira_repo
+            .replace_issue_histories(self.org_id, &histories)
+            .await
+        {
+            tracing::warn!(error = %e, "batch history write failed, storing one by one");
+            for h in &histories {
+                if let Err(e) = self
+                    .jira_repo
+                    .replace_issue_histories(self.org_id, std::slice::from_ref(h))
+                    .await
+                {
+                    tracing::warn!(
+                        key = %h.jira_key,
+                        error = %e,
+                        "failed to store issue history"
+                    );
+                    errors += 1;
+                }
+            }
+        }
+        errors
+    }
+
+    /// Refresh discovered field mappings from the site, then load the org's mapping.
+    /// A failed discovery keeps the stored mapping.
+    async fn load_field_mapping(&self) -> Result<JiraFieldMapping, ovia_common::error::OviaError> {
+        match self.client.fetch_fields().await {
+            Ok(fields) => {
+                for found in discover_fields(&fields) {
+                    self.jira_repo
+                        .upsert_discovered_field(
+                            self.org_id,
+                            found.role,
+                            &found.field_id,
+                            &found.field_name,
+                        )
+                        .await?;
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "jira field discovery failed"),
+        }
+        let rows = self.jira_repo.list_field_mappings(self.org_id).await?;
+        Ok(JiraFieldMapping::from_rows(&rows))
+    }
+}
+
 #[async_trait]
 impl<I, S> Connector for JiraIssueSyncer<I, S>
 where
@@ -180,6 +495,8 @@ where
                     upserted: 0,
                     skipped: 0,
                     errors: 0,
+                    deleted: 0,
+                    moved: 0,
                 });
             }
         };
@@ -194,12 +511,24 @@ where
                 Utc::now() - Duration::days(i64::from(self.client.config().sync_window_days))
             });
 
+        let mapping = match self.load_field_mapping().await {
+            Ok(m) => m,
+            Err(e) => {
+                let msg = e.to_string();
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
         let project_keys = &self.client.config().project_keys;
         let jql = build_issue_search_jql(project_keys, updated_after);
         tracing::info!(jql = %jql, "searching jira issues");
 
         // Fetch issues
-        let issues = match self.client.search_issues(&jql).await {
+        let issues = match self.client.search_issues(&jql, &mapping).await {
             Ok(issues) => issues,
             Err(e) => {
                 let msg = e.to_string();
@@ -214,62 +543,88 @@ where
 
         tracing::info!(count = issues.len(), "fetched jira issues");
 
+        // Offset pages can overlap when issues change mid-search; keep one copy
+        let mut seen = HashSet::new();
+        let mut unique: Vec<&ApiIssue> = issues
+            .iter()
+            .rev()
+            .filter(|i| seen.insert(i.key.as_str()))
+            .collect();
+        unique.reverse();
+
         let mut upserted: usize = 0;
         let mut errors: usize = 0;
+        let mut stored: Vec<&ApiIssue> = Vec::with_capacity(unique.len());
 
-        for issue in &issues {
-            // Upsert the issue itself
-            let db_issue = api_issue_to_db(self.org_id, issue);
-            match self.jira_repo.upsert_issue(&db_issue).await {
-                Ok(_) => upserted += 1,
-                Err(e) => {
-                    tracing::warn!(
-                        key = %issue.key,
-                        error = %e,
-                        "failed to upsert jira issue"
-                    );
-                    errors += 1;
-                    continue;
-                }
+        for batch in unique.chunks(ISSUE_BATCH_SIZE) {
+            let (ok, failed) = self.upsert_issue_batch(batch, &mapping).await;
+            upserted += ok.len();
+            errors += failed;
+            stored.extend(ok);
+        }
+
+        for issue in &stored {
+            if let Err(e) = self
+                .jira_repo
+                .replace_issue_relations(
+                    self.org_id,
+                    &issue.key,
+                    &issue_links_to_db(self.org_id, issue),
+                    &issue_parents_to_db(self.org_id, issue, &mapping),
+                )
+                .await
+            {
+                tracing::warn!(
+                    key = %issue.key,
+                    error = %e,
+                    "failed to store issue links"
+                );
+                errors += 1;
             }
+        }
 
-            // Fetch and store changelog (replace strategy: delete old, insert new)
-            match self.client.fetch_issue_changelog(&issue.key).await {
-                Ok(entries) => {
-                    let transitions = changelog_to_transitions(self.org_id, &issue.key, &entries);
-
-                    if !transitions.is_empty() {
-                        if let Err(e) = self
-                            .jira_repo
-                            .delete_transitions_for_issue(self.org_id, &issue.key)
-                            .await
-                        {
-                            tracing::warn!(
-                                key = %issue.key,
-                                error = %e,
-                                "failed to delete old transitions"
-                            );
-                        }
-
-                        for t in &transitions {
-                            if let Err(e) = self.jira_repo.insert_transition(t).await {
-                                tracing::warn!(
-                                    key = %issue.key,
-                                    field = %t.field,
-                                    error = %e,
-                                    "failed to insert transition"
-                                );
-                                errors += 1;
-                            }
-                        }
-                    }
-                }
+        // Only issues changed since their changelog was stored need it again
+        let cursors = match self.jira_repo.list_changelog_cursors(self.org_id).await {
+            Ok(cursors) => cursors,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load changelog cursors, refetching all");
+                HashMap::new()
+            }
+        };
+        let stale: Vec<&ApiIssue> = stored
+            .into_iter()
+            .filter(|i| changelog_is_stale(i, &cursors))
+            .collect();
+        tracing::info!(
+            stale = stale.len(),
+            unchanged = upserted - stale.len(),
+            "fetching jira changelogs"
+        );
+
+        // Fetch changelogs a batch at a time, several batches in flight, and store
+        // each batch as it completes
+        let by_key: HashMap<&str, &ApiIssue> = stale.iter().map(|i| (i.key.as_str(), *i)).collect();
+        let concurrency = changelog_concurrency();
+        let mut batches = stale.chunks(ISSUE_BATCH_SIZE);
+        let mut tasks = JoinSet::new();
+        loop {
+            while tasks.len() < concurrency {
+                let Some(batch) = batches.next() else {
+                    break;
+                };
+                let batch = batch
+                    .iter()
+                    .map(|i| (i.key.clone(), i.id.clone()))
+                    .collect();
+                tasks.spawn(fetch_changelogs(self.client.clone(), batch));
+            }
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok(results) => errors += self.store_histories(results, &by_key, &mapping).await,
                 Err(e) => {
-                    tracing::warn!(
-                        key = %issue.key,
-                        error = %e,
-                        "failed to fetch changelog"
-                    );
+                    tracing::warn!(error = %e, "changelog fetch task failed");
                     errors += 1;
                 }
             }
@@ -311,6 +666,8 @@ where
             upserted,
             skipped: 0,
             errors,
+            deleted: 0,
+            moved: 0,
         };
 
         tracing::info!(?result, "jira issue sync completed");
@@ -322,7 +679,7 @@ where
 mod tests {
     use super::*;
     use crate::jira::client::{JiraClient, JiraClientConfig};
-    use wiremock::matchers::{method, path, query_param};
+    use wiremock::matchers::{body_partial_json, method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     fn test_client_config(base_url: &str) -> JiraClientConfig {
@@ -405,14 +762,16 @@ mod tests {
 
     #[test]
     fn api_issue_to_db_extracts_all_fields() {
-        let json = make_issue_json("BEE-42", "In Progress", Some(5.0));
+        let mut json = make_issue_json("BEE-42", "In Progress", Some(5.0));
+        json["id"] = serde_json::json!("10042");
         let api_issue: ApiIssue = serde_json::from_value(json).unwrap();
 
         let org_id = Uuid::new_v4();
-        let db_issue = api_issue_to_db(org_id, &api_issue);
+        let db_issue = api_issue_to_db(org_id, &api_issue, &JiraFieldMapping::default());
 
         assert_eq!(db_issue.org_id, org_id);
         assert_eq!(db_issue.jira_key, "BEE-42");
+        assert_eq!(db_issue.jira_issue_id, Some(10042));
         assert_eq!(db_issue.project_key, "BEE");
         assert_eq!(db_issue.status, "In Progress");
         assert_eq!(db_issue.issue_type.as_deref(), Some("Story"));
@@ -437,7 +796,8 @@ mod tests {
                 "created": "2026-02-12T10:00:00.000Z",
                 "items": [
                     { "field": "status", "fromString": "To Do", "toString": "In Progress" },
-                    { "field": "assignee", "fromString": null, "toString": "User 1" }
+                    { "field": "assignee", "fromString": null, "toString": "User 1",
+                      "from": null, "to": "user-1" }
                 ]
             }),
             serde_json::json!({
@@ -453,37 +813,100 @@ mod tests {
             serde_json::from_value(serde_json::Value::Array(entries_json)).unwrap();
 
         let org_id = Uuid::new_v4();
-        let transitions = changelog_to_transitions(org_id, "BEE-1", &entries);
+        let transitions =
+            changelog_to_transitions(org_id, "BEE-1", &entries, &JiraFieldMapping::default());
 
-        // Should get 2: status + sprint. Assignee is filtered out.
-        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions.len(), 3);
         assert_eq!(transitions[0].field, "status");
         assert_eq!(transitions[0].from_value.as_deref(), Some("To Do"));
         assert_eq!(transitions[0].to_value.as_deref(), Some("In Progress"));
         assert_eq!(transitions[0].author_account_id.as_deref(), Some("user-1"));
-        assert_eq!(transitions[1].field, "sprint");
-        assert_eq!(transitions[1].from_value.as_deref(), Some("Sprint 0"));
-        assert_eq!(transitions[1].to_value.as_deref(), Some("Sprint 1"));
-        assert_eq!(transitions[1].author_account_id.as_deref(), Some("user-2"));
+        assert_eq!(transitions[1].field, "assignee");
+        assert!(transitions[1].from_value.is_none());
+        assert_eq!(transitions[1].to_value.as_deref(), Some("user-1"));
+        assert_eq!(transitions[2].field, "sprint");
+        assert_eq!(transitions[2].from_value.as_deref(), Some("Sprint 0"));
+        assert_eq!(transitions[2].to_value.as_deref(), Some("Sprint 1"));
+        assert_eq!(transitions[2].author_account_id.as_deref(), Some("user-2"));
     }
 
     #[test]
-    fn changelog_ignores_non_status_sprint_fields() {
+    fn sprint_history_tracks_additions_and_removals() {
+        let entries: Vec<JiraChangelogEntry> = serde_json::from_value(serde_json::json!([
+            {
+                "created": "2026-02-14T14:00:00.000Z",
+                "items": [{ "field": "Sprint", "fromString": "Sprint 1", "toString": "Sprint 1, Sprint 2",
+                            "from": "1", "to": "1, 2" }]
+            },
+            {
+                "created": "2026-02-10T09:00:00.000Z",
+                "items": [{ "field": "Sprint", "fromString": "", "toString": "Sprint 1",
+                            "from": "", "to": "1" }]
+            },
+            {
+                "created": "2026-02-20T08:00:00.000Z",
+                "items": [{ "field": "Sprint", "fromString": "Sprint 1, Sprint 2",
+                            "toString": "Sprint 1, Sprint 3", "from": "1, 2", "to": "1,3" }]
+            }
+        ]))
+        .unwrap();
+
+        let org_id = Uuid::new_v4();
+        let at = |s: &str| Some(s.parse::<chrono::DateTime<Utc>>().unwrap());
+        let spans: Vec<_> = sprint_history(org_id, "BEE-1", &[1, 3], &entries)
+            .into_iter()
+            .map(|h| (h.sprint_id, h.added_at, h.removed_at))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (1, at("2026-02-10T09:00:00Z"), None),
+                (2, at("2026-02-14T14:00:00Z"), at("2026-02-20T08:00:00Z")),
+                (3, at("2026-02-20T08:00:00Z"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn sprint_history_without_changes_uses_current_sprints() {
+        let history = sprint_history(Uuid::new_v4(), "BEE-2", &[5], &[]);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].sprint_id, 5);
+        assert!(history[0].added_at.is_none());
+        assert!(history[0].removed_at.is_none());
+    }
+
+    #[test]
+    fn changelog_ignores_fields_outside_the_allow_list() {
         let entries_json = vec![serde_json::json!({
             "author": { "accountId": "user-1" },
             "created": "2026-02-12T10:00:00.000Z",
             "items": [
-                { "field": "assignee", "fromString": null, "toString": "User 1" },
-                { "field": "priority", "fromString": "Low", "toString": "High" },
-                { "field": "labels", "fromString": "", "toString": "backend" }
+                { "field": "description", "fieldId": "description", "fromString": "a", "toString": "b" },
+                { "field": "priority", "fieldId": "priority", "fromString": "Low", "toString": "High" },
+                { "field": "Story Points", "fieldId": "customfield_10016", "fromString": "3", "toString": "5" }
             ]
         })];
 
         let entries: Vec<JiraChangelogEntry> =
             serde_json::from_value(serde_json::Value::Array(entries_json)).unwrap();
 
-        let transitions = changelog_to_transitions(Uuid::new_v4(), "BEE-1", &entries);
-        assert!(transitions.is_empty());
+        let fields: Vec<_> = changelog_to_transitions(
+            Uuid::new_v4(),
+            "BEE-1",
+            &entries,
+            &JiraFieldMapping::default(),
+        )
+        .into_iter()
+        .map(|t| t.field)
+        .collect();
+        assert_eq!(fields, vec!["priority", "story_points"]);
+
+        let mapping = JiraFieldMapping {
+            changelog: vec!["status".to_string(), "sprint".to_string()],
+            ..JiraFieldMapping::default()
+        };
+        assert!(changelog_to_transitions(Uuid::new_v4(), "BEE-1", &entries, &mapping).is_empty());
     }
 
     #[test]
@@ -500,7 +923,7 @@ mod tests {
         });
         let fields: super::super::models::JiraIssueFields = serde_json::from_value(json).unwrap();
 
-        let sprint = fields.latest_sprint().unwrap();
+        let sprint = fields.latest_sprint(&JiraFieldMapping::default()).unwrap();
         assert_eq!(sprint.name, "Sprint 2");
         assert_eq!(sprint.id, 2);
     }
@@ -518,7 +941,7 @@ mod tests {
         });
         let fields: super::super::models::JiraIssueFields = serde_json::from_value(json).unwrap();
 
-        let sprint = fields.latest_sprint().unwrap();
+        let sprint = fields.latest_sprint(&JiraFieldMapping::default()).unwrap();
         assert_eq!(sprint.name, "Sprint 3");
     }
 
@@ -531,7 +954,10 @@ mod tests {
             "customfield_10001": "Team Alpha"
         });
         let fields: super::super::models::JiraIssueFields = serde_json::from_value(json).unwrap();
-        assert_eq!(fields.team_name().as_deref(), Some("Team Alpha"));
+        assert_eq!(
+            fields.team_name(&JiraFieldMapping::default()).as_deref(),
+            Some("Team Alpha")
+        );
     }
 
     #[test]
@@ -543,7 +969,10 @@ mod tests {
             "customfield_10001": { "name": "Team Beta" }
         });
         let fields: super::super::models::JiraIssueFields = serde_json::from_value(json).unwrap();
-        assert_eq!(fields.team_name().as_deref(), Some("Team Beta"));
+        assert_eq!(
+            fields.team_name(&JiraFieldMapping::default()).as_deref(),
+            Some("Team Beta")
+        );
     }
 
     #[test]
@@ -554,7 +983,7 @@ mod tests {
             "labels": []
         });
         let fields: super::super::models::JiraIssueFields = serde_json::from_value(json).unwrap();
-        assert!(fields.team_name().is_none());
+        assert!(fields.team_name(&JiraFieldMapping::default()).is_none());
     }
 
     // ── Deserialization tests ───────────────────────────────────
@@ -583,6 +1012,111 @@ mod tests {
         assert_eq!(response.values[0].items[0].field, "status");
     }
 
+    #[test]
+    fn issue_links_and_parents_are_stored_outward() {
+        let mut json = make_issue_json("BEE-42", "In Progress", None);
+        json["fields"]["issuelinks"] = serde_json::json!([
+            {
+                "id": "10001",
+                "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                "outwardIssue": { "key": "BEE-43", "fields": { "status": { "name": "To Do" } } }
+            },
+            {
+                "id": "10002",
+                "type": { "name": "Blocks", "inward": "is blocked by", "outward": "blocks" },
+                "inwardIssue": { "key": "PAY-7" }
+            },
+            {
+                "id": "10003",
+                "type": { "name": "Duplicate", "inward": "is duplicated by", "outward": "duplicates" },
+                "inwardIssue": { "key": "BEE-12" }
+            }
+        ]);
+        json["fields"]["parent"] = serde_json::json!({
+            "key": "BEE-1",
+            "fields": { "issuetype": { "name": "Epic" } }
+        });
+        json["fields"]["subtasks"] = serde_json::json!([{ "key": "BEE-44" }]);
+        let issue: ApiIssue = serde_json::from_value(json).unwrap();
+        let org_id = Uuid::new_v4();
+
+        let links = issue_links_to_db(org_id, &issue);
+        let pairs: Vec<_> = links
+            .iter()
+            .map(|l| {
+                (
+                    l.link_type.as_str(),
+                    l.source_key.as_str(),
+                    l.target_key.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Blocks", "BEE-42", "BEE-43"),
+                ("Blocks", "PAY-7", "BEE-42"),
+                ("Duplicate", "BEE-12", "BEE-42"),
+            ]
+        );
+        assert_eq!(links[1].jira_link_id, 10002);
+        assert_eq!(links[1].inward.as_deref(), Some("is blocked by"));
+
+        let parents = issue_parents_to_db(org_id, &issue, &JiraFieldMapping::default());
+        assert_eq!(parents.len(), 2);
+        assert_eq!(parents[0].jira_key, "BEE-42");
+        assert_eq!(parents[0].parent_key, "BEE-1");
+        assert_eq!(parents[0].parent_issue_type.as_deref(), Some("Epic"));
+        assert_eq!(parents[1].jira_key, "BEE-44");
+        assert_eq!(parents[1].parent_key, "BEE-42");
+        assert_eq!(parents[1].parent_issue_type.as_deref(), Some("Story"));
+    }
+
+    #[test]
+    fn custom_fields_follow_the_org_mapping() {
+        // A second site: story points, sprint and epic link have other ids
+        let json = serde_json::json!({
+            "key": "OPS-7",
+            "fields": {
+                "summary": "Rotate certificates",
+                "status": { "name": "To Do" },
+                "issuetype": { "name": "Task" },
+                "customfield_10016": 99.0,
+                "customfield_10106": 2.0,
+                "customfield_10104": [{ "id": 12, "name": "Ops 12", "state": "active" }],
+                "customfield_10102": "OPS-1",
+                "customfield_10200": { "value": "High" },
+                "customfield_10201": null
+            }
+        });
+        let issue: ApiIssue = serde_json::from_value(json).unwrap();
+        let mapping = JiraFieldMapping {
+            story_points: Some("customfield_10106".to_string()),
+            sprint: Some("customfield_10104".to_string()),
+            team: None,
+            epic_link: Some("customfield_10102".to_string()),
+            extra: vec![
+                "customfield_10200".to_string(),
+                "customfield_10201".to_string(),
+            ],
+            ..JiraFieldMapping::default()
+        };
+
+        let db_issue = api_issue_to_db(Uuid::new_v4(), &issue, &mapping);
+        assert_eq!(db_issue.story_points, Some(2.0));
+        assert_eq!(db_issue.sprint_id, Some(12));
+        assert!(db_issue.team_name.is_none());
+        assert_eq!(
+            db_issue.extra_fields,
+            Some(serde_json::json!({ "customfield_10200": { "value": "High" } }))
+        );
+
+        let parents = issue_parents_to_db(Uuid::new_v4(), &issue, &mapping);
+        assert_eq!(parents.len(), 1);
+        assert_eq!(parents[0].parent_key, "OPS-1");
+        assert_eq!(parents[0].parent_issue_type.as_deref(), Some("Epic"));
+    }
+
     #[test]
     fn minimal_issue_deserializes() {
         let json = serde_json::json!({
@@ -596,9 +1130,12 @@ mod tests {
         let issue: ApiIssue = serde_json::from_value(json).unwrap();
         assert_eq!(issue.key, "BEE-1");
         assert!(issue.fields.assignee.is_none());
-        assert!(issue.fields.story_points.is_none());
-        assert!(issue.fields.sprints.is_none());
-        assert!(issue.fields.team.is_none());
+        let mapping = JiraFieldMapping::default();
+        assert!(issue.fields.story_points(&mapping).is_none());
+        assert!(issue.fields.sprints(&mapping).is_empty());
+        assert!(issue.fields.team_name(&mapping).is_none());
+        assert!(issue.fields.issuelinks.is_empty());
+        assert!(issue.fields.parent.is_none());
     }
 
     // ── Idempotency test (model level) ──────────────────────────
@@ -609,8 +1146,8 @@ mod tests {
         let api_issue: ApiIssue = serde_json::from_value(json).unwrap();
         let org_id = Uuid::new_v4();
 
-        let a = api_issue_to_db(org_id, &api_issue);
-        let b = api_issue_to_db(org_id, &api_issue);
+        let a = api_issue_to_db(org_id, &api_issue, &JiraFieldMapping::default());
+        let b = api_issue_to_db(org_id, &api_issue, &JiraFieldMapping::default());
 
         // IDs differ (Uuid::new_v4), but all other fields match
         assert_ne!(a.id, b.id);
@@ -637,7 +1174,10 @@ mod tests {
             .await;
 
         let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
-        let issues = client.search_issues("project in (BEE)").await.unwrap();
+        let issues = client
+            .search_issues("project in (BEE)", &JiraFieldMapping::default())
+            .await
+            .unwrap();
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].key, "BEE-1");
     }
@@ -679,7 +1219,10 @@ mod tests {
             .await;
 
         let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
-        let issues = client.search_issues("project in (BEE)").await.unwrap();
+        let issues = client
+            .search_issues("project in (BEE)", &JiraFieldMapping::default())
+            .await
+            .unwrap();
         assert_eq!(issues.len(), 60);
     }
 
@@ -703,6 +1246,115 @@ mod tests {
         assert_eq!(entries.len(), 2);
     }
 
+    #[tokio::test]
+    async fn bulk_changelogs_are_merged_across_pages_by_issue() {
+        let server = MockServer::start().await;
+        let history = |created: i64, to: &str| {
+            serde_json::json!({
+                "created": created,
+                "items": [{ "field": "status", "fromString": null, "toString": to }]
+            })
+        };
+
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/changelog/bulkfetch"))
+            .and(body_partial_json(
+                serde_json::json!({ "nextPageToken": "p2" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issueChangeLogs": [
+                    { "issueId": "10001", "changeHistories": [history(1771322400000, "Done")] }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/changelog/bulkfetch"))
+            .and(body_partial_json(
+                serde_json::json!({ "issueIdsOrKeys": ["10001", "10002", "10003"] }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issueChangeLogs": [
+                    { "issueId": "10001", "changeHistories": [history(1771236000000, "In Progress")] },
+                    { "issueId": "10002", "changeHistories": [history(1771236000000, "In Progress")] }
+                ],
+                "nextPageToken": "p2"
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+
+        let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
+        let batch = ["BEE-1", "BEE-2", "BEE-3"]
+            .iter()
+            .zip(["10001", "10002", "10003"])
+            .map(|(key, id)| (key.to_string(), Some(id.to_string())))
+            .collect();
+        let results = fetch_changelogs(client, batch).await;
+
+        let counts: Vec<(&str, usize)> = results
+            .iter()
+            .map(|(key, r)| (key.as_str(), r.as_ref().unwrap().len()))
+            .collect();
+        assert_eq!(counts, vec![("BEE-1", 2), ("BEE-2", 1), ("BEE-3", 0)]);
+        let bee1 = results[0].1.as_ref().unwrap();
+        assert_eq!(bee1[0].created.to_rfc3339(), "2026-02-16T10:00:00+00:00");
+        assert_eq!(bee1[1].items[0].to_string.as_deref(), Some("Done"));
+    }
+
+    #[tokio::test]
+    async fn changelogs_fall_back_per_issue_without_bulk_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/changelog/bulkfetch"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for key in ["BEE-1", "BEE-2"] {
+            Mock::given(method("GET"))
+                .and(path(format!("/rest/api/3/issue/{key}/changelog")))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(make_changelog_response(vec![
+                        make_status_transition("To Do", "Done"),
+                    ])),
+                )
+                .mount(&server)
+                .await;
+        }
+
+        let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
+        for key in ["BEE-1", "BEE-2"] {
+            let batch = vec![(key.to_string(), Some("10001".to_string()))];
+            let results = fetch_changelogs(client.clone(), batch).await;
+            assert_eq!(results[0].1.as_ref().unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn changelog_is_refetched_only_for_changed_issues() {
+        let issue: ApiIssue =
+            serde_json::from_value(make_issue_json("BEE-1", "Done", None)).unwrap();
+        let updated = issue.fields.updated.unwrap();
+
+        let mut cursors = HashMap::new();
+        assert!(changelog_is_stale(&issue, &cursors));
+        cursors.insert("BEE-1".to_string(), updated);
+        assert!(!changelog_is_stale(&issue, &cursors));
+        cursors.insert("BEE-1".to_string(), updated - Duration::minutes(5));
+        assert!(changelog_is_stale(&issue, &cursors));
+
+        let org_id = Uuid::new_v4();
+        let entries: Vec<JiraChangelogEntry> =
+            serde_json::from_value(serde_json::json!([make_status_transition("To Do", "Done")]))
+                .unwrap();
+        let history = issue_history(org_id, &issue, &entries, &JiraFieldMapping::default());
+        assert_eq!(history.changelog_cursor, Some(updated));
+        assert_eq!(history.transitions.len(), 1);
+        assert_eq!(history.sprints.len(), 1);
+        assert_eq!(history.sprints[0].sprint_id, 100);
+    }
+
     #[tokio::test]
     async fn search_issues_empty_result() {
         let server = MockServer::start().await;
@@ -719,7 +1371,10 @@ mod tests {
             .await;
 
         let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
-        let issues = client.search_issues("project in (BEE)").await.unwrap();
+        let issues = client
+            .search_issues("project in (BEE)", &JiraFieldMapping::default())
+            .await
+            .unwrap();
         assert!(issues.is_empty());
     }
 
@@ -805,6 +1460,7 @@ mod tests {
             display_name: Some("Test User".to_string()),
             email_address: Some("test@example.com".to_string()),
             account_type: Some("atlassian".to_string()),
+            active: None,
         };
 
         let org_id = Uuid::new_v4();
@@ -826,6 +1482,7 @@ mod tests {
             display_name: Some("Bot".to_string()),
             email_address: None,
             account_type: Some("app".to_string()),
+            active: None,
         };
 
         let identity = user_ref_to_identity(Uuid::new_v4(), &user_ref);
@@ -839,6 +1496,7 @@ mod tests {
             display_name: None,
             email_address: None,
             account_type: None,
+            active: None,
         };
 
         let identity = user_ref_to_identity(Uuid::new_v4(), &user_ref);
@@ -846,5 +1504,20 @@ mod tests {
         assert!(identity.display_name.is_none());
         assert!(identity.email.is_none());
         assert!(!identity.is_service_account);
+        assert!(identity.is_active);
+    }
+
+    #[test]
+    fn user_ref_to_identity_maps_inactive_account() {
+        let user_ref = JiraUserRef {
+            account_id: "former".to_string(),
+            display_name: Some("Former User".to_string()),
+            email_address: None,
+            account_type: Some("atlassian".to_string()),
+            active: Some(false),
+        };
+
+        let identity = user_ref_to_identity(Uuid::new_v4(), &user_ref);
+        assert!(!identity.is_active);
     }
 }
@@ -1,5 +1,87 @@
 pub mod client;
+pub mod fields;
 pub mod issue_sync;
 pub mod models;
 pub mod query;
+pub mod reconcile;
+pub mod sprint_sync;
 pub mod sync;
+pub mod worklog_sync;
+
+use ovia_db::identity::pg_repository::PgIdentityRepository;
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::pg_repository::PgSyncRepository;
+
+use crate::registry::{ConnectorContext, ConnectorSpec, SourceRegistration};
+use client::{JiraClient, JiraClientConfig};
+use issue_sync::JiraIssueSyncer;
+use reconcile::JiraReconciler;
+use sprint_sync::JiraSprintSyncer;
+use sync::JiraSyncer;
+use worklog_sync::JiraWorklogSyncer;
+
+inventory::submit! {
+    SourceRegistration {
+        source: "jira",
+        factory: connectors,
+    }
+}
+
+/// Jira users, then issues and sprints, then worklogs and a daily reconciliation of
+/// deleted and moved issues. Fails fast if creds are set but no project keys are.
+pub fn connectors(ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+    let Some(config) =
+        JiraClientConfig::for_org(&ctx.org, ctx.credential("jira"), ctx.allow_env_fallback)?
+    else {
+        tracing::info!(org_id = %ctx.org.id, "no jira credentials found, skipping jira sync");
+        return Ok(Vec::new());
+    };
+    tracing::info!(
+        org_id = %ctx.org.id,
+        projects = ?config.project_keys,
+        window_days = config.sync_window_days,
+        "jira connector configured"
+    );
+
+    let client = JiraClient::new(config).map_err(|e| e.to_string())?;
+    let pool = &ctx.pool;
+
+    Ok(vec![
+        ConnectorSpec::new(JiraSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgIdentityRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        )),
+        ConnectorSpec::new(JiraIssueSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgJiraRepository::new(pool.clone()),
+            PgIdentityRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("jira"),
+        ConnectorSpec::new(JiraSprintSyncer::new(
+            ctx.org.id,
+            client.clone(),
+            PgJiraRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("jira"),
+        ConnectorSpec::new(JiraReconciler::new(
+            ctx.org.id,
+            client.clone(),
+            PgJiraRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("jira_issues"),
+        ConnectorSpec::new(JiraWorklogSyncer::new(
+            ctx.org.id,
+            client,
+            PgJiraRepository::new(pool.clone()),
+            PgIdentityRepository::new(pool.clone()),
+            PgSyncRepository::new(pool.clone()),
+        ))
+        .depends_on("jira_issues"),
+    ])
+}
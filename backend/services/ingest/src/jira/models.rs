@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::fields::JiraFieldMapping;
 
 /// A user record from the Jira Cloud REST API (`/rest/api/3/users/search`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,9 +38,30 @@ pub struct JiraSearchResponse {
     pub next_page_token: Option<String>,
 }
 
+/// A search page that only carries issue keys, used to enumerate a project.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueKeyPage {
+    #[serde(default)]
+    pub issues: Vec<JiraIssueKey>,
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+/// Id and current key of an issue. Jira resolves old keys of moved issues, so
+/// the key may differ from the one requested.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct JiraIssueKey {
+    pub id: String,
+    pub key: String,
+}
+
 /// A single issue from the search response.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraIssue {
+    /// Numeric issue id, as a string
+    #[serde(default)]
+    pub id: Option<String>,
     pub key: String,
     pub fields: JiraIssueFields,
 }
@@ -65,15 +88,16 @@ pub struct JiraIssueFields {
     pub updated: Option<DateTime<Utc>>,
     #[serde(default)]
     pub resolution_date: Option<DateTime<Utc>>,
-    /// Story points — customfield_10016
-    #[serde(default, rename = "customfield_10016")]
-    pub story_points: Option<f64>,
-    /// Sprint — customfield_10020 (array of sprint objects)
-    #[serde(default, rename = "customfield_10020")]
-    pub sprints: Option<Vec<JiraSprint>>,
-    /// Team — customfield_10001 (string or object with name)
-    #[serde(default, rename = "customfield_10001")]
-    pub team: Option<serde_json::Value>,
+    #[serde(default)]
+    pub issuelinks: Vec<JiraIssueLink>,
+    /// Epic of a story, or the issue a subtask belongs to
+    #[serde(default)]
+    pub parent: Option<JiraLinkedIssue>,
+    #[serde(default)]
+    pub subtasks: Vec<JiraLinkedIssue>,
+    /// Custom fields (`customfield_*`), read through the org's `JiraFieldMapping`
+    #[serde(flatten)]
+    pub custom: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +120,8 @@ pub struct JiraUserRef {
     pub email_address: Option<String>,
     #[serde(default)]
     pub account_type: Option<String>,
+    #[serde(default)]
+    pub active: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -110,20 +136,86 @@ pub struct JiraSprint {
     pub state: Option<String>,
 }
 
+/// An entry of `issuelinks`. Only the far side of the link is set: `outward_issue`
+/// when this issue is the link's source ("blocks"), `inward_issue` when it is the
+/// target ("is blocked by").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueLink {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub link_type: JiraIssueLinkType,
+    #[serde(default)]
+    pub outward_issue: Option<JiraLinkedIssue>,
+    #[serde(default)]
+    pub inward_issue: Option<JiraLinkedIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIssueLinkType {
+    pub name: String,
+    #[serde(default)]
+    pub inward: Option<String>,
+    #[serde(default)]
+    pub outward: Option<String>,
+}
+
+/// A linked, parent or subtask issue, with the few fields Jira inlines for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraLinkedIssue {
+    pub key: String,
+    #[serde(default)]
+    pub fields: Option<JiraLinkedIssueFields>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraLinkedIssueFields {
+    #[serde(default)]
+    pub issuetype: Option<JiraIssueType>,
+    #[serde(default)]
+    pub status: Option<JiraStatus>,
+}
+
+impl JiraLinkedIssue {
+    pub fn issue_type(&self) -> Option<&str> {
+        self.fields
+            .as_ref()?
+            .issuetype
+            .as_ref()
+            .map(|t| t.name.as_str())
+    }
+}
+
 impl JiraIssueFields {
+    fn custom_field(&self, field_id: Option<&str>) -> Option<&serde_json::Value> {
+        self.custom.get(field_id?).filter(|v| !v.is_null())
+    }
+
+    pub fn story_points(&self, mapping: &JiraFieldMapping) -> Option<f64> {
+        self.custom_field(mapping.story_points.as_deref())?.as_f64()
+    }
+
+    /// Sprints of the issue (an array of sprint objects).
+    pub fn sprints(&self, mapping: &JiraFieldMapping) -> Vec<JiraSprint> {
+        self.custom_field(mapping.sprint.as_deref())
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
     /// Extract the latest (active or most recent) sprint.
-    pub fn latest_sprint(&self) -> Option<&JiraSprint> {
-        let sprints = self.sprints.as_ref()?;
+    pub fn latest_sprint(&self, mapping: &JiraFieldMapping) -> Option<JiraSprint> {
+        let sprints = self.sprints(mapping);
         // Prefer active sprint, fall back to last in array
         sprints
             .iter()
             .find(|s| s.state.as_deref() == Some("active"))
             .or_else(|| sprints.last())
+            .cloned()
     }
 
-    /// Extract team name from customfield_10001 (can be string or object with name).
-    pub fn team_name(&self) -> Option<String> {
-        let val = self.team.as_ref()?;
+    /// Extract the team name (the field can be a string or an object with name).
+    pub fn team_name(&self, mapping: &JiraFieldMapping) -> Option<String> {
+        let val = self.custom_field(mapping.team.as_deref())?;
         if let Some(s) = val.as_str() {
             return Some(s.to_string());
         }
@@ -131,6 +223,142 @@ impl JiraIssueFields {
             .and_then(|n| n.as_str())
             .map(|s| s.to_string())
     }
+
+    /// Key of the epic from the legacy Epic Link field (a key, or an object with one).
+    pub fn epic_key(&self, mapping: &JiraFieldMapping) -> Option<String> {
+        let val = self.custom_field(mapping.epic_link.as_deref())?;
+        val.as_str()
+            .or_else(|| val.get("key").and_then(|k| k.as_str()))
+            .map(|s| s.to_string())
+    }
+
+    /// Values of the mapped extra fields that are set, keyed by field id.
+    pub fn extra_fields(&self, mapping: &JiraFieldMapping) -> Option<serde_json::Value> {
+        let extra: serde_json::Map<_, _> = mapping
+            .extra
+            .iter()
+            .filter_map(|id| Some((id.clone(), self.custom_field(Some(id))?.clone())))
+            .collect();
+        (!extra.is_empty()).then_some(serde_json::Value::Object(extra))
+    }
+}
+
+// ── Field API response types ───────────────────────────────────
+
+/// A field from `/rest/api/3/field`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraField {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub custom: bool,
+    #[serde(default)]
+    pub schema: Option<JiraFieldSchema>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraFieldSchema {
+    #[serde(default, rename = "type")]
+    pub field_type: Option<String>,
+    /// Custom field type key, e.g. `com.pyxis.greenhopper.jira:gh-sprint`
+    #[serde(default)]
+    pub custom: Option<String>,
+}
+
+// ── Agile API response types ───────────────────────────────────
+
+/// A page from the Agile API (`/rest/agile/1.0/...`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraAgilePage<T> {
+    #[serde(default)]
+    pub is_last: bool,
+    #[serde(default = "Vec::new")]
+    pub values: Vec<T>,
+}
+
+/// A board (`/rest/agile/1.0/board`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraBoard {
+    pub id: i64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub board_type: String,
+    #[serde(default)]
+    pub location: Option<JiraBoardLocation>,
+}
+
+impl JiraBoard {
+    /// Only scrum boards have sprints; the sprint endpoint rejects the others.
+    pub fn has_sprints(&self) -> bool {
+        self.board_type == "scrum"
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraBoardLocation {
+    #[serde(default)]
+    pub project_key: Option<String>,
+}
+
+/// A sprint of a board (`/rest/agile/1.0/board/{id}/sprint`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraAgileSprint {
+    pub id: i64,
+    pub name: String,
+    pub state: String,
+    #[serde(default)]
+    pub goal: Option<String>,
+    #[serde(default)]
+    pub start_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub complete_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub origin_board_id: Option<i64>,
+}
+
+// ── Worklog API response types ──────────────────────────────────
+
+/// A page of `/rest/api/3/worklog/updated` or `/worklog/deleted`. The next page
+/// starts at `until`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWorklogChangePage {
+    #[serde(default = "Vec::new")]
+    pub values: Vec<JiraWorklogChange>,
+    pub since: i64,
+    pub until: i64,
+    #[serde(default = "default_true")]
+    pub last_page: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWorklogChange {
+    pub worklog_id: i64,
+    pub updated_time: i64,
+}
+
+/// A worklog from `/rest/api/3/worklog/list`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWorklog {
+    pub id: String,
+    pub issue_id: String,
+    #[serde(default)]
+    pub author: Option<JiraUserRef>,
+    pub started: DateTime<Utc>,
+    pub time_spent_seconds: i64,
+    #[serde(default)]
+    pub updated: Option<DateTime<Utc>>,
 }
 
 // ── Changelog API response types ────────────────────────────────
@@ -149,9 +377,30 @@ pub struct JiraChangelogResponse {
     pub values: Vec<JiraChangelogEntry>,
 }
 
+/// Response from `POST /rest/api/3/changelog/bulkfetch`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraBulkChangelogResponse {
+    #[serde(default)]
+    pub issue_change_logs: Vec<JiraIssueChangelog>,
+    #[serde(default)]
+    pub next_page_token: Option<String>,
+}
+
+/// Changelog entries of one issue in a bulk changelog page. An issue's entries
+/// may span several pages.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraIssueChangelog {
+    pub issue_id: String,
+    #[serde(default)]
+    pub change_histories: Vec<JiraChangelogEntry>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct JiraChangelogEntry {
     pub author: Option<JiraUserRef>,
+    #[serde(deserialize_with = "datetime_or_epoch_millis")]
     pub created: DateTime<Utc>,
     #[serde(default)]
     pub items: Vec<JiraChangelogItem>,
@@ -161,8 +410,79 @@ pub struct JiraChangelogEntry {
 #[serde(rename_all = "camelCase")]
 pub struct JiraChangelogItem {
     pub field: String,
+    /// e.g. "assignee", "fixVersions" or "customfield_10016"; absent on old entries.
+    #[serde(default)]
+    pub field_id: Option<String>,
     pub from_string: Option<String>,
     pub to_string: Option<String>,
+    /// Raw values; for the Sprint field, comma-separated sprint ids.
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+}
+
+/// Changelog times are ISO timestamps, except in the bulk changelog API, which
+/// returns epoch milliseconds.
+fn datetime_or_epoch_millis<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Utc>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Millis(i64),
+        Time(DateTime<Utc>),
+    }
+    match Raw::deserialize(d)? {
+        Raw::Millis(ms) => DateTime::from_timestamp_millis(ms)
+            .ok_or_else(|| serde::de::Error::custom("changelog time out of range")),
+        Raw::Time(t) => Ok(t),
+    }
+}
+
+// ── Webhook payload types ───────────────────────────────────────
+
+/// Body of a Jira `jira:issue_*` webhook.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraWebhookEvent {
+    pub webhook_event: String,
+    /// Event time in epoch milliseconds.
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    /// The user who triggered the event.
+    #[serde(default)]
+    pub user: Option<JiraUserRef>,
+    #[serde(default)]
+    pub issue: Option<JiraIssue>,
+    /// Only present on `jira:issue_updated`.
+    #[serde(default)]
+    pub changelog: Option<JiraWebhookChangelog>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JiraWebhookChangelog {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub items: Vec<JiraChangelogItem>,
+}
+
+impl JiraWebhookEvent {
+    /// The embedded changelog as a changelog entry, attributed to the acting user at
+    /// the event time, so it maps like entries from the changelog API.
+    pub fn changelog_entries(&self) -> Vec<JiraChangelogEntry> {
+        let Some(changelog) = &self.changelog else {
+            return Vec::new();
+        };
+        let created = self
+            .timestamp
+            .and_then(DateTime::<Utc>::from_timestamp_millis)
+            .unwrap_or_else(Utc::now);
+        vec![JiraChangelogEntry {
+            author: self.user.clone(),
+            created,
+            items: changelog.items.clone(),
+        }]
+    }
 }
 
 #[cfg(test)]
@@ -229,4 +549,39 @@ mod tests {
         assert!(!user.active);
         assert!(user.email_address.is_none());
     }
+
+    #[test]
+    fn webhook_changelog_maps_to_entry_at_event_time() {
+        let event: JiraWebhookEvent = serde_json::from_value(serde_json::json!({
+            "webhookEvent": "jira:issue_updated",
+            "timestamp": 1_704_103_200_000_i64,
+            "user": { "accountId": "acc-1", "displayName": "Dev" },
+            "changelog": {
+                "id": "10100",
+                "items": [
+                    { "field": "status", "fromString": "To Do", "toString": "In Progress" }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let entries = event.changelog_entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].created.to_rfc3339(), "2024-01-01T10:00:00+00:00");
+        assert_eq!(entries[0].author.as_ref().unwrap().account_id, "acc-1");
+        assert_eq!(
+            entries[0].items[0].to_string.as_deref(),
+            Some("In Progress")
+        );
+        assert!(event.issue.is_none());
+    }
+
+    #[test]
+    fn webhook_without_changelog_has_no_entries() {
+        let event: JiraWebhookEvent = serde_json::from_value(serde_json::json!({
+            "webhookEvent": "jira:issue_created"
+        }))
+        .unwrap();
+        assert!(event.changelog_entries().is_empty());
+    }
 }
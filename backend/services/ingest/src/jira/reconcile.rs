@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use ovia_db::jira::models::JiraIssueRef;
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::{JiraClient, JiraClientError};
+use super::models::JiraIssueKey;
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "jira_reconcile";
+
+/// Minimum time between two reconciliation passes.
+const RECONCILE_INTERVAL_HOURS: i64 = 24;
+
+/// What a reconciliation pass has to do with the stored issues.
+#[derive(Debug, Default, PartialEq)]
+pub struct ReconciliationPlan {
+    /// `(old_key, new_key)` of issues found under another key in the synced projects.
+    pub moved: Vec<(String, String)>,
+    /// Stored keys that are not in the synced projects anymore.
+    pub missing: Vec<String>,
+}
+
+/// Compare the stored issues with the keys currently in Jira. Issues are
+/// matched by Jira id first, so a move between synced projects is a re-key.
+pub fn plan_reconciliation(
+    stored: &[JiraIssueRef],
+    current: &[JiraIssueKey],
+) -> ReconciliationPlan {
+    let current_keys: HashSet<&str> = current.iter().map(|k| k.key.as_str()).collect();
+    let key_by_id: HashMap<i64, &str> = current
+        .iter()
+        .filter_map(|k| Some((k.id.parse().ok()?, k.key.as_str())))
+        .collect();
+
+    let mut plan = ReconciliationPlan::default();
+    for issue in stored {
+        let new_key = issue
+            .jira_issue_id
+            .and_then(|id| key_by_id.get(&id).copied());
+        match new_key {
+            Some(key) if key != issue.jira_key => {
+                plan.moved.push((issue.jira_key.clone(), key.to_string()))
+            }
+            Some(_) => {}
+            None if current_keys.contains(issue.jira_key.as_str()) => {}
+            None => plan.missing.push(issue.jira_key.clone()),
+        }
+    }
+    plan
+}
+
+fn project_of(key: &str) -> &str {
+    key.split_once('-').map_or(key, |(project, _)| project)
+}
+
+/// Periodically removes issues that were deleted in Jira or moved out of the
+/// synced projects, and re-keys issues moved between synced projects. The
+/// incremental issue sync only sees issues that still match its JQL, so it
+/// cannot notice either case.
+pub struct JiraReconciler<S> {
+    org_id: Uuid,
+    client: JiraClient,
+    jira_repo: PgJiraRepository,
+    sync_repo: S,
+}
+
+impl<S> JiraReconciler<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: JiraClient,
+        jira_repo: PgJiraRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            jira_repo,
+            sync_repo,
+        }
+    }
+
+    /// Current keys of every configured project. Fails as a whole so that a
+    /// partial listing never looks like mass deletion.
+    async fn list_current_keys(
+        &self,
+    ) -> Result<HashMap<String, Vec<JiraIssueKey>>, JiraClientError> {
+        let mut keys = HashMap::new();
+        for project_key in &self.client.config().project_keys {
+            let project_keys = self.client.list_issue_keys(project_key).await?;
+            keys.insert(project_key.clone(), project_keys);
+        }
+        Ok(keys)
+    }
+
+    fn empty_result() -> SyncResult {
+        SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted: 0,
+            skipped: 0,
+            errors: 0,
+            deleted: 0,
+            moved: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Connector for JiraReconciler<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        let existing = self
+            .sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        if existing
+            .last_synced_at
+            .is_some_and(|at| Utc::now() - at < Duration::hours(RECONCILE_INTERVAL_HOURS))
+        {
+            tracing::debug!(org_id = %self.org_id, "jira reconciliation ran recently, skipping");
+            return Ok(Self::empty_result());
+        }
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "jira reconciliation already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(Self::empty_result());
+            }
+        };
+
+        let current = match self.list_current_keys().await {
+            Ok(keys) => keys,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "jira issue key listing failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        let mut result = Self::empty_result();
+        let mut stored = Vec::new();
+        for (project_key, keys) in &current {
+            let refs = self
+                .jira_repo
+                .list_issue_refs(self.org_id, project_key)
+                .await
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+            // An empty project with stored issues is more likely lost access than
+            // everything being deleted
+            if keys.is_empty() && !refs.is_empty() {
+                tracing::warn!(
+                    project = %project_key,
+                    stored = refs.len(),
+                    "jira returned no issues for project, skipping reconciliation"
+                );
+                result.errors += 1;
+                continue;
+            }
+            stored.extend(refs);
+        }
+        let all_current: Vec<JiraIssueKey> = current.into_values().flatten().collect();
+        let plan = plan_reconciliation(&stored, &all_current);
+        let synced_projects: HashSet<&str> = self
+            .client
+            .config()
+            .project_keys
+            .iter()
+            .map(String::as_str)
+            .collect();
+
+        for (old_key, new_key) in &plan.moved {
+            match self
+                .jira_repo
+                .rekey_issue(self.org_id, old_key, new_key)
+                .await
+            {
+                Ok(_) => result.moved += 1,
+                Err(e) => {
+                    tracing::warn!(old_key = %old_key, new_key = %new_key, error = %e, "failed to re-key jira issue");
+                    result.errors += 1;
+                }
+            }
+        }
+
+        // Missing keys are confirmed one by one: Jira resolves the old key of a
+        // moved issue, so a lookup tells deletion and moves apart
+        for key in &plan.missing {
+            let outcome = match self.client.fetch_issue_key(key).await {
+                Ok(None) => {
+                    self.jira_repo
+                        .tombstone_issue(self.org_id, key, "deleted", None)
+                        .await
+                }
+                Ok(Some(found)) if found.key == *key => {
+                    result.skipped += 1;
+                    continue;
+                }
+                Ok(Some(found)) if synced_projects.contains(project_of(&found.key)) => {
+                    match self
+                        .jira_repo
+                        .rekey_issue(self.org_id, key, &found.key)
+                        .await
+                    {
+                        Ok(_) => {
+                            result.moved += 1;
+                            continue;
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+                Ok(Some(found)) => {
+                    self.jira_repo
+                        .tombstone_issue(self.org_id, key, "moved", Some(&found.key))
+                        .await
+                }
+                Err(e) => {
+                    tracing::warn!(key = %key, error = %e, "failed to look up jira issue");
+                    result.errors += 1;
+                    continue;
+                }
+            };
+            match outcome {
+                Ok(_) => result.deleted += 1,
+                Err(e) => {
+                    tracing::warn!(key = %key, error = %e, "failed to reconcile jira issue");
+                    result.errors += 1;
+                }
+            }
+        }
+
+        self.sync_repo
+            .mark_completed(watermark.id, None)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        tracing::info!(?result, "jira reconciliation completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::client::JiraClientConfig;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn issue_ref(key: &str, id: Option<i64>) -> JiraIssueRef {
+        JiraIssueRef {
+            jira_key: key.to_string(),
+            jira_issue_id: id,
+        }
+    }
+
+    fn issue_key(id: &str, key: &str) -> JiraIssueKey {
+        JiraIssueKey {
+            id: id.to_string(),
+            key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn plan_matches_by_id_then_key() {
+        let stored = vec![
+            issue_ref("BEE-1", Some(1)),
+            issue_ref("BEE-2", Some(2)),
+            issue_ref("BEE-3", None),
+            issue_ref("BEE-4", Some(4)),
+            issue_ref("BEE-5", None),
+        ];
+        let current = vec![
+            issue_key("1", "BEE-1"),
+            issue_key("2", "OPS-9"),
+            issue_key("3", "BEE-3"),
+        ];
+
+        let plan = plan_reconciliation(&stored, &current);
+        assert_eq!(plan.moved, vec![("BEE-2".to_string(), "OPS-9".to_string())]);
+        assert_eq!(plan.missing, vec!["BEE-4".to_string(), "BEE-5".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn client_lists_keys_and_resolves_moved_or_deleted_issues() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/search/jql"))
+            .and(query_param("fields", "id"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "issues": [{ "id": "1", "key": "BEE-1" }, { "id": "3", "key": "BEE-3" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/BEE-2"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "id": "2", "key": "OPS-9" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/issue/BEE-4"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "errorMessages": ["Issue does not exist or you do not have permission to see it."]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = JiraClient::new(JiraClientConfig {
+            base_url: server.uri(),
+            email: "test@example.com".to_string(),
+            api_token: "token".to_string(),
+            project_keys: vec!["BEE".to_string()],
+            sync_window_days: 7,
+            max_retries: 1,
+            timeout_secs: 5,
+        })
+        .unwrap();
+
+        let keys = client.list_issue_keys("BEE").await.unwrap();
+        assert_eq!(keys, vec![issue_key("1", "BEE-1"), issue_key("3", "BEE-3")]);
+
+        let moved = client.fetch_issue_key("BEE-2").await.unwrap();
+        assert_eq!(moved, Some(issue_key("2", "OPS-9")));
+        assert_eq!(project_of("OPS-9"), "OPS");
+
+        assert!(client.fetch_issue_key("BEE-4").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use ovia_db::jira::models::{JiraBoard as DbBoard, JiraSprint as DbSprint};
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::JiraClient;
+use super::models::{JiraAgileSprint, JiraBoard};
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "jira_sprints";
+
+pub fn api_board_to_db(org_id: Uuid, board: &JiraBoard) -> DbBoard {
+    let now = Utc::now();
+    DbBoard {
+        id: Uuid::new_v4(),
+        org_id,
+        board_id: board.id,
+        name: board.name.clone(),
+        board_type: board.board_type.clone(),
+        project_key: board.location.as_ref().and_then(|l| l.project_key.clone()),
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+pub fn api_sprint_to_db(org_id: Uuid, board_id: i64, sprint: &JiraAgileSprint) -> DbSprint {
+    let now = Utc::now();
+    DbSprint {
+        id: Uuid::new_v4(),
+        org_id,
+        sprint_id: sprint.id,
+        board_id: Some(sprint.origin_board_id.unwrap_or(board_id)),
+        name: sprint.name.clone(),
+        state: sprint.state.clone(),
+        goal: sprint.goal.clone().filter(|g| !g.trim().is_empty()),
+        start_at: sprint.start_date,
+        end_at: sprint.end_date,
+        completed_at: sprint.complete_date,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Syncs the boards of the configured projects and the sprints of their scrum
+/// boards. Both are small, so every run is a full sync.
+pub struct JiraSprintSyncer<S> {
+    org_id: Uuid,
+    client: JiraClient,
+    jira_repo: PgJiraRepository,
+    sync_repo: S,
+}
+
+impl<S> JiraSprintSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: JiraClient,
+        jira_repo: PgJiraRepository,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            jira_repo,
+            sync_repo,
+        }
+    }
+}
+
+#[async_trait]
+impl<S> Connector for JiraSprintSyncer<S>
+where
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "jira sprint sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: SOURCE_NAME.to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        let mut upserted: usize = 0;
+        let mut skipped: usize = 0;
+        let mut errors: usize = 0;
+        // A board can span several projects
+        let mut seen_boards = HashSet::new();
+
+        for project_key in &self.client.config().project_keys {
+            let boards = match self.client.fetch_boards(project_key).await {
+                Ok(b) => b,
+                Err(e) => {
+                    tracing::warn!(project = %project_key, error = %e, "failed to fetch jira boards");
+                    errors += 1;
+                    continue;
+                }
+            };
+
+            for board in boards.iter().filter(|b| seen_boards.insert(b.id)) {
+                match self
+                    .jira_repo
+                    .upsert_board(&api_board_to_db(self.org_id, board))
+                    .await
+                {
+                    Ok(_) => upserted += 1,
+                    Err(e) => {
+                        tracing::warn!(board_id = board.id, error = %e, "failed to upsert jira board");
+                        errors += 1;
+                        continue;
+                    }
+                }
+
+                if !board.has_sprints() {
+                    skipped += 1;
+                    continue;
+                }
+
+                let sprints = match self.client.fetch_board_sprints(board.id).await {
+                    Ok(s) => s,
+                    Err(e) => {
+                        tracing::warn!(board_id = board.id, error = %e, "failed to fetch jira sprints");
+                        errors += 1;
+                        continue;
+                    }
+                };
+                for sprint in &sprints {
+                    let db_sprint = api_sprint_to_db(self.org_id, board.id, sprint);
+                    match self.jira_repo.upsert_sprint(&db_sprint).await {
+                        Ok(_) => upserted += 1,
+                        Err(e) => {
+                            tracing::warn!(sprint_id = sprint.id, error = %e, "failed to upsert jira sprint");
+                            errors += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.sync_repo
+            .mark_completed(watermark.id, None)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted,
+            skipped,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "jira sprint sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::client::JiraClientConfig;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_client_config(base_url: &str) -> JiraClientConfig {
+        JiraClientConfig {
+            base_url: base_url.to_string(),
+            email: "test@example.com".to_string(),
+            api_token: "token".to_string(),
+            project_keys: vec!["BEE".to_string()],
+            sync_window_days: 7,
+            max_retries: 1,
+            timeout_secs: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn client_fetches_boards_and_paged_sprints() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board"))
+            .and(query_param("projectKeyOrId", "BEE"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "maxResults": 50, "startAt": 0, "isLast": true,
+                "values": [
+                    { "id": 7, "name": "BEE board", "type": "scrum",
+                      "location": { "projectKey": "BEE" } },
+                    { "id": 8, "name": "BEE support", "type": "kanban" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board/7/sprint"))
+            .and(query_param("startAt", "0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "maxResults": 1, "startAt": 0, "isLast": false,
+                "values": [{
+                    "id": 41, "name": "Sprint 41", "state": "closed", "goal": "Ship checkout",
+                    "startDate": "2026-02-02T09:00:00.000Z", "endDate": "2026-02-16T09:00:00.000Z",
+                    "completeDate": "2026-02-16T10:30:00.000Z", "originBoardId": 7
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/agile/1.0/board/7/sprint"))
+            .and(query_param("startAt", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "maxResults": 1, "startAt": 1, "isLast": true,
+                "values": [{ "id": 42, "name": "Sprint 42", "state": "active", "goal": "" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
+        let boards = client.fetch_boards("BEE").await.unwrap();
+        assert_eq!(boards.len(), 2);
+        assert!(boards[0].has_sprints());
+        assert!(!boards[1].has_sprints());
+
+        let sprints = client.fetch_board_sprints(7).await.unwrap();
+        assert_eq!(sprints.len(), 2);
+
+        let org_id = Uuid::new_v4();
+        let closed = api_sprint_to_db(org_id, 7, &sprints[0]);
+        assert_eq!(closed.state, "closed");
+        assert_eq!(closed.goal.as_deref(), Some("Ship checkout"));
+        assert_eq!(
+            closed.completed_at,
+            Some("2026-02-16T10:30:00Z".parse().unwrap())
+        );
+        let active = api_sprint_to_db(org_id, 7, &sprints[1]);
+        assert_eq!(active.board_id, Some(7));
+        assert!(active.goal.is_none());
+        assert!(active.completed_at.is_none());
+
+        let board = api_board_to_db(org_id, &boards[0]);
+        assert_eq!(board.project_key.as_deref(), Some("BEE"));
+    }
+}
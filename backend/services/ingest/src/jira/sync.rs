@@ -41,6 +41,8 @@ where
             email: user.email_address.clone(),
             display_name: user.display_name.clone(),
             is_service_account: user.is_service_account(),
+            is_active: user.active,
+            active_changed_at: None,
             first_seen_at: Some(Utc::now()),
             last_seen_at: Some(Utc::now()),
             raw_ref: serde_json::to_value(user).ok(),
@@ -84,6 +86,8 @@ where
                     upserted: 0,
                     skipped: 0,
                     errors: 0,
+                    deleted: 0,
+                    moved: 0,
                 });
             }
         };
@@ -134,6 +138,8 @@ where
             upserted,
             skipped: 0,
             errors,
+            deleted: 0,
+            moved: 0,
         };
 
         tracing::info!(?result, "jira sync completed");
@@ -0,0 +1,308 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use ovia_db::identity::repositories::IdentityRepository;
+use ovia_db::jira::models::JiraWorklog as DbWorklog;
+use ovia_db::jira::pg_repository::PgJiraRepository;
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+
+use super::client::JiraClient;
+use super::issue_sync::user_ref_to_identity;
+use super::models::{JiraUserRef, JiraWorklog};
+use crate::connector::{Connector, SyncResult};
+
+const SOURCE_NAME: &str = "jira_worklogs";
+
+/// Convert an API worklog to a DB row. `None` when the ids are not numeric.
+pub fn api_worklog_to_db(org_id: Uuid, w: &JiraWorklog) -> Option<DbWorklog> {
+    let now = Utc::now();
+    Some(DbWorklog {
+        id: Uuid::new_v4(),
+        org_id,
+        worklog_id: w.id.parse().ok()?,
+        jira_issue_id: w.issue_id.parse().ok()?,
+        author_account_id: w.author.as_ref().map(|a| a.account_id.clone()),
+        started_at: w.started,
+        time_spent_secs: w.time_spent_seconds,
+        updated_at_jira: w.updated,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// Syncs worklogs through the updated/deleted worklog feeds. The cursor is the
+/// feeds' `until` in epoch milliseconds. The feeds cover the whole Jira site, so
+/// worklogs are kept only for issues already synced by the issue syncer.
+pub struct JiraWorklogSyncer<I, S> {
+    org_id: Uuid,
+    client: JiraClient,
+    jira_repo: PgJiraRepository,
+    identity_repo: I,
+    sync_repo: S,
+}
+
+impl<I, S> JiraWorklogSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    pub fn new(
+        org_id: Uuid,
+        client: JiraClient,
+        jira_repo: PgJiraRepository,
+        identity_repo: I,
+        sync_repo: S,
+    ) -> Self {
+        Self {
+            org_id,
+            client,
+            jira_repo,
+            identity_repo,
+            sync_repo,
+        }
+    }
+}
+
+#[async_trait]
+impl<I, S> Connector for JiraWorklogSyncer<I, S>
+where
+    I: IdentityRepository,
+    S: SyncWatermarkRepository,
+{
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+        self.sync_repo
+            .get_or_create(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = self
+            .sync_repo
+            .acquire_lock(self.org_id, SOURCE_NAME)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let watermark = match watermark {
+            Some(wm) => wm,
+            None => {
+                tracing::info!(
+                    "jira worklog sync already running for org={}, skipping",
+                    self.org_id
+                );
+                return Ok(SyncResult {
+                    source: SOURCE_NAME.to_string(),
+                    upserted: 0,
+                    skipped: 0,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                });
+            }
+        };
+
+        let since_ms = watermark
+            .cursor_value
+            .as_deref()
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or_else(|| {
+                (Utc::now() - Duration::days(i64::from(self.client.config().sync_window_days)))
+                    .timestamp_millis()
+            });
+
+        let feeds = async {
+            let updated = self.client.fetch_updated_worklog_ids(since_ms).await?;
+            let deleted = self.client.fetch_deleted_worklog_ids(since_ms).await?;
+            let ids: Vec<i64> = updated.0.iter().map(|c| c.worklog_id).collect();
+            let worklogs = self.client.fetch_worklogs(&ids).await?;
+            Ok::<_, super::client::JiraClientError>((worklogs, deleted.0, updated.1.min(deleted.1)))
+        };
+        let (worklogs, deleted, next_since) = match feeds.await {
+            Ok(r) => r,
+            Err(e) => {
+                let msg = e.to_string();
+                tracing::error!(error = %msg, "jira worklog fetch failed");
+                self.sync_repo
+                    .mark_failed(watermark.id, &msg)
+                    .await
+                    .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+                return Err(Box::new(e));
+            }
+        };
+
+        let known_issues: HashSet<i64> = self
+            .jira_repo
+            .list_jira_issue_ids(self.org_id)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?
+            .into_iter()
+            .collect();
+
+        let mut upserted: usize = 0;
+        let mut skipped: usize = 0;
+        let mut errors: usize = 0;
+        let mut authors: HashMap<&str, &JiraUserRef> = HashMap::new();
+
+        for worklog in &worklogs {
+            let Some(db_worklog) = api_worklog_to_db(self.org_id, worklog) else {
+                errors += 1;
+                continue;
+            };
+            if !known_issues.contains(&db_worklog.jira_issue_id) {
+                skipped += 1;
+                continue;
+            }
+            match self.jira_repo.upsert_worklog(&db_worklog).await {
+                Ok(_) => upserted += 1,
+                Err(e) => {
+                    tracing::warn!(worklog_id = %worklog.id, error = %e, "failed to upsert jira worklog");
+                    errors += 1;
+                    continue;
+                }
+            }
+            if let Some(author) = &worklog.author {
+                authors.entry(author.account_id.as_str()).or_insert(author);
+            }
+        }
+
+        let deleted_ids: Vec<i64> = deleted.iter().map(|c| c.worklog_id).collect();
+        if !deleted_ids.is_empty() {
+            if let Err(e) = self
+                .jira_repo
+                .delete_worklogs(self.org_id, &deleted_ids)
+                .await
+            {
+                tracing::warn!(error = %e, "failed to delete jira worklogs");
+                errors += 1;
+            }
+        }
+
+        // ── Worklog authors feed identity discovery ─────────────────
+        for author in authors.values() {
+            let identity = user_ref_to_identity(self.org_id, author);
+            if let Err(e) = self.identity_repo.upsert_by_external_id(identity).await {
+                tracing::warn!(
+                    account_id = %author.account_id,
+                    error = %e,
+                    "failed to upsert identity from worklog author"
+                );
+            }
+        }
+
+        self.sync_repo
+            .mark_completed(watermark.id, Some(&next_since.to_string()))
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+
+        let result = SyncResult {
+            source: SOURCE_NAME.to_string(),
+            upserted,
+            skipped,
+            errors,
+            deleted: 0,
+            moved: 0,
+        };
+
+        tracing::info!(?result, "jira worklog sync completed");
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jira::client::JiraClientConfig;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_client_config(base_url: &str) -> JiraClientConfig {
+        JiraClientConfig {
+            base_url: base_url.to_string(),
+            email: "test@example.com".to_string(),
+            api_token: "token".to_string(),
+            project_keys: vec!["BEE".to_string()],
+            sync_window_days: 7,
+            max_retries: 1,
+            timeout_secs: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn client_follows_worklog_feed_and_fetches_worklogs() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/worklog/updated"))
+            .and(query_param("since", "1000"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [{ "worklogId": 11, "updatedTime": 1500, "properties": [] }],
+                "since": 1000, "until": 1500, "lastPage": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/3/worklog/updated"))
+            .and(query_param("since", "1500"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "values": [{ "worklogId": 12, "updatedTime": 1800, "properties": [] }],
+                "since": 1500, "until": 1800, "lastPage": true
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/3/worklog/list"))
+            .and(body_json(serde_json::json!({ "ids": [11, 12] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "id": "11", "issueId": "10042",
+                    "author": { "accountId": "acc-1", "displayName": "Alice", "active": true },
+                    "started": "2026-03-02T09:00:00.000+0000",
+                    "timeSpentSeconds": 5400,
+                    "updated": "2026-03-02T10:30:00.000+0000"
+                },
+                {
+                    "id": "12", "issueId": "10043",
+                    "started": "2026-03-03T09:00:00.000+0200",
+                    "timeSpentSeconds": 3600
+                }
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = JiraClient::new(test_client_config(&server.uri())).unwrap();
+        let (changes, until) = client.fetch_updated_worklog_ids(1000).await.unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(until, 1800);
+
+        let ids: Vec<i64> = changes.iter().map(|c| c.worklog_id).collect();
+        let worklogs = client.fetch_worklogs(&ids).await.unwrap();
+        assert_eq!(worklogs.len(), 2);
+
+        let org_id = Uuid::new_v4();
+        let first = api_worklog_to_db(org_id, &worklogs[0]).unwrap();
+        assert_eq!((first.worklog_id, first.jira_issue_id), (11, 10042));
+        assert_eq!(first.author_account_id.as_deref(), Some("acc-1"));
+        assert_eq!(first.time_spent_secs, 5400);
+        assert_eq!(
+            first.started_at,
+            "2026-03-02T09:00:00Z"
+                .parse::<chrono::DateTime<Utc>>()
+                .unwrap()
+        );
+
+        let second = api_worklog_to_db(org_id, &worklogs[1]).unwrap();
+        assert!(second.author_account_id.is_none());
+        assert_eq!(
+            second.started_at,
+            "2026-03-03T07:00:00Z"
+                .parse::<chrono::DateTime<Utc>>()
+                .unwrap()
+        );
+        assert!(second.updated_at_jira.is_none());
+    }
+}
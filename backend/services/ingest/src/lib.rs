@@ -0,0 +1,10 @@
+pub mod confluence;
+pub mod connector;
+pub mod github;
+pub mod gitlab;
+pub mod http;
+pub mod import;
+pub mod jira;
+pub mod matching;
+pub mod registry;
+pub mod runner;
@@ -1,20 +1,19 @@
-mod confluence;
-mod connector;
-mod gitlab;
-mod jira;
-mod matching;
-
 use ovia_config::init_tracing;
-
-use crate::confluence::client::{ConfluenceClient, ConfluenceClientConfig};
-use crate::confluence::sync::ConfluenceSyncer;
-use crate::connector::Connector;
-use crate::gitlab::client::{GitLabClient, GitLabClientConfig};
-use crate::gitlab::mr_sync::GitLabMrPipelineSyncer;
-use crate::gitlab::sync::GitLabSyncer;
-use crate::jira::client::{JiraClient, JiraClientConfig};
-use crate::jira::issue_sync::JiraIssueSyncer;
-use crate::jira::sync::JiraSyncer;
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::credentials::pg_repository::PgCredentialRepository;
+use ovia_db::credentials::repositories::CredentialRepository;
+use ovia_db::credentials::vault::MasterKeyring;
+use ovia_db::identity::repositories::PersonRepository;
+use ovia_db::org::loader::load_orgs;
+use ovia_db::org::models::Org;
+use ovia_db::org::pg_repository::PgOrgRepository;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use ovia_ingest::http;
+use ovia_ingest::matching;
+use ovia_ingest::registry::{ConnectorContext, ConnectorRegistry};
+use ovia_ingest::runner::{ConnectorRunner, RunnerConfig};
 
 #[tokio::main]
 async fn main() {
@@ -28,168 +27,145 @@ async fn main() {
         .await
         .expect("failed to connect to database");
 
-    // Default org_id — in production this would come from a config or multi-tenant loop
-    let org_id: uuid::Uuid = std::env::var("ORG_ID")
-        .ok()
-        .and_then(|v| v.parse().ok())
-        .unwrap_or_else(uuid::Uuid::new_v4);
+    let loaded = load_orgs(&PgOrgRepository::new(pool.clone()))
+        .await
+        .expect("failed to load orgs");
+    let (orgs, from_org_id) = (loaded.orgs, loaded.from_org_id);
+    if orgs.is_empty() {
+        tracing::warn!("no enabled orgs and ORG_ID not set — nothing to ingest");
+        return;
+    }
 
-    // Jira connector (optional — only runs if JIRA env vars are set)
-    // Fails fast if Jira creds are present but JIRA_PROJECT_KEYS is missing/empty
-    match JiraClientConfig::from_env() {
-        Ok(Some(jira_config)) => {
-            tracing::info!(
-                projects = ?jira_config.project_keys,
-                window_days = jira_config.sync_window_days,
-                "jira connector configured, starting sync"
-            );
+    let keyring = MasterKeyring::from_env().expect("invalid credential vault master keys");
+    if keyring.is_none() {
+        tracing::info!(
+            "CREDENTIALS_MASTER_KEYS not set, only the ORG_ID org can use env credentials"
+        );
+    }
 
-            let client = JiraClient::new(jira_config).expect("failed to create jira client");
-            let identity_repo =
-                ovia_db::identity::pg_repository::PgIdentityRepository::new(pool.clone());
-            let sync_repo = ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone());
-
-            let syncer = JiraSyncer::new(org_id, client, identity_repo, sync_repo);
-
-            match syncer.sync().await {
-                Ok(result) => {
-                    tracing::info!(
-                        source = result.source,
-                        upserted = result.upserted,
-                        errors = result.errors,
-                        "jira sync completed"
-                    );
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "jira sync failed");
-                }
+    let runner_config = RunnerConfig::from_env();
+    let org_count = orgs.len();
+    let mut failed = 0;
+
+    for org in orgs {
+        let org_id = org.id;
+        let org_name = org.name.clone();
+        // Each org runs in its own task so an error or panic in one org does not
+        // abort the others.
+        let handle = tokio::spawn(ingest_org(
+            pool.clone(),
+            org,
+            from_org_id,
+            keyring.clone(),
+            runner_config.clone(),
+        ));
+        match handle.await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                failed += 1;
+                tracing::error!(org_id = %org_id, org = %org_name, error = %e, "org ingest failed");
             }
-
-            // Jira Issue sync (separate watermark: "jira_issues")
-            tracing::info!("starting jira issue sync");
-
-            let issue_client = JiraClient::new(JiraClientConfig::from_env().unwrap().unwrap())
-                .expect("jira client for issue sync");
-            let jira_repo = ovia_db::jira::pg_repository::PgJiraRepository::new(pool.clone());
-            let issue_identity_repo =
-                ovia_db::identity::pg_repository::PgIdentityRepository::new(pool.clone());
-            let issue_sync_repo = ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone());
-
-            let issue_syncer = JiraIssueSyncer::new(
-                org_id,
-                issue_client,
-                jira_repo,
-                issue_identity_repo,
-                issue_sync_repo,
-            );
-
-            match issue_syncer.sync().await {
-                Ok(result) => {
-                    tracing::info!(
-                        source = result.source,
-                        upserted = result.upserted,
-                        errors = result.errors,
-                        "jira issue sync completed"
-                    );
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "jira issue sync failed");
-                }
+            Err(e) => {
+                failed += 1;
+                tracing::error!(org_id = %org_id, org = %org_name, error = %e, "org ingest panicked");
             }
         }
-        Ok(None) => {
-            tracing::info!("no jira credentials found, skipping jira sync");
-        }
-        Err(e) => {
-            panic!("jira configuration error (fail-fast): {e}");
-        }
     }
 
-    // GitLab connector (optional — only runs if GITLAB env vars are set)
-    if let Some(gitlab_config) = GitLabClientConfig::from_env() {
-        tracing::info!("gitlab connector configured, starting sync");
-
-        let client = GitLabClient::new(gitlab_config).expect("failed to create gitlab client");
-        let identity_repo =
-            ovia_db::identity::pg_repository::PgIdentityRepository::new(pool.clone());
-        let sync_repo = ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone());
+    for m in http::metrics_snapshot() {
+        tracing::info!(
+            host = %m.host,
+            requests = m.requests,
+            retries = m.retries,
+            rate_limited = m.rate_limited,
+            failures = m.failures,
+            circuit_opens = m.circuit_opens,
+            rejected = m.rejected,
+            avg_latency_ms = m.avg_latency_ms,
+            "http host metrics"
+        );
+    }
 
-        let syncer = GitLabSyncer::new(org_id, client, identity_repo, sync_repo);
+    tracing::info!(orgs = org_count, failed, "ingest service finished");
+}
 
-        match syncer.sync().await {
-            Ok(result) => {
-                tracing::info!(
-                    source = result.source,
-                    upserted = result.upserted,
-                    errors = result.errors,
-                    "gitlab sync completed"
-                );
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "gitlab sync failed");
-            }
-        }
-        // GitLab MR/Pipeline sync (reuses same config)
-        tracing::info!("starting gitlab MR/pipeline sync");
-
-        let mr_client =
-            GitLabClient::new(GitLabClientConfig::from_env().unwrap()).expect("gitlab client");
-        let gitlab_repo = ovia_db::gitlab::pg_repository::PgGitlabRepository::new(pool.clone());
-        let mr_sync_repo = ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone());
-
-        let mr_syncer = GitLabMrPipelineSyncer::new(org_id, mr_client, gitlab_repo, mr_sync_repo);
-
-        match mr_syncer.sync().await {
-            Ok(result) => {
-                tracing::info!(
-                    source = result.source,
-                    upserted = result.upserted,
-                    errors = result.errors,
-                    "gitlab MR/pipeline sync completed"
-                );
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "gitlab MR/pipeline sync failed");
-            }
-        }
-    } else {
-        tracing::info!("no gitlab credentials found, skipping gitlab sync");
+/// Decrypt the org's vault credentials, first rewrapping any still sealed under a
+/// retired master key so old keys can be dropped from the env after one run.
+async fn load_credentials(
+    pool: &PgPool,
+    org_id: Uuid,
+    keyring: MasterKeyring,
+) -> Result<Vec<SourceSecret>, String> {
+    let vault = PgCredentialRepository::new(pool.clone(), Some(keyring));
+    let rotated = vault
+        .rotate_keys(org_id)
+        .await
+        .map_err(|e| format!("credential key rotation failed: {e}"))?;
+    if rotated > 0 {
+        tracing::info!(org_id = %org_id, rotated, "rewrapped credentials under active master key");
     }
+    vault
+        .reveal_all(org_id)
+        .await
+        .map_err(|e| format!("failed to load credentials: {e}"))
+}
 
-    // Confluence connector (optional — only runs if CONFLUENCE env vars are set)
-    if let Some(confluence_config) = ConfluenceClientConfig::from_env() {
-        tracing::info!("confluence connector configured, starting sync");
-
-        let client =
-            ConfluenceClient::new(confluence_config).expect("failed to create confluence client");
-        let identity_repo =
-            ovia_db::identity::pg_repository::PgIdentityRepository::new(pool.clone());
-        let sync_repo = ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone());
-
-        let syncer = ConfluenceSyncer::new(org_id, client, identity_repo, sync_repo);
-
-        match syncer.sync().await {
-            Ok(result) => {
-                tracing::info!(
-                    source = result.source,
-                    upserted = result.upserted,
-                    errors = result.errors,
-                    "confluence sync completed"
-                );
-            }
-            Err(e) => {
-                tracing::error!(error = %e, "confluence sync failed");
-            }
-        }
-    } else {
-        tracing::info!("no confluence credentials found, skipping confluence sync");
+async fn ingest_org(
+    pool: PgPool,
+    org: Org,
+    allow_env_fallback: bool,
+    keyring: Option<MasterKeyring>,
+    runner_config: RunnerConfig,
+) -> Result<(), String> {
+    let org_id = org.id;
+    tracing::info!(org_id = %org_id, org = %org.name, "starting org ingest");
+
+    let credentials = match keyring {
+        Some(keyring) => load_credentials(&pool, org_id, keyring).await?,
+        None => Vec::new(),
+    };
+
+    // Connectors register themselves in the registry; each one is optional and
+    // skipped when it is not configured for the org, or misconfigured (e.g. Jira
+    // creds without project keys).
+    let ctx = ConnectorContext {
+        org,
+        pool: pool.clone(),
+        credentials,
+        allow_env_fallback,
+    };
+    let specs = ConnectorRegistry::builtin().build(&ctx, &runner_config);
+
+    let runner = ConnectorRunner::new(
+        org_id,
+        ovia_db::sync::pg_repository::PgSyncRepository::new(pool.clone()),
+        runner_config,
+    );
+    let report = runner.run(specs).await;
+    for outcome in &report.outcomes {
+        tracing::info!(
+            org_id = %org_id,
+            source = %outcome.source,
+            status = %outcome.status,
+            "connector outcome"
+        );
     }
+    let total = report.combined();
+    tracing::info!(
+        org_id = %org_id,
+        connectors = report.outcomes.len(),
+        upserted = total.upserted,
+        skipped = total.skipped,
+        errors = total.errors,
+        "all connector syncs finished"
+    );
 
     // ── Batch matching: link identities to people ──
-    tracing::info!("starting batch matching");
+    tracing::info!(org_id = %org_id, "starting batch matching");
     match matching::run_batch_matching(&pool, org_id).await {
         Ok(result) => {
             tracing::info!(
+                org_id = %org_id,
                 people_created = result.people_created,
                 links_created = result.links_created,
                 auto = result.auto,
@@ -199,9 +175,28 @@ async fn main() {
             );
         }
         Err(e) => {
-            tracing::error!(error = %e, "batch matching failed");
+            tracing::error!(org_id = %org_id, error = %e, "batch matching failed");
+        }
+    }
+
+    // ── Offboarding: people whose source accounts are all deactivated ──
+    let identity_repo = ovia_db::identity::pg_repository::PgIdentityRepository::new(pool.clone());
+    match identity_repo.list_offboarding_candidates(org_id).await {
+        Ok(candidates) => {
+            for person in &candidates {
+                tracing::warn!(
+                    org_id = %org_id,
+                    person_id = %person.id,
+                    display_name = %person.display_name,
+                    "all identities inactive, flagged for offboarding"
+                );
+            }
+            tracing::info!(org_id = %org_id, count = candidates.len(), "offboarding check completed");
+        }
+        Err(e) => {
+            tracing::error!(org_id = %org_id, error = %e, "offboarding check failed");
         }
     }
 
-    tracing::info!("ingest service finished");
+    Ok(())
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use chrono::Utc;
 use ovia_db::identity::models::{Identity, LinkStatus, Person};
 use ovia_matching::{evaluate, MatchingConfig};
@@ -18,7 +20,7 @@ pub async fn run_batch_matching(pool: &PgPool, org_id: Uuid) -> anyhow::Result<M
     let now = Utc::now();
 
     // 1. Fetch all identities for this org that do NOT have an active link
-    let unlinked: Vec<Identity> = sqlx::query_as!(
+    let mut unlinked: Vec<Identity> = sqlx::query_as!(
         IdentityRow,
         r#"
         SELECT i.id, i.org_id, i.source, i.external_id, i.username, i.email,
@@ -41,6 +43,30 @@ pub async fn run_batch_matching(pool: &PgPool, org_id: Uuid) -> anyhow::Result<M
     .map(|r| r.into())
     .collect();
 
+    // Links of departed people are closed on purpose; don't hand their identities
+    // back to the matcher or to anyone else.
+    let departed_people: HashSet<Uuid> =
+        sqlx::query_scalar("SELECT id FROM people WHERE org_id = $1 AND left_at IS NOT NULL")
+            .bind(org_id)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .collect();
+    if !departed_people.is_empty() {
+        let departed_ids: Vec<Uuid> = departed_people.iter().copied().collect();
+        let departed_identities: HashSet<Uuid> = sqlx::query_scalar(
+            "SELECT DISTINCT identity_id FROM person_identity_links
+             WHERE org_id = $1 AND person_id = ANY($2)",
+        )
+        .bind(org_id)
+        .bind(&departed_ids)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+        unlinked.retain(|i| !departed_identities.contains(&i.id));
+    }
+
     tracing::info!(unlinked = unlinked.len(), "found unlinked identities");
 
     if unlinked.is_empty() {
@@ -68,6 +94,7 @@ pub async fn run_batch_matching(pool: &PgPool, org_id: Uuid) -> anyhow::Result<M
     .await?
     .into_iter()
     .map(|r| r.into())
+    .filter(|p: &Person| !departed_people.contains(&p.id))
     .collect();
 
     let mut result = MatchingResult {
@@ -134,6 +161,7 @@ pub async fn run_batch_matching(pool: &PgPool, org_id: Uuid) -> anyhow::Result<M
                 team: None,
                 role: None,
                 status: "active".to_string(),
+                left_at: None,
                 created_at: now,
                 updated_at: now,
             };
@@ -204,6 +232,9 @@ impl From<IdentityRow> for Identity {
             email: r.email,
             display_name: r.display_name,
             is_service_account: r.is_service_account,
+            // Not selected by the matching query; the engine does not look at it.
+            is_active: true,
+            active_changed_at: None,
             first_seen_at: r.first_seen_at,
             last_seen_at: r.last_seen_at,
             raw_ref: r.raw_ref,
@@ -235,6 +266,7 @@ impl From<PersonRow> for Person {
             team: r.team,
             role: r.role,
             status: r.status,
+            left_at: None,
             created_at: r.created_at,
             updated_at: r.updated_at,
         }
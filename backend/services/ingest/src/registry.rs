@@ -0,0 +1,217 @@
+use std::time::Duration;
+
+use ovia_db::credentials::models::SourceSecret;
+use ovia_db::org::models::Org;
+use sqlx::PgPool;
+
+use crate::connector::Connector;
+use crate::runner::RunnerConfig;
+
+/// Everything a factory needs to build its connectors.
+#[derive(Clone)]
+pub struct ConnectorContext {
+    pub org: Org,
+    pub pool: PgPool,
+    /// Decrypted vault credentials of the org.
+    pub credentials: Vec<SourceSecret>,
+    /// Whether connectors may fill unset org settings and missing credentials from
+    /// env. Only set for the single org built from `ORG_ID`; registry orgs must
+    /// configure each source themselves, or it is skipped.
+    pub allow_env_fallback: bool,
+}
+
+impl ConnectorContext {
+    pub fn credential(&self, source: &str) -> Option<&SourceSecret> {
+        self.credentials.iter().find(|c| c.source == source)
+    }
+}
+
+/// A built connector plus the scheduling hints the runner needs.
+pub struct ConnectorSpec {
+    pub connector: Box<dyn Connector>,
+    /// Source names that must complete successfully before this connector runs.
+    pub depends_on: Vec<String>,
+    /// Overrides the runner's default timeout for this connector.
+    pub timeout: Option<Duration>,
+}
+
+impl ConnectorSpec {
+    pub fn new(connector: impl Connector + 'static) -> Self {
+        Self {
+            connector: Box::new(connector),
+            depends_on: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    pub fn depends_on(mut self, source: &str) -> Self {
+        self.depends_on.push(source.to_string());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.connector.source_name()
+    }
+}
+
+/// Builds the connectors of one source from the context.
+///
+/// Returns an empty list when the source is not configured, and `Err` when it is
+/// configured but invalid.
+pub type ConnectorFactory = fn(&ConnectorContext) -> Result<Vec<ConnectorSpec>, String>;
+
+/// A source's factory. Each source module submits one with `inventory::submit!`,
+/// so adding a source does not touch the registry.
+pub struct SourceRegistration {
+    pub source: &'static str,
+    pub factory: ConnectorFactory,
+}
+
+inventory::collect!(SourceRegistration);
+
+#[derive(Default)]
+pub struct ConnectorRegistry {
+    factories: Vec<(&'static str, ConnectorFactory)>,
+}
+
+impl ConnectorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every source submitted in this crate, ordered by source name.
+    /// Connectors of different sources do not depend on each other, so the order
+    /// only keeps runs reproducible.
+    pub fn builtin() -> Self {
+        let mut registrations: Vec<&SourceRegistration> =
+            inventory::iter::<SourceRegistration>.into_iter().collect();
+        registrations.sort_by_key(|r| r.source);
+
+        let mut registry = Self::new();
+        for r in registrations {
+            registry.register(r.source, r.factory);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, source: &'static str, factory: ConnectorFactory) {
+        self.factories.push((source, factory));
+    }
+
+    /// Registered source names, in build order.
+    pub fn sources(&self) -> Vec<&'static str> {
+        self.factories.iter().map(|(source, _)| *source).collect()
+    }
+
+    /// Run every factory and keep the connectors enabled by `config`. A source whose
+    /// factory fails is logged and skipped; the other sources still run.
+    pub fn build(&self, ctx: &ConnectorContext, config: &RunnerConfig) -> Vec<ConnectorSpec> {
+        let mut specs = Vec::new();
+        for (source, factory) in &self.factories {
+            match factory(ctx) {
+                Ok(built) => {
+                    specs.extend(built.into_iter().filter(|s| config.is_enabled(s.name())))
+                }
+                Err(e) => tracing::error!(
+                    org_id = %ctx.org.id,
+                    source = %source,
+                    error = %e,
+                    "connector configuration error, skipping source"
+                ),
+            }
+        }
+        specs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connector::SyncResult;
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl Connector for Named {
+        fn source_name(&self) -> &str {
+            self.0
+        }
+
+        async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(SyncResult {
+                source: self.0.to_string(),
+                upserted: 0,
+                skipped: 0,
+                errors: 0,
+                deleted: 0,
+                moved: 0,
+            })
+        }
+    }
+
+    fn users_and_issues(_ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+        Ok(vec![
+            ConnectorSpec::new(Named("users")),
+            ConnectorSpec::new(Named("issues")).depends_on("users"),
+        ])
+    }
+
+    fn broken(_ctx: &ConnectorContext) -> Result<Vec<ConnectorSpec>, String> {
+        Err("missing project keys".to_string())
+    }
+
+    fn test_ctx() -> ConnectorContext {
+        ConnectorContext {
+            org: Org::new(Uuid::new_v4(), "test"),
+            credentials: Vec::new(),
+            allow_env_fallback: false,
+            pool: PgPool::connect_lazy("postgres://localhost/unused").unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn build_collects_specs_from_all_factories() {
+        let mut registry = ConnectorRegistry::new();
+        registry.register("test", users_and_issues);
+
+        let specs = registry.build(&test_ctx(), &RunnerConfig::default());
+        let names: Vec<&str> = specs.iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["users", "issues"]);
+        assert_eq!(specs[1].depends_on, vec!["users".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn build_filters_disabled_connectors() {
+        let mut registry = ConnectorRegistry::new();
+        registry.register("test", users_and_issues);
+        let config = RunnerConfig {
+            enabled: Some(vec!["issues".to_string()]),
+            ..RunnerConfig::default()
+        };
+
+        let specs = registry.build(&test_ctx(), &config);
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].name(), "issues");
+    }
+
+    #[tokio::test]
+    async fn build_skips_only_the_failing_source() {
+        let mut registry = ConnectorRegistry::new();
+        registry.register("broken", broken);
+        registry.register("test", users_and_issues);
+
+        let specs = registry.build(&test_ctx(), &RunnerConfig::default());
+        let names: Vec<&str> = specs.iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["users", "issues"]);
+    }
+
+    #[test]
+    fn builtin_registers_every_source() {
+        assert_eq!(
+            ConnectorRegistry::builtin().sources(),
+            vec!["confluence", "file_import", "github", "gitlab", "jira"]
+        );
+    }
+}
@@ -0,0 +1,553 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use ovia_db::sync::repositories::SyncWatermarkRepository;
+use uuid::Uuid;
+
+use crate::connector::SyncResult;
+use crate::registry::ConnectorSpec;
+
+const DEFAULT_TIMEOUT_SECS: u64 = 1800;
+const TIMEOUT_OVERRIDE_PREFIX: &str = "INGEST_TIMEOUT_SECS_";
+
+/// Which connectors run and how long each one may take.
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    /// Source names to run. `None` runs every configured connector.
+    pub enabled: Option<Vec<String>>,
+    pub default_timeout: Duration,
+    /// Per-source overrides, keyed by lowercase source name.
+    pub timeouts: HashMap<String, Duration>,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            default_timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl RunnerConfig {
+    /// Reads `INGEST_CONNECTORS` (comma-separated source names), `INGEST_TIMEOUT_SECS`
+    /// and per-source `INGEST_TIMEOUT_SECS_<SOURCE>` overrides.
+    pub fn from_env() -> Self {
+        Self::from_vars(std::env::vars())
+    }
+
+    fn from_vars(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut config = Self::default();
+        for (key, value) in vars {
+            if key == "INGEST_CONNECTORS" {
+                let names: Vec<String> = value
+                    .split(',')
+                    .map(|s| s.trim().to_lowercase())
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                if !names.is_empty() {
+                    config.enabled = Some(names);
+                }
+            } else if key == "INGEST_TIMEOUT_SECS" {
+                if let Ok(secs) = value.parse() {
+                    config.default_timeout = Duration::from_secs(secs);
+                }
+            } else if let Some(source) = key.strip_prefix(TIMEOUT_OVERRIDE_PREFIX) {
+                if let Ok(secs) = value.parse() {
+                    config
+                        .timeouts
+                        .insert(source.to_lowercase(), Duration::from_secs(secs));
+                }
+            }
+        }
+        config
+    }
+
+    pub fn is_enabled(&self, source: &str) -> bool {
+        match &self.enabled {
+            Some(names) => names.iter().any(|n| n == source),
+            None => true,
+        }
+    }
+
+    fn timeout_for(&self, spec: &ConnectorSpec) -> Duration {
+        self.timeouts
+            .get(spec.name())
+            .copied()
+            .or(spec.timeout)
+            .unwrap_or(self.default_timeout)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConnectorStatus {
+    Completed(SyncResult),
+    Failed(String),
+    TimedOut(Duration),
+    Skipped(String),
+}
+
+impl std::fmt::Display for ConnectorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Completed(r) => write!(
+                f,
+                "completed ({} upserted, {} errors)",
+                r.upserted, r.errors
+            ),
+            Self::Failed(e) => write!(f, "failed: {e}"),
+            Self::TimedOut(d) => write!(f, "timed out after {}s", d.as_secs()),
+            Self::Skipped(reason) => write!(f, "skipped: {reason}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ConnectorOutcome {
+    pub source: String,
+    pub status: ConnectorStatus,
+}
+
+#[derive(Debug, Default)]
+pub struct RunReport {
+    pub outcomes: Vec<ConnectorOutcome>,
+}
+
+impl RunReport {
+    /// Totals across every completed connector. Failed and timed-out connectors
+    /// each count as one error; skipped connectors are not counted.
+    pub fn combined(&self) -> SyncResult {
+        let mut total = SyncResult {
+            source: "all".to_string(),
+            upserted: 0,
+            skipped: 0,
+            errors: 0,
+            deleted: 0,
+            moved: 0,
+        };
+        for outcome in &self.outcomes {
+            match &outcome.status {
+                ConnectorStatus::Completed(result) => {
+                    total.upserted += result.upserted;
+                    total.skipped += result.skipped;
+                    total.errors += result.errors;
+                    total.deleted += result.deleted;
+                    total.moved += result.moved;
+                }
+                ConnectorStatus::Failed(_) | ConnectorStatus::TimedOut(_) => total.errors += 1,
+                ConnectorStatus::Skipped(_) => {}
+            }
+        }
+        total
+    }
+}
+
+/// Runs connectors one at a time in dependency order.
+///
+/// A connector whose dependency failed, timed out or was skipped is skipped too.
+/// Dependencies that are not part of the run (unconfigured or disabled) are
+/// treated as satisfied.
+pub struct ConnectorRunner<S: SyncWatermarkRepository> {
+    org_id: Uuid,
+    sync_repo: S,
+    config: RunnerConfig,
+}
+
+impl<S: SyncWatermarkRepository> ConnectorRunner<S> {
+    pub fn new(org_id: Uuid, sync_repo: S, config: RunnerConfig) -> Self {
+        Self {
+            org_id,
+            sync_repo,
+            config,
+        }
+    }
+
+    pub async fn run(&self, specs: Vec<ConnectorSpec>) -> RunReport {
+        let (ordered, cyclic) = order_by_dependencies(specs);
+        let mut report = RunReport::default();
+        let mut unhealthy: HashSet<String> = HashSet::new();
+
+        for spec in ordered {
+            let source = spec.name().to_string();
+
+            if let Some(dep) = spec.depends_on.iter().find(|d| unhealthy.contains(*d)) {
+                let reason = format!("dependency {dep} did not complete");
+                tracing::warn!(source = %source, reason = %reason, "sync skipped");
+                unhealthy.insert(source.clone());
+                report.outcomes.push(ConnectorOutcome {
+                    source,
+                    status: ConnectorStatus::Skipped(reason),
+                });
+                continue;
+            }
+
+            let timeout = self.config.timeout_for(&spec);
+            tracing::info!(source = %source, timeout_secs = timeout.as_secs(), "starting sync");
+
+            let status = match tokio::time::timeout(timeout, spec.connector.sync()).await {
+                Ok(Ok(result)) => {
+                    tracing::info!(
+                        source = result.source,
+                        upserted = result.upserted,
+                        skipped = result.skipped,
+                        errors = result.errors,
+                        "sync completed"
+                    );
+                    ConnectorStatus::Completed(result)
+                }
+                Ok(Err(e)) => {
+                    tracing::error!(source = %source, error = %e, "sync failed");
+                    ConnectorStatus::Failed(e.to_string())
+                }
+                Err(_) => {
+                    tracing::error!(
+                        source = %source,
+                        timeout_secs = timeout.as_secs(),
+                        "sync timed out"
+                    );
+                    self.release_lock(&source, timeout).await;
+                    ConnectorStatus::TimedOut(timeout)
+                }
+            };
+
+            if !matches!(status, ConnectorStatus::Completed(_)) {
+                unhealthy.insert(source.clone());
+            }
+            report.outcomes.push(ConnectorOutcome { source, status });
+        }
+
+        for spec in cyclic {
+            let source = spec.name().to_string();
+            tracing::error!(source = %source, "sync skipped: dependency cycle");
+            report.outcomes.push(ConnectorOutcome {
+                source,
+                status: ConnectorStatus::Skipped("dependency cycle".to_string()),
+            });
+        }
+
+        report
+    }
+
+    /// A connector cancelled mid-sync leaves its watermark in `running`, which
+    /// would block every later run. Mark it failed so the next run can proceed.
+    async fn release_lock(&self, source: &str, timeout: Duration) {
+        let message = format!("sync timed out after {}s", timeout.as_secs());
+        let result = match self.sync_repo.get_or_create(self.org_id, source).await {
+            Ok(wm) => self
+                .sync_repo
+                .mark_failed(wm.id, &message)
+                .await
+                .map(|_| ()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            tracing::error!(source = %source, error = %e, "failed to release sync lock");
+        }
+    }
+}
+
+/// Stable topological sort: connectors keep registration order unless a
+/// dependency forces them later. Returns `(ordered, cyclic)`.
+fn order_by_dependencies(specs: Vec<ConnectorSpec>) -> (Vec<ConnectorSpec>, Vec<ConnectorSpec>) {
+    let present: HashSet<String> = specs.iter().map(|s| s.name().to_string()).collect();
+    let mut done: HashSet<String> = HashSet::new();
+    let mut pending = specs;
+    let mut ordered = Vec::new();
+
+    loop {
+        let before = pending.len();
+        let mut rest = Vec::new();
+        for spec in pending {
+            let ready = spec
+                .depends_on
+                .iter()
+                .all(|d| done.contains(d) || !present.contains(d));
+            if ready {
+                done.insert(spec.name().to_string());
+                ordered.push(spec);
+            } else {
+                rest.push(spec);
+            }
+        }
+        pending = rest;
+        if pending.is_empty() || pending.len() == before {
+            break;
+        }
+    }
+
+    (ordered, pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connector::Connector;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use ovia_db::sync::models::SyncWatermark;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Copy)]
+    enum Behavior {
+        Succeed(usize),
+        Fail,
+        Hang,
+    }
+
+    struct FakeConnector {
+        name: &'static str,
+        behavior: Behavior,
+        calls: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl Connector for FakeConnector {
+        fn source_name(&self) -> &str {
+            self.name
+        }
+
+        async fn sync(&self) -> Result<SyncResult, Box<dyn std::error::Error + Send + Sync>> {
+            self.calls.lock().unwrap().push(self.name.to_string());
+            match self.behavior {
+                Behavior::Succeed(upserted) => Ok(SyncResult {
+                    source: self.name.to_string(),
+                    upserted,
+                    skipped: 1,
+                    errors: 0,
+                    deleted: 0,
+                    moved: 0,
+                }),
+                Behavior::Fail => Err("boom".into()),
+                Behavior::Hang => {
+                    tokio::time::sleep(Duration::from_secs(3600)).await;
+                    unreachable!()
+                }
+            }
+        }
+    }
+
+    #[derive(Default, Clone)]
+    struct MockSyncRepo {
+        failed: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    fn dummy_watermark(source: &str) -> SyncWatermark {
+        SyncWatermark {
+            id: Uuid::new_v4(),
+            org_id: Uuid::new_v4(),
+            source: source.to_string(),
+            last_synced_at: None,
+            cursor_value: None,
+            status: "running".to_string(),
+            error_message: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[async_trait]
+    impl SyncWatermarkRepository for MockSyncRepo {
+        async fn get_or_create(
+            &self,
+            _org_id: Uuid,
+            source: &str,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            Ok(dummy_watermark(source))
+        }
+
+        async fn acquire_lock(
+            &self,
+            _org_id: Uuid,
+            source: &str,
+        ) -> ovia_common::error::OviaResult<Option<SyncWatermark>> {
+            Ok(Some(dummy_watermark(source)))
+        }
+
+        async fn mark_completed(
+            &self,
+            _id: Uuid,
+            _cursor_value: Option<&str>,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            Ok(dummy_watermark("any"))
+        }
+
+        async fn mark_failed(
+            &self,
+            _id: Uuid,
+            error_message: &str,
+        ) -> ovia_common::error::OviaResult<SyncWatermark> {
+            self.failed
+                .lock()
+                .unwrap()
+                .push(("any".to_string(), error_message.to_string()));
+            Ok(dummy_watermark("any"))
+        }
+    }
+
+    fn spec(
+        name: &'static str,
+        behavior: Behavior,
+        calls: &Arc<Mutex<Vec<String>>>,
+    ) -> ConnectorSpec {
+        ConnectorSpec::new(FakeConnector {
+            name,
+            behavior,
+            calls: calls.clone(),
+        })
+    }
+
+    fn runner(repo: MockSyncRepo, config: RunnerConfig) -> ConnectorRunner<MockSyncRepo> {
+        ConnectorRunner::new(Uuid::new_v4(), repo, config)
+    }
+
+    #[tokio::test]
+    async fn runs_dependencies_first() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let specs = vec![
+            spec("issues", Behavior::Succeed(2), &calls).depends_on("users"),
+            spec("users", Behavior::Succeed(3), &calls),
+            spec("pages", Behavior::Succeed(1), &calls),
+        ];
+
+        let report = runner(MockSyncRepo::default(), RunnerConfig::default())
+            .run(specs)
+            .await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["users", "pages", "issues"]);
+        let total = report.combined();
+        assert_eq!(total.upserted, 6);
+        assert_eq!(total.skipped, 3);
+        assert_eq!(total.errors, 0);
+    }
+
+    #[tokio::test]
+    async fn missing_dependency_is_treated_as_satisfied() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let specs = vec![spec("issues", Behavior::Succeed(1), &calls).depends_on("users")];
+
+        let report = runner(MockSyncRepo::default(), RunnerConfig::default())
+            .run(specs)
+            .await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["issues"]);
+        assert!(matches!(
+            report.outcomes[0].status,
+            ConnectorStatus::Completed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn skips_dependents_of_failed_connector() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let specs = vec![
+            spec("users", Behavior::Fail, &calls),
+            spec("issues", Behavior::Succeed(5), &calls).depends_on("users"),
+            spec("pages", Behavior::Succeed(1), &calls),
+        ];
+
+        let report = runner(MockSyncRepo::default(), RunnerConfig::default())
+            .run(specs)
+            .await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["users", "pages"]);
+        assert!(matches!(
+            report.outcomes[0].status,
+            ConnectorStatus::Failed(_)
+        ));
+        assert!(matches!(
+            report.outcomes[1].status,
+            ConnectorStatus::Skipped(_)
+        ));
+        let total = report.combined();
+        assert_eq!(total.upserted, 1);
+        assert_eq!(total.errors, 1);
+    }
+
+    #[tokio::test]
+    async fn timeout_releases_lock_and_skips_dependents() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let repo = MockSyncRepo::default();
+        let mut config = RunnerConfig::default();
+        config
+            .timeouts
+            .insert("users".to_string(), Duration::from_millis(50));
+        let specs = vec![
+            spec("users", Behavior::Hang, &calls),
+            spec("issues", Behavior::Succeed(1), &calls).depends_on("users"),
+        ];
+
+        let report = runner(repo.clone(), config).run(specs).await;
+
+        assert!(matches!(
+            report.outcomes[0].status,
+            ConnectorStatus::TimedOut(d) if d == Duration::from_millis(50)
+        ));
+        assert!(matches!(
+            report.outcomes[1].status,
+            ConnectorStatus::Skipped(_)
+        ));
+        let failed = repo.failed.lock().unwrap();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].1.contains("timed out"));
+    }
+
+    #[tokio::test]
+    async fn dependency_cycle_is_skipped() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let specs = vec![
+            spec("a", Behavior::Succeed(1), &calls).depends_on("b"),
+            spec("b", Behavior::Succeed(1), &calls).depends_on("a"),
+            spec("c", Behavior::Succeed(1), &calls),
+        ];
+
+        let report = runner(MockSyncRepo::default(), RunnerConfig::default())
+            .run(specs)
+            .await;
+
+        assert_eq!(*calls.lock().unwrap(), vec!["c"]);
+        assert_eq!(report.outcomes.len(), 3);
+        assert!(report.outcomes[1..]
+            .iter()
+            .all(|o| matches!(o.status, ConnectorStatus::Skipped(_))));
+    }
+
+    #[test]
+    fn config_from_vars() {
+        let config = RunnerConfig::from_vars(vec![
+            (
+                "INGEST_CONNECTORS".to_string(),
+                " jira, GitLab ,".to_string(),
+            ),
+            ("INGEST_TIMEOUT_SECS".to_string(), "60".to_string()),
+            (
+                "INGEST_TIMEOUT_SECS_JIRA_ISSUES".to_string(),
+                "120".to_string(),
+            ),
+            ("UNRELATED".to_string(), "x".to_string()),
+        ]);
+
+        assert_eq!(
+            config.enabled,
+            Some(vec!["jira".to_string(), "gitlab".to_string()])
+        );
+        assert!(config.is_enabled("gitlab"));
+        assert!(!config.is_enabled("confluence"));
+        assert_eq!(config.default_timeout, Duration::from_secs(60));
+        assert_eq!(
+            config.timeouts.get("jira_issues"),
+            Some(&Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn config_defaults_enable_everything() {
+        let config = RunnerConfig::from_vars(Vec::new());
+        assert!(config.enabled.is_none());
+        assert!(config.is_enabled("anything"));
+        assert_eq!(
+            config.default_timeout,
+            Duration::from_secs(DEFAULT_TIMEOUT_SECS)
+        );
+    }
+}
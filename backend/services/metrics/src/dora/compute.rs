@@ -0,0 +1,255 @@
+//! DORA metrics from production deployments.
+//!
+//! - Deployment frequency: successful production deployments per day of the period.
+//! - Lead time for changes: median hours from an MR's first commit to the production
+//!   deployment that shipped it.
+//! - Change failure rate: failed / (successful + failed) production deployments. There
+//!   is no incident source yet, so a failed deployment stands in for a failed change.
+//! - Time to restore: median hours from the first failed deployment of an environment
+//!   to the end of the next successful one.
+//!
+//! Each metric is reported for the whole org, per project and per team.
+
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use ovia_db::gitlab::models::{DeployLeadTimeRow, DeploymentRow};
+use ovia_db::kpi::models::DoraMetrics;
+use uuid::Uuid;
+
+use crate::kpi::service::percentile;
+
+pub const SCOPE_ORG: &str = "org";
+pub const SCOPE_PROJECT: &str = "project";
+pub const SCOPE_TEAM: &str = "team";
+
+/// Hours from a failed production deployment to the recovering one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restore {
+    pub project: String,
+    pub team: Option<String>,
+    pub hours: f64,
+}
+
+fn project_key(project_id: i64, path: Option<&str>) -> String {
+    path.map(str::to_string)
+        .unwrap_or_else(|| project_id.to_string())
+}
+
+/// Pair each run of failed deployments with the next success on the same project and
+/// environment. `deployments` must be ordered by start time. The team is that of
+/// whoever made the first failed deployment.
+pub fn restores(deployments: &[DeploymentRow]) -> Vec<Restore> {
+    let mut failing_since: BTreeMap<(i64, &str), &DeploymentRow> = BTreeMap::new();
+    let mut restores = Vec::new();
+
+    for d in deployments {
+        let key = (d.gitlab_project_id, d.environment_name.as_str());
+        match d.status.as_str() {
+            "failed" => {
+                failing_since.entry(key).or_insert(d);
+            }
+            "success" => {
+                if let (Some(failed), Some(restored_at)) =
+                    (failing_since.remove(&key), d.finished_at_gl)
+                {
+                    let hours = (restored_at - failed.created_at_gl).num_seconds() as f64 / 3600.0;
+                    restores.push(Restore {
+                        project: project_key(
+                            failed.gitlab_project_id,
+                            failed.project_path.as_deref(),
+                        ),
+                        team: failed.team.clone(),
+                        hours,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    restores
+}
+
+#[derive(Default)]
+struct ScopeAcc {
+    successes: i32,
+    failures: i32,
+    lead_times: Vec<f64>,
+    restore_hours: Vec<f64>,
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    values.sort_by(f64::total_cmp);
+    percentile(&values, 50.0)
+}
+
+/// DORA metrics for the org, every project and every known team with activity in the
+/// period. `period_days` must be at least 1.
+pub fn compute_dora(
+    org_id: Uuid,
+    snapshot_id: Uuid,
+    period_days: i64,
+    deployments: &[DeploymentRow],
+    lead_times: &[DeployLeadTimeRow],
+) -> Vec<DoraMetrics> {
+    let mut scopes: BTreeMap<(&'static str, Option<String>), ScopeAcc> = BTreeMap::new();
+    scopes.entry((SCOPE_ORG, None)).or_default();
+
+    let scope_keys = |project: String, team: Option<String>| {
+        let mut keys = vec![(SCOPE_ORG, None), (SCOPE_PROJECT, Some(project))];
+        if let Some(team) = team {
+            keys.push((SCOPE_TEAM, Some(team)));
+        }
+        keys
+    };
+
+    for d in deployments {
+        let project = project_key(d.gitlab_project_id, d.project_path.as_deref());
+        for key in scope_keys(project, d.team.clone()) {
+            let acc = scopes.entry(key).or_default();
+            match d.status.as_str() {
+                "success" => acc.successes += 1,
+                "failed" => acc.failures += 1,
+                _ => {}
+            }
+        }
+    }
+
+    for l in lead_times.iter().filter(|l| l.hours >= 0.0) {
+        let project = project_key(l.gitlab_project_id, l.project_path.as_deref());
+        for key in scope_keys(project, l.team.clone()) {
+            scopes.entry(key).or_default().lead_times.push(l.hours);
+        }
+    }
+
+    for r in restores(deployments) {
+        for key in scope_keys(r.project.clone(), r.team.clone()) {
+            scopes.entry(key).or_default().restore_hours.push(r.hours);
+        }
+    }
+
+    let now = Utc::now();
+    scopes
+        .into_iter()
+        .map(|((scope_type, scope_key), acc)| {
+            let finished = acc.successes + acc.failures;
+            DoraMetrics {
+                id: Uuid::new_v4(),
+                org_id,
+                snapshot_id,
+                scope_type: scope_type.to_string(),
+                scope_key,
+                deployment_count: acc.successes,
+                deployments_per_day: Some(acc.successes as f64 / period_days.max(1) as f64),
+                lead_time_p50_hours: median(acc.lead_times),
+                change_failure_rate: (finished > 0).then(|| acc.failures as f64 / finished as f64),
+                time_to_restore_p50_hours: median(acc.restore_hours),
+                created_at: now,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration};
+
+    fn t(hours: i64) -> DateTime<Utc> {
+        "2026-03-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap() + Duration::hours(hours)
+    }
+
+    fn deploy(project: i64, env: &str, status: &str, team: Option<&str>, at: i64) -> DeploymentRow {
+        DeploymentRow {
+            gitlab_project_id: project,
+            project_path: Some(format!("acme/p{project}")),
+            environment_name: env.to_string(),
+            status: status.to_string(),
+            team: team.map(str::to_string),
+            created_at_gl: t(at),
+            finished_at_gl: Some(t(at) + Duration::minutes(30)),
+        }
+    }
+
+    #[test]
+    fn restores_pair_first_failure_with_next_success() {
+        let deployments = vec![
+            deploy(1, "production", "failed", Some("core"), 0),
+            deploy(1, "production", "failed", Some("web"), 1),
+            deploy(2, "production", "success", None, 2), // other project
+            deploy(1, "production", "success", None, 4),
+            deploy(1, "production", "failed", None, 10), // never restored
+        ];
+        assert_eq!(
+            restores(&deployments),
+            vec![Restore {
+                project: "acme/p1".to_string(),
+                team: Some("core".to_string()),
+                hours: 4.5,
+            }]
+        );
+    }
+
+    #[test]
+    fn compute_dora_per_org_project_and_team() {
+        let deployments = vec![
+            deploy(1, "production", "success", Some("core"), 0),
+            deploy(1, "production", "failed", Some("core"), 24),
+            deploy(1, "production", "success", Some("core"), 26),
+            deploy(2, "production", "success", None, 48),
+        ];
+        let lead_times = vec![
+            DeployLeadTimeRow {
+                gitlab_project_id: 1,
+                project_path: Some("acme/p1".to_string()),
+                team: Some("core".to_string()),
+                hours: 10.0,
+            },
+            DeployLeadTimeRow {
+                gitlab_project_id: 2,
+                project_path: Some("acme/p2".to_string()),
+                team: None,
+                hours: 30.0,
+            },
+        ];
+
+        let metrics = compute_dora(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            10,
+            &deployments,
+            &lead_times,
+        );
+        let find = |scope_type: &str, key: Option<&str>| {
+            metrics
+                .iter()
+                .find(|m| m.scope_type == scope_type && m.scope_key.as_deref() == key)
+                .unwrap()
+        };
+        assert_eq!(metrics.len(), 4);
+
+        let org = find(SCOPE_ORG, None);
+        assert_eq!(org.deployment_count, 3);
+        assert_eq!(org.deployments_per_day, Some(0.3));
+        assert_eq!(org.change_failure_rate, Some(0.25));
+        assert_eq!(org.lead_time_p50_hours, Some(20.0));
+        assert_eq!(org.time_to_restore_p50_hours, Some(2.5));
+
+        let p2 = find(SCOPE_PROJECT, Some("acme/p2"));
+        assert_eq!(p2.change_failure_rate, Some(0.0));
+        assert_eq!(p2.time_to_restore_p50_hours, None);
+
+        let core = find(SCOPE_TEAM, Some("core"));
+        assert_eq!(core.deployment_count, 2);
+        assert_eq!(core.lead_time_p50_hours, Some(10.0));
+    }
+
+    #[test]
+    fn compute_dora_without_deployments_reports_org_only() {
+        let metrics = compute_dora(Uuid::new_v4(), Uuid::new_v4(), 14, &[], &[]);
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].deployment_count, 0);
+        assert_eq!(metrics[0].change_failure_rate, None);
+        assert_eq!(metrics[0].lead_time_p50_hours, None);
+    }
+}
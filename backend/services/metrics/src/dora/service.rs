@@ -0,0 +1,37 @@
+use sqlx::PgPool;
+
+use ovia_common::error::OviaResult;
+use ovia_db::gitlab::pg_repository::PgGitlabRepository;
+use ovia_db::kpi::models::{DoraMetrics, KpiSnapshot};
+use ovia_db::kpi::repositories::KpiRepository;
+
+use super::compute::compute_dora;
+
+pub struct DoraService<R: KpiRepository> {
+    repo: R,
+    pool: PgPool,
+}
+
+impl<R: KpiRepository> DoraService<R> {
+    pub fn new(repo: R, pool: PgPool) -> Self {
+        Self { repo, pool }
+    }
+
+    /// Compute DORA metrics over a saved snapshot's period and store them with it,
+    /// replacing any from an earlier run for the same period.
+    pub async fn compute_and_save(&self, snapshot: &KpiSnapshot) -> OviaResult<Vec<DoraMetrics>> {
+        let gl_repo = PgGitlabRepository::new(self.pool.clone());
+        let (org_id, from, to) = (snapshot.org_id, snapshot.period_start, snapshot.period_end);
+
+        let deployments = gl_repo
+            .list_production_deployments(org_id, from, to)
+            .await?;
+        let lead_times = gl_repo
+            .get_deploy_lead_times_hours(org_id, from, to)
+            .await?;
+        let period_days = (to - from).num_days() + 1;
+
+        let metrics = compute_dora(org_id, snapshot.id, period_days, &deployments, &lead_times);
+        self.repo.save_dora_metrics(snapshot.id, metrics).await
+    }
+}
@@ -1,3 +1,5 @@
+use ovia_db::jira::models::SprintSpilloverRow;
+
 /// Compute a delivery health score (0-100) from throughput, review latency, blockers, and spillover.
 ///
 /// Weighted formula:
@@ -45,6 +47,19 @@ pub fn compute_delivery_health(
     raw.clamp(0.0, 100.0)
 }
 
+/// Pooled spillover rate across closed sprints: issues still unresolved when their
+/// sprint completed, over all issues the sprints held at completion.
+///
+/// Returns `None` when no sprint held any issue, so callers can fall back.
+pub fn compute_sprint_spillover(sprints: &[SprintSpilloverRow]) -> Option<f64> {
+    let committed: i64 = sprints.iter().map(|s| s.committed).sum();
+    if committed == 0 {
+        return None;
+    }
+    let unresolved: i64 = sprints.iter().map(|s| s.unresolved).sum();
+    Some(unresolved as f64 / committed as f64)
+}
+
 /// Compute release risk as a label and score (0-100).
 ///
 /// Factors:
@@ -136,6 +151,32 @@ mod tests {
         assert!((score - 90.0).abs() < 0.01);
     }
 
+    // ── compute_sprint_spillover tests ─────────────────────────────
+
+    fn sprint(committed: i64, unresolved: i64) -> SprintSpilloverRow {
+        SprintSpilloverRow {
+            sprint_id: committed,
+            name: format!("Sprint {committed}"),
+            board_id: Some(7),
+            completed_at: chrono::Utc::now(),
+            committed,
+            unresolved,
+        }
+    }
+
+    #[test]
+    fn sprint_spillover_pools_closed_sprints() {
+        // (1 + 3) / (4 + 6)
+        let rate = compute_sprint_spillover(&[sprint(4, 1), sprint(6, 3)]).unwrap();
+        assert!((rate - 0.4).abs() < 0.001);
+    }
+
+    #[test]
+    fn sprint_spillover_none_without_committed_issues() {
+        assert!(compute_sprint_spillover(&[]).is_none());
+        assert!(compute_sprint_spillover(&[sprint(0, 0)]).is_none());
+    }
+
     // ── compute_release_risk tests ─────────────────────────────────
 
     #[test]
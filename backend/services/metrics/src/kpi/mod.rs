@@ -1,3 +1,4 @@
 pub mod classify;
 pub mod compute;
 pub mod service;
+pub mod size;
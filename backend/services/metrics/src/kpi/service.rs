@@ -3,13 +3,16 @@ use sqlx::PgPool;
 use uuid::Uuid;
 
 use ovia_common::error::OviaResult;
+use ovia_db::gitlab::models::{DeployLeadTimeRow, LeadTimeRow};
 use ovia_db::gitlab::pg_repository::PgGitlabRepository;
 use ovia_db::jira::pg_repository::PgJiraRepository;
 use ovia_db::kpi::models::{KpiSnapshot, RiskItem};
 use ovia_db::kpi::repositories::KpiRepository;
+use ovia_db::work_items::pg_repository::PgWorkItemRepository;
 
 use super::classify::{BUG_ISSUE_TYPES, BUG_LABELS, FEATURE_ISSUE_TYPES, FEATURE_LABELS};
-use super::compute::{compute_delivery_health, compute_release_risk};
+use super::compute::{compute_delivery_health, compute_release_risk, compute_sprint_spillover};
+use super::size::{compute_mr_size_stats, OVERSIZED_MR_LINES, OVERSIZED_MR_OPEN_DAYS};
 
 pub struct KpiService<R: KpiRepository> {
     repo: R,
@@ -23,15 +26,30 @@ impl<R: KpiRepository> KpiService<R> {
 
     /// Compute and save a KPI snapshot for the given org and period.
     ///
+    /// Work items are Jira issues and GitLab issues alike (the `work_items` view), so
+    /// throughput, cycle time, blockers and spillover cover teams on either tracker.
+    ///
     /// Throughput classification priority:
     ///   1. Jira issue_type mapping (Bug, Defect → bug; Story, Epic, … → feature)
-    ///   2. GitLab label fallback (bug/defect/fix/hotfix → bug; feature/enhancement/story → feature)
+    ///   2. GitLab label fallback for MRs and GitLab issues
+    ///      (bug/defect/fix/hotfix → bug; feature/enhancement/story → feature)
     ///   3. Unmatched → chore
     ///
     /// Extend `classify::BUG_ISSUE_TYPES`, `FEATURE_ISSUE_TYPES`, `BUG_LABELS`,
     /// `FEATURE_LABELS` to add new mappings.
     ///
-    /// Risk items are generated from stale open MRs (>7 days) and failed pipelines.
+    /// Lead time for changes runs from an MR's first authored commit to its merge,
+    /// plus the wait until the first successful pipeline on the target branch when one
+    /// exists. Without such a pipeline the clock stops at the merge.
+    ///
+    /// Spillover is pooled over Jira sprints closed in the period: issues in a sprint
+    /// when it completed that were still unresolved. Without closed sprints it falls
+    /// back to the share of sprint (or milestone) work items that are not done.
+    ///
+    /// MR size stats bucket merged MRs by lines changed (see `size::MR_SIZE_BUCKETS`).
+    ///
+    /// Risk items are generated from stale open MRs (>7 days), oversized open MRs
+    /// and failed pipelines.
     pub async fn compute_and_save(
         &self,
         org_id: Uuid,
@@ -39,6 +57,7 @@ impl<R: KpiRepository> KpiService<R> {
         period_end: NaiveDate,
     ) -> OviaResult<KpiSnapshot> {
         let gl_repo = PgGitlabRepository::new(self.pool.clone());
+        let work_repo = PgWorkItemRepository::new(self.pool.clone());
         let jira_repo = PgJiraRepository::new(self.pool.clone());
 
         // ── GitLab throughput (label-based classification) ─────────────
@@ -54,24 +73,36 @@ impl<R: KpiRepository> KpiService<R> {
             .count_merged_mrs_by_labels(org_id, period_start, period_end, FEATURE_LABELS)
             .await? as i32;
 
-        // ── Jira throughput (issue-type-based classification) ──────────
-        let jira_bugs = jira_repo
-            .count_resolved_issues_by_types(org_id, period_start, period_end, BUG_ISSUE_TYPES)
+        // ── Work-item throughput (issue type, else labels) ─────────────
+        let item_bugs = work_repo
+            .count_resolved_by_class(
+                org_id,
+                period_start,
+                period_end,
+                BUG_ISSUE_TYPES,
+                BUG_LABELS,
+            )
             .await? as i32;
 
-        let jira_features = jira_repo
-            .count_resolved_issues_by_types(org_id, period_start, period_end, FEATURE_ISSUE_TYPES)
+        let item_features = work_repo
+            .count_resolved_by_class(
+                org_id,
+                period_start,
+                period_end,
+                FEATURE_ISSUE_TYPES,
+                FEATURE_LABELS,
+            )
             .await? as i32;
 
-        let jira_resolved_total = jira_repo
-            .count_resolved_issues(org_id, period_start, period_end)
+        let items_resolved_total = work_repo
+            .count_resolved(org_id, period_start, period_end)
             .await? as i32;
 
-        // Combine MR + Jira throughput
+        // Combine MR + work-item throughput
         // Jira issue_type is the primary classifier; GitLab labels are fallback
-        let throughput_bugs = mr_bugs + jira_bugs;
-        let throughput_features = mr_features + jira_features;
-        let throughput_total = mr_total + jira_resolved_total;
+        let throughput_bugs = mr_bugs + item_bugs;
+        let throughput_features = mr_features + item_features;
+        let throughput_total = mr_total + items_resolved_total;
         let throughput_chores = (throughput_total - throughput_bugs - throughput_features).max(0);
 
         // ── Review latency ──────────────────────────────────────────
@@ -84,16 +115,53 @@ impl<R: KpiRepository> KpiService<R> {
         let review_latency_p90 =
             percentile(&durations.iter().map(|d| d.hours).collect::<Vec<_>>(), 90.0);
 
-        // ── Jira metrics ──────────────────────────────────────────────
-        let blocker_count = jira_repo.count_open_blockers(org_id).await? as i32;
-        let spillover_rate = jira_repo.spillover_rate(org_id).await?;
+        // ── Review engagement (notes and approvals) ─────────────────
+        let first_review = gl_repo
+            .get_first_review_hours(org_id, period_start, period_end)
+            .await?;
+        let time_to_first_review_p50 = percentile(&first_review, 50.0);
+        let time_to_first_review_p90 = percentile(&first_review, 90.0);
+
+        let rounds = gl_repo
+            .get_review_rounds(org_id, period_start, period_end)
+            .await?;
+        let review_rounds_avg = mean(&rounds.iter().map(|r| *r as f64).collect::<Vec<_>>());
+
+        // ── MR size ─────────────────────────────────────────────────
+        let mr_size_stats = compute_mr_size_stats(
+            &gl_repo
+                .get_merged_mr_sizes(org_id, period_start, period_end)
+                .await?,
+        );
+
+        // ── Work-item metrics ─────────────────────────────────────────
+        let blocker_count = work_repo.count_open_blockers(org_id).await? as i32;
+        let closed_sprints = jira_repo
+            .get_sprint_spillover(org_id, period_start, period_end)
+            .await?;
+        let spillover_rate = match compute_sprint_spillover(&closed_sprints) {
+            Some(rate) => rate,
+            None => work_repo.spillover_rate(org_id).await?,
+        };
 
-        let cycle_times = jira_repo
+        let cycle_times = work_repo
             .get_cycle_times_hours(org_id, period_start, period_end)
             .await?;
         let cycle_time_p50 = percentile(&cycle_times, 50.0);
         let cycle_time_p90 = percentile(&cycle_times, 90.0);
 
+        // ── Lead time for changes ───────────────────────────────────
+        let lead_times = lead_time_hours(
+            &gl_repo
+                .get_deploy_lead_times_hours(org_id, period_start, period_end)
+                .await?,
+            &gl_repo
+                .get_lead_times_hours(org_id, period_start, period_end)
+                .await?,
+        );
+        let lead_time_p50 = percentile(&lead_times, 50.0);
+        let lead_time_p90 = percentile(&lead_times, 90.0);
+
         // ── Risk inputs ─────────────────────────────────────────────
         let failing_pipelines = gl_repo
             .count_pipelines_by_status(org_id, period_start, period_end, "failed")
@@ -101,7 +169,7 @@ impl<R: KpiRepository> KpiService<R> {
 
         let stale_mr_pct = gl_repo.stale_mr_percentage(org_id, 7).await?;
 
-        let blocker_age_days = jira_repo.list_open_blocker_age_days(org_id).await?;
+        let blocker_age_days = work_repo.list_open_blocker_age_days(org_id).await?;
 
         // ── Scores ──────────────────────────────────────────────────
         let delivery_health = compute_delivery_health(
@@ -133,6 +201,12 @@ impl<R: KpiRepository> KpiService<R> {
             spillover_rate: Some(spillover_rate),
             cycle_time_p50_hours: cycle_time_p50,
             cycle_time_p90_hours: cycle_time_p90,
+            lead_time_p50_hours: lead_time_p50,
+            lead_time_p90_hours: lead_time_p90,
+            time_to_first_review_p50_hours: time_to_first_review_p50,
+            time_to_first_review_p90_hours: time_to_first_review_p90,
+            review_rounds_avg,
+            mr_size_stats,
             computed_at: now,
             created_at: now,
         };
@@ -160,6 +234,26 @@ impl<R: KpiRepository> KpiService<R> {
             });
         }
 
+        // Oversized open MRs
+        let oversized_mrs = gl_repo
+            .list_oversized_open_mrs(org_id, OVERSIZED_MR_LINES, OVERSIZED_MR_OPEN_DAYS)
+            .await?;
+        for mr in &oversized_mrs {
+            risk_items.push(RiskItem {
+                id: Uuid::new_v4(),
+                org_id,
+                snapshot_id: saved.id,
+                entity_type: "merge_request".to_string(),
+                title: format!("Oversized MR: {}", mr.title),
+                owner: mr.author_username.clone(),
+                age_days: mr.age_days,
+                impact_scope: None,
+                status: "open".to_string(),
+                source_url: Some(mr.web_url.clone()),
+                created_at: now,
+            });
+        }
+
         // Failed pipelines in period
         let failed_pipelines = gl_repo
             .list_failed_pipelines(org_id, period_start, period_end)
@@ -188,6 +282,36 @@ impl<R: KpiRepository> KpiService<R> {
             });
         }
 
+        // Flaky jobs: failed, then passed on retry for the same commit
+        let job_reliability = gl_repo
+            .get_job_reliability(org_id, period_start, period_end)
+            .await?;
+        for job in job_reliability.iter().filter(|j| j.flaky_count > 0) {
+            let project = job
+                .project_path
+                .clone()
+                .unwrap_or_else(|| job.gitlab_project_id.to_string());
+            risk_items.push(RiskItem {
+                id: Uuid::new_v4(),
+                org_id,
+                snapshot_id: saved.id,
+                entity_type: "job".to_string(),
+                title: format!(
+                    "Flaky job: {} in {} ({} of {} runs failed)",
+                    job.job_name, project, job.failures, job.runs
+                ),
+                owner: None,
+                age_days: job
+                    .first_flaky_at
+                    .map(|f| (now - f).num_days() as i32)
+                    .unwrap_or(0),
+                impact_scope: Some(project),
+                status: "flaky".to_string(),
+                source_url: job.last_flaky_url.clone(),
+                created_at: now,
+            });
+        }
+
         if !risk_items.is_empty() {
             tracing::info!(count = risk_items.len(), "saving risk items");
             self.repo.save_risk_items(risk_items).await?;
@@ -197,8 +321,34 @@ impl<R: KpiRepository> KpiService<R> {
     }
 }
 
+/// Total lead time per MR in hours, sorted ascending for `percentile`.
+///
+/// Uses the production-deployment lead times behind the DORA metric when any MR in
+/// the period reached production, so both report the same number. Orgs without
+/// synced deployments fall back to the first successful target-branch pipeline.
+fn lead_time_hours(deploys: &[DeployLeadTimeRow], pipelines: &[LeadTimeRow]) -> Vec<f64> {
+    let mut hours: Vec<f64> = if deploys.is_empty() {
+        pipelines
+            .iter()
+            .map(|r| r.first_commit_to_merge_hours + r.merge_to_deploy_hours.unwrap_or(0.0))
+            .collect()
+    } else {
+        deploys.iter().map(|r| r.hours).collect()
+    };
+    hours.retain(|h| *h >= 0.0);
+    hours.sort_by(f64::total_cmp);
+    hours
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f64>() / values.len() as f64)
+}
+
 /// Compute a percentile from a sorted-ascending slice. Returns None for empty input.
-fn percentile(sorted: &[f64], pct: f64) -> Option<f64> {
+pub(crate) fn percentile(sorted: &[f64], pct: f64) -> Option<f64> {
     if sorted.is_empty() {
         return None;
     }
@@ -217,7 +367,7 @@ fn percentile(sorted: &[f64], pct: f64) -> Option<f64> {
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use ovia_db::kpi::models::KpiFilter;
+    use ovia_db::kpi::models::{DoraMetrics, KpiFilter};
     use std::sync::Mutex;
 
     struct MockKpiRepo {
@@ -257,6 +407,18 @@ mod tests {
         async fn list_risk_items(&self, _snapshot_id: Uuid) -> OviaResult<Vec<RiskItem>> {
             Ok(self.saved_risks.lock().unwrap().clone())
         }
+
+        async fn save_dora_metrics(
+            &self,
+            _snapshot_id: Uuid,
+            metrics: Vec<DoraMetrics>,
+        ) -> OviaResult<Vec<DoraMetrics>> {
+            Ok(metrics)
+        }
+
+        async fn list_dora_metrics(&self, _snapshot_id: Uuid) -> OviaResult<Vec<DoraMetrics>> {
+            Ok(vec![])
+        }
     }
 
     #[test]
@@ -277,6 +439,50 @@ mod tests {
         assert_eq!(percentile(&[], 50.0), None);
     }
 
+    #[test]
+    fn lead_time_adds_deploy_wait_when_known() {
+        let rows = vec![
+            LeadTimeRow {
+                first_commit_to_merge_hours: 20.0,
+                merge_to_deploy_hours: Some(4.0),
+            },
+            LeadTimeRow {
+                first_commit_to_merge_hours: 6.0,
+                merge_to_deploy_hours: None,
+            },
+        ];
+        assert_eq!(lead_time_hours(&[], &rows), vec![6.0, 24.0]);
+    }
+
+    #[test]
+    fn lead_time_prefers_production_deployments() {
+        let deploys = vec![
+            DeployLeadTimeRow {
+                gitlab_project_id: 1,
+                project_path: None,
+                team: None,
+                hours: 30.0,
+            },
+            DeployLeadTimeRow {
+                gitlab_project_id: 1,
+                project_path: None,
+                team: None,
+                hours: 10.0,
+            },
+        ];
+        let pipelines = vec![LeadTimeRow {
+            first_commit_to_merge_hours: 2.0,
+            merge_to_deploy_hours: Some(1.0),
+        }];
+        assert_eq!(lead_time_hours(&deploys, &pipelines), vec![10.0, 30.0]);
+    }
+
+    #[test]
+    fn mean_of_values_and_empty() {
+        assert_eq!(mean(&[1.0, 2.0, 4.0, 5.0]), Some(3.0));
+        assert_eq!(mean(&[]), None);
+    }
+
     #[test]
     fn percentile_single_value() {
         assert_eq!(percentile(&[42.0], 50.0), Some(42.0));
@@ -305,6 +511,12 @@ mod tests {
             spillover_rate: Some(0.2),
             cycle_time_p50_hours: Some(24.0),
             cycle_time_p90_hours: Some(48.0),
+            lead_time_p50_hours: Some(30.0),
+            lead_time_p90_hours: Some(60.0),
+            time_to_first_review_p50_hours: Some(2.0),
+            time_to_first_review_p90_hours: Some(12.0),
+            review_rounds_avg: Some(1.2),
+            mr_size_stats: None,
             computed_at: Utc::now(),
             created_at: Utc::now(),
         };
@@ -0,0 +1,140 @@
+//! MR size buckets by lines changed (additions + deletions).
+//!
+//! Each bucket holds MRs below its upper bound; anything at or above the last
+//! bound is `xl`. Extend or retune the bounds here.
+
+use std::collections::BTreeMap;
+
+use ovia_db::gitlab::models::MrSizeRow;
+use ovia_db::kpi::models::{MrSizeBucketStats, MrSizeStats, TeamMrSizeStats};
+
+use super::service::percentile;
+
+/// `(bucket, exclusive upper bound of lines changed)`, smallest first.
+pub const MR_SIZE_BUCKETS: &[(&str, i32)] = &[("xs", 10), ("s", 50), ("m", 250), ("l", 1000)];
+
+/// Bucket for MRs at or above the last bound in `MR_SIZE_BUCKETS`.
+pub const XL_BUCKET: &str = "xl";
+
+/// Open MRs of at least this many changed lines become risk items...
+pub const OVERSIZED_MR_LINES: i32 = 1000;
+
+/// ...once they have been open this many days.
+pub const OVERSIZED_MR_OPEN_DAYS: i32 = 3;
+
+pub fn size_bucket(lines_changed: i32) -> &'static str {
+    MR_SIZE_BUCKETS
+        .iter()
+        .find(|(_, upper)| lines_changed < *upper)
+        .map(|(bucket, _)| *bucket)
+        .unwrap_or(XL_BUCKET)
+}
+
+/// Bucket counts and median open-to-merge hours, overall and per team.
+/// Returns `None` when no merged MR in the period has a known size.
+pub fn compute_mr_size_stats(rows: &[MrSizeRow]) -> Option<MrSizeStats> {
+    if rows.is_empty() {
+        return None;
+    }
+
+    let mut by_team: BTreeMap<Option<&str>, Vec<&MrSizeRow>> = BTreeMap::new();
+    for row in rows {
+        by_team.entry(row.team.as_deref()).or_default().push(row);
+    }
+
+    Some(MrSizeStats {
+        buckets: bucket_stats(rows.iter()),
+        teams: by_team
+            .into_iter()
+            .map(|(team, rows)| TeamMrSizeStats {
+                team: team.map(str::to_string),
+                buckets: bucket_stats(rows.into_iter()),
+            })
+            .collect(),
+    })
+}
+
+fn bucket_stats<'a>(rows: impl Iterator<Item = &'a MrSizeRow>) -> Vec<MrSizeBucketStats> {
+    let mut hours: BTreeMap<&str, Vec<f64>> = BTreeMap::new();
+    let mut counts: BTreeMap<&str, i32> = BTreeMap::new();
+    for row in rows {
+        let bucket = size_bucket(row.lines_changed);
+        *counts.entry(bucket).or_default() += 1;
+        if let Some(h) = row.review_hours.filter(|h| *h >= 0.0) {
+            hours.entry(bucket).or_default().push(h);
+        }
+    }
+
+    MR_SIZE_BUCKETS
+        .iter()
+        .map(|(bucket, _)| *bucket)
+        .chain(std::iter::once(XL_BUCKET))
+        .map(|bucket| {
+            let mut bucket_hours = hours.remove(bucket).unwrap_or_default();
+            bucket_hours.sort_by(f64::total_cmp);
+            MrSizeBucketStats {
+                bucket: bucket.to_string(),
+                mr_count: counts.get(bucket).copied().unwrap_or(0),
+                review_latency_median_hours: percentile(&bucket_hours, 50.0),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(team: Option<&str>, lines_changed: i32, review_hours: f64) -> MrSizeRow {
+        MrSizeRow {
+            team: team.map(str::to_string),
+            lines_changed,
+            changed_files: 1,
+            review_hours: Some(review_hours),
+        }
+    }
+
+    #[test]
+    fn size_bucket_boundaries() {
+        assert_eq!(size_bucket(0), "xs");
+        assert_eq!(size_bucket(9), "xs");
+        assert_eq!(size_bucket(10), "s");
+        assert_eq!(size_bucket(249), "m");
+        assert_eq!(size_bucket(999), "l");
+        assert_eq!(size_bucket(1000), "xl");
+    }
+
+    #[test]
+    fn stats_split_by_bucket_and_team() {
+        let rows = vec![
+            row(Some("core"), 5, 1.0),
+            row(Some("core"), 8, 3.0),
+            row(Some("core"), 1200, 40.0),
+            row(None, 30, 6.0),
+        ];
+        let stats = compute_mr_size_stats(&rows).unwrap();
+
+        let overall: Vec<(&str, i32)> = stats
+            .buckets
+            .iter()
+            .map(|b| (b.bucket.as_str(), b.mr_count))
+            .collect();
+        assert_eq!(
+            overall,
+            vec![("xs", 2), ("s", 1), ("m", 0), ("l", 0), ("xl", 1)]
+        );
+        assert_eq!(stats.buckets[0].review_latency_median_hours, Some(2.0));
+        assert_eq!(stats.buckets[2].review_latency_median_hours, None);
+
+        assert_eq!(stats.teams.len(), 2);
+        assert_eq!(stats.teams[0].team, None);
+        assert_eq!(stats.teams[0].buckets[1].mr_count, 1);
+        assert_eq!(stats.teams[1].team.as_deref(), Some("core"));
+        assert_eq!(stats.teams[1].buckets[4].mr_count, 1);
+    }
+
+    #[test]
+    fn stats_absent_without_sized_mrs() {
+        assert!(compute_mr_size_stats(&[]).is_none());
+    }
+}
@@ -1,10 +1,13 @@
+mod dora;
 mod kpi;
 
 use chrono::{Datelike, NaiveDate, Utc};
 use ovia_config::{init_tracing, AppConfig};
 use ovia_db::kpi::pg_repository::PgKpiRepository;
-use uuid::Uuid;
+use ovia_db::org::loader::load_orgs;
+use ovia_db::org::pg_repository::PgOrgRepository;
 
+use dora::service::DoraService;
 use kpi::service::KpiService;
 
 #[tokio::main]
@@ -18,41 +21,62 @@ async fn main() {
         .await
         .expect("failed to create database pool");
 
-    let kpi_repo = PgKpiRepository::new(pool.clone());
-    let kpi_service = KpiService::new(kpi_repo, pool);
-
     // One-shot computation: compute current period snapshot
     let today = Utc::now().date_naive();
     let period_start = NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today);
     let period_end = today;
 
-    // Use a configurable org_id for the one-shot run
-    let org_id = std::env::var("ORG_ID")
-        .ok()
-        .and_then(|s| Uuid::parse_str(&s).ok())
-        .unwrap_or_else(Uuid::nil);
-
-    if org_id.is_nil() {
-        tracing::warn!("ORG_ID not set or invalid — skipping KPI computation");
-    } else {
-        match kpi_service
-            .compute_and_save(org_id, period_start, period_end)
-            .await
-        {
-            Ok(snapshot) => {
+    let orgs = load_orgs(&PgOrgRepository::new(pool.clone()))
+        .await
+        .expect("failed to load orgs")
+        .orgs;
+    if orgs.is_empty() {
+        tracing::warn!("no enabled orgs and ORG_ID not set — skipping KPI computation");
+        return;
+    }
+
+    let org_count = orgs.len();
+    let mut failed = 0;
+
+    for org in orgs {
+        let org_id = org.id;
+        // Each org runs in its own task so an error or panic in one org does not
+        // abort the others.
+        let kpi_service = KpiService::new(PgKpiRepository::new(pool.clone()), pool.clone());
+        let dora_service = DoraService::new(PgKpiRepository::new(pool.clone()), pool.clone());
+        let handle = tokio::spawn(async move {
+            let snapshot = kpi_service
+                .compute_and_save(org_id, period_start, period_end)
+                .await?;
+            let dora = dora_service.compute_and_save(&snapshot).await?;
+            Ok::<_, ovia_common::error::OviaError>((snapshot, dora))
+        });
+
+        match handle.await {
+            Ok(Ok((snapshot, dora))) => {
                 tracing::info!(
                     snapshot_id = %snapshot.id,
                     org_id = %snapshot.org_id,
                     health = ?snapshot.delivery_health_score,
                     risk = ?snapshot.release_risk_score,
+                    dora_scopes = dora.len(),
                     "KPI snapshot saved"
                 );
             }
+            Ok(Err(e)) => {
+                failed += 1;
+                tracing::error!(org_id = %org_id, org = %org.name, error = %e, "failed to compute KPI snapshot");
+            }
             Err(e) => {
-                tracing::error!(error = %e, "failed to compute KPI snapshot");
+                failed += 1;
+                tracing::error!(org_id = %org_id, org = %org.name, error = %e, "KPI computation panicked");
             }
         }
     }
 
-    tracing::info!("metrics service completed one-shot KPI computation");
+    tracing::info!(
+        orgs = org_count,
+        failed,
+        "metrics service completed one-shot KPI computation"
+    );
 }
@@ -2,8 +2,11 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use ovia_common::error::OviaResult;
+use ovia_common::refs::find_jira_issue_keys;
 use ovia_db::ask::models::{AskSession, Citation};
 use ovia_db::ask::repositories::AskRepository;
+use ovia_db::confluence::models::LinkedPage;
+use ovia_db::confluence::repositories::ConfluenceLinkRepository;
 use ovia_db::kpi::repositories::KpiRepository;
 
 use super::filters::AskFilters;
@@ -18,20 +21,51 @@ pub struct AskResponse {
     pub citations: Vec<Citation>,
 }
 
+const KPI_ASSUMPTIONS: &str = "Stub response based on latest KPI snapshot data.";
+const PAGE_ASSUMPTIONS: &str =
+    "Pages found through links to the Jira issues mentioned in the query.";
+
 /// Stub Ask engine that returns structured responses from DB data.
-/// LLM integration is pending — this provides a basic response from KPI data.
-pub struct AskEngine<A: AskRepository, K: KpiRepository> {
+/// LLM integration is pending — this provides a basic response from KPI data,
+/// or the Confluence pages covering the Jira issues named in the query.
+pub struct AskEngine<A: AskRepository, K: KpiRepository, C: ConfluenceLinkRepository> {
     ask_repo: A,
     kpi_repo: K,
+    link_repo: C,
 }
 
-impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
-    pub fn new(ask_repo: A, kpi_repo: K) -> Self {
-        Self { ask_repo, kpi_repo }
+impl<A: AskRepository, K: KpiRepository, C: ConfluenceLinkRepository> AskEngine<A, K, C> {
+    pub fn new(ask_repo: A, kpi_repo: K, link_repo: C) -> Self {
+        Self {
+            ask_repo,
+            kpi_repo,
+            link_repo,
+        }
+    }
+
+    /// Confluence pages linked to each Jira issue key in the query, for keys
+    /// with at least one page.
+    async fn pages_for_issues(
+        &self,
+        org_id: Uuid,
+        query: &str,
+    ) -> OviaResult<Vec<(String, Vec<LinkedPage>)>> {
+        let mut found = Vec::new();
+        for key in find_jira_issue_keys(query) {
+            let pages = self
+                .link_repo
+                .list_pages_linked_to(org_id, "jira_issue", &key)
+                .await?;
+            if !pages.is_empty() {
+                found.push((key, pages));
+            }
+        }
+        Ok(found)
     }
 
     /// Answer a question for a given org. For MVP this is a stub that:
-    /// 1. Looks up the latest KPI snapshot
+    /// 1. Looks up Confluence pages linked to Jira issue keys in the query,
+    ///    falling back to the latest KPI snapshot
     /// 2. Formats a basic text answer with citations pointing to real data
     /// 3. Saves the session to DB
     pub async fn answer(
@@ -42,10 +76,41 @@ impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
     ) -> OviaResult<AskResponse> {
         let start = std::time::Instant::now();
 
+        let issue_pages = self.pages_for_issues(org_id, query).await?;
+
         // Look up latest KPI data
-        let snapshot = self.kpi_repo.get_latest(org_id).await?;
+        let snapshot = if issue_pages.is_empty() {
+            self.kpi_repo.get_latest(org_id).await?
+        } else {
+            None
+        };
+
+        let (answer, confidence, citations, assumptions) = if !issue_pages.is_empty() {
+            let mut answer = String::from("Confluence pages covering the issues in your query:\n");
+            let mut citations = Vec::new();
+            for (key, pages) in &issue_pages {
+                for page in pages {
+                    answer.push_str(&format!(
+                        "- {key}: \"{title}\" in space {space}\n",
+                        title = page.title,
+                        space = page.space_key,
+                    ));
+                    citations.push(Citation {
+                        source: "confluence_page".to_string(),
+                        url: page.web_url.clone(),
+                        excerpt: format!(
+                            "{key} linked from \"{}\" ({})",
+                            page.title, page.link_source
+                        ),
+                    });
+                }
+            }
+            answer.push_str(
+                "\nNote: This is an automated summary. LLM-powered analysis is pending integration.",
+            );
 
-        let (answer, confidence, citations) = if let Some(snap) = &snapshot {
+            (answer, "medium".to_string(), citations, PAGE_ASSUMPTIONS)
+        } else if let Some(snap) = &snapshot {
             let answer = format!(
                 "Based on the latest KPI snapshot (period {start} to {end}):\n\
                  - Delivery health score: {health}\n\
@@ -77,7 +142,7 @@ impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
                 ),
             }];
 
-            (answer, "medium".to_string(), citations)
+            (answer, "medium".to_string(), citations, KPI_ASSUMPTIONS)
         } else {
             let answer = format!(
                 "No KPI data is available for this organization yet. \
@@ -86,7 +151,7 @@ impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
                  Note: This is an automated stub response. LLM-powered analysis is pending integration."
             );
 
-            (answer, "low".to_string(), vec![])
+            (answer, "low".to_string(), vec![], KPI_ASSUMPTIONS)
         };
 
         let latency_ms = start.elapsed().as_millis() as i32;
@@ -101,7 +166,7 @@ impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
             query: query.to_string(),
             answer: Some(answer.clone()),
             confidence: Some(confidence.clone()),
-            assumptions: Some("Stub response based on latest KPI snapshot data.".to_string()),
+            assumptions: Some(assumptions.to_string()),
             citations: Some(citations.clone()),
             filters: filters_json,
             model: Some("stub-v1".to_string()),
@@ -117,7 +182,7 @@ impl<A: AskRepository, K: KpiRepository> AskEngine<A, K> {
             session_id: saved.id,
             answer,
             confidence,
-            assumptions: Some("Stub response based on latest KPI snapshot data.".to_string()),
+            assumptions: Some(assumptions.to_string()),
             citations,
         })
     }
@@ -129,7 +194,7 @@ mod tests {
     use async_trait::async_trait;
     use chrono::{NaiveDate, Utc};
     use ovia_db::ask::models::AskFilter;
-    use ovia_db::kpi::models::{KpiFilter, KpiSnapshot, RiskItem};
+    use ovia_db::kpi::models::{DoraMetrics, KpiFilter, KpiSnapshot, RiskItem};
     use std::sync::Mutex;
 
     struct MockAskRepo {
@@ -199,6 +264,45 @@ mod tests {
         async fn list_risk_items(&self, _snapshot_id: Uuid) -> OviaResult<Vec<RiskItem>> {
             Ok(vec![])
         }
+
+        async fn save_dora_metrics(
+            &self,
+            _snapshot_id: Uuid,
+            metrics: Vec<DoraMetrics>,
+        ) -> OviaResult<Vec<DoraMetrics>> {
+            Ok(metrics)
+        }
+
+        async fn list_dora_metrics(&self, _snapshot_id: Uuid) -> OviaResult<Vec<DoraMetrics>> {
+            Ok(vec![])
+        }
+    }
+
+    struct MockLinkRepo {
+        pages: Vec<(String, LinkedPage)>,
+    }
+
+    impl MockLinkRepo {
+        fn empty() -> Self {
+            Self { pages: Vec::new() }
+        }
+    }
+
+    #[async_trait]
+    impl ConfluenceLinkRepository for MockLinkRepo {
+        async fn list_pages_linked_to(
+            &self,
+            _org_id: Uuid,
+            _target_type: &str,
+            target_ref: &str,
+        ) -> OviaResult<Vec<LinkedPage>> {
+            Ok(self
+                .pages
+                .iter()
+                .filter(|(key, _)| key == target_ref)
+                .map(|(_, page)| page.clone())
+                .collect())
+        }
     }
 
     fn make_snapshot() -> KpiSnapshot {
@@ -219,6 +323,12 @@ mod tests {
             spillover_rate: None,
             cycle_time_p50_hours: None,
             cycle_time_p90_hours: None,
+            lead_time_p50_hours: None,
+            lead_time_p90_hours: None,
+            time_to_first_review_p50_hours: None,
+            time_to_first_review_p90_hours: None,
+            review_rounds_avg: None,
+            mr_size_stats: None,
             computed_at: Utc::now(),
             created_at: Utc::now(),
         }
@@ -228,7 +338,7 @@ mod tests {
     async fn answer_with_kpi_data_returns_structured_response() {
         let ask_repo = MockAskRepo::new();
         let kpi_repo = MockKpiRepo::new(Some(make_snapshot()));
-        let engine = AskEngine::new(ask_repo, kpi_repo);
+        let engine = AskEngine::new(ask_repo, kpi_repo, MockLinkRepo::empty());
 
         let response = engine
             .answer(Uuid::new_v4(), "What is our delivery health?", None)
@@ -245,7 +355,7 @@ mod tests {
     async fn answer_without_kpi_data_returns_stub() {
         let ask_repo = MockAskRepo::new();
         let kpi_repo = MockKpiRepo::new(None);
-        let engine = AskEngine::new(ask_repo, kpi_repo);
+        let engine = AskEngine::new(ask_repo, kpi_repo, MockLinkRepo::empty());
 
         let response = engine
             .answer(Uuid::new_v4(), "What is our velocity?", None)
@@ -261,7 +371,7 @@ mod tests {
     async fn answer_saves_session_to_repo() {
         let ask_repo = MockAskRepo::new();
         let kpi_repo = MockKpiRepo::new(Some(make_snapshot()));
-        let engine = AskEngine::new(ask_repo, kpi_repo);
+        let engine = AskEngine::new(ask_repo, kpi_repo, MockLinkRepo::empty());
 
         let response = engine
             .answer(Uuid::new_v4(), "Test query", None)
@@ -276,7 +386,7 @@ mod tests {
     async fn answer_with_filters_includes_them() {
         let ask_repo = MockAskRepo::new();
         let kpi_repo = MockKpiRepo::new(Some(make_snapshot()));
-        let engine = AskEngine::new(ask_repo, kpi_repo);
+        let engine = AskEngine::new(ask_repo, kpi_repo, MockLinkRepo::empty());
 
         let filters = AskFilters {
             team: Some("backend".to_string()),
@@ -296,4 +406,51 @@ mod tests {
 
         assert!(response.answer.contains("75.5"));
     }
+
+    #[tokio::test]
+    async fn answer_with_issue_key_cites_linked_pages() {
+        let link_repo = MockLinkRepo {
+            pages: vec![(
+                "BEE-123".to_string(),
+                LinkedPage {
+                    page_id: "1001".to_string(),
+                    title: "Checkout design".to_string(),
+                    space_key: "ENG".to_string(),
+                    web_url: Some(
+                        "https://acme.atlassian.net/wiki/spaces/ENG/pages/1001".to_string(),
+                    ),
+                    updated_at_confluence: Some(Utc::now()),
+                    link_source: "jira_macro".to_string(),
+                },
+            )],
+        };
+        let engine = AskEngine::new(
+            MockAskRepo::new(),
+            MockKpiRepo::new(Some(make_snapshot())),
+            link_repo,
+        );
+
+        let response = engine
+            .answer(Uuid::new_v4(), "Which design doc covers BEE-123?", None)
+            .await
+            .expect("should succeed");
+
+        assert!(response
+            .answer
+            .contains("BEE-123: \"Checkout design\" in space ENG"));
+        assert!(!response.answer.contains("75.5"));
+        assert_eq!(response.citations.len(), 1);
+        assert_eq!(response.citations[0].source, "confluence_page");
+        assert_eq!(
+            response.citations[0].url.as_deref(),
+            Some("https://acme.atlassian.net/wiki/spaces/ENG/pages/1001")
+        );
+
+        // Keys without linked pages fall back to the KPI summary
+        let response = engine
+            .answer(Uuid::new_v4(), "What about BEE-999?", None)
+            .await
+            .expect("should succeed");
+        assert!(response.answer.contains("75.5"));
+    }
 }
@@ -8,6 +8,7 @@ use ovia_config::{init_tracing, AppConfig};
 use ovia_db::ask::models::AskFilter;
 use ovia_db::ask::pg_repository::PgAskRepository;
 use ovia_db::ask::repositories::AskRepository;
+use ovia_db::confluence::pg_repository::PgConfluenceRepository;
 use ovia_db::kpi::pg_repository::PgKpiRepository;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
@@ -20,6 +21,7 @@ use ask::filters::AskFilters;
 struct RagState {
     ask_repo: PgAskRepository,
     kpi_repo: PgKpiRepository,
+    confluence_repo: PgConfluenceRepository,
 }
 
 // ── Request/Response types ──────────────────────────────────────
@@ -96,7 +98,11 @@ async fn post_ask(
         ));
     }
 
-    let engine = AskEngine::new(state.ask_repo.clone(), state.kpi_repo.clone());
+    let engine = AskEngine::new(
+        state.ask_repo.clone(),
+        state.kpi_repo.clone(),
+        state.confluence_repo.clone(),
+    );
     let response = engine
         .answer(org_id, &body.query, body.filters)
         .await
@@ -184,7 +190,8 @@ async fn main() {
 
     let state = RagState {
         ask_repo: PgAskRepository::new(pool.clone()),
-        kpi_repo: PgKpiRepository::new(pool),
+        kpi_repo: PgKpiRepository::new(pool.clone()),
+        confluence_repo: PgConfluenceRepository::new(pool),
     };
 
     let rag_port = std::env::var("RAG_PORT")
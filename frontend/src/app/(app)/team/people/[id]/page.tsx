@@ -213,7 +213,9 @@ export default function Person360Page() {
                   <SelectContent>
                     <SelectItem value="all">{t("person360.sourceAll")}</SelectItem>
                     <SelectItem value="gitlab">{t("person360.sourceGitlab")}</SelectItem>
+                    <SelectItem value="github">{t("person360.sourceGithub")}</SelectItem>
                     <SelectItem value="jira">{t("person360.sourceJira")}</SelectItem>
+                    <SelectItem value="confluence">{t("person360.sourceConfluence")}</SelectItem>
                     <SelectItem value="identity">{t("person360.sourceIdentity")}</SelectItem>
                   </SelectContent>
                 </Select>
@@ -226,6 +228,7 @@ export default function Person360Page() {
                     <SelectItem value="all">{t("person360.typeAll")}</SelectItem>
                     <SelectItem value="merge_request">{t("person360.typeMr")}</SelectItem>
                     <SelectItem value="issue">{t("person360.typeIssue")}</SelectItem>
+                    <SelectItem value="page_edit">{t("person360.typePageEdit")}</SelectItem>
                     <SelectItem value="identity_event">{t("person360.typeIdentityEvent")}</SelectItem>
                   </SelectContent>
                 </Select>
@@ -259,6 +262,7 @@ export default function Person360Page() {
 function SourceBadge({ source }: { source: string }) {
   const colors: Record<string, string> = {
     gitlab: "bg-orange-100 text-orange-800 dark:bg-orange-900 dark:text-orange-200",
+    github: "bg-violet-100 text-violet-800 dark:bg-violet-900 dark:text-violet-200",
     jira: "bg-blue-100 text-blue-800 dark:bg-blue-900 dark:text-blue-200",
     confluence: "bg-teal-100 text-teal-800 dark:bg-teal-900 dark:text-teal-200",
     git: "bg-gray-100 text-gray-800 dark:bg-gray-800 dark:text-gray-200",
@@ -304,6 +308,8 @@ function ActivityRow({
   const icon =
     item.source === "gitlab" ? (
       <GitMerge className="h-4 w-4 text-orange-500" />
+    ) : item.source === "github" ? (
+      <GitMerge className="h-4 w-4 text-violet-500" />
     ) : item.source === "jira" ? (
       <Briefcase className="h-4 w-4 text-blue-500" />
     ) : (
@@ -20,6 +20,12 @@ function makeSnapshot(overrides: Partial<KpiSnapshot> = {}): KpiSnapshot {
     spillover_rate: null,
     cycle_time_p50_hours: null,
     cycle_time_p90_hours: null,
+    lead_time_p50_hours: null,
+    lead_time_p90_hours: null,
+    time_to_first_review_p50_hours: null,
+    time_to_first_review_p90_hours: null,
+    review_rounds_avg: null,
+    mr_size_stats: null,
     computed_at: "2026-02-09T00:00:00Z",
     created_at: "2026-02-09T00:00:00Z",
     ...overrides,
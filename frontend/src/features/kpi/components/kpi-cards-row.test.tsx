@@ -20,6 +20,12 @@ const base: KpiSnapshot = {
   spillover_rate: 0.15,
   cycle_time_p50_hours: 36.5,
   cycle_time_p90_hours: 72.0,
+  lead_time_p50_hours: null,
+  lead_time_p90_hours: null,
+  time_to_first_review_p50_hours: null,
+  time_to_first_review_p90_hours: null,
+  review_rounds_avg: null,
+  mr_size_stats: null,
   computed_at: "2026-02-22T00:00:00Z",
   created_at: "2026-02-22T00:00:00Z",
 };
@@ -53,6 +59,12 @@ describe("KpiCardsRow", () => {
       spillover_rate: null,
       cycle_time_p50_hours: null,
       cycle_time_p90_hours: null,
+      lead_time_p50_hours: null,
+      lead_time_p90_hours: null,
+      time_to_first_review_p50_hours: null,
+      time_to_first_review_p90_hours: null,
+      review_rounds_avg: null,
+      mr_size_stats: null,
     };
     render(<KpiCardsRow latest={allNull} />);
     const naElements = screen.getAllByText("N/A");
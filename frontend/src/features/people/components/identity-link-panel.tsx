@@ -54,6 +54,11 @@ const SOURCE_CONFIG: Record<
     color: "bg-orange-100 text-orange-800 dark:bg-orange-900 dark:text-orange-200",
     label: "GitLab",
   },
+  github: {
+    icon: GitMerge,
+    color: "bg-violet-100 text-violet-800 dark:bg-violet-900 dark:text-violet-200",
+    label: "GitHub",
+  },
   jira: {
     icon: Briefcase,
     color: "bg-blue-100 text-blue-800 dark:bg-blue-900 dark:text-blue-200",
@@ -35,6 +35,7 @@ interface LinkIdentityDialogProps {
 
 const SOURCE_ICONS: Record<string, React.ElementType> = {
   gitlab: GitMerge,
+  github: GitMerge,
   jira: Briefcase,
   confluence: FileText,
   git_commit_author: GitCommit,
@@ -44,6 +45,7 @@ const SOURCE_ICONS: Record<string, React.ElementType> = {
 
 const SOURCE_COLORS: Record<string, string> = {
   gitlab: "bg-orange-100 text-orange-800 dark:bg-orange-900 dark:text-orange-200",
+  github: "bg-violet-100 text-violet-800 dark:bg-violet-900 dark:text-violet-200",
   jira: "bg-blue-100 text-blue-800 dark:bg-blue-900 dark:text-blue-200",
   confluence: "bg-teal-100 text-teal-800 dark:bg-teal-900 dark:text-teal-200",
 };
@@ -125,6 +127,7 @@ export function LinkIdentityDialog({
               <SelectContent>
                 <SelectItem value="all">{t("person360.sourceAll")}</SelectItem>
                 <SelectItem value="gitlab">{t("person360.sourceGitlab")}</SelectItem>
+                <SelectItem value="github">{t("person360.sourceGithub")}</SelectItem>
                 <SelectItem value="jira">{t("person360.sourceJira")}</SelectItem>
                 <SelectItem value="confluence">Confluence</SelectItem>
               </SelectContent>
@@ -47,7 +47,7 @@ export const messages = {
   "kpi.throughputDesc": "Total completed work items per period.\nBreakdown: features + bugs + chores.\nSource: closed Jira issues / MRs merged.",
   "kpi.latencyDesc": "Median time from MR opened to first review.\nP90 = 90th percentile (worst 10% of reviews).\nTarget: median <4h, P90 <12h.",
   "kpi.blockerCount": "Blockers",
-  "kpi.blockerCountDesc": "Open Jira issues with Blocker/Highest priority.\nDirectly impacts release risk score (40% weight).",
+  "kpi.blockerCountDesc": "Open Jira issues that block another open issue, and GitLab issues labelled as blockers.\nDirectly impacts release risk score (40% weight).",
   "kpi.spilloverRate": "Spillover Rate",
   "kpi.spilloverRateDesc": "Fraction of sprint items not completed in the sprint.\n0% = all done, 100% = nothing completed.\nAffects delivery health score (20% weight).",
   "kpi.cycleTime": "Cycle Time",
@@ -204,11 +204,14 @@ export const messages = {
   "person360.period90d": "Last 90 days",
   "person360.sourceAll": "All sources",
   "person360.sourceGitlab": "GitLab",
+  "person360.sourceGithub": "GitHub",
   "person360.sourceJira": "Jira",
+  "person360.sourceConfluence": "Confluence",
   "person360.sourceIdentity": "Identity",
   "person360.typeAll": "All types",
   "person360.typeMr": "Merge Requests",
   "person360.typeIssue": "Issues",
+  "person360.typePageEdit": "Page Edits",
   "person360.typeIdentityEvent": "Identity Events",
   "person360.stats": "Stats",
   "person360.totalActivity": "Total Activity",
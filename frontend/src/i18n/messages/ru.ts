@@ -49,7 +49,7 @@ export const messages: Messages = {
   "kpi.throughputDesc": "Общее количество выполненных задач за период.\nРаспределение: фичи + баги + техдолг.\nИсточник: закрытые задачи Jira / слитые MR.",
   "kpi.latencyDesc": "Медианное время от открытия MR до первого ревью.\nP90 = 90-й перцентиль (худшие 10% ревью).\nЦель: медиана <4ч, P90 <12ч.",
   "kpi.blockerCount": "Блокеры",
-  "kpi.blockerCountDesc": "Открытые задачи Jira с приоритетом Blocker/Highest.\nВлияют на оценку риска релиза (вес 40%).",
+  "kpi.blockerCountDesc": "Открытые задачи Jira, блокирующие другую открытую задачу, и задачи GitLab с меткой блокера.\nВлияют на оценку риска релиза (вес 40%).",
   "kpi.spilloverRate": "Доля переноса",
   "kpi.spilloverRateDesc": "Доля задач спринта, не завершённых в срок.\n0% = всё сделано, 100% = ничего не завершено.\nВлияет на здоровье поставки (вес 20%).",
   "kpi.cycleTime": "Время цикла",
@@ -206,11 +206,14 @@ export const messages: Messages = {
   "person360.period90d": "90 дней",
   "person360.sourceAll": "Все источники",
   "person360.sourceGitlab": "GitLab",
+  "person360.sourceGithub": "GitHub",
   "person360.sourceJira": "Jira",
+  "person360.sourceConfluence": "Confluence",
   "person360.sourceIdentity": "Идентичность",
   "person360.typeAll": "Все типы",
   "person360.typeMr": "Merge Requests",
   "person360.typeIssue": "Задачи",
+  "person360.typePageEdit": "Правки страниц",
   "person360.typeIdentityEvent": "События идентичностей",
   "person360.stats": "Статистика",
   "person360.totalActivity": "Всего активности",
@@ -99,10 +99,27 @@ export interface KpiSnapshot {
   spillover_rate: number | null;
   cycle_time_p50_hours: number | null;
   cycle_time_p90_hours: number | null;
+  lead_time_p50_hours: number | null;
+  lead_time_p90_hours: number | null;
+  time_to_first_review_p50_hours: number | null;
+  time_to_first_review_p90_hours: number | null;
+  review_rounds_avg: number | null;
+  mr_size_stats: MrSizeStats | null;
   computed_at: string;
   created_at: string;
 }
 
+export interface MrSizeBucketStats {
+  bucket: string;
+  mr_count: number;
+  review_latency_median_hours: number | null;
+}
+
+export interface MrSizeStats {
+  buckets: MrSizeBucketStats[];
+  teams: { team: string | null; buckets: MrSizeBucketStats[] }[];
+}
+
 export interface RiskItem {
   id: string;
   org_id: string;
@@ -117,6 +134,56 @@ export interface RiskItem {
   created_at: string;
 }
 
+export interface DoraMetrics {
+  id: string;
+  org_id: string;
+  snapshot_id: string;
+  scope_type: 'org' | 'project' | 'team';
+  scope_key: string | null;
+  deployment_count: number;
+  deployments_per_day: number | null;
+  lead_time_p50_hours: number | null;
+  change_failure_rate: number | null;
+  time_to_restore_p50_hours: number | null;
+  created_at: string;
+}
+
+export interface JobReliability {
+  gitlab_project_id: number;
+  project_path: string | null;
+  job_name: string;
+  stage: string | null;
+  runs: number;
+  failures: number;
+  flaky_count: number;
+  success_rate: number;
+  duration_p50_secs: number | null;
+  last_flaky_url: string | null;
+  first_flaky_at: string | null;
+}
+
+export interface EffortGroup {
+  group: string;
+  hours: number;
+  issues: number;
+  authors: number;
+}
+
+export interface StoryPointEffort {
+  story_points: number;
+  issues: number;
+  hours_avg: number;
+  hours_p50: number;
+}
+
+export interface EffortBreakdown {
+  by_issue_type: EffortGroup[];
+  by_team: EffortGroup[];
+  by_story_points: StoryPointEffort[];
+  period_start: string;
+  period_end: string;
+}
+
 export interface KpiHistoryFilter {
   period_start?: string;
   period_end?: string;
@@ -250,3 +317,16 @@ export interface Citation {
   url: string | null;
   snippet: string | null;
 }
+
+export type JiraFieldRole = "story_points" | "sprint" | "team" | "epic_link" | "extra" | "changelog";
+
+export interface JiraFieldMapping {
+  id: string;
+  org_id: string;
+  role: JiraFieldRole;
+  field_id: string;
+  field_name: string | null;
+  is_manual: boolean;
+  created_at: string;
+  updated_at: string;
+}